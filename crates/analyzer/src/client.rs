@@ -1,31 +1,295 @@
-use solana_pda_analyzer_core::{PdaAnalyzerError, Result, AccountState};
-use solana_client::rpc_client::RpcClient;
+use crate::rate_limit::RateLimiter;
+use solana_pda_analyzer_core::{extract_seed_strings, ChainContext, PdaAnalyzerError, Result, AccountState};
+use solana_client::nonblocking::rpc_client::RpcClient;
+use solana_client::rpc_client::GetConfirmedSignaturesForAddress2Config;
 use solana_sdk::pubkey::Pubkey;
 use solana_sdk::signature::Signature;
 use solana_sdk::transaction::Transaction;
 use solana_rpc_client_api::config::{RpcTransactionConfig, RpcAccountInfoConfig};
+use solana_rpc_client_api::filter::{Memcmp, RpcFilterType};
 use solana_transaction_status::{EncodedConfirmedTransactionWithStatusMeta, UiTransactionEncoding};
+use std::fmt;
+use std::num::NonZeroU32;
 use std::str::FromStr;
 use std::collections::HashMap;
+use std::sync::Arc;
 use tracing::{info, warn, error};
 
+/// Accounts sampled when harvesting seed strings for a program
+const HARVEST_ACCOUNT_SAMPLE: usize = 50;
+
+/// Only the head of each account is scanned; seed strings are usually stored near the discriminator
+const HARVEST_DATA_BYTES: usize = 256;
+
+/// Recent transactions whose logs are scanned when harvesting seed strings
+const HARVEST_TRANSACTION_SAMPLE: usize = 25;
+
+/// Default request rate per endpoint. Public mainnet RPC allows 100 requests per 10 seconds
+/// per IP, and fewer for `getProgramAccounts`, before it starts refusing a client.
+pub const DEFAULT_REQUESTS_PER_SECOND: u32 = 4;
+
+/// Clusters with a well-known public RPC endpoint, selectable with `--network`
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Network {
+    #[default]
+    Mainnet,
+    Devnet,
+    Testnet,
+    Localhost,
+}
+
+impl Network {
+    pub const ALL: [Network; 4] = [Network::Mainnet, Network::Devnet, Network::Testnet, Network::Localhost];
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Network::Mainnet => "mainnet",
+            Network::Devnet => "devnet",
+            Network::Testnet => "testnet",
+            Network::Localhost => "localhost",
+        }
+    }
+
+    pub fn rpc_url(&self) -> &'static str {
+        match self {
+            Network::Mainnet => "https://api.mainnet-beta.solana.com",
+            Network::Devnet => "https://api.devnet.solana.com",
+            Network::Testnet => "https://api.testnet.solana.com",
+            Network::Localhost => "http://127.0.0.1:8899",
+        }
+    }
+
+    /// Pick the RPC endpoint for a `--network` preset, letting an explicit `--rpc-url` win
+    pub fn resolve_rpc_url(network: Option<Network>, rpc_url: Option<&str>) -> String {
+        match rpc_url {
+            Some(url) => url.to_string(),
+            None => network.unwrap_or_default().rpc_url().to_string(),
+        }
+    }
+}
+
+impl FromStr for Network {
+    type Err = PdaAnalyzerError;
+
+    fn from_str(s: &str) -> Result<Self> {
+        match s.trim().to_ascii_lowercase().as_str() {
+            "mainnet" | "mainnet-beta" => Ok(Network::Mainnet),
+            "devnet" => Ok(Network::Devnet),
+            "testnet" => Ok(Network::Testnet),
+            "localhost" | "localnet" => Ok(Network::Localhost),
+            other => Err(PdaAnalyzerError::ConfigurationError(format!(
+                "Unknown network '{}', expected one of: mainnet, devnet, testnet, localhost",
+                other
+            ))),
+        }
+    }
+}
+
+impl fmt::Display for Network {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+/// Program accounts served by an offline client, keyed by owning program
+pub type ProgramAccountFixtures = HashMap<Pubkey, Vec<Pubkey>>;
+
+/// How a large program's accounts are split across several `getProgramAccounts` calls.
+/// The RPC has no cursor, so each page is a disjoint filter on the account data.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum AccountPaging {
+    /// One page per account data size, for programs with a few fixed-size account types.
+    /// Accounts of any other size are not returned.
+    DataSizes(Vec<u64>),
+    /// 256 pages keyed on the data byte at `offset`, plus one page per data size up to
+    /// `offset` so accounts too short to have that byte are still covered
+    ByteAt(usize),
+}
+
+impl Default for AccountPaging {
+    /// The first byte after an Anchor discriminator, which varies across accounts of one type
+    fn default() -> Self {
+        Self::ByteAt(8)
+    }
+}
+
+impl AccountPaging {
+    pub fn pages(&self) -> Vec<Vec<RpcFilterType>> {
+        match self {
+            Self::DataSizes(sizes) => sizes.iter().map(|size| vec![RpcFilterType::DataSize(*size)]).collect(),
+            Self::ByteAt(offset) => {
+                let short = (0..=*offset as u64).map(|size| vec![RpcFilterType::DataSize(size)]);
+                let by_byte = (0..=u8::MAX).map(|byte| vec![RpcFilterType::Memcmp(Memcmp::new_raw_bytes(*offset, vec![byte]))]);
+                short.chain(by_byte).collect()
+            }
+        }
+    }
+}
+
+/// How a `SolanaClient` connects to its RPC endpoint
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SolanaClientConfig {
+    pub rpc_url: String,
+    /// Most RPC calls per second, across every client in the process using this `rpc_url`.
+    /// `None` doesn't throttle, which only suits private endpoints.
+    pub requests_per_second: Option<NonZeroU32>,
+}
+
+impl SolanaClientConfig {
+    pub fn new(rpc_url: &str) -> Self {
+        Self {
+            rpc_url: rpc_url.to_string(),
+            requests_per_second: NonZeroU32::new(DEFAULT_REQUESTS_PER_SECOND),
+        }
+    }
+}
+
+impl Default for SolanaClientConfig {
+    fn default() -> Self {
+        Self::new(Network::default().rpc_url())
+    }
+}
+
+#[derive(Clone)]
+enum ClientBackend {
+    /// Shared so clones of a client reuse one connection pool
+    Rpc(Arc<RpcClient>),
+    Offline(ProgramAccountFixtures),
+}
+
+impl fmt::Debug for ClientBackend {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            // RpcClient itself isn't Debug
+            ClientBackend::Rpc(rpc_client) => f.debug_tuple("Rpc").field(&rpc_client.url()).finish(),
+            ClientBackend::Offline(fixtures) => f.debug_tuple("Offline").field(fixtures).finish(),
+        }
+    }
+}
+
 #[derive(Debug, Clone)]
 pub struct SolanaClient {
-    rpc_client: RpcClient,
+    backend: ClientBackend,
     commitment: solana_sdk::commitment_config::CommitmentConfig,
+    rate_limiter: Option<RateLimiter>,
 }
 
 impl SolanaClient {
     pub fn new(rpc_url: &str) -> Self {
-        let rpc_client = RpcClient::new(rpc_url.to_string());
+        Self::with_config(SolanaClientConfig::new(rpc_url))
+    }
+
+    pub fn with_config(config: SolanaClientConfig) -> Self {
+        let rate_limiter = config
+            .requests_per_second
+            .map(|requests| RateLimiter::for_endpoint(&config.rpc_url, requests));
+        let rpc_client = RpcClient::new(config.rpc_url);
         let commitment = solana_sdk::commitment_config::CommitmentConfig::confirmed();
         
         Self {
-            rpc_client,
+            backend: ClientBackend::Rpc(Arc::new(rpc_client)),
             commitment,
+            rate_limiter,
         }
     }
 
+    /// Client over an already built `RpcClient`, unthrottled, e.g. one from
+    /// `RpcClient::new_mock_with_mocks` in tests
+    pub fn from_rpc_client(rpc_client: RpcClient) -> Self {
+        Self {
+            backend: ClientBackend::Rpc(Arc::new(rpc_client)),
+            commitment: solana_sdk::commitment_config::CommitmentConfig::confirmed(),
+            rate_limiter: None,
+        }
+    }
+
+    pub fn for_network(network: Network) -> Self {
+        Self::new(network.rpc_url())
+    }
+
+    /// Client that never touches the network, for tests and demos. `fetch_program_accounts`
+    /// is answered from `fixtures`; every other RPC call fails with `NetworkError`.
+    pub fn offline(fixtures: ProgramAccountFixtures) -> Self {
+        Self {
+            backend: ClientBackend::Offline(fixtures),
+            commitment: solana_sdk::commitment_config::CommitmentConfig::confirmed(),
+            rate_limiter: None,
+        }
+    }
+
+    pub fn is_offline(&self) -> bool {
+        matches!(self.backend, ClientBackend::Offline(_))
+    }
+
+    /// Wait for the rate limiter before an RPC call
+    async fn throttle(&self) {
+        if let Some(rate_limiter) = &self.rate_limiter {
+            rate_limiter.acquire().await;
+        }
+    }
+
+    /// The RPC connection, once the rate limiter allows another call
+    async fn rpc(&self) -> Result<&RpcClient> {
+        match &self.backend {
+            ClientBackend::Rpc(rpc_client) => {
+                self.throttle().await;
+                Ok(rpc_client)
+            }
+            ClientBackend::Offline(_) => Err(PdaAnalyzerError::NetworkError(
+                "client is in offline mode and has no RPC connection".to_string(),
+            )),
+        }
+    }
+
+    /// Addresses of every account owned by `program_id`
+    pub async fn fetch_program_accounts(&self, program_id: &Pubkey) -> Result<Vec<Pubkey>> {
+        self.fetch_program_accounts_page(program_id, Vec::new()).await
+    }
+
+    /// Addresses of the accounts owned by `program_id` that match every filter in `filters`
+    pub async fn fetch_program_accounts_page(
+        &self,
+        program_id: &Pubkey,
+        filters: Vec<RpcFilterType>,
+    ) -> Result<Vec<Pubkey>> {
+        let rpc_client = match &self.backend {
+            // Fixtures carry no account data to filter on
+            ClientBackend::Offline(fixtures) => {
+                return Ok(fixtures.get(program_id).cloned().unwrap_or_default());
+            }
+            ClientBackend::Rpc(rpc_client) => rpc_client,
+        };
+        self.throttle().await;
+
+        // Only the addresses are needed, so skip the account data entirely
+        let config = solana_rpc_client_api::config::RpcProgramAccountsConfig {
+            filters: (!filters.is_empty()).then_some(filters),
+            account_config: RpcAccountInfoConfig {
+                encoding: Some(solana_account_decoder::UiAccountEncoding::Base64),
+                commitment: Some(self.commitment),
+                data_slice: Some(solana_account_decoder::UiDataSliceConfig { offset: 0, length: 0 }),
+                min_context_slot: None,
+            },
+            with_context: None,
+        };
+
+        let accounts = rpc_client
+            .get_program_accounts_with_config(program_id, config)
+            .await
+            .map_err(|e| PdaAnalyzerError::NetworkError(e.to_string()))?;
+
+        Ok(accounts.into_iter().map(|(pubkey, _)| pubkey).collect())
+    }
+
+    /// Filter sets that split `getProgramAccounts` into pages as described by `paging`.
+    /// An offline client answers with a single unfiltered page.
+    pub fn program_account_pages(&self, paging: &AccountPaging) -> Vec<Vec<RpcFilterType>> {
+        if self.is_offline() {
+            return vec![Vec::new()];
+        }
+        paging.pages()
+    }
+
     pub async fn get_transaction_with_meta(
         &self,
         signature: &str,
@@ -33,14 +297,16 @@ impl SolanaClient {
         let sig = Signature::from_str(signature)
             .map_err(|e| PdaAnalyzerError::TransactionParsingError(e.to_string()))?;
         
+        // Binary, so the processor can decode the message itself
         let config = RpcTransactionConfig {
-            encoding: Some(UiTransactionEncoding::JsonParsed),
+            encoding: Some(UiTransactionEncoding::Base64),
             commitment: Some(self.commitment),
             max_supported_transaction_version: Some(0),
         };
         
-        let transaction = self.rpc_client
+        let transaction = self.rpc().await?
             .get_transaction_with_config(&sig, config)
+            .await
             .map_err(|e| PdaAnalyzerError::NetworkError(e.to_string()))?;
         
         Ok(transaction)
@@ -54,13 +320,13 @@ impl SolanaClient {
             min_context_slot: None,
         };
         
-        match self.rpc_client.get_account_with_config(pubkey, config) {
+        match self.rpc().await?.get_account_with_config(pubkey, config).await {
             Ok(account_info) => {
                 if let Some(account) = account_info.value {
                     Ok(Some(AccountState {
                         pubkey: *pubkey,
                         lamports: account.lamports,
-                        data: account.data.decode().unwrap_or_default(),
+                        data: account.data,
                         owner: account.owner,
                         executable: account.executable,
                         rent_epoch: account.rent_epoch,
@@ -76,33 +342,84 @@ impl SolanaClient {
         }
     }
 
+    /// Whether an account is instantiated at `address`. Unlike `get_account_info`, RPC
+    /// failures are errors rather than "no account". An offline client checks its fixtures.
+    pub async fn account_exists(&self, address: &Pubkey) -> Result<bool> {
+        let rpc_client = match &self.backend {
+            ClientBackend::Offline(fixtures) => {
+                return Ok(fixtures.values().any(|accounts| accounts.contains(address)));
+            }
+            ClientBackend::Rpc(rpc_client) => rpc_client,
+        };
+        self.throttle().await;
+
+        let account = rpc_client
+            .get_account_with_commitment(address, self.commitment)
+            .await
+            .map_err(|e| PdaAnalyzerError::NetworkError(e.to_string()))?;
+
+        Ok(account.value.is_some())
+    }
+
+    /// The program that owns the account at `address`, which is the program a PDA was derived
+    /// under. Fails when no account exists there, since an uncreated PDA records no owner.
+    /// An offline client answers from its fixtures.
+    pub async fn owner_program(&self, address: &Pubkey) -> Result<Pubkey> {
+        let no_account = || PdaAnalyzerError::InvalidProgramId(format!("no account exists at {}, so its owner is unknown", address));
+
+        let rpc_client = match &self.backend {
+            ClientBackend::Offline(fixtures) => {
+                return fixtures
+                    .iter()
+                    .find(|(_, accounts)| accounts.contains(address))
+                    .map(|(program_id, _)| *program_id)
+                    .ok_or_else(no_account);
+            }
+            ClientBackend::Rpc(rpc_client) => rpc_client,
+        };
+        self.throttle().await;
+
+        let account = rpc_client
+            .get_account_with_commitment(address, self.commitment)
+            .await
+            .map_err(|e| PdaAnalyzerError::NetworkError(e.to_string()))?;
+
+        account.value.map(|account| account.owner).ok_or_else(no_account)
+    }
+
     pub async fn get_multiple_accounts(&self, pubkeys: &[Pubkey]) -> Result<Vec<Option<AccountState>>> {
+        self.get_multiple_accounts_sliced(pubkeys, None).await
+    }
+
+    /// `get_multiple_accounts`, returning only the `data_slice` part of each account's data
+    async fn get_multiple_accounts_sliced(
+        &self,
+        pubkeys: &[Pubkey],
+        data_slice: Option<solana_account_decoder::UiDataSliceConfig>,
+    ) -> Result<Vec<Option<AccountState>>> {
         let config = RpcAccountInfoConfig {
             encoding: Some(solana_account_decoder::UiAccountEncoding::Base64),
             commitment: Some(self.commitment),
-            data_slice: None,
+            data_slice,
             min_context_slot: None,
         };
         
-        let accounts = self.rpc_client
+        let accounts = self.rpc().await?
             .get_multiple_accounts_with_config(pubkeys, config)
+            .await
             .map_err(|e| PdaAnalyzerError::NetworkError(e.to_string()))?;
         
-        let mut result = Vec::new();
-        for (i, account_opt) in accounts.value.iter().enumerate() {
-            if let Some(account) = account_opt {
-                result.push(Some(AccountState {
-                    pubkey: pubkeys[i],
-                    lamports: account.lamports,
-                    data: account.data.decode().unwrap_or_default(),
-                    owner: account.owner,
-                    executable: account.executable,
-                    rent_epoch: account.rent_epoch,
-                }));
-            } else {
-                result.push(None);
-            }
-        }
+        let result = accounts.value.into_iter()
+            .zip(pubkeys)
+            .map(|(account, pubkey)| account.map(|account| AccountState {
+                pubkey: *pubkey,
+                lamports: account.lamports,
+                data: account.data,
+                owner: account.owner,
+                executable: account.executable,
+                rent_epoch: account.rent_epoch,
+            }))
+            .collect();
         
         Ok(result)
     }
@@ -113,28 +430,108 @@ impl SolanaClient {
         limit: Option<usize>,
         before: Option<&Signature>,
     ) -> Result<Vec<String>> {
-        let config = solana_rpc_client_api::config::RpcGetConfirmedSignaturesForAddress2Config {
-            before: before.map(|s| s.to_string()),
+        let config = GetConfirmedSignaturesForAddress2Config {
+            before: before.copied(),
             until: None,
             limit,
             commitment: Some(self.commitment),
         };
         
-        let signatures = self.rpc_client
+        let signatures = self.rpc().await?
             .get_signatures_for_address_with_config(address, config)
+            .await
             .map_err(|e| PdaAnalyzerError::NetworkError(e.to_string()))?;
         
         Ok(signatures.into_iter().map(|s| s.signature).collect())
     }
 
+    /// Scan a sample of the program's account data and transaction logs for plausible seed strings
+    pub async fn harvest_seed_strings(&self, program_id: &Pubkey) -> Result<Vec<String>> {
+        let mut strings = Vec::new();
+        let mut push_all = |found: Vec<String>| {
+            for string in found {
+                if !strings.contains(&string) {
+                    strings.push(string);
+                }
+            }
+        };
+
+        // List addresses without their data, then fetch the head of only the sampled accounts
+        let data_slice = solana_account_decoder::UiDataSliceConfig { offset: 0, length: HARVEST_DATA_BYTES };
+        let sampled = async {
+            let mut addresses = self.fetch_program_accounts(program_id).await?;
+            addresses.truncate(HARVEST_ACCOUNT_SAMPLE);
+            self.get_multiple_accounts_sliced(&addresses, Some(data_slice)).await
+        }
+        .await;
+        match sampled {
+            Ok(accounts) => {
+                for account in accounts.into_iter().flatten() {
+                    push_all(extract_seed_strings(&account.data));
+                }
+            }
+            Err(e) => warn!("Failed to sample accounts for {}: {}", program_id, e),
+        }
+
+        let signatures = self
+            .get_signatures_for_address(program_id, Some(HARVEST_TRANSACTION_SAMPLE), None)
+            .await?;
+
+        for signature in &signatures {
+            let transaction = match self.get_transaction_with_meta(signature).await {
+                Ok(transaction) => transaction,
+                Err(e) => {
+                    warn!("Failed to fetch transaction {}: {}", signature, e);
+                    continue;
+                }
+            };
+
+            let log_messages: Option<Vec<String>> = transaction
+                .transaction
+                .meta
+                .and_then(|meta| meta.log_messages.into());
+
+            for line in log_messages.unwrap_or_default() {
+                push_all(extract_seed_strings(line.as_bytes()));
+            }
+        }
+
+        info!("Harvested {} candidate seed strings for program {}", strings.len(), program_id);
+        Ok(strings)
+    }
+
     pub async fn get_slot(&self) -> Result<u64> {
-        self.rpc_client
+        self.rpc().await?
             .get_slot_with_commitment(self.commitment)
+            .await
             .map_err(|e| PdaAnalyzerError::NetworkError(e.to_string()))
     }
 
+    /// Slot and epoch to search around for epoch- and slot-seeded PDAs: `first_seen_slot` and
+    /// its epoch when known, otherwise the current slot and epoch
+    pub async fn chain_context(&self, first_seen_slot: Option<u64>) -> Result<ChainContext> {
+        let rpc = self.rpc().await?;
+
+        match first_seen_slot {
+            Some(slot) => {
+                let schedule = rpc
+                    .get_epoch_schedule()
+                    .await
+                    .map_err(|e| PdaAnalyzerError::NetworkError(e.to_string()))?;
+                Ok(ChainContext::new(slot, schedule.get_epoch(slot)))
+            }
+            None => {
+                let info = rpc
+                    .get_epoch_info_with_commitment(self.commitment)
+                    .await
+                    .map_err(|e| PdaAnalyzerError::NetworkError(e.to_string()))?;
+                Ok(ChainContext::new(info.absolute_slot, info.epoch))
+            }
+        }
+    }
+
     pub async fn get_block_time(&self, slot: u64) -> Result<Option<i64>> {
-        match self.rpc_client.get_block_time(slot) {
+        match self.rpc().await?.get_block_time(slot).await {
             Ok(time) => Ok(Some(time)),
             Err(_) => Ok(None),
         }
@@ -142,7 +539,7 @@ impl SolanaClient {
 
     pub fn parse_transaction_from_encoded(
         &self,
-        encoded_transaction: &EncodedConfirmedTransactionWithStatusMeta,
+        _encoded_transaction: &EncodedConfirmedTransactionWithStatusMeta,
     ) -> Result<(Transaction, Vec<AccountState>, Vec<AccountState>)> {
         // This is a simplified parser - in practice you'd need to handle all the
         // different encoding formats and extract pre/post account states
@@ -252,6 +649,9 @@ impl TransactionFetcher {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use solana_account_decoder::{UiAccount, UiAccountEncoding};
+    use solana_rpc_client_api::request::RpcRequest;
+    use solana_rpc_client_api::response::{Response, RpcKeyedAccount, RpcResponseContext};
     
     #[test]
     fn test_solana_client_creation() {
@@ -259,10 +659,163 @@ mod tests {
         assert_eq!(client.commitment, solana_sdk::commitment_config::CommitmentConfig::confirmed());
     }
     
+    #[test]
+    fn test_client_config_throttles_by_default() {
+        let config = SolanaClientConfig::new("http://my-node:8899");
+        assert_eq!(config.requests_per_second, NonZeroU32::new(DEFAULT_REQUESTS_PER_SECOND));
+        assert!(SolanaClient::with_config(config).rate_limiter.is_some());
+
+        let unthrottled = SolanaClientConfig {
+            requests_per_second: None,
+            ..SolanaClientConfig::default()
+        };
+        assert!(SolanaClient::with_config(unthrottled).rate_limiter.is_none());
+        assert!(SolanaClient::offline(HashMap::new()).rate_limiter.is_none());
+    }
+    
     #[test]
     fn test_transaction_fetcher_creation() {
         let client = SolanaClient::new("https://api.mainnet-beta.solana.com");
         let fetcher = TransactionFetcher::new(client, 100);
         assert_eq!(fetcher.batch_size, 100);
     }
-}
\ No newline at end of file
+    
+    #[tokio::test]
+    async fn test_offline_client_serves_fixtures() {
+        let program_id = Pubkey::new_unique();
+        let accounts = vec![Pubkey::new_unique(), Pubkey::new_unique()];
+        let client = SolanaClient::offline(HashMap::from([(program_id, accounts.clone())]));
+
+        assert!(client.is_offline());
+        assert_eq!(client.fetch_program_accounts(&program_id).await.unwrap(), accounts);
+        assert!(client.fetch_program_accounts(&Pubkey::new_unique()).await.unwrap().is_empty());
+
+        // Anything not backed by fixtures fails instead of reaching the network
+        assert!(matches!(client.get_slot().await, Err(PdaAnalyzerError::NetworkError(_))));
+    }
+
+    #[tokio::test]
+    async fn test_offline_owner_program_lookup() {
+        let program_id = Pubkey::new_unique();
+        let account = Pubkey::new_unique();
+        let client = SolanaClient::offline(HashMap::from([(program_id, vec![account])]));
+
+        assert_eq!(client.owner_program(&account).await.unwrap(), program_id);
+        assert!(matches!(
+            client.owner_program(&Pubkey::new_unique()).await,
+            Err(PdaAnalyzerError::InvalidProgramId(_))
+        ));
+    }
+
+    /// An account encoded the way RPC returns it
+    fn ui_account(owner: &Pubkey, data: Vec<u8>) -> UiAccount {
+        let account = solana_sdk::account::Account { lamports: 1_000_000, data, owner: *owner, executable: false, rent_epoch: 0 };
+        UiAccount::encode(owner, &account, UiAccountEncoding::Base64, None, None)
+    }
+
+    fn rpc_response<T: serde::Serialize>(value: T) -> serde_json::Value {
+        serde_json::to_value(Response { context: RpcResponseContext { slot: 1, api_version: None }, value }).unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_rpc_client_reads_accounts() {
+        let program_id = Pubkey::new_unique();
+        let (existing, missing) = (Pubkey::new_unique(), Pubkey::new_unique());
+        let mocks = HashMap::from([
+            (RpcRequest::GetAccountInfo, rpc_response(ui_account(&program_id, vec![]))),
+            (RpcRequest::GetMultipleAccounts, rpc_response(vec![Some(ui_account(&program_id, vec![7, 8])), None])),
+            (RpcRequest::GetProgramAccounts, serde_json::to_value(vec![RpcKeyedAccount {
+                pubkey: existing.to_string(),
+                account: ui_account(&program_id, vec![]),
+            }]).unwrap()),
+        ]);
+        let client = SolanaClient::from_rpc_client(RpcClient::new_mock_with_mocks("succeeds".to_string(), mocks));
+
+        assert_eq!(client.owner_program(&existing).await.unwrap(), program_id);
+        // Mocks answer once; afterwards getAccountInfo reports no account
+        assert!(!client.account_exists(&missing).await.unwrap());
+        assert!(matches!(client.owner_program(&missing).await, Err(PdaAnalyzerError::InvalidProgramId(_))));
+
+        let accounts = client.get_multiple_accounts(&[existing, missing]).await.unwrap();
+        let first = accounts[0].as_ref().unwrap();
+        assert_eq!((first.pubkey, first.owner, first.data.as_slice()), (existing, program_id, &[7u8, 8][..]));
+        assert!(accounts[1].is_none());
+
+        assert_eq!(client.fetch_program_accounts(&program_id).await.unwrap(), vec![existing]);
+    }
+
+    #[tokio::test]
+    async fn test_rpc_chain_context() {
+        let client = SolanaClient::from_rpc_client(RpcClient::new_mock("succeeds".to_string()));
+
+        // The mock's epoch info puts the current slot at 34 in epoch 1
+        let current = client.chain_context(None).await.unwrap();
+        assert_eq!((current.slot, current.epoch), (34, 1));
+
+        let schedule = solana_sdk::epoch_schedule::EpochSchedule::default();
+        let slot = schedule.get_first_slot_in_epoch(20) + 5;
+        let past = client.chain_context(Some(slot)).await.unwrap();
+        assert_eq!((past.slot, past.epoch), (slot, 20));
+    }
+
+    #[tokio::test]
+    async fn test_harvest_reads_account_data_and_logs() {
+        let program_id = Pubkey::new_unique();
+        // The mock's stock transaction, with a log line naming an instruction
+        let mut transaction = RpcClient::new_mock("succeeds".to_string())
+            .get_transaction(&Signature::default(), UiTransactionEncoding::Json)
+            .await
+            .unwrap();
+        if let Some(meta) = transaction.transaction.meta.as_mut() {
+            meta.log_messages = Some(vec!["Program log: Instruction: InitVault".to_string()]).into();
+        }
+        let mocks = HashMap::from([
+            (RpcRequest::GetProgramAccounts, serde_json::to_value(vec![RpcKeyedAccount {
+                pubkey: Pubkey::new_unique().to_string(),
+                account: ui_account(&program_id, vec![]),
+            }]).unwrap()),
+            (RpcRequest::GetMultipleAccounts, rpc_response(vec![Some(ui_account(&program_id, b"\x00\x01escrow_state\x00".to_vec()))])),
+            (RpcRequest::GetTransaction, serde_json::to_value(transaction).unwrap()),
+        ]);
+        let client = SolanaClient::from_rpc_client(RpcClient::new_mock_with_mocks("succeeds".to_string(), mocks));
+
+        let strings = client.harvest_seed_strings(&program_id).await.unwrap();
+        assert!(strings.iter().any(|s| s == "escrow_state"), "{:?}", strings);
+        assert!(strings.iter().any(|s| s.contains("InitVault")), "{:?}", strings);
+    }
+
+    #[test]
+    fn test_account_paging_covers_short_accounts() {
+        assert_eq!(AccountPaging::DataSizes(vec![165, 82]).pages().len(), 2);
+
+        let pages = AccountPaging::ByteAt(8).pages();
+        assert_eq!(pages.len(), 9 + 256);
+        assert!(matches!(pages[0][..], [RpcFilterType::DataSize(0)]));
+        assert!(matches!(pages[9][..], [RpcFilterType::Memcmp(_)]));
+
+        let offline = SolanaClient::offline(HashMap::new());
+        assert_eq!(offline.program_account_pages(&AccountPaging::default()).len(), 1);
+    }
+
+    #[test]
+    fn test_network_presets() {
+        assert_eq!(Network::from_str("mainnet").unwrap().rpc_url(), "https://api.mainnet-beta.solana.com");
+        assert_eq!(Network::from_str("Devnet").unwrap(), Network::Devnet);
+        assert_eq!(Network::from_str("localnet").unwrap(), Network::Localhost);
+        assert!(Network::from_str("mainnet-alpha").is_err());
+
+        for network in Network::ALL {
+            assert_eq!(Network::from_str(network.as_str()).unwrap(), network);
+        }
+    }
+    
+    #[test]
+    fn test_rpc_url_overrides_network() {
+        assert_eq!(
+            Network::resolve_rpc_url(Some(Network::Devnet), Some("http://my-node:8899")),
+            "http://my-node:8899"
+        );
+        assert_eq!(Network::resolve_rpc_url(Some(Network::Testnet), None), "https://api.testnet.solana.com");
+        assert_eq!(Network::resolve_rpc_url(None, None), Network::Mainnet.rpc_url());
+    }
+}
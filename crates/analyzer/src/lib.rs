@@ -1,7 +1,9 @@
 pub mod client;
 pub mod processor;
 pub mod patterns;
+pub mod rate_limit;
 
 pub use client::*;
 pub use processor::*;
-pub use patterns::*;
\ No newline at end of file
+pub use patterns::*;
+pub use rate_limit::RateLimiter;
\ No newline at end of file
@@ -1,14 +1,15 @@
 use solana_pda_analyzer_core::{
-    PdaAnalyzerError, Result, SeedValue, PdaInfo, PdaPattern, SeedTemplate,
+    validate_seeds, PdaAnalyzerError, Result, SeedValue, PdaInfo, PdaPatternTemplate, SeedTemplate,
 };
 use solana_sdk::pubkey::Pubkey;
 use std::collections::HashMap;
 use uuid::Uuid;
 use serde::{Deserialize, Serialize};
+use tracing::warn;
 
 #[derive(Debug, Clone)]
 pub struct PatternDetector {
-    known_patterns: HashMap<Pubkey, Vec<PdaPattern>>,
+    known_patterns: HashMap<Pubkey, Vec<PdaPatternTemplate>>,
     detected_patterns: HashMap<Pubkey, Vec<DetectedPattern>>,
 }
 
@@ -20,19 +21,25 @@ impl PatternDetector {
         }
     }
 
-    pub fn add_known_pattern(&mut self, pattern: PdaPattern) {
+    pub fn add_known_pattern(&mut self, pattern: PdaPatternTemplate) {
         self.known_patterns
             .entry(pattern.program_id)
-            .or_insert_with(Vec::new)
+            .or_default()
             .push(pattern);
     }
 
     pub fn detect_patterns(&mut self, program_id: &Pubkey, pdas: &[PdaInfo]) -> Result<Vec<DetectedPattern>> {
         let mut pattern_frequency = HashMap::new();
-        let mut seed_combinations = HashMap::new();
+        let mut seed_combinations: HashMap<String, Vec<PdaInfo>> = HashMap::new();
         
         // Analyze seed patterns
         for pda in pdas {
+            // Too many or over-long seeds can't be a real PDA; corrupt imports shouldn't become patterns
+            if let Err(e) = validate_seeds(&pda.seeds) {
+                warn!("Ignoring malformed PDA {} in pattern detection: {}", pda.address, e);
+                continue;
+            }
+
             if pda.program_id == *program_id {
                 let pattern_signature = self.create_pattern_signature(&pda.seeds);
                 let entry = pattern_frequency.entry(pattern_signature.clone()).or_insert(0);
@@ -40,7 +47,7 @@ impl PatternDetector {
                 
                 seed_combinations
                     .entry(pattern_signature)
-                    .or_insert_with(Vec::new)
+                    .or_default()
                     .push(pda.clone());
             }
         }
@@ -67,11 +74,7 @@ impl PatternDetector {
             }
         }
         
-        // Sort by frequency and confidence
-        detected_patterns.sort_by(|a, b| {
-            b.frequency.cmp(&a.frequency)
-                .then_with(|| b.confidence.partial_cmp(&a.confidence).unwrap_or(std::cmp::Ordering::Equal))
-        });
+        sort_detected(&mut detected_patterns);
         
         // Cache detected patterns
         self.detected_patterns.insert(*program_id, detected_patterns.clone());
@@ -107,14 +110,14 @@ impl PatternDetector {
         for pda in pdas {
             if pda.program_id == *program_id {
                 for (index, seed) in pda.seeds.iter().enumerate() {
-                    let key = (index, seed.seed_type());
+                    let key = (index, seed.seed_type().to_string());
                     *seed_type_frequency.entry(key).or_insert(0) += 1;
                 }
             }
         }
         
         // Generate suggestions based on common patterns
-        if seed_type_frequency.len() > 0 {
+        if !seed_type_frequency.is_empty() {
             let max_seed_count = seed_type_frequency.keys().map(|(index, _)| index).max().unwrap_or(&0) + 1;
             
             for seed_index in 0..max_seed_count {
@@ -125,11 +128,11 @@ impl PatternDetector {
                     }
                 }
                 
-                if let Some((most_common_type, _)) = type_counts.iter().max_by_key(|(_, count)| *count) {
+                if let Some((most_common_type, frequency)) = type_counts.iter().max_by_key(|(_, count)| *count) {
                     suggestions.push(PatternSuggestion {
                         seed_index,
                         suggested_type: most_common_type.clone(),
-                        frequency: *type_counts.get(*most_common_type).unwrap_or(&0),
+                        frequency: *frequency,
                         confidence: self.calculate_type_confidence(&type_counts),
                     });
                 }
@@ -139,6 +142,69 @@ impl PatternDetector {
         suggestions
     }
 
+    /// Addresses stored with more than one distinct recovered seed set, in order of first appearance.
+    /// A correct recovery is unique, so these are low-quality matches worth reviewing by hand.
+    pub fn find_ambiguous(&self, pdas: &[PdaInfo]) -> Vec<(Pubkey, Vec<Vec<SeedValue>>)> {
+        let mut order = Vec::new();
+        let mut seed_sets: HashMap<Pubkey, Vec<Vec<SeedValue>>> = HashMap::new();
+
+        for pda in pdas {
+            let sets = seed_sets.entry(pda.address).or_insert_with(|| {
+                order.push(pda.address);
+                Vec::new()
+            });
+            if !sets.contains(&pda.seeds) {
+                sets.push(pda.seeds.clone());
+            }
+        }
+
+        order
+            .into_iter()
+            .filter_map(|address| {
+                let sets = seed_sets.remove(&address)?;
+                (sets.len() > 1).then_some((address, sets))
+            })
+            .collect()
+    }
+
+    /// Every cached detected pattern, across all programs
+    pub fn detected_patterns(&self) -> impl Iterator<Item = &DetectedPattern> {
+        self.detected_patterns.values().flatten()
+    }
+
+    /// Merge patterns detected elsewhere into the cache, each under its own `program_id`.
+    /// An import replaces a cached pattern with the same program and signature. Nothing is
+    /// merged unless every signature matches its seed template. Returns how many were merged.
+    pub fn import_detected(&mut self, patterns: Vec<DetectedPattern>) -> Result<usize> {
+        for pattern in &patterns {
+            let template_signature = if pattern.seed_template.is_empty() {
+                "empty".to_string()
+            } else {
+                pattern.seed_template
+                    .iter()
+                    .map(|seed| SeedValue::normalize_seed_type(&seed.seed_type).unwrap_or("?"))
+                    .collect::<Vec<_>>()
+                    .join(":")
+            };
+            if template_signature != pattern.pattern_signature {
+                return Err(PdaAnalyzerError::InvalidSeedData(format!(
+                    "Pattern {} for program {} has signature '{}' but its seed template is '{}'",
+                    pattern.id, pattern.program_id, pattern.pattern_signature, template_signature
+                )));
+            }
+        }
+
+        let imported = patterns.len();
+        for pattern in patterns {
+            let cached = self.detected_patterns.entry(pattern.program_id).or_default();
+            cached.retain(|existing| existing.pattern_signature != pattern.pattern_signature);
+            cached.push(pattern);
+            sort_detected(cached);
+        }
+
+        Ok(imported)
+    }
+
     fn create_pattern_signature(&self, seeds: &[SeedValue]) -> String {
         if seeds.is_empty() {
             return "empty".to_string();
@@ -179,7 +245,7 @@ impl PatternDetector {
         
         let mut matches = 0;
         for (seed, template_seed) in seeds.iter().zip(template.iter()) {
-            if seed.seed_type() == template_seed.seed_type {
+            if template_seed.accepts(seed) {
                 matches += 1;
             }
         }
@@ -199,6 +265,14 @@ impl PatternDetector {
     }
 }
 
+/// Most frequent first, then most confident
+fn sort_detected(patterns: &mut [DetectedPattern]) {
+    patterns.sort_by(|a, b| {
+        b.frequency.cmp(&a.frequency)
+            .then_with(|| b.confidence.partial_cmp(&a.confidence).unwrap_or(std::cmp::Ordering::Equal))
+    });
+}
+
 impl Default for PatternDetector {
     fn default() -> Self {
         Self::new()
@@ -235,7 +309,7 @@ pub struct PatternSuggestion {
 #[derive(Debug, Clone)]
 pub struct PatternRegistry {
     detector: PatternDetector,
-    builtin_patterns: HashMap<Pubkey, Vec<PdaPattern>>,
+    builtin_patterns: HashMap<Pubkey, Vec<PdaPatternTemplate>>,
 }
 
 impl PatternRegistry {
@@ -254,7 +328,7 @@ impl PatternRegistry {
         
         // SPL Token patterns
         if let Ok(spl_token_id) = "TokenkegQfeZyiNwAJbNbGKPFXCWuBvf9Ss623VQ5DA".parse::<Pubkey>() {
-            self.add_pattern(PdaPattern {
+            self.add_pattern(PdaPatternTemplate {
                 id: Uuid::new_v4(),
                 program_id: spl_token_id,
                 pattern_name: "Token Account".to_string(),
@@ -278,7 +352,7 @@ impl PatternRegistry {
         
         // Metaplex patterns
         if let Ok(metaplex_id) = "metaqbxxUerdq28cj1RbAWkYQm3ybzjb6a8bt518x1s".parse::<Pubkey>() {
-            self.add_pattern(PdaPattern {
+            self.add_pattern(PdaPatternTemplate {
                 id: Uuid::new_v4(),
                 program_id: metaplex_id,
                 pattern_name: "Metadata Account".to_string(),
@@ -307,18 +381,80 @@ impl PatternRegistry {
         }
     }
 
-    pub fn add_pattern(&mut self, pattern: PdaPattern) {
+    pub fn add_pattern(&mut self, pattern: PdaPatternTemplate) {
         self.detector.add_known_pattern(pattern.clone());
         self.builtin_patterns
             .entry(pattern.program_id)
-            .or_insert_with(Vec::new)
+            .or_default()
             .push(pattern);
     }
 
+    /// Templates registered for `program_id`, built-in ones first
+    pub fn templates(&self, program_id: &Pubkey) -> &[PdaPatternTemplate] {
+        self.builtin_patterns.get(program_id).map(Vec::as_slice).unwrap_or(&[])
+    }
+
     pub fn detect_patterns(&mut self, program_id: &Pubkey, pdas: &[PdaInfo]) -> Result<Vec<DetectedPattern>> {
         self.detector.detect_patterns(program_id, pdas)
     }
 
+    /// Forward-derive up to `count` PDAs matching `template`, for test fixtures and demo data.
+    /// Each slot draws from `values[slot.name]`, skipping values of the wrong type; fixed slots
+    /// only use their first value. Combinations go in odometer order, last slot fastest, and
+    /// seed sets no PDA can be derived from (e.g. over-long seeds) are skipped.
+    pub fn generate_examples(
+        &self,
+        program_id: &Pubkey,
+        template: &[SeedTemplate],
+        values: &HashMap<String, Vec<SeedValue>>,
+        count: usize,
+    ) -> Vec<PdaInfo> {
+        let choices: Vec<Vec<&SeedValue>> = template
+            .iter()
+            .map(|slot| {
+                let accepted = values.get(&slot.name).into_iter().flatten().filter(|value| slot.accepts(value));
+                if slot.is_variable {
+                    accepted.collect()
+                } else {
+                    accepted.take(1).collect()
+                }
+            })
+            .collect();
+        if choices.is_empty() || choices.iter().any(Vec::is_empty) {
+            return Vec::new();
+        }
+
+        let mut examples = Vec::new();
+        let mut indices = vec![0; choices.len()];
+        'combinations: while examples.len() < count {
+            let seeds: Vec<SeedValue> = indices.iter().zip(&choices).map(|(&i, slot)| slot[i].clone()).collect();
+            let bytes: Vec<Vec<u8>> = seeds.iter().map(SeedValue::as_bytes).collect();
+            let seed_slices: Vec<&[u8]> = bytes.iter().map(Vec::as_slice).collect();
+
+            if let Some((address, bump)) = Pubkey::try_find_program_address(&seed_slices, program_id) {
+                examples.push(PdaInfo {
+                    address,
+                    program_id: *program_id,
+                    seeds,
+                    bump,
+                    first_seen_slot: None,
+                    first_seen_transaction: None,
+                });
+            }
+
+            for slot in (0..indices.len()).rev() {
+                indices[slot] += 1;
+                if indices[slot] < choices[slot].len() {
+                    continue 'combinations;
+                }
+                indices[slot] = 0;
+            }
+            break;
+        }
+
+        examples
+    }
+
     pub fn match_pda(&self, pda: &PdaInfo) -> Vec<PatternMatch> {
         self.detector.match_against_known_patterns(pda)
     }
@@ -326,6 +462,30 @@ impl PatternRegistry {
     pub fn get_suggestions(&self, program_id: &Pubkey, pdas: &[PdaInfo]) -> Vec<PatternSuggestion> {
         self.detector.generate_pattern_suggestions(program_id, pdas)
     }
+
+    /// Every detected pattern, ordered by program so exported libraries diff cleanly
+    pub fn export(&self) -> Vec<DetectedPattern> {
+        let mut patterns: Vec<DetectedPattern> = self.detector.detected_patterns().cloned().collect();
+        // Stable sort keeps each program's frequency order
+        patterns.sort_by_key(|pattern| pattern.program_id.to_string());
+        patterns
+    }
+
+    /// Merge a shared pattern library; see `PatternDetector::import_detected`
+    pub fn import(&mut self, patterns: Vec<DetectedPattern>) -> Result<usize> {
+        self.detector.import_detected(patterns)
+    }
+
+    pub fn export_json(&self) -> Result<String> {
+        serde_json::to_string_pretty(&self.export())
+            .map_err(|e| PdaAnalyzerError::SerializationError(e.to_string()))
+    }
+
+    pub fn import_json(&mut self, json: &str) -> Result<usize> {
+        let patterns: Vec<DetectedPattern> = serde_json::from_str(json)
+            .map_err(|e| PdaAnalyzerError::SerializationError(format!("Invalid pattern library: {}", e)))?;
+        self.import(patterns)
+    }
 }
 
 impl Default for PatternRegistry {
@@ -355,10 +515,45 @@ mod tests {
         assert_eq!(signature, "string:u64");
     }
     
+    #[test]
+    fn test_pattern_library_round_trip() {
+        let program_id = Pubkey::new_unique();
+        let pdas: Vec<PdaInfo> = (0..3u64)
+            .map(|i| PdaInfo {
+                address: Pubkey::new_unique(),
+                program_id,
+                seeds: vec![SeedValue::String("pool".to_string()), SeedValue::U64(i)],
+                bump: 255,
+                first_seen_slot: None,
+                first_seen_transaction: None,
+            })
+            .collect();
+
+        let mut source = PatternRegistry::new();
+        source.detect_patterns(&program_id, &pdas).unwrap();
+        let library = source.export_json().unwrap();
+
+        let mut target = PatternRegistry::new();
+        assert_eq!(target.import_json(&library).unwrap(), 1);
+        let imported = target.export();
+        assert_eq!(imported.len(), 1);
+        assert_eq!(imported[0].program_id, program_id);
+        assert_eq!(imported[0].pattern_signature, "string:u64");
+
+        // Re-importing replaces rather than duplicates
+        target.import_json(&library).unwrap();
+        assert_eq!(target.export().len(), 1);
+
+        let mut mislabeled = imported[0].clone();
+        mislabeled.pattern_signature = "pubkey".to_string();
+        assert!(target.import(vec![mislabeled]).is_err());
+        assert!(target.import_json("not json").is_err());
+    }
+    
     #[test]
     fn test_pattern_registry_creation() {
         let registry = PatternRegistry::new();
-        assert!(registry.builtin_patterns.len() > 0);
+        assert!(!registry.builtin_patterns.is_empty());
     }
     
     #[test]
@@ -370,4 +565,135 @@ mod tests {
         let confidence = detector.calculate_confidence(200, 100);
         assert_eq!(confidence, 95.0); // Capped at 95%
     }
-}
\ No newline at end of file
+    
+    #[test]
+    fn test_builtin_templates_use_canonical_seed_types() {
+        let registry = PatternRegistry::new();
+        for pattern in registry.builtin_patterns.values().flatten() {
+            for seed in &pattern.seeds_template {
+                assert_eq!(
+                    SeedValue::normalize_seed_type(&seed.seed_type),
+                    Some(seed.seed_type.as_str()),
+                    "{} uses non-canonical seed type {}",
+                    pattern.pattern_name,
+                    seed.seed_type
+                );
+            }
+        }
+    }
+    
+    #[test]
+    fn test_pattern_match_ignores_template_casing() {
+        let detector = PatternDetector::new();
+        let seeds = vec![SeedValue::String("vault".to_string()), SeedValue::U64(1)];
+        let template = vec![
+            SeedTemplate {
+                name: "prefix".to_string(),
+                seed_type: "String".to_string(),
+                description: None,
+                is_variable: false,
+            },
+            SeedTemplate {
+                name: "index".to_string(),
+                seed_type: "U64".to_string(),
+                description: None,
+                is_variable: true,
+            },
+        ];
+        assert_eq!(detector.calculate_pattern_match(&seeds, &template), Some(100.0));
+    }
+    
+    #[test]
+    fn test_find_ambiguous_reports_conflicting_seed_sets() {
+        let detector = PatternDetector::new();
+        let program_id = Pubkey::new_unique();
+        let pda = |address: Pubkey, seeds: Vec<SeedValue>| PdaInfo {
+            address,
+            program_id,
+            seeds,
+            bump: 255,
+            first_seen_slot: None,
+            first_seen_transaction: None,
+        };
+        let ambiguous = Pubkey::new_unique();
+        let unique = Pubkey::new_unique();
+        
+        let pdas = vec![
+            pda(ambiguous, vec![SeedValue::String("vault".to_string())]),
+            pda(unique, vec![SeedValue::U64(1)]),
+            pda(unique, vec![SeedValue::U64(1)]),
+            pda(ambiguous, vec![SeedValue::String("pool".to_string()), SeedValue::U8(3)]),
+            pda(ambiguous, vec![SeedValue::String("vault".to_string())]),
+        ];
+        
+        let found = detector.find_ambiguous(&pdas);
+        assert_eq!(found.len(), 1);
+        assert_eq!(found[0].0, ambiguous);
+        assert_eq!(found[0].1, vec![
+            vec![SeedValue::String("vault".to_string())],
+            vec![SeedValue::String("pool".to_string()), SeedValue::U8(3)],
+        ]);
+    }
+
+    #[test]
+    fn test_generate_examples_derives_matching_pdas() {
+        let registry = PatternRegistry::new();
+        let program_id = Pubkey::new_unique();
+        let slot = |name: &str, seed_type: &str, is_variable| SeedTemplate {
+            name: name.to_string(),
+            seed_type: seed_type.to_string(),
+            description: None,
+            is_variable,
+        };
+        let template = vec![slot("prefix", "string", false), slot("index", "u64", true)];
+
+        let mut values = HashMap::new();
+        values.insert("prefix".to_string(), vec![SeedValue::String("vault".to_string()), SeedValue::String("pool".to_string())]);
+        values.insert("index".to_string(), vec![SeedValue::U8(9), SeedValue::U64(0), SeedValue::U64(1), SeedValue::U64(2)]);
+
+        let examples = registry.generate_examples(&program_id, &template, &values, 2);
+        assert_eq!(examples.len(), 2);
+        for (i, example) in examples.iter().enumerate() {
+            assert_eq!(example.seeds, vec![SeedValue::String("vault".to_string()), SeedValue::U64(i as u64)]);
+            let expected = Pubkey::create_program_address(&[b"vault", &(i as u64).to_le_bytes(), &[example.bump]], &program_id);
+            assert_eq!(expected.unwrap(), example.address);
+        }
+
+        // Asking for more than exist yields every combination once
+        assert_eq!(registry.generate_examples(&program_id, &template, &values, 10).len(), 3);
+
+        // A slot with nothing of its type can't be filled
+        values.insert("index".to_string(), vec![SeedValue::String("0".to_string())]);
+        assert!(registry.generate_examples(&program_id, &template, &values, 10).is_empty());
+    }
+
+    #[test]
+    fn test_detect_patterns_ignores_malformed_seed_sets() {
+        let mut detector = PatternDetector::new();
+        let program_id = Pubkey::new_unique();
+        let pda = |seeds: Vec<SeedValue>| PdaInfo {
+            address: Pubkey::new_unique(),
+            program_id,
+            seeds,
+            bump: 255,
+            first_seen_slot: None,
+            first_seen_transaction: None,
+        };
+
+        let too_many: Vec<SeedValue> = (0..16).map(SeedValue::U8).collect();
+        let too_long = vec![SeedValue::String("x".repeat(33))];
+        let pdas = vec![
+            pda(too_many.clone()),
+            pda(too_many),
+            pda(too_long.clone()),
+            pda(too_long),
+            pda(vec![SeedValue::String("vault".to_string())]),
+            pda(vec![SeedValue::String("pool".to_string())]),
+        ];
+
+        let detected = detector.detect_patterns(&program_id, &pdas).unwrap();
+        assert_eq!(detected.len(), 1);
+        assert_eq!(detected[0].pattern_signature, "string");
+        assert_eq!(detected[0].frequency, 2);
+    }
+}
@@ -1,21 +1,35 @@
 use solana_pda_analyzer_core::{
     PdaAnalyzerError, Result, TransactionAnalysis, PdaAnalyzer, TransactionAnalyzer,
-    PdaInfo, AccountState,
+    PdaInfo, AccountState, PdaAnalysisResult, PatternHistogram, Reservoir,
 };
+use crate::client::{AccountPaging, SolanaClient};
+use solana_sdk::message::{Message, VersionedMessage};
 use solana_sdk::pubkey::Pubkey;
+use solana_sdk::sanitize::Sanitize;
 use solana_sdk::transaction::Transaction;
-use solana_transaction_status::EncodedConfirmedTransactionWithStatusMeta;
+use solana_transaction_status::{EncodedConfirmedTransactionWithStatusMeta, EncodedTransaction, UiLoadedAddresses};
 use chrono::{DateTime, Utc};
-use std::collections::HashMap;
+use rand::rngs::StdRng;
+use rand::SeedableRng;
+use std::collections::{HashMap, HashSet};
+use std::str::FromStr;
 use std::sync::Arc;
-use tokio::sync::RwLock;
+use std::time::Duration;
+use tokio::sync::{mpsc, RwLock};
+use tokio::time::Instant;
 use tracing::{info, warn, error, debug};
 
+/// Results buffered between page analysis and collection in `discover_and_analyze`
+const DISCOVERY_CHANNEL_CAPACITY: usize = 1024;
+
 #[derive(Debug, Clone)]
 pub struct BatchProcessor {
     pda_analyzer: Arc<RwLock<PdaAnalyzer>>,
     transaction_analyzer: Arc<RwLock<TransactionAnalyzer>>,
     stats: Arc<RwLock<ProcessingStats>>,
+    check_account_existence: bool,
+    /// Seeds the sampling RNG; `None` draws from OS entropy
+    sample_seed: Option<u64>,
 }
 
 impl BatchProcessor {
@@ -24,14 +38,46 @@ impl BatchProcessor {
             pda_analyzer: Arc::new(RwLock::new(PdaAnalyzer::new())),
             transaction_analyzer: Arc::new(RwLock::new(TransactionAnalyzer::new())),
             stats: Arc::new(RwLock::new(ProcessingStats::new())),
+            check_account_existence: false,
+            sample_seed: None,
         }
     }
 
+    /// Check each streamed result's account on chain and record it in `account_exists`,
+    /// at one extra RPC call per recognized PDA
+    pub fn with_account_existence_check(mut self, enabled: bool) -> Self {
+        self.check_account_existence = enabled;
+        self
+    }
+
+    /// Analyze with `analyzer` instead of a default one, e.g. to honor a configured pattern
+    /// filter or registered program names
+    pub fn with_pda_analyzer(mut self, analyzer: PdaAnalyzer) -> Self {
+        self.pda_analyzer = Arc::new(RwLock::new(analyzer));
+        self
+    }
+
+    /// Seed the RNG behind `sample_program_analysis` so a sampled run can be repeated exactly,
+    /// e.g. to see why it recovered or missed particular PDAs
+    pub fn with_sample_seed(mut self, seed: u64) -> Self {
+        self.sample_seed = Some(seed);
+        self
+    }
+
+    /// Record whether `result`'s account exists on chain, separating PDAs that are merely
+    /// derivable from instantiated ones
+    pub async fn mark_account_existence(&self, client: &SolanaClient, result: &mut PdaAnalysisResult) -> Result<()> {
+        result.account_exists = Some(client.account_exists(&result.pda_info.address).await?);
+        Ok(())
+    }
+
     pub async fn process_transaction(
         &self,
         encoded_transaction: EncodedConfirmedTransactionWithStatusMeta,
     ) -> Result<TransactionAnalysis> {
-        let signature = encoded_transaction.transaction.signatures[0].clone();
+        let signature = first_signature(&encoded_transaction.transaction.transaction).ok_or_else(|| {
+            PdaAnalyzerError::TransactionParsingError("transaction has no signature".to_string())
+        })?;
         debug!("Processing transaction: {}", signature);
         
         // Parse the transaction and extract account states
@@ -40,7 +86,7 @@ impl BatchProcessor {
         
         let slot = encoded_transaction.slot;
         let block_time = encoded_transaction.block_time.map(|t| {
-            DateTime::from_timestamp(t, 0).unwrap_or_else(|| Utc::now())
+            DateTime::from_timestamp(t, 0).unwrap_or_else(Utc::now)
         });
         
         let success = encoded_transaction.transaction.meta
@@ -135,23 +181,390 @@ impl BatchProcessor {
         Ok(patterns)
     }
 
+    /// Current data hash of each account, for storing as `data_hash`. Accounts that
+    /// don't exist are left out.
+    pub async fn fetch_data_hashes(&self, client: &SolanaClient, addresses: &[Pubkey]) -> Result<HashMap<Pubkey, String>> {
+        let mut hashes = HashMap::with_capacity(addresses.len());
+
+        // getMultipleAccounts takes at most 100 keys per call
+        for chunk in addresses.chunks(100) {
+            for account in client.get_multiple_accounts(chunk).await?.into_iter().flatten() {
+                hashes.insert(account.pubkey, TransactionAnalyzer::hash_account_data(&account.data));
+            }
+        }
+
+        Ok(hashes)
+    }
+
+    /// Analyze `address` under the program that owns its account, for when only the address
+    /// is known. Returns the owner with the analysis.
+    pub async fn analyze_with_owner(
+        &self,
+        client: &SolanaClient,
+        address: &Pubkey,
+    ) -> Result<(Pubkey, Option<PdaAnalysisResult>)> {
+        let program_id = client.owner_program(address).await?;
+        debug!("{} is owned by {}", address, program_id);
+
+        let result = self.pda_analyzer.write().await.analyze_pda(address, &program_id)?;
+        Ok((program_id, result))
+    }
+
+    /// Analyze `address`, also trying epoch and slot seeds near when it was first seen, or
+    /// near the current slot when that isn't known. Costs an RPC call for the chain context.
+    pub async fn analyze_near_slot(
+        &self,
+        client: &SolanaClient,
+        address: &Pubkey,
+        program_id: &Pubkey,
+        first_seen_slot: Option<u64>,
+    ) -> Result<Option<PdaAnalysisResult>> {
+        let context = client.chain_context(first_seen_slot).await?;
+        debug!("Analyzing {} near slot {} (epoch {})", address, context.slot, context.epoch);
+
+        let outcome = self.pda_analyzer.write().await.analyze_near(address, program_id, context)?;
+        Ok(outcome.into_result())
+    }
+
+    /// Harvest seed strings for a program from chain data and add them to the analyzer's vocabulary
+    pub async fn learn_seed_strings(&self, client: &SolanaClient, program_id: &Pubkey) -> Result<usize> {
+        let strings = client.harvest_seed_strings(program_id).await?;
+        let added = self.pda_analyzer.write().await.register_seed_strings(*program_id, strings);
+
+        info!("Learned {} new seed strings for program {}", added, program_id);
+        Ok(added)
+    }
+
+    /// Page through every account owned by `program_id`, analyzing each page as it arrives and
+    /// sending recognized PDAs to `results`, so only one page is ever held in memory.
+    /// Stops early if the receiver is dropped. Returns the number of accounts examined.
+    pub async fn stream_program_analysis(
+        &self,
+        client: &SolanaClient,
+        program_id: &Pubkey,
+        paging: &AccountPaging,
+        results: mpsc::Sender<PdaAnalysisResult>,
+    ) -> Result<usize> {
+        let mut progress = StreamProgress::default();
+        self.stream_until(client, program_id, paging, results, None, &HashSet::new(), &mut progress).await?;
+        Ok(progress.examined)
+    }
+
+    /// Like `stream_program_analysis`, but for at most `budget` of wall-clock time rather than
+    /// until every account is done, for continuous indexing that should stop on schedule.
+    /// Results reach `results` as they are found, so the receiver can persist them as it goes;
+    /// an RPC failure ends discovery early and is reported in the summary.
+    pub async fn discover_for_duration(
+        &self,
+        client: &SolanaClient,
+        program_id: &Pubkey,
+        paging: &AccountPaging,
+        budget: Duration,
+        results: mpsc::Sender<PdaAnalysisResult>,
+    ) -> TimedDiscoverySummary {
+        let started = Instant::now();
+        let mut progress = StreamProgress::default();
+        let streamed = self
+            .stream_until(client, program_id, paging, results, started.checked_add(budget), &HashSet::new(), &mut progress)
+            .await;
+
+        let summary = TimedDiscoverySummary {
+            examined: progress.examined,
+            recognized: progress.recognized,
+            elapsed: started.elapsed(),
+            completed: progress.finished,
+            error: streamed.err().map(|e| e.to_string()),
+        };
+        info!(
+            "Discovery for {} ran {:.1}s: {} accounts examined ({:.1}/s), {} PDAs recognized{}",
+            program_id,
+            summary.elapsed.as_secs_f64(),
+            summary.examined,
+            summary.accounts_per_second(),
+            summary.recognized,
+            if summary.completed { ", every account covered" } else { "" }
+        );
+        summary
+    }
+
+    /// Analyze `program_id`'s accounts page by page into `results`, stopping at `deadline` if
+    /// one is given and passing over accounts in `skip`. `progress` is kept up to date so
+    /// callers have it even after an error.
+    #[allow(clippy::too_many_arguments)]
+    async fn stream_until(
+        &self,
+        client: &SolanaClient,
+        program_id: &Pubkey,
+        paging: &AccountPaging,
+        results: mpsc::Sender<PdaAnalysisResult>,
+        deadline: Option<Instant>,
+        skip: &HashSet<Pubkey>,
+        progress: &mut StreamProgress,
+    ) -> Result<()> {
+        let pages = client.program_account_pages(paging);
+
+        for (page_index, filters) in pages.iter().enumerate() {
+            let fetch = client.fetch_program_accounts_page(program_id, filters.clone());
+            let accounts = match deadline {
+                // A slow page shouldn't carry discovery past its budget
+                Some(deadline) => match tokio::time::timeout_at(deadline, fetch).await {
+                    Ok(accounts) => accounts?,
+                    Err(_) => return Ok(()),
+                },
+                None => fetch.await?,
+            };
+            debug!("Page {}/{} for {}: {} accounts", page_index + 1, pages.len(), program_id, accounts.len());
+
+            for address in accounts {
+                if deadline.is_some_and(|deadline| Instant::now() >= deadline) {
+                    info!("Time budget for {} ran out after {} accounts", program_id, progress.examined);
+                    return Ok(());
+                }
+
+                if skip.contains(&address) {
+                    progress.skipped += 1;
+                    continue;
+                }
+
+                progress.examined += 1;
+                let analysis = self.pda_analyzer.write().await.analyze_pda(&address, program_id);
+                match analysis {
+                    Ok(Some(mut result)) => {
+                        if self.check_account_existence {
+                            if let Err(e) = self.mark_account_existence(client, &mut result).await {
+                                warn!("Failed to check whether {} exists: {}", address, e);
+                            }
+                        }
+                        if results.send(result).await.is_err() {
+                            info!("Result receiver dropped after {} accounts of {}", progress.examined, program_id);
+                            return Ok(());
+                        }
+                        progress.recognized += 1;
+                    }
+                    Ok(None) => {}
+                    Err(e) => warn!("Failed to analyze account {}: {}", address, e),
+                }
+            }
+        }
+
+        progress.finished = true;
+        info!("Streamed analysis of {} accounts for program {}", progress.examined, program_id);
+        Ok(())
+    }
+
+    /// Analyze a uniform random sample of `sample_size` of the accounts owned by `program_id`,
+    /// for a quick read of a large program's pattern distribution. Every page is still
+    /// fetched, but only the sampled addresses are held and analyzed.
+    pub async fn sample_program_analysis(
+        &self,
+        client: &SolanaClient,
+        program_id: &Pubkey,
+        paging: &AccountPaging,
+        sample_size: usize,
+    ) -> Result<SampleOutcome> {
+        let mut rng = match self.sample_seed {
+            Some(seed) => StdRng::seed_from_u64(seed),
+            None => StdRng::from_entropy(),
+        };
+        let mut reservoir = Reservoir::new(sample_size);
+
+        for filters in client.program_account_pages(paging) {
+            for address in client.fetch_program_accounts_page(program_id, filters).await? {
+                reservoir.offer(address, &mut rng);
+            }
+        }
+
+        let population = reservoir.seen();
+        let sample = reservoir.into_items();
+        let mut results = Vec::new();
+        let mut counts: HashMap<String, i64> = HashMap::new();
+        {
+            let mut analyzer = self.pda_analyzer.write().await;
+            for address in &sample {
+                match analyzer.analyze_pda(address, program_id) {
+                    Ok(Some(result)) => {
+                        *counts.entry(result.pattern.name().into_owned()).or_insert(0) += 1;
+                        results.push(result);
+                    }
+                    Ok(None) => *counts.entry("UNRECOGNIZED".to_string()).or_insert(0) += 1,
+                    Err(e) => warn!("Failed to analyze account {}: {}", address, e),
+                }
+            }
+        }
+
+        let outcome = SampleOutcome {
+            population,
+            sample_size: sample.len(),
+            results,
+            histogram: PatternHistogram::from_counts(counts),
+        };
+
+        info!("Sampled {} of {} accounts for program {}", outcome.sample_size, population, program_id);
+        for entry in &outcome.histogram.patterns {
+            info!(
+                "   {}: {:.1}% of sample, ~{} accounts",
+                entry.stat.pattern,
+                entry.stat.percentage,
+                (entry.stat.percentage / 100.0 * population as f64).round()
+            );
+        }
+
+        Ok(outcome)
+    }
+
+    /// Analyze every account owned by `program_id`, keeping whatever was recovered before
+    /// an RPC failure instead of discarding it. Check `completed` before treating the results as exhaustive.
+    pub async fn discover_and_analyze(
+        &self,
+        client: &SolanaClient,
+        program_id: &Pubkey,
+        paging: &AccountPaging,
+    ) -> DiscoveryOutcome {
+        let (sender, mut receiver) = mpsc::channel(DISCOVERY_CHANNEL_CAPACITY);
+        let collect = async {
+            let mut results = Vec::new();
+            while let Some(result) = receiver.recv().await {
+                results.push(result);
+            }
+            results
+        };
+
+        let (streamed, results) = tokio::join!(
+            self.stream_program_analysis(client, program_id, paging, sender),
+            collect,
+        );
+
+        match streamed {
+            Ok(_) => DiscoveryOutcome {
+                results,
+                completed: true,
+                error: None,
+            },
+            Err(e) => {
+                warn!("Discovery for {} stopped early after {} results: {}", program_id, results.len(), e);
+                DiscoveryOutcome {
+                    results,
+                    completed: false,
+                    error: Some(e.to_string()),
+                }
+            }
+        }
+    }
+
+    /// Like `discover_and_analyze`, but only for accounts not in `known`, e.g. the PDAs already
+    /// stored through an earlier run's slot. Results are stamped with the slot read before the
+    /// scan as their `first_seen_slot`; once the run completes, that slot is the new
+    /// high-water mark, as every account existing at it has been seen.
+    pub async fn discover_new_accounts(
+        &self,
+        client: &SolanaClient,
+        program_id: &Pubkey,
+        paging: &AccountPaging,
+        known: &HashSet<Pubkey>,
+    ) -> IncrementalDiscoveryOutcome {
+        // Offline clients can't tell the slot
+        let scanned_slot = match client.get_slot().await {
+            Ok(slot) => Some(slot),
+            Err(e) => {
+                debug!("No slot for discovery of {}: {}", program_id, e);
+                None
+            }
+        };
+
+        let (sender, mut receiver) = mpsc::channel::<PdaAnalysisResult>(DISCOVERY_CHANNEL_CAPACITY);
+        let collect = async {
+            let mut results = Vec::new();
+            while let Some(mut result) = receiver.recv().await {
+                result.pda_info.first_seen_slot = result.pda_info.first_seen_slot.or(scanned_slot);
+                results.push(result);
+            }
+            results
+        };
+
+        let mut progress = StreamProgress::default();
+        let (streamed, results) = tokio::join!(
+            self.stream_until(client, program_id, paging, sender, None, known, &mut progress),
+            collect,
+        );
+        if let Err(e) = &streamed {
+            warn!("Incremental discovery for {} stopped early after {} results: {}", program_id, results.len(), e);
+        }
+        info!(
+            "Incremental discovery for {}: {} new accounts examined, {} already recorded, {} PDAs recognized",
+            program_id,
+            progress.examined,
+            progress.skipped,
+            results.len()
+        );
+
+        IncrementalDiscoveryOutcome {
+            results,
+            examined: progress.examined,
+            skipped: progress.skipped,
+            scanned_slot,
+            completed: progress.finished,
+            error: streamed.err().map(|e| e.to_string()),
+        }
+    }
+
     pub async fn get_stats(&self) -> ProcessingStats {
         self.stats.read().await.clone()
     }
 
+    /// Decode a transaction fetched in a binary encoding, with every account its instructions
+    /// index. RPC keeps no historical account data, so the account states only carry the
+    /// lamports from the status meta.
     fn parse_encoded_transaction(
         &self,
         encoded_transaction: &EncodedConfirmedTransactionWithStatusMeta,
     ) -> Result<(Transaction, Vec<AccountState>, Vec<AccountState>)> {
-        // This is a simplified parser
-        // In a full implementation, you'd need to properly decode the transaction
-        // and extract pre/post account states from the meta
-        
-        // For now, return placeholder values
-        let transaction = Transaction::default();
-        let pre_account_states = Vec::new();
-        let post_account_states = Vec::new();
-        
+        let parse_error = |message: String| PdaAnalyzerError::TransactionParsingError(message);
+        let encoded = &encoded_transaction.transaction;
+        let meta = encoded.meta.as_ref();
+
+        let versioned = encoded.transaction.decode()
+            .ok_or_else(|| parse_error("transaction isn't binary encoded or doesn't decode".to_string()))?;
+        let message = match versioned.message {
+            VersionedMessage::Legacy(message) => message,
+            VersionedMessage::V0(message) => {
+                // Instructions index the static keys, then the writable and readonly
+                // addresses loaded from lookup tables
+                let loaded: Option<UiLoadedAddresses> = meta.and_then(|meta| meta.loaded_addresses.clone().into());
+                let mut account_keys = message.account_keys;
+                for address in loaded.iter().flat_map(|loaded| loaded.writable.iter().chain(&loaded.readonly)) {
+                    account_keys.push(Pubkey::from_str(address).map_err(|e| parse_error(e.to_string()))?);
+                }
+                Message {
+                    header: message.header,
+                    account_keys,
+                    recent_blockhash: message.recent_blockhash,
+                    instructions: message.instructions,
+                }
+            }
+        };
+        message.sanitize()
+            .map_err(|e| parse_error(format!("instructions reference accounts the transaction doesn't list: {}", e)))?;
+
+        let account_states = |balances: Option<&Vec<u64>>| -> Vec<AccountState> {
+            message.account_keys.iter()
+                .zip(balances.into_iter().flatten())
+                .map(|(pubkey, lamports)| AccountState {
+                    pubkey: *pubkey,
+                    lamports: *lamports,
+                    data: Vec::new(),
+                    owner: Pubkey::default(),
+                    executable: false,
+                    rent_epoch: 0,
+                })
+                .collect()
+        };
+        let pre_account_states = account_states(meta.map(|meta| &meta.pre_balances));
+        let post_account_states = account_states(meta.map(|meta| &meta.post_balances));
+
+        let transaction = Transaction {
+            signatures: versioned.signatures,
+            message,
+        };
         Ok((transaction, pre_account_states, post_account_states))
     }
 
@@ -167,6 +580,15 @@ impl BatchProcessor {
     }
 }
 
+/// The transaction's first signature, which is its id, in whichever encoding it was fetched
+fn first_signature(transaction: &EncodedTransaction) -> Option<String> {
+    match transaction {
+        EncodedTransaction::Json(ui_transaction) => ui_transaction.signatures.first().cloned(),
+        EncodedTransaction::Accounts(accounts_list) => accounts_list.signatures.first().cloned(),
+        encoded => encoded.decode()?.signatures.first().map(|signature| signature.to_string()),
+    }
+}
+
 impl Default for BatchProcessor {
     fn default() -> Self {
         Self::new()
@@ -198,7 +620,7 @@ impl ProcessingStats {
     }
     
     pub fn transactions_per_second(&self) -> f64 {
-        let duration = self.processing_duration().num_seconds() as f64;
+        let duration = self.processing_duration().num_milliseconds() as f64 / 1000.0;
         if duration > 0.0 {
             self.transactions_processed as f64 / duration
         } else {
@@ -213,6 +635,86 @@ impl Default for ProcessingStats {
     }
 }
 
+/// What `discover_and_analyze` recovered, and whether it got through every page
+#[derive(Debug, Clone)]
+pub struct DiscoveryOutcome {
+    pub results: Vec<PdaAnalysisResult>,
+    pub completed: bool,
+    /// Why discovery stopped early, when `completed` is false
+    pub error: Option<String>,
+}
+
+/// What `discover_new_accounts` recovered from the accounts it hadn't seen
+#[derive(Debug, Clone)]
+pub struct IncrementalDiscoveryOutcome {
+    pub results: Vec<PdaAnalysisResult>,
+    /// Accounts analyzed, not counting skipped ones
+    pub examined: usize,
+    /// Accounts passed over as already known
+    pub skipped: usize,
+    /// Slot read before the scan, the high-water mark to persist once `completed`; `None`
+    /// when the client couldn't tell
+    pub scanned_slot: Option<u64>,
+    pub completed: bool,
+    /// Why discovery stopped early, when `completed` is false
+    pub error: Option<String>,
+}
+
+/// How far `stream_until` got
+#[derive(Debug, Clone, Copy, Default)]
+struct StreamProgress {
+    examined: usize,
+    /// Accounts passed over without analysis
+    skipped: usize,
+    recognized: usize,
+    /// Every page was analyzed
+    finished: bool,
+}
+
+/// What `discover_for_duration` got through in its time budget
+#[derive(Debug, Clone)]
+pub struct TimedDiscoverySummary {
+    /// Accounts analyzed before the budget ran out
+    pub examined: usize,
+    /// Recognized PDAs sent to the receiver
+    pub recognized: usize,
+    pub elapsed: Duration,
+    /// Every account was analyzed within the budget
+    pub completed: bool,
+    /// Why discovery stopped before the budget ran out, if it failed
+    pub error: Option<String>,
+}
+
+impl TimedDiscoverySummary {
+    pub fn accounts_per_second(&self) -> f64 {
+        let seconds = self.elapsed.as_secs_f64();
+        if seconds > 0.0 {
+            self.examined as f64 / seconds
+        } else {
+            0.0
+        }
+    }
+}
+
+/// What `sample_program_analysis` found in a random subset of a program's accounts
+#[derive(Debug, Clone)]
+pub struct SampleOutcome {
+    /// Accounts the program owns, every one of which was offered to the sampler
+    pub population: usize,
+    /// Accounts actually analyzed; less than requested only for small programs
+    pub sample_size: usize,
+    pub results: Vec<PdaAnalysisResult>,
+    /// Pattern shares across the sample, with unrecognized accounts as `UNRECOGNIZED`
+    pub histogram: PatternHistogram,
+}
+
+impl SampleOutcome {
+    /// Pattern counts the whole program likely holds, scaled up from the sample
+    pub fn extrapolated_counts(&self) -> Vec<(String, i64)> {
+        self.histogram.extrapolate(self.population as i64)
+    }
+}
+
 #[derive(Debug, Clone)]
 pub struct PdaPatternAnalysis {
     pub pattern: String,
@@ -291,6 +793,20 @@ pub struct ProgramAnalysis {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use solana_account_decoder::{UiAccount, UiAccountEncoding};
+    use solana_client::nonblocking::rpc_client::RpcClient;
+    use solana_rpc_client_api::request::RpcRequest;
+    use solana_rpc_client_api::response::RpcKeyedAccount;
+    use solana_sdk::hash::Hash;
+    use solana_sdk::instruction::{AccountMeta, CompiledInstruction, Instruction};
+    use solana_sdk::message::{v0, MessageHeader};
+    use solana_sdk::message::v0::MessageAddressTableLookup;
+    use solana_sdk::signature::Signature;
+    use solana_sdk::transaction::VersionedTransaction;
+    use solana_transaction_status::option_serializer::OptionSerializer;
+    use solana_transaction_status::{
+        EncodableWithMeta, EncodedTransactionWithStatusMeta, TransactionStatusMeta, UiTransactionEncoding, UiTransactionStatusMeta,
+    };
     
     #[tokio::test]
     async fn test_batch_processor_creation() {
@@ -311,6 +827,268 @@ mod tests {
         assert!(tps > 0.0);
     }
     
+    #[tokio::test]
+    async fn test_stream_program_analysis_sends_recognized_pdas() {
+        let program_id = Pubkey::new_unique();
+        let (state_pda, _) = Pubkey::find_program_address(&[b"state"], &program_id);
+        let client = SolanaClient::offline(HashMap::from([(program_id, vec![state_pda, Pubkey::new_unique()])]));
+        let processor = BatchProcessor::new();
+        let (sender, mut receiver) = mpsc::channel(16);
+
+        let examined = processor
+            .stream_program_analysis(&client, &program_id, &AccountPaging::default(), sender)
+            .await
+            .unwrap();
+
+        assert_eq!(examined, 2);
+        let result = receiver.recv().await.unwrap();
+        assert_eq!(result.pda_info.address, state_pda);
+        assert!(receiver.recv().await.is_none());
+    }
+    
+    #[tokio::test]
+    async fn test_discover_and_analyze_completes_offline() {
+        let program_id = Pubkey::new_unique();
+        let (config_pda, _) = Pubkey::find_program_address(&[b"config"], &program_id);
+        let client = SolanaClient::offline(HashMap::from([(program_id, vec![config_pda])]));
+
+        let outcome = BatchProcessor::new()
+            .discover_and_analyze(&client, &program_id, &AccountPaging::default())
+            .await;
+
+        assert!(outcome.completed);
+        assert!(outcome.error.is_none());
+        assert_eq!(outcome.results.len(), 1);
+        assert_eq!(outcome.results[0].account_exists, None);
+    }
+
+    #[tokio::test]
+    async fn test_discover_new_accounts_skips_known() {
+        let program_id = Pubkey::new_unique();
+        let (config_pda, _) = Pubkey::find_program_address(&[b"config"], &program_id);
+        let (vault_pda, _) = Pubkey::find_program_address(&[b"vault"], &program_id);
+        let client = SolanaClient::offline(HashMap::from([(program_id, vec![config_pda, vault_pda])]));
+
+        let outcome = BatchProcessor::new()
+            .discover_new_accounts(&client, &program_id, &AccountPaging::default(), &HashSet::from([config_pda]))
+            .await;
+
+        assert!(outcome.completed);
+        assert_eq!((outcome.examined, outcome.skipped), (1, 1));
+        assert_eq!(outcome.results.len(), 1);
+        assert_eq!(outcome.results[0].pda_info.address, vault_pda);
+        // Offline clients have no slot to stamp or record
+        assert_eq!(outcome.scanned_slot, None);
+        assert_eq!(outcome.results[0].pda_info.first_seen_slot, None);
+    }
+
+    #[tokio::test]
+    async fn test_analyze_with_owner_finds_program() {
+        let program_id = Pubkey::new_unique();
+        let (config_pda, _) = Pubkey::find_program_address(&[b"config"], &program_id);
+        let client = SolanaClient::offline(HashMap::from([(program_id, vec![config_pda])]));
+
+        let (owner, result) = BatchProcessor::new().analyze_with_owner(&client, &config_pda).await.unwrap();
+        assert_eq!(owner, program_id);
+        assert_eq!(result.unwrap().pda_info.program_id, program_id);
+    }
+
+    #[tokio::test]
+    async fn test_account_existence_marks_phantom_pdas() {
+        let program_id = Pubkey::new_unique();
+        let (config_pda, _) = Pubkey::find_program_address(&[b"config"], &program_id);
+        let (vault_pda, _) = Pubkey::find_program_address(&[b"vault"], &program_id);
+        let client = SolanaClient::offline(HashMap::from([(program_id, vec![config_pda])]));
+        let processor = BatchProcessor::new().with_account_existence_check(true);
+
+        let outcome = processor.discover_and_analyze(&client, &program_id, &AccountPaging::default()).await;
+        assert_eq!(outcome.results[0].account_exists, Some(true));
+
+        // Derivable, but never created
+        let mut phantom = PdaAnalyzer::new().analyze_pda(&vault_pda, &program_id).unwrap().unwrap();
+        processor.mark_account_existence(&client, &mut phantom).await.unwrap();
+        assert_eq!(phantom.account_exists, Some(false));
+    }
+    
+    #[tokio::test]
+    async fn test_sample_program_analysis_extrapolates() {
+        let program_id = Pubkey::new_unique();
+        let accounts: Vec<Pubkey> = (0..40u64)
+            .map(|i| Pubkey::find_program_address(&[b"user", &i.to_le_bytes()], &program_id).0)
+            .collect();
+        let client = SolanaClient::offline(HashMap::from([(program_id, accounts.clone())]));
+
+        let outcome = BatchProcessor::new()
+            .sample_program_analysis(&client, &program_id, &AccountPaging::default(), 10)
+            .await
+            .unwrap();
+
+        assert_eq!(outcome.population, 40);
+        assert_eq!(outcome.sample_size, 10);
+        assert!(outcome.results.iter().all(|result| accounts.contains(&result.pda_info.address)));
+        assert_eq!(outcome.extrapolated_counts(), vec![("SEQUENTIAL".to_string(), 40)]);
+    }
+
+    #[tokio::test]
+    async fn test_seeded_sample_is_repeatable() {
+        let program_id = Pubkey::new_unique();
+        let accounts: Vec<Pubkey> = (0..40u64)
+            .map(|i| Pubkey::find_program_address(&[b"user", &i.to_le_bytes()], &program_id).0)
+            .collect();
+        let client = SolanaClient::offline(HashMap::from([(program_id, accounts)]));
+
+        let sampled = |seed| {
+            let client = &client;
+            async move {
+                let outcome = BatchProcessor::new()
+                    .with_sample_seed(seed)
+                    .sample_program_analysis(client, &program_id, &AccountPaging::default(), 10)
+                    .await
+                    .unwrap();
+                outcome.results.into_iter().map(|result| result.pda_info.address).collect::<Vec<_>>()
+            }
+        };
+
+        assert_eq!(sampled(7).await, sampled(7).await);
+        assert_ne!(sampled(7).await, sampled(8).await);
+    }
+
+    #[tokio::test]
+    async fn test_discover_for_duration_stops_at_budget() {
+        let program_id = Pubkey::new_unique();
+        let (config_pda, _) = Pubkey::find_program_address(&[b"config"], &program_id);
+        let client = SolanaClient::offline(HashMap::from([(program_id, vec![config_pda, Pubkey::new_unique()])]));
+        let processor = BatchProcessor::new();
+
+        let (sender, mut receiver) = mpsc::channel(16);
+        let summary = processor
+            .discover_for_duration(&client, &program_id, &AccountPaging::default(), Duration::ZERO, sender)
+            .await;
+        assert_eq!(summary.examined, 0);
+        assert!(!summary.completed);
+        assert!(summary.error.is_none());
+        assert!(receiver.recv().await.is_none());
+
+        let (sender, mut receiver) = mpsc::channel(16);
+        let summary = processor
+            .discover_for_duration(&client, &program_id, &AccountPaging::default(), Duration::from_secs(60), sender)
+            .await;
+        assert_eq!((summary.examined, summary.recognized), (2, 1));
+        assert!(summary.completed);
+        assert_eq!(receiver.recv().await.unwrap().pda_info.address, config_pda);
+    }
+
+    /// `transaction` as RPC returns it in base64, with balances for its static keys and
+    /// `loaded` as the lookup table addresses
+    fn encoded_transaction(transaction: &VersionedTransaction, loaded: Option<UiLoadedAddresses>) -> EncodedConfirmedTransactionWithStatusMeta {
+        let balances = vec![1_000_000; transaction.message.static_account_keys().len()];
+        EncodedConfirmedTransactionWithStatusMeta {
+            slot: 77,
+            transaction: EncodedTransactionWithStatusMeta {
+                // The status meta only matters to JSON encodings
+                transaction: transaction.encode_with_meta(UiTransactionEncoding::Base64, &TransactionStatusMeta::default()),
+                meta: Some(UiTransactionStatusMeta {
+                    err: None,
+                    status: Ok(()),
+                    fee: 5000,
+                    pre_balances: balances.clone(),
+                    post_balances: balances,
+                    inner_instructions: OptionSerializer::None,
+                    log_messages: OptionSerializer::None,
+                    pre_token_balances: OptionSerializer::None,
+                    post_token_balances: OptionSerializer::None,
+                    rewards: OptionSerializer::None,
+                    loaded_addresses: loaded.into(),
+                    return_data: OptionSerializer::Skip,
+                    compute_units_consumed: OptionSerializer::Skip,
+                }),
+                version: None,
+            },
+            block_time: Some(1_700_000_000),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_process_fetched_transaction_finds_pdas() {
+        let program_id = Pubkey::new_unique();
+        let (state_pda, _) = Pubkey::find_program_address(&[b"state"], &program_id);
+        let instruction = Instruction::new_with_bytes(program_id, &[], vec![AccountMeta::new(state_pda, false)]);
+        let transaction = Transaction::new_unsigned(Message::new(&[instruction], Some(&Pubkey::new_unique())));
+        let signature = Signature::from([3; 64]);
+        let transaction = VersionedTransaction { signatures: vec![signature], ..VersionedTransaction::from(transaction) };
+
+        // Ingestion fetches the transaction and hands it to the processor unchanged
+        let mocks = HashMap::from([(
+            RpcRequest::GetTransaction,
+            serde_json::to_value(encoded_transaction(&transaction, None)).unwrap(),
+        )]);
+        let client = SolanaClient::from_rpc_client(RpcClient::new_mock_with_mocks("succeeds".to_string(), mocks));
+        let fetched = client.get_transaction_with_meta(&signature.to_string()).await.unwrap();
+
+        let processor = BatchProcessor::new();
+        let analysis = processor.process_transaction(fetched).await.unwrap();
+        assert_eq!(analysis.signature, signature.to_string());
+        assert_eq!(analysis.slot, 77);
+        assert!(analysis.success);
+        assert_eq!(analysis.discovered_pdas.len(), 1);
+        assert_eq!(analysis.discovered_pdas[0].address, state_pda);
+        assert_eq!(processor.get_stats().await.transactions_processed, 1);
+    }
+
+    #[tokio::test]
+    async fn test_process_v0_transaction_with_loaded_addresses() {
+        let program_id = Pubkey::new_unique();
+        let (state_pda, _) = Pubkey::find_program_address(&[b"state"], &program_id);
+        // The PDA is only reachable through the lookup table, at index 2
+        let message = v0::Message {
+            header: MessageHeader { num_required_signatures: 1, num_readonly_signed_accounts: 0, num_readonly_unsigned_accounts: 1 },
+            account_keys: vec![Pubkey::new_unique(), program_id],
+            recent_blockhash: Hash::default(),
+            instructions: vec![CompiledInstruction::new_from_raw_parts(1, vec![], vec![2])],
+            address_table_lookups: vec![MessageAddressTableLookup {
+                account_key: Pubkey::new_unique(),
+                writable_indexes: vec![0],
+                readonly_indexes: vec![],
+            }],
+        };
+        let transaction = VersionedTransaction { signatures: vec![Signature::from([4; 64])], message: VersionedMessage::V0(message) };
+        let processor = BatchProcessor::new();
+
+        let loaded = UiLoadedAddresses { writable: vec![state_pda.to_string()], readonly: vec![] };
+        let analysis = processor.process_transaction(encoded_transaction(&transaction, Some(loaded))).await.unwrap();
+        assert_eq!(analysis.discovered_pdas.len(), 1);
+        assert_eq!(analysis.pda_interactions[0].pda_address, state_pda);
+
+        // Without the loaded addresses the instruction points past the account list
+        let result = processor.process_transaction(encoded_transaction(&transaction, None)).await;
+        assert!(matches!(result, Err(PdaAnalyzerError::TransactionParsingError(_))));
+    }
+
+    #[tokio::test]
+    async fn test_discover_new_accounts_stamps_scanned_slot() {
+        let program_id = Pubkey::new_unique();
+        let (state_pda, _) = Pubkey::find_program_address(&[b"state"], &program_id);
+        let account = solana_sdk::account::Account { lamports: 1, data: vec![], owner: program_id, executable: false, rent_epoch: 0 };
+        let keyed = |address: Pubkey| RpcKeyedAccount {
+            pubkey: address.to_string(),
+            account: UiAccount::encode(&address, &account, UiAccountEncoding::Base64, None, None),
+        };
+        let mocks = HashMap::from([
+            (RpcRequest::GetSlot, serde_json::json!(4242)),
+            (RpcRequest::GetProgramAccounts, serde_json::to_value(vec![keyed(state_pda), keyed(Pubkey::new_unique())]).unwrap()),
+        ]);
+        let client = SolanaClient::from_rpc_client(RpcClient::new_mock_with_mocks("succeeds".to_string(), mocks));
+
+        let outcome = BatchProcessor::new()
+            .discover_new_accounts(&client, &program_id, &AccountPaging::DataSizes(vec![0]), &HashSet::new())
+            .await;
+        assert!(outcome.completed);
+        assert_eq!(outcome.scanned_slot, Some(4242));
+        assert_eq!(outcome.examined, 2);
+        assert_eq!(outcome.results.len(), 1);
+        assert_eq!(outcome.results[0].pda_info.first_seen_slot, Some(4242));
+    }
+
     #[test]
     fn test_program_analyzer_creation() {
         let program_id = Pubkey::new_unique();
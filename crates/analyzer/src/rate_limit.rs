@@ -0,0 +1,74 @@
+use dashmap::DashMap;
+use std::num::NonZeroU32;
+use std::sync::{Arc, Mutex, OnceLock};
+use tokio::time::{Duration, Instant};
+
+/// Spaces calls evenly at a fixed rate. Clones share one schedule, so every task
+/// holding one draws from the same allowance.
+#[derive(Debug, Clone)]
+pub struct RateLimiter {
+    interval: Duration,
+    next_slot: Arc<Mutex<Instant>>,
+}
+
+impl RateLimiter {
+    pub fn per_second(requests: NonZeroU32) -> Self {
+        Self {
+            interval: Duration::from_secs(1) / requests.get(),
+            next_slot: Arc::new(Mutex::new(Instant::now())),
+        }
+    }
+
+    /// The process-wide limiter for the endpoint at `url`, so every client talking to it
+    /// shares one allowance. The first caller for an endpoint sets its rate.
+    pub fn for_endpoint(url: &str, requests: NonZeroU32) -> Self {
+        static ENDPOINTS: OnceLock<DashMap<String, RateLimiter>> = OnceLock::new();
+        ENDPOINTS
+            .get_or_init(DashMap::new)
+            .entry(url.to_string())
+            .or_insert_with(|| Self::per_second(requests))
+            .clone()
+    }
+
+    /// Wait for the next free slot. Slots are handed out in call order, and an idle
+    /// limiter lets the next call through immediately rather than saving up a burst.
+    pub async fn acquire(&self) {
+        let slot = {
+            let mut next_slot = self.next_slot.lock().unwrap();
+            let slot = (*next_slot).max(Instant::now());
+            *next_slot = slot + self.interval;
+            slot
+        };
+        tokio::time::sleep_until(slot).await;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_calls_are_spaced_at_the_rate() {
+        let limiter = RateLimiter::per_second(NonZeroU32::new(50).unwrap());
+        let start = Instant::now();
+
+        for _ in 0..6 {
+            limiter.clone().acquire().await;
+        }
+
+        // The first call is free; the other five wait 20ms each
+        assert!(start.elapsed() >= Duration::from_millis(100));
+    }
+
+    #[test]
+    fn test_endpoints_share_one_limiter() {
+        let rate = NonZeroU32::new(10).unwrap();
+        let first = RateLimiter::for_endpoint("http://shared-node:8899", rate);
+        let second = RateLimiter::for_endpoint("http://shared-node:8899", NonZeroU32::new(99).unwrap());
+        let other = RateLimiter::for_endpoint("http://other-node:8899", rate);
+
+        assert!(Arc::ptr_eq(&first.next_slot, &second.next_slot));
+        assert_eq!(second.interval, Duration::from_millis(100));
+        assert!(!Arc::ptr_eq(&first.next_slot, &other.next_slot));
+    }
+}
@@ -1,11 +1,11 @@
-use solana_pda_analyzer_analyzer::{PatternDetector, PatternRegistry, DetectedPattern};
-use solana_pda_analyzer_core::{PdaInfo, SeedValue, PdaPattern, SeedTemplate};
+use solana_pda_analyzer_analyzer::{PatternDetector, PatternRegistry};
+use solana_pda_analyzer_core::{PdaInfo, SeedValue, PdaPatternTemplate, SeedTemplate};
 use solana_sdk::pubkey::Pubkey;
 use uuid::Uuid;
 
 fn create_test_pda(program_id: Pubkey, seeds: Vec<SeedValue>, address: Option<Pubkey>) -> PdaInfo {
     PdaInfo {
-        address: address.unwrap_or_else(|| Pubkey::new_unique()),
+        address: address.unwrap_or_else(Pubkey::new_unique),
         program_id,
         seeds,
         bump: 254,
@@ -17,8 +17,7 @@ fn create_test_pda(program_id: Pubkey, seeds: Vec<SeedValue>, address: Option<Pu
 #[test]
 fn test_pattern_detector_creation() {
     let detector = PatternDetector::new();
-    assert_eq!(detector.known_patterns.len(), 0);
-    assert_eq!(detector.detected_patterns.len(), 0);
+    assert_eq!(detector.detected_patterns().count(), 0);
 }
 
 #[test]
@@ -26,7 +25,7 @@ fn test_add_known_pattern() {
     let mut detector = PatternDetector::new();
     let program_id = Pubkey::new_unique();
     
-    let pattern = PdaPattern {
+    let pattern = PdaPatternTemplate {
         id: Uuid::new_v4(),
         program_id,
         pattern_name: "Test Pattern".to_string(),
@@ -43,9 +42,14 @@ fn test_add_known_pattern() {
     
     detector.add_known_pattern(pattern.clone());
     
-    assert_eq!(detector.known_patterns.len(), 1);
-    assert!(detector.known_patterns.contains_key(&program_id));
-    assert_eq!(detector.known_patterns[&program_id].len(), 1);
+    let pda = create_test_pda(program_id, vec![SeedValue::String("prefix".to_string())], None);
+    let matches = detector.match_against_known_patterns(&pda);
+    assert_eq!(matches.len(), 1);
+    assert_eq!(matches[0].pattern_id, pattern.id);
+
+    // Known patterns are per program
+    let other = create_test_pda(Pubkey::new_unique(), vec![SeedValue::String("prefix".to_string())], None);
+    assert!(detector.match_against_known_patterns(&other).is_empty());
 }
 
 #[test]
@@ -108,68 +112,75 @@ fn test_pattern_detection_with_multiple_patterns() {
 
 #[test]
 fn test_pattern_confidence_calculation() {
-    let detector = PatternDetector::new();
-    
-    // Test with 50% frequency
-    let confidence = detector.calculate_confidence(5, 10);
-    assert_eq!(confidence, 50.0);
-    
-    // Test with 100% frequency (should cap at 95%)
-    let confidence = detector.calculate_confidence(10, 10);
-    assert_eq!(confidence, 95.0);
-    
-    // Test with >100% frequency (should cap at 95%)
-    let confidence = detector.calculate_confidence(15, 10);
-    assert_eq!(confidence, 95.0);
-    
-    // Test with 0 total
-    let confidence = detector.calculate_confidence(5, 0);
-    assert_eq!(confidence, 0.0);
+    let mut detector = PatternDetector::new();
+    let program_id = Pubkey::new_unique();
+
+    // Half the PDAs share a shape: 50%
+    let mut pdas: Vec<PdaInfo> = (0..5)
+        .map(|i| create_test_pda(program_id, vec![SeedValue::U64(i)], None))
+        .collect();
+    pdas.extend((0..5).map(|i| create_test_pda(program_id, vec![SeedValue::U8(i)], None)));
+    let patterns = detector.detect_patterns(&program_id, &pdas).unwrap();
+    assert!(patterns.iter().all(|p| p.confidence == 50.0));
+
+    // Every PDA shares a shape: capped at 95%
+    let pdas: Vec<PdaInfo> = (0..10)
+        .map(|i| create_test_pda(program_id, vec![SeedValue::U64(i)], None))
+        .collect();
+    let patterns = detector.detect_patterns(&program_id, &pdas).unwrap();
+    assert_eq!(patterns[0].confidence, 95.0);
 }
 
 #[test]
 fn test_pattern_matching() {
-    let detector = PatternDetector::new();
+    let mut detector = PatternDetector::new();
     let program_id = Pubkey::new_unique();
     
     // Create a pattern template
-    let template = vec![
-        SeedTemplate {
-            name: "prefix".to_string(),
-            seed_type: "string".to_string(),
-            description: None,
-            is_variable: false,
-        },
-        SeedTemplate {
-            name: "id".to_string(),
-            seed_type: "u64".to_string(),
-            description: None,
-            is_variable: true,
-        },
-    ];
+    detector.add_known_pattern(PdaPatternTemplate {
+        id: Uuid::new_v4(),
+        program_id,
+        pattern_name: "Prefixed ID".to_string(),
+        seeds_template: vec![
+            SeedTemplate {
+                name: "prefix".to_string(),
+                seed_type: "string".to_string(),
+                description: None,
+                is_variable: false,
+            },
+            SeedTemplate {
+                name: "id".to_string(),
+                seed_type: "u64".to_string(),
+                description: None,
+                is_variable: true,
+            },
+        ],
+        description: None,
+    });
+    let match_score = |seeds: Vec<SeedValue>| {
+        detector
+            .match_against_known_patterns(&create_test_pda(program_id, seeds, None))
+            .first()
+            .map(|m| m.match_score)
+    };
     
     // Test matching seeds
     let matching_seeds = vec![
         SeedValue::String("test".to_string()),
         SeedValue::U64(12345),
     ];
-    
-    let match_score = detector.calculate_pattern_match(&matching_seeds, &template);
-    assert_eq!(match_score, Some(100.0));
+    assert_eq!(match_score(matching_seeds), Some(100.0));
     
     // Test non-matching seeds
     let non_matching_seeds = vec![
         SeedValue::String("test".to_string()),
         SeedValue::String("wrong_type".to_string()),
     ];
-    
-    let match_score = detector.calculate_pattern_match(&non_matching_seeds, &template);
-    assert_eq!(match_score, Some(50.0));
+    assert_eq!(match_score(non_matching_seeds), Some(50.0));
     
     // Test different length
     let wrong_length_seeds = vec![SeedValue::String("test".to_string())];
-    let match_score = detector.calculate_pattern_match(&wrong_length_seeds, &template);
-    assert_eq!(match_score, None);
+    assert_eq!(match_score(wrong_length_seeds), None);
 }
 
 #[test]
@@ -215,12 +226,9 @@ fn test_pattern_suggestions() {
 fn test_pattern_registry() {
     let mut registry = PatternRegistry::new();
     
-    // Should have some builtin patterns
-    assert!(registry.builtin_patterns.len() > 0);
-    
     // Test adding a custom pattern
     let program_id = Pubkey::new_unique();
-    let custom_pattern = PdaPattern {
+    let custom_pattern = PdaPatternTemplate {
         id: Uuid::new_v4(),
         program_id,
         pattern_name: "Custom Pattern".to_string(),
@@ -252,19 +260,16 @@ fn test_pattern_registry() {
 fn test_builtin_patterns() {
     let registry = PatternRegistry::new();
     
-    // Test that builtin patterns are loaded
-    assert!(registry.builtin_patterns.len() >= 2); // Should have at least SPL Token and Metaplex patterns
-    
     // Find SPL Token pattern
-    let spl_token_patterns = registry.builtin_patterns.values()
-        .flatten()
+    let spl_token_id: Pubkey = "TokenkegQfeZyiNwAJbNbGKPFXCWuBvf9Ss623VQ5DA".parse().unwrap();
+    let spl_token_patterns = registry.templates(&spl_token_id).iter()
         .find(|p| p.pattern_name.contains("Token"));
     
     assert!(spl_token_patterns.is_some());
     
     // Find Metaplex pattern
-    let metaplex_patterns = registry.builtin_patterns.values()
-        .flatten()
+    let metaplex_id: Pubkey = "metaqbxxUerdq28cj1RbAWkYQm3ybzjb6a8bt518x1s".parse().unwrap();
+    let metaplex_patterns = registry.templates(&metaplex_id).iter()
         .find(|p| p.pattern_name.contains("Metadata"));
     
     assert!(metaplex_patterns.is_some());
@@ -299,6 +304,10 @@ fn test_complex_seed_patterns() {
             SeedValue::String("vault".to_string()),
             SeedValue::Bytes(vec![1, 2, 3, 4]),
         ], None),
+        create_test_pda(program_id, vec![
+            SeedValue::String("vault".to_string()),
+            SeedValue::Bytes(vec![5, 6, 7, 8]),
+        ], None),
     ];
     
     let patterns = detector.detect_patterns(&program_id, &pdas).unwrap();
@@ -316,7 +325,7 @@ fn test_complex_seed_patterns() {
     let vault_pattern = patterns.iter()
         .find(|p| p.pattern_signature == "string:bytes")
         .unwrap();
-    assert_eq!(vault_pattern.frequency, 1);
+    assert_eq!(vault_pattern.frequency, 2);
 }
 
 #[test]
@@ -339,20 +348,24 @@ fn test_empty_patterns() {
 
 #[test]
 fn test_pattern_signature_creation() {
-    let detector = PatternDetector::new();
+    let mut detector = PatternDetector::new();
+    let program_id = Pubkey::new_unique();
+    let signature = |detector: &mut PatternDetector, seeds: Vec<SeedValue>| {
+        let pdas = vec![
+            create_test_pda(program_id, seeds.clone(), None),
+            create_test_pda(program_id, seeds, None),
+        ];
+        detector.detect_patterns(&program_id, &pdas).unwrap()[0].pattern_signature.clone()
+    };
     
     // Test empty seeds
-    let empty_signature = detector.create_pattern_signature(&[]);
-    assert_eq!(empty_signature, "empty");
+    assert_eq!(signature(&mut detector, vec![]), "empty");
     
     // Test single seed
-    let single_signature = detector.create_pattern_signature(&[
-        SeedValue::String("test".to_string())
-    ]);
-    assert_eq!(single_signature, "string");
+    assert_eq!(signature(&mut detector, vec![SeedValue::String("test".to_string())]), "string");
     
     // Test multiple seeds
-    let multi_signature = detector.create_pattern_signature(&[
+    let multi_signature = signature(&mut detector, vec![
         SeedValue::String("prefix".to_string()),
         SeedValue::Pubkey(Pubkey::new_unique()),
         SeedValue::U64(123),
@@ -363,15 +376,20 @@ fn test_pattern_signature_creation() {
 
 #[test]
 fn test_seed_template_creation() {
-    let detector = PatternDetector::new();
+    let mut detector = PatternDetector::new();
+    let program_id = Pubkey::new_unique();
     
     let seeds = vec![
         SeedValue::String("test".to_string()),
         SeedValue::U64(123),
         SeedValue::Pubkey(Pubkey::new_unique()),
     ];
+    let pdas = vec![
+        create_test_pda(program_id, seeds.clone(), None),
+        create_test_pda(program_id, seeds, None),
+    ];
     
-    let template = detector.create_seed_template(&seeds);
+    let template = detector.detect_patterns(&program_id, &pdas).unwrap()[0].seed_template.clone();
     
     assert_eq!(template.len(), 3);
     assert_eq!(template[0].name, "seed_0");
@@ -383,4 +401,4 @@ fn test_seed_template_creation() {
     
     // All should be variable by default
     assert!(template.iter().all(|t| t.is_variable));
-}
\ No newline at end of file
+}
@@ -0,0 +1,96 @@
+use axum::{
+    http::{header::{CACHE_CONTROL, ETAG, IF_NONE_MATCH}, HeaderMap, HeaderValue, StatusCode},
+    response::{IntoResponse, Response},
+};
+use serde::Serialize;
+use sha2::{Digest, Sha256};
+use solana_pda_analyzer_core::PdaAnalysisResult;
+
+/// How long browsers and CDNs may reuse an analysis or derivation. Derivations never change,
+/// but a newer build may name the program or score the match differently, so not forever.
+pub const IMMUTABLE_MAX_AGE_SECS: u64 = 86_400;
+
+/// Weak ETag over `key`. Weak because the bodies it stands for also carry a timestamp and
+/// timings that differ from one response to the next.
+pub fn etag_for<T: Serialize>(key: &T) -> String {
+    let json = serde_json::to_vec(key).expect("cache keys serialize to JSON");
+    format!("W/\"{}\"", hex::encode(&Sha256::digest(json)[..16]))
+}
+
+/// ETag for analysis responses: the derivations found, not how long finding them took.
+/// Dry runs get their own tag since their body says nothing was stored.
+pub fn analysis_etag<'a, I>(results: I, dry_run: bool) -> String
+where
+    I: IntoIterator<Item = &'a PdaAnalysisResult>,
+{
+    let derivations: Vec<_> = results
+        .into_iter()
+        .map(|result| {
+            let pda = &result.pda_info;
+            (pda.address.to_string(), pda.program_id.to_string(), &pda.seeds, pda.bump, result.pattern.name())
+        })
+        .collect();
+    etag_for(&(derivations, dry_run))
+}
+
+/// `response` marked cacheable for `IMMUTABLE_MAX_AGE_SECS` under `etag`, or an empty 304
+/// when the request's `If-None-Match` already holds that tag
+pub fn immutable_response(request_headers: &HeaderMap, etag: &str, response: impl IntoResponse) -> Response {
+    let mut response = if if_none_match(request_headers, etag) {
+        StatusCode::NOT_MODIFIED.into_response()
+    } else {
+        response.into_response()
+    };
+
+    let headers = response.headers_mut();
+    headers.insert(CACHE_CONTROL, HeaderValue::from_str(&format!("public, max-age={}", IMMUTABLE_MAX_AGE_SECS)).expect("valid header"));
+    headers.insert(ETAG, HeaderValue::from_str(etag).expect("ETags are ASCII"));
+    response
+}
+
+/// Whether `If-None-Match` lists `etag`, compared weakly as RFC 9110 requires for this header
+fn if_none_match(headers: &HeaderMap, etag: &str) -> bool {
+    let opaque = |tag: &str| tag.trim().trim_start_matches("W/").to_string();
+    headers
+        .get_all(IF_NONE_MATCH)
+        .iter()
+        .filter_map(|value| value.to_str().ok())
+        .flat_map(|value| value.split(','))
+        .any(|tag| tag.trim() == "*" || opaque(tag) == opaque(etag))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_etag_is_weak_and_stable() {
+        let etag = etag_for(&("address", 254u8));
+        assert!(etag.starts_with("W/\"") && etag.ends_with('"'));
+        assert_eq!(etag, etag_for(&("address", 254u8)));
+        assert_ne!(etag, etag_for(&("address", 253u8)));
+    }
+
+    #[test]
+    fn test_immutable_response_sets_headers_or_answers_304() {
+        let etag = etag_for(&"derivation");
+
+        let response = immutable_response(&HeaderMap::new(), &etag, "body");
+        assert_eq!(response.status(), StatusCode::OK);
+        assert_eq!(response.headers()[CACHE_CONTROL], format!("public, max-age={}", IMMUTABLE_MAX_AGE_SECS));
+        assert_eq!(response.headers()[ETAG], etag.as_str());
+
+        // A strong form of the same tag still matches, as does `*`
+        for if_none_match in [format!("\"other\", {}", etag.trim_start_matches("W/")), "*".to_string()] {
+            let mut headers = HeaderMap::new();
+            headers.insert(IF_NONE_MATCH, HeaderValue::from_str(&if_none_match).unwrap());
+            let response = immutable_response(&headers, &etag, "body");
+            assert_eq!(response.status(), StatusCode::NOT_MODIFIED);
+            assert_eq!(response.headers()[ETAG], etag.as_str());
+        }
+
+        let mut headers = HeaderMap::new();
+        headers.insert(IF_NONE_MATCH, HeaderValue::from_static("W/\"stale\""));
+        assert_eq!(immutable_response(&headers, &etag, "body").status(), StatusCode::OK);
+    }
+}
@@ -0,0 +1,496 @@
+//! Typed client for the analyzer API, over HTTP or against an in-process `Router`.
+//! Request and response bodies are the server's own types, so the two cannot drift apart.
+
+use crate::{ApiError, ApiResponse};
+use axum::Router;
+use hyper::{body::Bytes, client::HttpConnector, Body, Client, Request, Uri};
+use serde::{de::DeserializeOwned, Serialize};
+use solana_pda_analyzer_core::{BuildInfo, PdaAnalysisResult, SeedValue};
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+use thiserror::Error;
+use tower::ServiceExt;
+
+pub use crate::handlers_simple::{AnalyzePdaRequest, BatchAnalyzePdaRequest, CapabilitiesResponse, DerivePdaRequest, DerivePdaResponse, HealthCheckResponse};
+pub use hyper::{Method, StatusCode};
+
+#[derive(Error, Debug)]
+pub enum ClientError {
+    #[error("Invalid base URL: {0}")]
+    InvalidUrl(String),
+
+    #[error("Request failed: {0}")]
+    Transport(String),
+
+    #[error("Request timed out after {0:?}")]
+    Timeout(Duration),
+
+    /// The server answered with its structured error body
+    #[error("{} ({}): {}", .0.error, .0.status_code, .0.message)]
+    Api(ApiError),
+
+    /// The server answered with an error status but no `ApiError` body
+    #[error("HTTP {status}: {body}")]
+    Status { status: StatusCode, body: String },
+
+    #[error("Failed to decode response: {0}")]
+    Decode(#[from] serde_json::Error),
+
+    /// The server failed repeatedly, so requests fail fast until the cooldown ends
+    #[error("Server is failing; not retrying for another {0:?}")]
+    CircuitOpen(Duration),
+}
+
+impl ClientError {
+    /// HTTP status the server answered with, if it answered at all
+    pub fn status(&self) -> Option<StatusCode> {
+        match self {
+            ClientError::Api(error) => StatusCode::from_u16(error.status_code).ok(),
+            ClientError::Status { status, .. } => Some(*status),
+            _ => None,
+        }
+    }
+
+    /// Connection failures and server errors are worth another attempt; a 4xx will fail the
+    /// same way however often it is sent
+    fn is_retryable(&self) -> bool {
+        match self {
+            ClientError::Transport(_) | ClientError::Timeout(_) => true,
+            _ => self.status().is_some_and(|status| status.is_server_error()),
+        }
+    }
+}
+
+pub type ClientResult<T> = Result<T, ClientError>;
+
+#[derive(Debug, Clone)]
+pub struct ClientConfig {
+    /// Limit for a single attempt, including reading the body
+    pub timeout: Duration,
+    pub max_retries: u32,
+    pub retry_delay: Duration,
+    /// Consecutive server failures, across requests, before requests fail fast; 0 never trips
+    pub circuit_threshold: u32,
+    /// How long to fail fast before letting a probe request through
+    pub circuit_cooldown: Duration,
+}
+
+impl Default for ClientConfig {
+    fn default() -> Self {
+        Self {
+            timeout: Duration::from_secs(30),
+            max_retries: 3,
+            retry_delay: Duration::from_millis(500),
+            circuit_threshold: 5,
+            circuit_cooldown: Duration::from_secs(30),
+        }
+    }
+}
+
+/// Exchanges counted by a client since creation or the last `reset_stats`
+#[derive(Debug, Clone, Default)]
+pub struct ClientStats {
+    pub total_requests: u64,
+    pub successful_requests: u64,
+    pub failed_requests: u64,
+    pub total_response_time: Duration,
+}
+
+/// Status and body of a response, before any interpretation
+#[derive(Debug, Clone)]
+pub struct RawResponse {
+    pub status: StatusCode,
+    pub body: Bytes,
+}
+
+impl RawResponse {
+    pub fn json<T: DeserializeOwned>(&self) -> ClientResult<T> {
+        Ok(serde_json::from_slice(&self.body)?)
+    }
+
+    pub fn text(&self) -> String {
+        String::from_utf8_lossy(&self.body).into_owned()
+    }
+
+    /// Unwrap the `ApiResponse` envelope, turning error statuses into `ClientError`s
+    fn into_data<T: DeserializeOwned>(self) -> ClientResult<T> {
+        if !self.status.is_success() {
+            return Err(match serde_json::from_slice::<ApiError>(&self.body) {
+                Ok(error) => ClientError::Api(error),
+                Err(_) => ClientError::Status { status: self.status, body: self.text() },
+            });
+        }
+
+        let response: ApiResponse<T> = self.json()?;
+        match (response.success, response.data) {
+            (true, Some(data)) => Ok(data),
+            (_, _) => Err(ClientError::Status {
+                status: self.status,
+                body: response.error.unwrap_or_else(|| "Response has no data".to_string()),
+            }),
+        }
+    }
+}
+
+/// Counts consecutive server failures and, past the threshold, fails requests fast until a
+/// probe after the cooldown gets through
+#[derive(Debug, Default)]
+struct ServerBreaker {
+    consecutive_failures: u32,
+    open_until: Option<Instant>,
+}
+
+impl ServerBreaker {
+    /// `Err` with the time left while open. Once the cooldown has passed one caller is let
+    /// through as a probe and the breaker stays open for the others.
+    fn allow_request(&mut self, cooldown: Duration) -> Result<(), Duration> {
+        match self.open_until {
+            Some(until) if until > Instant::now() => Err(until - Instant::now()),
+            Some(_) => {
+                self.open_until = Some(Instant::now() + cooldown);
+                Ok(())
+            }
+            None => Ok(()),
+        }
+    }
+
+    /// Any answer short of a server failure shows the server is up, including a 4xx
+    fn record<T>(&mut self, result: &ClientResult<T>, config: &ClientConfig) {
+        match result {
+            Err(e) if e.is_retryable() => {
+                self.consecutive_failures += 1;
+                if config.circuit_threshold > 0 && self.consecutive_failures >= config.circuit_threshold {
+                    self.open_until = Some(Instant::now() + config.circuit_cooldown);
+                }
+            }
+            _ => *self = Self::default(),
+        }
+    }
+
+    fn is_open(&self) -> bool {
+        self.open_until.is_some_and(|until| until > Instant::now())
+    }
+}
+
+#[derive(Clone)]
+enum Transport {
+    Http { client: Client<HttpConnector>, base_url: String },
+    Router(Router),
+}
+
+#[derive(Clone)]
+pub struct PdaAnalyzerClient {
+    transport: Transport,
+    config: ClientConfig,
+    stats: Arc<Mutex<ClientStats>>,
+    breaker: Arc<Mutex<ServerBreaker>>,
+}
+
+impl PdaAnalyzerClient {
+    /// Client for a server at `base_url`, e.g. `http://localhost:8080`
+    pub fn new(base_url: &str) -> ClientResult<Self> {
+        Self::with_config(base_url, ClientConfig::default())
+    }
+
+    pub fn with_config(base_url: &str, config: ClientConfig) -> ClientResult<Self> {
+        let base_url = base_url.trim_end_matches('/').to_string();
+        let uri: Uri = base_url.parse().map_err(|_| ClientError::InvalidUrl(base_url.clone()))?;
+        if uri.scheme_str() != Some("http") || uri.host().is_none() {
+            return Err(ClientError::InvalidUrl(base_url));
+        }
+
+        Ok(Self::from_transport(
+            Transport::Http { client: Client::new(), base_url },
+            config,
+        ))
+    }
+
+    /// Client that calls `router` directly, without a socket; for tests and embedding
+    pub fn from_router(router: Router) -> Self {
+        Self::from_transport(Transport::Router(router), ClientConfig::default())
+    }
+
+    fn from_transport(transport: Transport, config: ClientConfig) -> Self {
+        Self {
+            transport,
+            config,
+            stats: Arc::new(Mutex::new(ClientStats::default())),
+            breaker: Arc::new(Mutex::new(ServerBreaker::default())),
+        }
+    }
+
+    /// Send one request and return the response as-is, whatever its status
+    pub async fn send<B: Serialize>(&self, method: Method, path: &str, body: Option<&B>) -> ClientResult<RawResponse> {
+        let mut request = Request::builder().method(method);
+        let body = match body {
+            Some(body) => {
+                request = request.header(hyper::header::CONTENT_TYPE, "application/json");
+                Body::from(serde_json::to_vec(body)?)
+            }
+            None => Body::empty(),
+        };
+
+        let start = Instant::now();
+        let result = tokio::time::timeout(self.config.timeout, self.exchange(request, path, body))
+            .await
+            .unwrap_or(Err(ClientError::Timeout(self.config.timeout)));
+
+        let mut stats = self.stats.lock().unwrap();
+        stats.total_requests += 1;
+        stats.total_response_time += start.elapsed();
+        match &result {
+            Ok(response) if response.status.is_success() => stats.successful_requests += 1,
+            _ => stats.failed_requests += 1,
+        }
+
+        result
+    }
+
+    async fn exchange(&self, request: hyper::http::request::Builder, path: &str, body: Body) -> ClientResult<RawResponse> {
+        let transport_error = |e: &dyn std::fmt::Display| ClientError::Transport(e.to_string());
+
+        match &self.transport {
+            Transport::Http { client, base_url } => {
+                let request = request
+                    .uri(format!("{}{}", base_url, path))
+                    .body(body)
+                    .map_err(|e| transport_error(&e))?;
+                let response = client.request(request).await.map_err(|e| transport_error(&e))?;
+                let status = response.status();
+                let body = hyper::body::to_bytes(response.into_body()).await.map_err(|e| transport_error(&e))?;
+                Ok(RawResponse { status, body })
+            }
+            Transport::Router(router) => {
+                let request = request.uri(path).body(body).map_err(|e| transport_error(&e))?;
+                let response = router.clone().oneshot(request).await.map_err(|e| transport_error(&e))?;
+                let status = response.status();
+                let body = hyper::body::to_bytes(response.into_body()).await.map_err(|e| transport_error(&e))?;
+                Ok(RawResponse { status, body })
+            }
+        }
+    }
+
+    /// Send a request, retrying connection failures and 5xx responses, and unwrap the
+    /// `ApiResponse` data. While the server keeps failing, requests fail fast with
+    /// `ClientError::CircuitOpen` instead of being sent.
+    pub async fn request<B: Serialize, T: DeserializeOwned>(&self, method: Method, path: &str, body: Option<&B>) -> ClientResult<T> {
+        let mut attempt = 0;
+        loop {
+            self.breaker
+                .lock()
+                .unwrap()
+                .allow_request(self.config.circuit_cooldown)
+                .map_err(ClientError::CircuitOpen)?;
+
+            let result = match self.send(method.clone(), path, body).await {
+                Ok(response) => response.into_data(),
+                Err(e) => Err(e),
+            };
+
+            let circuit_open = {
+                let mut breaker = self.breaker.lock().unwrap();
+                breaker.record(&result, &self.config);
+                breaker.is_open()
+            };
+
+            match result {
+                // The failure that opened the breaker is more useful to return than `CircuitOpen`
+                Err(e) if e.is_retryable() && attempt < self.config.max_retries && !circuit_open => {
+                    attempt += 1;
+                    tokio::time::sleep(self.config.retry_delay).await;
+                }
+                result => return result,
+            }
+        }
+    }
+
+    async fn get<T: DeserializeOwned>(&self, path: &str) -> ClientResult<T> {
+        self.request(Method::GET, path, None::<&()>).await
+    }
+
+    pub async fn health_check(&self) -> ClientResult<HealthCheckResponse> {
+        self.get("/health").await
+    }
+
+    pub async fn analyze_pda(&self, address: &str, program_id: &str) -> ClientResult<PdaAnalysisResult> {
+        let request = AnalyzePdaRequest {
+            address: address.to_string(),
+            program_id: program_id.to_string(),
+        };
+        self.request(Method::POST, "/api/v1/analyze/pda", Some(&request)).await
+    }
+
+    /// One entry per requested PDA, `None` where no pattern matched
+    pub async fn analyze_pdas_batch(&self, pdas: Vec<AnalyzePdaRequest>) -> ClientResult<Vec<Option<PdaAnalysisResult>>> {
+        let request = BatchAnalyzePdaRequest { pdas };
+        self.request(Method::POST, "/api/v1/analyze/pda/batch", Some(&request)).await
+    }
+
+    /// Address and canonical bump `seeds` derive under `program_id`
+    pub async fn derive_pda(&self, program_id: &str, seeds: Vec<SeedValue>) -> ClientResult<DerivePdaResponse> {
+        let request = DerivePdaRequest {
+            program_id: program_id.to_string(),
+            seeds,
+        };
+        self.request(Method::POST, "/api/v1/derive", Some(&request)).await
+    }
+
+    pub async fn capabilities(&self) -> ClientResult<CapabilitiesResponse> {
+        self.get("/api/v1/capabilities").await
+    }
+
+    pub async fn version(&self) -> ClientResult<BuildInfo> {
+        self.get("/api/v1/version").await
+    }
+
+    pub async fn get_performance_metrics(&self) -> ClientResult<HashMap<String, serde_json::Value>> {
+        self.get("/api/v1/analytics/performance").await
+    }
+
+    pub fn stats(&self) -> ClientStats {
+        self.stats.lock().unwrap().clone()
+    }
+
+    pub fn reset_stats(&self) {
+        *self.stats.lock().unwrap() = ClientStats::default();
+    }
+}
+
+// Endpoints backed by PostgreSQL
+#[cfg(feature = "database")]
+impl PdaAnalyzerClient {
+    pub async fn list_programs(&self, limit: Option<i64>, offset: Option<i64>) -> ClientResult<Vec<solana_pda_analyzer_database::ProgramRecord>> {
+        self.get(&with_query("/api/v1/programs", &[("limit", limit.map(|l| l.to_string())), ("offset", offset.map(|o| o.to_string()))])).await
+    }
+
+    pub async fn get_program(&self, program_id: &str) -> ClientResult<solana_pda_analyzer_database::ProgramRecord> {
+        self.get(&format!("/api/v1/programs/{}", program_id)).await
+    }
+
+    pub async fn list_pdas(&self, limit: Option<i64>, program_id: Option<&str>) -> ClientResult<Vec<crate::handlers::PdaListEntry>> {
+        self.get(&with_query("/api/v1/pdas", &[("limit", limit.map(|l| l.to_string())), ("program_id", program_id.map(str::to_string))])).await
+    }
+
+    pub async fn get_database_stats(&self) -> ClientResult<solana_pda_analyzer_database::DatabaseMetrics> {
+        self.get("/api/v1/analytics/database").await
+    }
+
+    pub async fn get_interaction_distribution(&self, program_id: Option<&str>) -> ClientResult<HashMap<String, i64>> {
+        self.get(&with_query("/api/v1/analytics/interactions", &[("program_id", program_id.map(str::to_string))])).await
+    }
+}
+
+/// `path` with the present parameters appended; values are pubkeys and numbers, so need no escaping
+#[cfg(feature = "database")]
+fn with_query(path: &str, params: &[(&str, Option<String>)]) -> String {
+    let query: Vec<String> = params
+        .iter()
+        .filter_map(|(name, value)| value.as_ref().map(|value| format!("{}={}", name, value)))
+        .collect();
+
+    if query.is_empty() {
+        path.to_string()
+    } else {
+        format!("{}?{}", path, query.join("&"))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{create_simple_router, AnalyzerPool, SimpleAppState};
+
+    fn in_process_client() -> PdaAnalyzerClient {
+        PdaAnalyzerClient::from_router(create_simple_router(SimpleAppState {
+            analyzer_pool: AnalyzerPool::new(1),
+            analysis_timeout: Duration::from_secs(5),
+            latency: Default::default(),
+        }))
+    }
+
+    #[test]
+    fn test_rejects_invalid_base_url() {
+        assert!(PdaAnalyzerClient::new("http://localhost:8080/").is_ok());
+        assert!(matches!(PdaAnalyzerClient::new("localhost:8080"), Err(ClientError::InvalidUrl(_))));
+        assert!(matches!(PdaAnalyzerClient::new("https://localhost"), Err(ClientError::InvalidUrl(_))));
+    }
+
+    #[tokio::test]
+    async fn test_in_process_round_trip() {
+        let client = in_process_client();
+
+        assert_eq!(client.health_check().await.unwrap().status, "healthy");
+
+        let program_id = solana_sdk::pubkey::Pubkey::new_unique();
+        let (address, _) = solana_sdk::pubkey::Pubkey::find_program_address(&[b"vault"], &program_id);
+        let result = client.analyze_pda(&address.to_string(), &program_id.to_string()).await.unwrap();
+        assert_eq!(result.pda_info.address, address);
+
+        assert_eq!(client.stats().successful_requests, 2);
+    }
+
+    /// Client for a router answering every request with `status`, and how many it has answered
+    fn failing_client(status: StatusCode, config: ClientConfig) -> (PdaAnalyzerClient, Arc<std::sync::atomic::AtomicUsize>) {
+        let calls = Arc::new(std::sync::atomic::AtomicUsize::new(0));
+        let counter = calls.clone();
+        let router = Router::new().route(
+            "/health",
+            axum::routing::get(move || {
+                counter.fetch_add(1, std::sync::atomic::Ordering::SeqCst);
+                async move { (status, "failing") }
+            }),
+        );
+        (PdaAnalyzerClient::from_transport(Transport::Router(router), config), calls)
+    }
+
+    #[tokio::test]
+    async fn test_client_errors_are_not_retried() {
+        let config = ClientConfig {
+            retry_delay: Duration::ZERO,
+            ..ClientConfig::default()
+        };
+        let (client, calls) = failing_client(StatusCode::UNPROCESSABLE_ENTITY, config);
+
+        let error = client.health_check().await.unwrap_err();
+        assert_eq!(error.status(), Some(StatusCode::UNPROCESSABLE_ENTITY));
+        assert_eq!(calls.load(std::sync::atomic::Ordering::SeqCst), 1);
+    }
+
+    #[tokio::test]
+    async fn test_circuit_opens_after_repeated_server_errors() {
+        let config = ClientConfig {
+            max_retries: 3,
+            retry_delay: Duration::ZERO,
+            circuit_threshold: 2,
+            circuit_cooldown: Duration::from_millis(50),
+            ..ClientConfig::default()
+        };
+        let (client, calls) = failing_client(StatusCode::INTERNAL_SERVER_ERROR, config);
+
+        // Retried once, then the breaker opens and the 500 is returned
+        let error = client.health_check().await.unwrap_err();
+        assert_eq!(error.status(), Some(StatusCode::INTERNAL_SERVER_ERROR));
+        assert_eq!(calls.load(std::sync::atomic::Ordering::SeqCst), 2);
+
+        // Further requests fail fast without reaching the server
+        assert!(matches!(client.health_check().await, Err(ClientError::CircuitOpen(_))));
+        assert_eq!(calls.load(std::sync::atomic::Ordering::SeqCst), 2);
+
+        // After the cooldown a single probe goes out and, failing, reopens the breaker
+        tokio::time::sleep(Duration::from_millis(50)).await;
+        assert_eq!(client.health_check().await.unwrap_err().status(), Some(StatusCode::INTERNAL_SERVER_ERROR));
+        assert_eq!(calls.load(std::sync::atomic::Ordering::SeqCst), 3);
+        assert!(matches!(client.health_check().await, Err(ClientError::CircuitOpen(_))));
+    }
+
+    #[tokio::test]
+    async fn test_api_errors_are_typed() {
+        let client = in_process_client();
+
+        let error = client.analyze_pda("not-a-pubkey", "11111111111111111111111111111111").await.unwrap_err();
+        assert_eq!(error.status(), Some(StatusCode::BAD_REQUEST));
+        assert!(matches!(error, ClientError::Api(ref api_error) if !api_error.field_errors.is_empty()));
+        assert_eq!(client.stats().failed_requests, 1);
+    }
+}
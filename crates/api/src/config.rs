@@ -0,0 +1,197 @@
+use anyhow::{anyhow, Result};
+use std::collections::BTreeMap;
+use std::fmt::Display;
+use std::str::FromStr;
+use tracing::{warn, Level};
+
+/// Every environment variable the servers and their database pool read, with what it sets
+pub const RECOGNIZED_VARS: &[(&str, &str)] = &[
+    ("HOST", "Interface to bind (default 127.0.0.1)"),
+    ("PORT", "Port to bind, 1-65535 (default 8080)"),
+    ("STATIC_FILES_DIR", "Directory served under /static (default: none)"),
+    ("LOG_LEVEL", "error, warn, info, debug or trace (default info)"),
+    ("LOG_FORMAT", "pretty or json (default pretty)"),
+    ("DATABASE_URL", "PostgreSQL URL of the database-backed server"),
+    ("DATABASE_HOST", "PostgreSQL host for DatabaseConfig (default localhost)"),
+    ("DATABASE_PORT", "PostgreSQL port for DatabaseConfig (default 5432)"),
+    ("DATABASE_NAME", "Database name for DatabaseConfig (default solana_pda_analyzer)"),
+    ("DATABASE_USER", "Database user for DatabaseConfig (default postgres)"),
+    ("DATABASE_PASSWORD", "Database password for DatabaseConfig (default empty)"),
+    ("DATABASE_MAX_CONNECTIONS", "Pool size limit (default 10)"),
+    ("DATABASE_MIN_CONNECTIONS", "Connections kept open (default 1)"),
+    ("DATABASE_ACQUIRE_TIMEOUT", "Seconds to wait for a connection (default 30)"),
+    ("DATABASE_IDLE_TIMEOUT", "Seconds before an idle connection closes (default 600)"),
+    ("DATABASE_MAX_LIFETIME", "Seconds before a connection is recycled (default 1800)"),
+    ("SOLANA_RPC_URL", "RPC endpoint transactions are ingested from (default mainnet-beta)"),
+    ("ADMIN_API_TOKEN", "Bearer token for admin endpoints; unset or empty disables them"),
+    ("ANALYZER_WORKERS", "Analyzers serving the simple server concurrently (default: CPU count)"),
+    ("ANALYSIS_TIMEOUT_SECS", "Longest one simple-server analysis may take, in seconds (default 30)"),
+];
+
+/// Prefixes of recognized variables; an unrecognized variable sharing one is likely a typo
+const KNOWN_PREFIXES: &[&str] = &["DATABASE_", "SOLANA_", "LOG_", "ADMIN_", "ANALYZER_", "ANALYSIS_", "STATIC_"];
+
+/// Most edits a typo may be from a recognized name for it to be suggested
+const MAX_SUGGESTION_DISTANCE: usize = 3;
+
+/// A snapshot of environment variables to read configuration from
+#[derive(Debug, Clone, Default)]
+pub struct EnvVars {
+    vars: BTreeMap<String, String>,
+}
+
+impl EnvVars {
+    pub fn from_process() -> Self {
+        Self::from_pairs(std::env::vars())
+    }
+
+    pub fn from_pairs<K: Into<String>, V: Into<String>>(pairs: impl IntoIterator<Item = (K, V)>) -> Self {
+        Self {
+            vars: pairs.into_iter().map(|(k, v)| (k.into(), v.into())).collect(),
+        }
+    }
+
+    /// The variable's value, treating an empty value as unset
+    pub fn get(&self, name: &str) -> Option<&str> {
+        self.vars.get(name).map(String::as_str).filter(|value| !value.is_empty())
+    }
+
+    pub fn get_or(&self, name: &str, default: &str) -> String {
+        self.get(name).unwrap_or(default).to_string()
+    }
+
+    /// The variable parsed as `T` if set, with an error naming the variable and value otherwise
+    pub fn parse<T>(&self, name: &str) -> Result<Option<T>>
+    where
+        T: FromStr,
+        T::Err: Display,
+    {
+        self.get(name)
+            .map(|value| {
+                value
+                    .trim()
+                    .parse()
+                    .map_err(|e| anyhow!("Invalid {} '{}': {}", name, value, e))
+            })
+            .transpose()
+    }
+
+    /// Variables sharing a recognized prefix without being recognized, each with the recognized
+    /// name it most resembles, if any is close
+    pub fn unrecognized(&self) -> Vec<(String, Option<&'static str>)> {
+        self.vars
+            .keys()
+            .filter(|name| KNOWN_PREFIXES.iter().any(|prefix| name.starts_with(prefix)))
+            .filter(|name| !RECOGNIZED_VARS.iter().any(|(known, _)| known == name))
+            .map(|name| (name.clone(), closest_recognized(name)))
+            .collect()
+    }
+
+    /// Log a warning for each of `unrecognized`
+    pub fn warn_unrecognized(&self) {
+        for (name, suggestion) in self.unrecognized() {
+            match suggestion {
+                Some(known) => warn!("Ignoring unrecognized environment variable {}; did you mean {}?", name, known),
+                None => warn!("Ignoring unrecognized environment variable {}", name),
+            }
+        }
+    }
+}
+
+/// Reject a `LOG_LEVEL` that `init_tracing` would refuse
+pub(crate) fn check_log_level(level: &str) -> Result<()> {
+    Level::from_str(level)
+        .map(|_| ())
+        .map_err(|_| anyhow!("Invalid LOG_LEVEL '{}': expected error, warn, info, debug or trace", level))
+}
+
+/// Reject a URL variable whose value doesn't start with one of `schemes`. Only the
+/// database-backed server takes URLs.
+#[cfg(feature = "database")]
+pub(crate) fn check_url_scheme(name: &str, url: &str, schemes: &[&str]) -> Result<()> {
+    if schemes.iter().any(|scheme| url.starts_with(&format!("{}://", scheme))) {
+        return Ok(());
+    }
+    Err(anyhow!("Invalid {} '{}': expected a {}:// URL", name, url, schemes.join("://, ")))
+}
+
+/// Reject a zero where at least one is needed
+pub(crate) fn check_nonzero(name: &str, value: u64) -> Result<()> {
+    if value == 0 {
+        return Err(anyhow!("Invalid {} '0': must be at least 1", name));
+    }
+    Ok(())
+}
+
+fn closest_recognized(name: &str) -> Option<&'static str> {
+    RECOGNIZED_VARS
+        .iter()
+        .map(|(known, _)| (*known, edit_distance(name, known)))
+        .filter(|(_, distance)| *distance <= MAX_SUGGESTION_DISTANCE)
+        .min_by_key(|(_, distance)| *distance)
+        .map(|(known, _)| known)
+}
+
+/// Levenshtein distance between `a` and `b`
+fn edit_distance(a: &str, b: &str) -> usize {
+    let b: Vec<char> = b.chars().collect();
+    let mut previous: Vec<usize> = (0..=b.len()).collect();
+
+    for (i, ca) in a.chars().enumerate() {
+        let mut current = vec![i + 1];
+        for (j, cb) in b.iter().enumerate() {
+            let substitution = previous[j] + usize::from(ca != *cb);
+            current.push(substitution.min(previous[j + 1] + 1).min(current[j] + 1));
+        }
+        previous = current;
+    }
+
+    previous[b.len()]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_reports_variable_and_value() {
+        let env = EnvVars::from_pairs([("PORT", "80a"), ("HOST", "")]);
+        let error = env.parse::<u16>("PORT").unwrap_err().to_string();
+        assert!(error.contains("PORT") && error.contains("80a"), "{}", error);
+
+        assert_eq!(env.parse::<u16>("MISSING").unwrap(), None);
+        assert_eq!(env.get("HOST"), None);
+        assert_eq!(env.get_or("HOST", "127.0.0.1"), "127.0.0.1");
+    }
+
+    #[test]
+    fn test_unrecognized_suggests_close_names() {
+        let env = EnvVars::from_pairs([
+            ("DATABASE_MAX_CONECTIONS", "20"),
+            ("DATABASE_URL", "postgresql://localhost/db"),
+            ("LOG_FROMAT", "json"),
+            ("SOLANA_SOMETHING_ELSE", "1"),
+            ("PATH", "/usr/bin"),
+        ]);
+
+        let unrecognized = env.unrecognized();
+        assert_eq!(unrecognized.len(), 3);
+        assert!(unrecognized.contains(&("DATABASE_MAX_CONECTIONS".to_string(), Some("DATABASE_MAX_CONNECTIONS"))));
+        assert!(unrecognized.contains(&("LOG_FROMAT".to_string(), Some("LOG_FORMAT"))));
+        assert!(unrecognized.contains(&("SOLANA_SOMETHING_ELSE".to_string(), None)));
+    }
+
+    #[test]
+    fn test_checks() {
+        assert!(check_log_level("debug").is_ok());
+        assert!(check_log_level("loud").is_err());
+        assert!(check_nonzero("ANALYZER_WORKERS", 0).is_err());
+    }
+
+    #[cfg(feature = "database")]
+    #[test]
+    fn test_check_url_scheme() {
+        assert!(check_url_scheme("SOLANA_RPC_URL", "https://api.devnet.solana.com", &["http", "https"]).is_ok());
+        assert!(check_url_scheme("SOLANA_RPC_URL", "api.devnet.solana.com", &["http", "https"]).is_err());
+    }
+}
@@ -0,0 +1,57 @@
+use solana_pda_analyzer_analyzer::{AccountPaging, BatchProcessor, IncrementalDiscoveryOutcome, SolanaClient};
+use solana_pda_analyzer_core::Result;
+use solana_pda_analyzer_database::DatabaseRepository;
+use solana_sdk::pubkey::Pubkey;
+use std::collections::HashSet;
+use tracing::info;
+
+/// What `discover_since` analyzed and stored
+#[derive(Debug, Clone)]
+pub struct StoredDiscovery {
+    /// Slot accounts were skipped through: the one asked for, or the last completed run's
+    pub since_slot: Option<u64>,
+    pub outcome: IncrementalDiscoveryOutcome,
+    /// New PDAs recognized and stored
+    pub stored: usize,
+}
+
+/// Analyze `program_id`'s accounts over RPC, skipping those already stored as first seen
+/// through `since_slot`, or through the last completed run's slot when it's `None`, and store
+/// the PDAs found. Only a completed run advances the recorded slot, as a partial one may have
+/// missed older accounts.
+pub async fn discover_since(
+    repository: &DatabaseRepository,
+    client: &SolanaClient,
+    processor: &BatchProcessor,
+    program_id: &Pubkey,
+    since_slot: Option<u64>,
+) -> Result<StoredDiscovery> {
+    let since_slot = match since_slot {
+        Some(slot) => Some(slot),
+        None => repository.discovery_high_water_mark(program_id).await?,
+    };
+    let known = match since_slot {
+        Some(slot) => repository.pdas_recorded_through_slot(program_id, slot).await?,
+        None => HashSet::new(),
+    };
+
+    let outcome = processor
+        .discover_new_accounts(client, program_id, &AccountPaging::default(), &known)
+        .await;
+
+    let mut stored = 0;
+    for result in &outcome.results {
+        repository.store_pda_analysis(result).await?;
+        stored += 1;
+    }
+
+    if let (true, Some(slot)) = (outcome.completed, outcome.scanned_slot) {
+        repository.record_discovery_high_water_mark(program_id, slot).await?;
+    }
+
+    info!(
+        "Discovered {} new PDAs for {} ({} accounts skipped as stored through slot {:?})",
+        stored, program_id, outcome.skipped, since_slot
+    );
+    Ok(StoredDiscovery { since_slot, outcome, stored })
+}
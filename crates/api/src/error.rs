@@ -5,12 +5,18 @@ use axum::{
 };
 use serde::{Deserialize, Serialize};
 use solana_pda_analyzer_core::PdaAnalyzerError;
+use crate::middleware::current_request_id;
+use crate::validation::FieldError;
 
 #[derive(Debug, Serialize, Deserialize)]
 pub struct ApiError {
     pub error: String,
     pub message: String,
     pub status_code: u16,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub request_id: Option<String>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub field_errors: Vec<FieldError>,
 }
 
 impl ApiError {
@@ -19,6 +25,16 @@ impl ApiError {
             error,
             message,
             status_code: status_code.as_u16(),
+            request_id: current_request_id().map(|id| id.to_string()),
+            field_errors: Vec::new(),
+        }
+    }
+
+    pub fn validation_failed(field_errors: Vec<FieldError>) -> Self {
+        let message = format!("Request validation failed for {} field(s)", field_errors.len());
+        Self {
+            field_errors,
+            ..Self::bad_request(message)
         }
     }
 
@@ -30,6 +46,18 @@ impl ApiError {
         Self::new("Not Found".to_string(), message, StatusCode::NOT_FOUND)
     }
 
+    pub fn unauthorized(message: String) -> Self {
+        Self::new("Unauthorized".to_string(), message, StatusCode::UNAUTHORIZED)
+    }
+
+    pub fn forbidden(message: String) -> Self {
+        Self::new("Forbidden".to_string(), message, StatusCode::FORBIDDEN)
+    }
+
+    pub fn conflict(message: String) -> Self {
+        Self::new("Conflict".to_string(), message, StatusCode::CONFLICT)
+    }
+
     pub fn internal_server_error(message: String) -> Self {
         Self::new("Internal Server Error".to_string(), message, StatusCode::INTERNAL_SERVER_ERROR)
     }
@@ -41,12 +69,54 @@ impl ApiError {
     pub fn unprocessable_entity(message: String) -> Self {
         Self::new("Unprocessable Entity".to_string(), message, StatusCode::UNPROCESSABLE_ENTITY)
     }
+
+    pub fn unsupported_media_type(message: String) -> Self {
+        Self::new("Unsupported Media Type".to_string(), message, StatusCode::UNSUPPORTED_MEDIA_TYPE)
+    }
+
+    /// The work behind a request took longer than the server allows
+    pub fn gateway_timeout(message: String) -> Self {
+        Self::new("Gateway Timeout".to_string(), message, StatusCode::GATEWAY_TIMEOUT)
+    }
+
+    /// An error for a status the framework answered on its own, e.g. 405 or 413, named by its
+    /// canonical reason phrase
+    pub fn from_status(status_code: StatusCode, message: String) -> Self {
+        Self::new(
+            status_code.canonical_reason().unwrap_or("Error").to_string(),
+            message,
+            status_code,
+        )
+    }
+
+    /// The database circuit breaker is open; clients should back off and retry
+    pub fn database_unavailable(message: String) -> Self {
+        Self::new("DB_UNAVAILABLE".to_string(), message, StatusCode::SERVICE_UNAVAILABLE)
+    }
+
+    /// Wrap a repository error, keeping fast-fail outages distinct from other failures
+    pub fn database(context: &str, err: PdaAnalyzerError) -> Self {
+        match err {
+            PdaAnalyzerError::DatabaseUnavailable(msg) => Self::database_unavailable(msg),
+            err => Self::internal_server_error(format!("{}: {}", context, err)),
+        }
+    }
+}
+
+/// Wire form of an `ApiError`: the `{ success: false, error, code }` envelope plus the error's own fields
+#[derive(Serialize)]
+struct ErrorBody<'a> {
+    success: bool,
+    code: u16,
+    #[serde(flatten)]
+    error: &'a ApiError,
 }
 
 impl IntoResponse for ApiError {
     fn into_response(self) -> Response {
         let status_code = StatusCode::from_u16(self.status_code).unwrap_or(StatusCode::INTERNAL_SERVER_ERROR);
-        (status_code, Json(self)).into_response()
+        let body = ErrorBody { success: false, code: self.status_code, error: &self };
+        (status_code, Json(body)).into_response()
     }
 }
 
@@ -55,6 +125,7 @@ impl From<PdaAnalyzerError> for ApiError {
         match err {
             PdaAnalyzerError::InvalidSeedData(msg) => ApiError::bad_request(msg),
             PdaAnalyzerError::PdaDerivationFailed(msg) => ApiError::unprocessable_entity(msg),
+            PdaAnalyzerError::SeedCollision(msg) => ApiError::unprocessable_entity(msg),
             PdaAnalyzerError::InvalidProgramId(msg) => ApiError::bad_request(msg),
             PdaAnalyzerError::InvalidPublicKey(msg) => ApiError::bad_request(msg),
             PdaAnalyzerError::TransactionParsingError(msg) => ApiError::unprocessable_entity(msg),
@@ -62,6 +133,14 @@ impl From<PdaAnalyzerError> for ApiError {
             PdaAnalyzerError::SerializationError(msg) => ApiError::internal_server_error(msg),
             PdaAnalyzerError::NetworkError(msg) => ApiError::internal_server_error(msg),
             PdaAnalyzerError::ConfigurationError(msg) => ApiError::internal_server_error(msg),
+            PdaAnalyzerError::DatabaseUnavailable(msg) => ApiError::database_unavailable(msg),
+            #[cfg(feature = "database")]
+            PdaAnalyzerError::Sqlx(sqlx::Error::RowNotFound) => ApiError::not_found("Record not found".to_string()),
+            PdaAnalyzerError::ParsePubkey(e) => ApiError::bad_request(e.to_string()),
+            // Also covers `Sqlx`, which exists whenever core's `database` feature is on, even
+            // if another crate enabled it rather than this one; unreachable when it's off
+            #[allow(unreachable_patterns)]
+            err => ApiError::internal_server_error(err.to_string()),
         }
     }
 }
@@ -86,6 +165,9 @@ pub struct ApiResponse<T> {
     pub data: Option<T>,
     pub error: Option<String>,
     pub timestamp: chrono::DateTime<chrono::Utc>,
+    /// How the request was served, e.g. batch deduplication; omitted when there's nothing to say
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub meta: Option<serde_json::Value>,
 }
 
 impl<T> ApiResponse<T> {
@@ -95,15 +177,22 @@ impl<T> ApiResponse<T> {
             data: Some(data),
             error: None,
             timestamp: chrono::Utc::now(),
+            meta: None,
         }
     }
 
+    pub fn with_meta(mut self, meta: impl Serialize) -> Self {
+        self.meta = serde_json::to_value(meta).ok();
+        self
+    }
+
     pub fn error(error: String) -> Self {
         Self {
             success: false,
             data: None,
             error: Some(error),
             timestamp: chrono::Utc::now(),
+            meta: None,
         }
     }
 }
@@ -1,23 +1,164 @@
-use crate::{ApiError, ApiResponse};
+use crate::{caching, ApiError, ApiResponse};
+use crate::handlers_simple::{dedupe_batch, fan_out, CapabilitiesResponse, DerivePdaRequest, DerivePdaResponse};
+use crate::discovery::{discover_since, StoredDiscovery};
+use crate::ingest::{distinct_signatures, ingest_signatures};
+use crate::middleware::DRY_RUN_HEADER;
 use crate::routes::AppState;
+use crate::validation::{self, FieldError, Validate};
 use axum::{
     extract::{Path, Query, State},
+    http::{header::AUTHORIZATION, HeaderMap},
     Json,
     response::IntoResponse,
 };
 use serde::{Deserialize, Serialize};
-use solana_pda_analyzer_core::PdaAnalysisResult;
-use solana_pda_analyzer_database::{DatabaseMetrics as DatabaseStats, PdaRecord as DbPdaInfo, ProgramRecord as DbProgram};
-use solana_sdk::pubkey::Pubkey;
-use std::str::FromStr;
-use std::collections::HashMap;
+use solana_pda_analyzer_analyzer::{BatchProcessor, DetectedPattern, PatternDetector};
+use solana_pda_analyzer_core::{audit_bumps, AddressKind, BuildInfo, PatternHistogram, PdaAnalyzer, PdaInfo, PdaPattern, PdaPatternTemplate, SeedTemplate, SeedValue};
+use solana_pda_analyzer_database::{
+    PdaRecord as DbPdaInfo, ProgramRecord as DbProgram,
+    CircuitState, CreateCustomPatternRequest, CreateProgramRequest, PdaFilter, ProgramFilter, TransactionFilter, reanalyze_stored_pdas,
+};
+use solana_sdk::{pubkey::Pubkey, signature::Signature};
+use std::collections::{HashMap, HashSet};
 use tracing::{info, error};
 
 // Request/Response types
 #[derive(Debug, Serialize, Deserialize)]
 pub struct AnalyzePdaRequest {
     pub address: String,
-    pub program_id: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub program_id: Option<String>,
+    /// Candidate owner programs, for addresses whose program is unknown
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub program_ids: Vec<String>,
+    /// Only try these pattern families, e.g. `["sequential", "complex"]`
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub patterns: Vec<String>,
+}
+
+impl Validate for AnalyzePdaRequest {
+    fn validate(&self) -> Result<(), Vec<FieldError>> {
+        let mut errors = Vec::new();
+        self.collect_errors("", &mut errors);
+        validation::finish(errors)
+    }
+}
+
+impl Validate for BatchAnalyzePdaRequest {
+    fn validate(&self) -> Result<(), Vec<FieldError>> {
+        let mut errors = Vec::new();
+        if self.pdas.is_empty() {
+            errors.push(FieldError::new("pdas", "must not be empty"));
+        } else if self.pdas.len() > validation::MAX_BATCH_SIZE {
+            errors.push(FieldError::new("pdas", format!("must contain at most {} entries", validation::MAX_BATCH_SIZE)));
+        }
+        for (i, pda) in self.pdas.iter().enumerate() {
+            pda.collect_errors(&format!("pdas[{}].", i), &mut errors);
+        }
+        validation::finish(errors)
+    }
+}
+
+impl AnalyzePdaRequest {
+    fn collect_errors(&self, prefix: &str, errors: &mut Vec<FieldError>) {
+        validation::check_pubkey(&format!("{}address", prefix), &self.address, errors);
+        if let Some(program_id) = &self.program_id {
+            validation::check_pubkey(&format!("{}program_id", prefix), program_id, errors);
+        }
+        for (i, program_id) in self.program_ids.iter().enumerate() {
+            validation::check_pubkey(&format!("{}program_ids[{}]", prefix, i), program_id, errors);
+        }
+        if self.program_id.is_none() && self.program_ids.is_empty() {
+            errors.push(FieldError::new(format!("{}program_id", prefix), "either program_id or program_ids is required"));
+        }
+        if let Err(e) = self.enabled_patterns() {
+            errors.push(FieldError::new(format!("{}patterns", prefix), e.message));
+        }
+    }
+
+    /// Pattern filter from `patterns`, or `None` to use the analyzer's configured set
+    fn enabled_patterns(&self) -> Result<Option<HashSet<PdaPattern>>, ApiError> {
+        if self.patterns.is_empty() {
+            return Ok(None);
+        }
+        PdaPattern::parse_filter(&self.patterns.join(","))
+            .map(Some)
+            .map_err(|e| ApiError::bad_request(e.to_string()))
+    }
+
+    /// All candidate program ids from `program_id` and `program_ids`, deduplicated in request order
+    fn candidate_program_ids(&self) -> Result<Vec<Pubkey>, ApiError> {
+        let mut candidates = Vec::new();
+        for program_id in self.program_id.iter().chain(self.program_ids.iter()) {
+            let program_id = validation::parse_pubkey("program ID", program_id)?;
+            if !candidates.contains(&program_id) {
+                candidates.push(program_id);
+            }
+        }
+
+        if candidates.is_empty() {
+            return Err(ApiError::bad_request("Either program_id or program_ids is required".to_string()));
+        }
+        Ok(candidates)
+    }
+}
+
+/// `?dry_run=true`, or the `X-Dry-Run: true` header, on endpoints that store their analyses:
+/// analyze as usual but persist nothing
+#[derive(Debug, Default, Serialize, Deserialize)]
+pub struct DryRunQuery {
+    #[serde(default)]
+    pub dry_run: bool,
+}
+
+impl DryRunQuery {
+    pub fn requested(&self, headers: &HeaderMap) -> bool {
+        self.dry_run
+            || headers
+                .get(DRY_RUN_HEADER)
+                .and_then(|value| value.to_str().ok())
+                .is_some_and(|value| matches!(value.trim().to_ascii_lowercase().as_str(), "true" | "1"))
+    }
+}
+
+/// Add `"persisted": false` to a response's meta, keeping whatever else it says
+fn mark_not_persisted<T>(mut response: ApiResponse<T>) -> ApiResponse<T> {
+    let mut meta = match response.meta.take() {
+        Some(serde_json::Value::Object(meta)) => meta,
+        _ => serde_json::Map::new(),
+    };
+    meta.insert("persisted".to_string(), serde_json::Value::Bool(false));
+    response.meta = Some(serde_json::Value::Object(meta));
+    response
+}
+
+/// Query string form of `AnalyzePdaRequest` for `GET /api/v1/analyze/pda`; lists are comma-separated
+#[derive(Debug, Deserialize)]
+pub struct AnalyzePdaQuery {
+    pub address: String,
+    pub program_id: Option<String>,
+    pub program_ids: Option<String>,
+    pub patterns: Option<String>,
+}
+
+impl From<AnalyzePdaQuery> for AnalyzePdaRequest {
+    fn from(query: AnalyzePdaQuery) -> Self {
+        let split = |list: Option<String>| -> Vec<String> {
+            list.iter()
+                .flat_map(|list| list.split(','))
+                .map(str::trim)
+                .filter(|item| !item.is_empty())
+                .map(str::to_string)
+                .collect()
+        };
+
+        Self {
+            address: query.address,
+            program_id: query.program_id,
+            program_ids: split(query.program_ids),
+            patterns: split(query.patterns),
+        }
+    }
 }
 
 #[derive(Debug, Serialize, Deserialize)]
@@ -30,6 +171,66 @@ pub struct BatchAnalyzePdaRequest {
     pub pdas: Vec<AnalyzePdaRequest>,
 }
 
+#[derive(Debug, Serialize, Deserialize)]
+pub struct RegisterCustomProgramRequest {
+    pub program_id: String,
+    pub name: String,
+    pub description: Option<String>,
+    #[serde(default)]
+    pub patterns: Vec<CustomPatternRequest>,
+}
+
+/// One program in a `POST /api/v1/programs/bulk` import
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BulkProgramEntry {
+    pub program_id: String,
+    #[serde(default)]
+    pub name: Option<String>,
+    #[serde(default)]
+    pub description: Option<String>,
+}
+
+impl BulkProgramEntry {
+    /// The row to upsert, keyed by the base58 form of `program_id`, or why the entry is rejected
+    fn to_create_request(&self) -> Result<CreateProgramRequest, String> {
+        if let Some(problem) = validation::diagnose_pubkey(&self.program_id) {
+            return Err(format!("program_id {}", problem));
+        }
+        let program_id = solana_pda_analyzer_core::parse_address(&self.program_id).map_err(|e| e.to_string())?;
+
+        Ok(CreateProgramRequest {
+            program_id: program_id.to_string(),
+            name: self.name.clone(),
+            description: self.description.clone(),
+        })
+    }
+}
+
+/// How one entry of a bulk import went
+#[derive(Debug, Serialize, Deserialize)]
+pub struct BulkProgramResult {
+    /// As given in the request
+    pub program_id: String,
+    pub success: bool,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub program: Option<DbProgram>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct CustomPatternRequest {
+    pub pattern_name: String,
+    pub seeds_template: Vec<SeedTemplate>,
+    pub description: Option<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct RegisterCustomProgramResponse {
+    pub program: DbProgram,
+    pub patterns_registered: usize,
+}
+
 #[derive(Debug, Deserialize)]
 pub struct ProgramQuery {
     pub limit: Option<i64>,
@@ -46,12 +247,173 @@ pub struct TransactionQuery {
     pub max_slot: Option<i64>,
 }
 
+/// Optional `program_id` narrowing a stats endpoint to one program
+#[derive(Debug, Deserialize)]
+pub struct ProgramFilterQuery {
+    pub program_id: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct BumpAuditQuery {
+    pub program_id: String,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct CoverageQuery {
+    pub limit: Option<i64>,
+    /// Leave out programs with fewer stored PDAs than this
+    pub min_pdas: Option<i64>,
+}
+
 #[derive(Debug, Deserialize)]
 pub struct PdaQuery {
     pub limit: Option<i64>,
     pub offset: Option<i64>,
     pub program_id: Option<String>,
     pub pattern: Option<String>,
+    /// First seed as a `type:value` spec, e.g. `str:vault`
+    pub seed: Option<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct PrunePdasRequest {
+    /// PDAs not created or re-seen within this many days are pruned
+    pub older_than_days: u32,
+    /// Only count what would be deleted; defaults to true so a bare request is harmless
+    #[serde(default = "default_dry_run")]
+    pub dry_run: bool,
+}
+
+fn default_dry_run() -> bool {
+    true
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct DedupeProgramsRequest {
+    /// Only report what would be merged; defaults to true like pruning
+    #[serde(default = "default_dry_run")]
+    pub dry_run: bool,
+}
+
+/// A stored PDA as `GET /api/v1/pdas` lists it, with its seeds summarized for scanning
+#[derive(Debug, Serialize, Deserialize)]
+pub struct PdaListEntry {
+    #[serde(flatten)]
+    pub pda: DbPdaInfo,
+    /// `PdaRecord::seed_summary`, e.g. `metadata + pubkey + u64`
+    pub seed_summary: String,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct PrunePdasResponse {
+    pub cutoff: chrono::DateTime<chrono::Utc>,
+    pub dry_run: bool,
+    /// Records deleted, or that would be deleted on a dry run
+    pub count: u64,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct IngestTransactionsRequest {
+    /// Transaction signatures, in base58; blanks and repeats are skipped
+    pub signatures: Vec<String>,
+}
+
+impl Validate for IngestTransactionsRequest {
+    fn validate(&self) -> Result<(), Vec<FieldError>> {
+        let mut errors = Vec::new();
+        if self.signatures.is_empty() {
+            errors.push(FieldError::new("signatures", "must not be empty"));
+        } else if self.signatures.len() > validation::MAX_BATCH_SIZE {
+            errors.push(FieldError::new("signatures", format!("must contain at most {} entries", validation::MAX_BATCH_SIZE)));
+        }
+        for (i, signature) in self.signatures.iter().enumerate() {
+            let signature = signature.trim();
+            if !signature.is_empty() && signature.parse::<Signature>().is_err() {
+                errors.push(FieldError::new(format!("signatures[{}]", i), "is not a valid transaction signature"));
+            }
+        }
+        validation::finish(errors)
+    }
+}
+
+#[derive(Debug, Default, Serialize, Deserialize)]
+pub struct DiscoverProgramRequest {
+    /// Skip accounts already stored as first seen at or before this slot. Defaults to the slot
+    /// the last completed discovery ran through; with neither, every account is analyzed.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub since_slot: Option<u64>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct DiscoverProgramResponse {
+    pub program_id: String,
+    pub since_slot: Option<u64>,
+    /// Slot the scan started at, recorded as the next run's `since_slot` once `completed`
+    pub scanned_through_slot: Option<u64>,
+    /// Accounts analyzed
+    pub examined: usize,
+    /// Accounts passed over as already stored
+    pub skipped: usize,
+    /// New PDAs recognized and stored
+    pub stored: usize,
+    pub completed: bool,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct DetectPatternsRequest {
+    pub program_id: String,
+    /// PDAs with their seeds already recovered, e.g. from earlier analyses
+    #[serde(default)]
+    pub pdas: Vec<PdaInfo>,
+    /// Addresses to analyze under `program_id` first; unrecognized ones are left out
+    #[serde(default)]
+    pub addresses: Vec<String>,
+}
+
+impl Validate for DetectPatternsRequest {
+    fn validate(&self) -> Result<(), Vec<FieldError>> {
+        let mut errors = Vec::new();
+        validation::check_pubkey("program_id", &self.program_id, &mut errors);
+
+        let total = self.pdas.len() + self.addresses.len();
+        if total == 0 {
+            errors.push(FieldError::new("pdas", "pdas and addresses must not both be empty"));
+        } else if total > validation::MAX_BATCH_SIZE {
+            errors.push(FieldError::new("pdas", format!("pdas and addresses must contain at most {} entries together", validation::MAX_BATCH_SIZE)));
+        }
+
+        // Detection only counts the requested program's PDAs, so others would vanish silently
+        if let Ok(program_id) = validation::parse_pubkey("program_id", &self.program_id) {
+            for (i, pda) in self.pdas.iter().enumerate() {
+                if pda.program_id != program_id {
+                    errors.push(FieldError::new(format!("pdas[{}].program_id", i), "must match program_id"));
+                }
+            }
+        }
+        for (i, address) in self.addresses.iter().enumerate() {
+            validation::check_pubkey(&format!("addresses[{}]", i), address, &mut errors);
+        }
+        validation::finish(errors)
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct DetectPatternsResponse {
+    /// Seed shapes shared by at least two PDAs, most frequent first
+    pub patterns: Vec<DetectedPattern>,
+    /// PDAs the detection ran over: those given plus the addresses recognized
+    pub pdas_considered: usize,
+    /// Requested addresses no pattern recognized
+    pub unrecognized: Vec<String>,
+}
+
+#[derive(Debug, Default, Serialize, Deserialize)]
+pub struct ReanalyzePdasRequest {
+    /// Only retry PDAs stored with no pattern or `UNKNOWN`
+    #[serde(default)]
+    pub only_unknown: bool,
 }
 
 #[derive(Debug, Serialize, Deserialize)]
@@ -59,6 +421,7 @@ pub struct HealthCheckResponse {
     pub status: String,
     pub timestamp: chrono::DateTime<chrono::Utc>,
     pub database_connected: bool,
+    pub database_circuit: CircuitState,
     pub version: String,
 }
 
@@ -88,10 +451,19 @@ pub async fn health_check(State(state): State<AppState>) -> impl IntoResponse {
         }
     };
 
+    // Report degraded rather than unhealthy: analysis still works without the database
+    let database_circuit = state.database.circuit_state();
+    let status = if database_connected && database_circuit == CircuitState::Closed {
+        "healthy"
+    } else {
+        "degraded"
+    };
+
     let response = HealthCheckResponse {
-        status: "healthy".to_string(),
+        status: status.to_string(),
         timestamp: chrono::Utc::now(),
         database_connected,
+        database_circuit,
         version: env!("CARGO_PKG_VERSION").to_string(),
     };
 
@@ -110,33 +482,159 @@ pub async fn api_docs() -> impl IntoResponse {
         EndpointDoc {
             method: "POST".to_string(),
             path: "/api/v1/analyze/pda".to_string(),
-            description: "Analyze a single PDA".to_string(),
-            example: Some(r#"{"address": "...", "program_id": "..."}"#.to_string()),
+            description: "Analyze a single PDA, optionally against several candidate program_ids; ?dry_run=true or X-Dry-Run: true skips storing it".to_string(),
+            example: Some(r#"{"address": "...", "program_id": "...", "patterns": ["sequential", "complex"]}"#.to_string()),
+        },
+        EndpointDoc {
+            method: "GET".to_string(),
+            path: "/api/v1/analyze/pda".to_string(),
+            description: "Analyze a single PDA from query parameters, for sharing links".to_string(),
+            example: Some("/api/v1/analyze/pda?address=...&program_id=...&patterns=sequential,complex".to_string()),
         },
         EndpointDoc {
             method: "POST".to_string(),
             path: "/api/v1/analyze/pda/batch".to_string(),
-            description: "Batch analyze multiple PDAs".to_string(),
+            description: "Batch analyze multiple PDAs; ?dry_run=true or X-Dry-Run: true skips storing them".to_string(),
             example: Some(r#"{"pdas": [{"address": "...", "program_id": "..."}]}"#.to_string()),
         },
+        EndpointDoc {
+            method: "POST".to_string(),
+            path: "/api/v1/derive".to_string(),
+            description: "Derive a PDA's address and canonical bump from its program and seeds".to_string(),
+            example: Some(r#"{"program_id": "...", "seeds": [{"type": "String", "value": "vault"}, {"type": "U64", "value": 7}]}"#.to_string()),
+        },
+        EndpointDoc {
+            method: "POST".to_string(),
+            path: "/api/v1/patterns/detect".to_string(),
+            description: "Mine recurring seed shapes from PDAs given inline or addresses to analyze first; nothing is stored".to_string(),
+            example: Some(r#"{"program_id": "...", "addresses": ["...", "..."], "pdas": []}"#.to_string()),
+        },
+        EndpointDoc {
+            method: "GET".to_string(),
+            path: "/api/v1/capabilities".to_string(),
+            description: "Enabled patterns, known program count, vocabulary size, limits and version".to_string(),
+            example: None,
+        },
+        EndpointDoc {
+            method: "GET".to_string(),
+            path: "/api/v1/version".to_string(),
+            description: "Version, git commit, build time and rustc version of the running build".to_string(),
+            example: None,
+        },
         EndpointDoc {
             method: "GET".to_string(),
             path: "/api/v1/programs".to_string(),
             description: "List all programs".to_string(),
             example: None,
         },
+        EndpointDoc {
+            method: "POST".to_string(),
+            path: "/api/v1/programs/custom".to_string(),
+            description: "Register a custom program and its seed templates at runtime".to_string(),
+            example: Some(r#"{"program_id": "...", "name": "My Program", "patterns": [{"pattern_name": "Vault", "seeds_template": [...]}]}"#.to_string()),
+        },
         EndpointDoc {
             method: "GET".to_string(),
             path: "/api/v1/programs/:program_id".to_string(),
             description: "Get program details".to_string(),
             example: None,
         },
+        EndpointDoc {
+            method: "GET".to_string(),
+            path: "/api/v1/programs/:program_id/transactions".to_string(),
+            description: "Stored transactions that touched the program's PDAs, newest first; filter by success, min_slot and max_slot".to_string(),
+            example: Some("/api/v1/programs/:program_id/transactions?success=true&limit=50".to_string()),
+        },
+        EndpointDoc {
+            method: "POST".to_string(),
+            path: "/api/v1/programs/bulk".to_string(),
+            description: "Upsert an array of programs in one transaction, reporting each entry's success or error (requires admin token)".to_string(),
+            example: Some(r#"[{"program_id": "TokenkegQfeZyiNwAJbNbGKPFXCWuBvf9Ss623VQ5DA", "name": "SPL Token", "description": "Token program"}]"#.to_string()),
+        },
+        EndpointDoc {
+            method: "POST".to_string(),
+            path: "/api/v1/programs/dedupe".to_string(),
+            description: "Merge program rows storing the same program id in different forms, repointing their PDAs; dry run by default (requires admin token)".to_string(),
+            example: Some(r#"{"dry_run": false}"#.to_string()),
+        },
+        EndpointDoc {
+            method: "POST".to_string(),
+            path: "/api/v1/programs/:program_id/discover".to_string(),
+            description: "Analyze the program's accounts over RPC and store new PDAs, skipping those stored through since_slot or the last completed run (requires admin token)".to_string(),
+            example: Some(r#"{"since_slot": 250000000}"#.to_string()),
+        },
+        EndpointDoc {
+            method: "GET".to_string(),
+            path: "/api/v1/pdas/search".to_string(),
+            description: "Search stored PDAs by first seed (`type:value`) or pattern".to_string(),
+            example: Some("/api/v1/pdas/search?seed=str:vault&limit=50".to_string()),
+        },
+        EndpointDoc {
+            method: "DELETE".to_string(),
+            path: "/api/v1/pdas/:address".to_string(),
+            description: "Delete a stored PDA and its interactions (requires `Authorization: Bearer <admin token>`)".to_string(),
+            example: None,
+        },
+        EndpointDoc {
+            method: "POST".to_string(),
+            path: "/api/v1/pdas/prune".to_string(),
+            description: "Prune PDAs not seen for a number of days; dry run by default (requires admin token)".to_string(),
+            example: Some(r#"{"older_than_days": 30, "dry_run": false}"#.to_string()),
+        },
+        EndpointDoc {
+            method: "POST".to_string(),
+            path: "/api/v1/transactions/ingest".to_string(),
+            description: "Fetch transactions by signature over RPC, analyze them and store their PDAs and interactions (requires admin token)".to_string(),
+            example: Some(r#"{"signatures": ["5VERv8NMvzbJMEkV8xnrLkEaWRtSz9CosKDYjCJjBRnbJLgp8uirBgmQpjKhoR4tjF3ZpRzrFmBV6UjKdiSZkQUW"]}"#.to_string()),
+        },
+        EndpointDoc {
+            method: "POST".to_string(),
+            path: "/api/v1/pdas/reanalyze".to_string(),
+            description: "Retry stored PDAs with the current heuristics and vocabulary, reporting how many were newly resolved (requires admin token)".to_string(),
+            example: Some(r#"{"only_unknown": true}"#.to_string()),
+        },
+        EndpointDoc {
+            method: "GET".to_string(),
+            path: "/api/v1/analytics/patterns/histogram".to_string(),
+            description: "Recognized patterns ranked by frequency, with counts, percentages and a total".to_string(),
+            example: None,
+        },
         EndpointDoc {
             method: "GET".to_string(),
             path: "/api/v1/analytics/database".to_string(),
             description: "Get database metrics".to_string(),
             example: None,
         },
+        EndpointDoc {
+            method: "GET".to_string(),
+            path: "/api/v1/analytics/migrations".to_string(),
+            description: "List schema migrations with when each was applied, or null while pending".to_string(),
+            example: None,
+        },
+        EndpointDoc {
+            method: "GET".to_string(),
+            path: "/api/v1/analytics/fees".to_string(),
+            description: "Get transaction fee statistics, optionally for one program_id".to_string(),
+            example: None,
+        },
+        EndpointDoc {
+            method: "GET".to_string(),
+            path: "/api/v1/analytics/interactions".to_string(),
+            description: "Count account interactions by type, optionally for one program_id".to_string(),
+            example: None,
+        },
+        EndpointDoc {
+            method: "GET".to_string(),
+            path: "/api/v1/analytics/coverage".to_string(),
+            description: "Programs ranked by their share of stored PDAs no heuristic recognized".to_string(),
+            example: Some("/api/v1/analytics/coverage?min_pdas=10&limit=20".to_string()),
+        },
+        EndpointDoc {
+            method: "GET".to_string(),
+            path: "/api/v1/analytics/bumps".to_string(),
+            description: "Audit a program's stored PDAs for non-canonical bumps, with the share that use one".to_string(),
+            example: Some("/api/v1/analytics/bumps?program_id=TokenkegQfeZyiNwAJbNbGKPFXCWuBvf9Ss623VQ5DA".to_string()),
+        },
     ];
 
     let response = ApiDocsResponse {
@@ -152,87 +650,258 @@ pub async fn api_docs() -> impl IntoResponse {
 // PDA analysis handlers
 pub async fn analyze_pda(
     State(state): State<AppState>,
+    Query(options): Query<DryRunQuery>,
+    headers: HeaderMap,
     Json(request): Json<AnalyzePdaRequest>,
 ) -> Result<impl IntoResponse, ApiError> {
-    info!("Analyzing PDA: {} for program: {}", request.address, request.program_id);
+    request.validate().map_err(ApiError::validation_failed)?;
+    let dry_run = options.requested(&headers);
 
-    let address = Pubkey::from_str(&request.address)
-        .map_err(|e| ApiError::bad_request(format!("Invalid PDA address: {}", e)))?;
-    
-    let program_id = Pubkey::from_str(&request.program_id)
-        .map_err(|e| ApiError::bad_request(format!("Invalid program ID: {}", e)))?;
+    let address = validation::parse_pubkey("PDA address", &request.address)?;
 
-    let mut analyzer = state.pda_analyzer.write().await;
-    let result = analyzer.analyze_pda(&address, &program_id)
-        .map_err(|e| ApiError::internal_server_error(format!("Analysis failed: {}", e)))?;
+    let program_ids = request.candidate_program_ids()?;
+    info!("Analyzing PDA: {} for {} candidate program(s)", request.address, program_ids.len());
 
-    match result {
-        Some(analysis_result) => {
-            // Store the result in the database
-            if let Err(e) = state.database.store_pda_analysis(&analysis_result).await {
-                error!("Failed to store PDA analysis: {}", e);
+    let patterns = request.enabled_patterns()?;
+
+    // An address already solved for its one program is served from the database
+    if request.program_ids.is_empty() {
+        match state.database.get_cached_analysis(&address, &program_ids[0]).await {
+            Ok(Some(cached)) if patterns.as_ref().is_none_or(|patterns| patterns.contains(&cached.pattern)) => {
+                info!("Serving stored analysis of {}", address);
+                let etag = caching::analysis_etag([&cached], dry_run);
+                let response = ApiResponse::success(Some(cached));
+                let response = if dry_run { mark_not_persisted(response) } else { response };
+                return Ok(caching::immutable_response(&headers, &etag, Json(response)));
             }
+            Ok(_) => {}
+            Err(e) => error!("Failed to look up stored analysis of {}: {}", address, e),
+        }
+    }
+
+    let results = run_analysis(&state, move |analyzer| {
+        let results = match patterns {
+            Some(patterns) => analyzer.with_enabled_patterns(patterns, |analyzer| {
+                analyzer.analyze_pda_across_programs(&address, &program_ids)
+            }),
+            None => analyzer.analyze_pda_across_programs(&address, &program_ids),
+        }
+            .map_err(|e| ApiError::internal_server_error(format!("Analysis failed: {}", e)))?;
 
-            // Update program stats
-            if let Err(e) = state.database.update_program_pda_count(&request.program_id).await {
-                error!("Failed to update program stats: {}", e);
+        if results.is_empty() {
+            // Name the program only when there was a single candidate; the curve check holds for all of them
+            let unrecognized = analyzer.describe_unrecognized(&address, &program_ids[0]);
+            let message = match (unrecognized.address_kind, program_ids.len()) {
+                (AddressKind::OffCurve, candidates) if candidates > 1 => format!(
+                    "{} is a valid PDA, but no known seed pattern derives it for any of the {} candidate programs",
+                    address, candidates
+                ),
+                _ => unrecognized.message(),
+            };
+            let message = format!("Could not analyze PDA - {}", message);
+
+            // Other candidates may still own PDAs, unless the address itself is on-curve
+            if unrecognized.address_kind == AddressKind::OnCurve
+                || (program_ids.len() == 1 && unrecognized.is_inapplicable())
+            {
+                return Err(ApiError::unprocessable_entity(message));
             }
+            return Err(ApiError::not_found(message));
+        }
+
+        Ok(results)
+    }).await?;
 
-            Ok(Json(ApiResponse::success(analysis_result)))
+    if !dry_run {
+        for analysis_result in &results {
+            // Store the result and update program stats together
+            if let Err(e) = state.database.store_analysis_atomic(analysis_result).await {
+                error!("Failed to store PDA analysis: {}", e);
+            }
         }
-        None => Err(ApiError::not_found("Could not analyze PDA - pattern not recognized".to_string())),
+    }
+
+    // A single program id keeps the original single-result response shape
+    let etag = caching::analysis_etag(&results, dry_run);
+    if request.program_ids.is_empty() {
+        let response = ApiResponse::success(results.into_iter().next());
+        let response = if dry_run { mark_not_persisted(response) } else { response };
+        Ok(caching::immutable_response(&headers, &etag, Json(response)))
+    } else {
+        let response = ApiResponse::success(results);
+        let response = if dry_run { mark_not_persisted(response) } else { response };
+        Ok(caching::immutable_response(&headers, &etag, Json(response)))
     }
 }
 
+/// `GET` form of `analyze_pda`, so analyses can be linked to and cached
+pub async fn analyze_pda_get(
+    State(state): State<AppState>,
+    Query(query): Query<AnalyzePdaQuery>,
+    Query(options): Query<DryRunQuery>,
+    headers: HeaderMap,
+) -> Result<impl IntoResponse, ApiError> {
+    analyze_pda(State(state), Query(options), headers, Json(query.into())).await
+}
+
 pub async fn batch_analyze_pda(
     State(state): State<AppState>,
+    Query(options): Query<DryRunQuery>,
+    headers: HeaderMap,
     Json(request): Json<BatchAnalyzePdaRequest>,
 ) -> Result<impl IntoResponse, ApiError> {
+    request.validate().map_err(ApiError::validation_failed)?;
+    let dry_run = options.requested(&headers);
     info!("Batch analyzing {} PDAs", request.pdas.len());
 
-    let mut results = Vec::new();
-    let mut analyzer = state.pda_analyzer.write().await;
-
-    for pda_request in request.pdas {
-        let address = Pubkey::from_str(&pda_request.address)
-            .map_err(|e| ApiError::bad_request(format!("Invalid PDA address: {}", e)))?;
-        
-        let program_id = Pubkey::from_str(&pda_request.program_id)
-            .map_err(|e| ApiError::bad_request(format!("Invalid program ID: {}", e)))?;
-
-        let result = analyzer.analyze_pda(&address, &program_id)
-            .map_err(|e| ApiError::internal_server_error(format!("Analysis failed: {}", e)))?;
-
-        if let Some(ref analysis_result) = result {
+    let mut targets = Vec::with_capacity(request.pdas.len());
+    let mut keys = Vec::with_capacity(request.pdas.len());
+    for pda_request in &request.pdas {
+        let address = validation::parse_pubkey("PDA address", &pda_request.address)?;
+        let program_ids = pda_request.candidate_program_ids()?;
+        targets.push((address, program_ids.clone(), pda_request.enabled_patterns()?));
+
+        // Requests repeating an earlier one, pattern filter included, are analyzed once
+        let mut patterns = pda_request.patterns.clone();
+        patterns.sort();
+        keys.push((address, program_ids, patterns));
+    }
+    let (firsts, slots) = dedupe_batch(&keys);
+    let targets: Vec<_> = firsts.into_iter().map(|i| targets[i].clone()).collect();
+
+    let results = run_analysis(&state, move |analyzer| {
+        targets
+            .into_iter()
+            .map(|(address, program_ids, patterns)| {
+                // Keep the best match when several candidate programs are given
+                let results = match patterns {
+                    Some(patterns) => analyzer.with_enabled_patterns(patterns, |analyzer| {
+                        analyzer.analyze_pda_across_programs(&address, &program_ids)
+                    }),
+                    None => analyzer.analyze_pda_across_programs(&address, &program_ids),
+                };
+                results
+                    .map(|results| results.into_iter().next())
+                    .map_err(|e| ApiError::internal_server_error(format!("Analysis failed: {}", e)))
+            })
+            .collect::<Result<Vec<_>, _>>()
+    }).await?;
+
+    if !dry_run {
+        for analysis_result in results.iter().flatten() {
             // Store the result in the database
             if let Err(e) = state.database.store_pda_analysis(analysis_result).await {
                 error!("Failed to store PDA analysis: {}", e);
             }
         }
-
-        results.push(result);
     }
 
-    Ok(Json(ApiResponse::success(results)))
+    let (results, meta) = fan_out(results, &slots);
+    let response = ApiResponse::success(results).with_meta(meta);
+    Ok(Json(if dry_run { mark_not_persisted(response) } else { response }))
 }
 
-// Program handlers
-pub async fn list_programs(
+/// Forward-derive a PDA from typed seeds, for clients that can't do the curve math
+pub async fn derive_pda(
     State(state): State<AppState>,
-    Query(query): Query<ProgramQuery>,
+    headers: HeaderMap,
+    Json(request): Json<DerivePdaRequest>,
 ) -> Result<impl IntoResponse, ApiError> {
-    let programs = state.database.get_all_programs().await
-        .map_err(|e| ApiError::internal_server_error(format!("Failed to fetch programs: {}", e)))?;
+    request.validate().map_err(ApiError::validation_failed)?;
+    let program_id = validation::parse_pubkey("program ID", &request.program_id)?;
+
+    let seeds = request.seeds;
+    let pda_info = run_analysis(&state, move |analyzer| {
+        analyzer.derive_pda(&program_id, &seeds).map_err(ApiError::from)
+    }).await?;
+
+    let response = DerivePdaResponse::from(&pda_info);
+    let etag = caching::etag_for(&response);
+    Ok(caching::immutable_response(&headers, &etag, Json(ApiResponse::success(response))))
+}
+
+/// The shared analyzer's configuration, including programs registered at runtime
+pub async fn get_capabilities(State(state): State<AppState>) -> impl IntoResponse {
+    let analyzer = state.pda_analyzer.read().await;
+    Json(ApiResponse::success(CapabilitiesResponse::from_analyzer(&analyzer, None)))
+}
+
+/// The running build's version, commit, build time and compiler
+pub async fn get_version() -> impl IntoResponse {
+    Json(ApiResponse::success(BuildInfo::current()))
+}
+
+/// Mine seed patterns from a caller's PDAs and bare addresses on a fresh detector, storing nothing
+pub async fn detect_patterns(
+    State(state): State<AppState>,
+    Json(request): Json<DetectPatternsRequest>,
+) -> Result<impl IntoResponse, ApiError> {
+    request.validate().map_err(ApiError::validation_failed)?;
+    let program_id = validation::parse_pubkey("program_id", &request.program_id)?;
+    let addresses = request
+        .addresses
+        .iter()
+        .map(|address| validation::parse_pubkey("address", address))
+        .collect::<Result<Vec<_>, _>>()?;
+
+    let mut pdas = request.pdas;
+    let unrecognized = if addresses.is_empty() {
+        Vec::new()
+    } else {
+        let (recognized, unrecognized) = run_analysis(&state, move |analyzer| {
+            let mut recognized = Vec::new();
+            let mut unrecognized = Vec::new();
+            for address in addresses {
+                match analyzer.analyze_pda(&address, &program_id) {
+                    Ok(Some(result)) => recognized.push(result.pda_info),
+                    Ok(None) => unrecognized.push(address.to_string()),
+                    Err(e) => return Err(ApiError::internal_server_error(format!("Analysis of {} failed: {}", address, e))),
+                }
+            }
+            Ok((recognized, unrecognized))
+        }).await?;
+        pdas.extend(recognized);
+        unrecognized
+    };
+
+    let patterns = PatternDetector::new()
+        .detect_patterns(&program_id, &pdas)
+        .map_err(|e| ApiError::internal_server_error(format!("Pattern detection failed: {}", e)))?;
+    info!("Detected {} patterns across {} PDAs of {}", patterns.len(), pdas.len(), program_id);
 
-    let limit = query.limit.unwrap_or(50).min(500) as usize;
-    let offset = query.offset.unwrap_or(0) as usize;
+    Ok(Json(ApiResponse::success(DetectPatternsResponse {
+        patterns,
+        pdas_considered: pdas.len(),
+        unrecognized,
+    })))
+}
 
-    let paginated_programs = programs.into_iter()
-        .skip(offset)
-        .take(limit)
-        .collect::<Vec<_>>();
+/// Run `analyze` on the shared analyzer on the blocking pool. Seed searches are
+/// CPU-bound and would otherwise stall every request scheduled on the same worker.
+async fn run_analysis<T, F>(state: &AppState, analyze: F) -> Result<T, ApiError>
+where
+    T: Send + 'static,
+    F: FnOnce(&mut PdaAnalyzer) -> Result<T, ApiError> + Send + 'static,
+{
+    let mut analyzer = state.pda_analyzer.clone().write_owned().await;
+    tokio::task::spawn_blocking(move || analyze(&mut analyzer))
+        .await
+        .map_err(|e| ApiError::internal_server_error(format!("Analysis task failed: {}", e)))?
+}
 
-    Ok(Json(ApiResponse::success(paginated_programs)))
+// Program handlers
+pub async fn list_programs(
+    State(state): State<AppState>,
+    Query(query): Query<ProgramQuery>,
+) -> Result<impl IntoResponse, ApiError> {
+    let programs = state.database.list_programs(ProgramFilter {
+        limit: Some(query.limit.unwrap_or(50).min(500)),
+        offset: Some(query.offset.unwrap_or(0)),
+        ..Default::default()
+    }).await
+        .map_err(|e| ApiError::database("Failed to fetch programs", e))?;
+
+    Ok(Json(ApiResponse::success(programs)))
 }
 
 pub async fn get_program(
@@ -240,7 +909,7 @@ pub async fn get_program(
     Path(program_id): Path<String>,
 ) -> Result<impl IntoResponse, ApiError> {
     let program = state.database.get_program(&program_id).await
-        .map_err(|e| ApiError::internal_server_error(format!("Failed to fetch program: {}", e)))?;
+        .map_err(|e| ApiError::database("Failed to fetch program", e))?;
 
     match program {
         Some(program) => Ok(Json(ApiResponse::success(program))),
@@ -248,31 +917,149 @@ pub async fn get_program(
     }
 }
 
+pub async fn register_custom_program(
+    State(state): State<AppState>,
+    Json(mut request): Json<RegisterCustomProgramRequest>,
+) -> Result<impl IntoResponse, ApiError> {
+    info!("Registering custom program: {} ({})", request.name, request.program_id);
+
+    let program_id = validation::parse_pubkey("program ID", &request.program_id)?;
+
+    // Pattern matching compares seed types exactly, so store them in canonical form
+    for pattern in &mut request.patterns {
+        for seed in &mut pattern.seeds_template {
+            seed.seed_type = SeedValue::normalize_seed_type(&seed.seed_type)
+                .ok_or_else(|| ApiError::bad_request(format!(
+                    "Unknown seed type '{}' in pattern {}", seed.seed_type, pattern.pattern_name
+                )))?
+                .to_string();
+        }
+    }
+
+    if state.pda_analyzer.read().await.get_program_name(&program_id).is_some() {
+        return Err(ApiError::conflict(format!("Program {} is already registered", program_id)));
+    }
+
+    let mut patterns = Vec::with_capacity(request.patterns.len());
+    for pattern in &request.patterns {
+        patterns.push(CreateCustomPatternRequest {
+            pattern_name: pattern.pattern_name.clone(),
+            seeds_template: serde_json::to_value(&pattern.seeds_template)?,
+            description: pattern.description.clone(),
+        });
+    }
+
+    // The insert skips existing rows, so of two concurrent registrations only one stores anything
+    let (program, records) = state.database.register_custom_program(CreateProgramRequest {
+        program_id: program_id.to_string(),
+        name: Some(request.name.clone()),
+        description: request.description.clone(),
+    }, patterns).await
+        .map_err(|e| ApiError::database("Failed to store program", e))?
+        .ok_or_else(|| ApiError::conflict(format!("Program {} is already registered", program_id)))?;
+
+    // Only teach the live analyzer once everything has been persisted
+    state.pda_analyzer.write().await.register_program(program_id, request.name);
+
+    let patterns_registered = records.len();
+    let mut registry = state.pattern_registry.write().await;
+    for (record, pattern) in records.into_iter().zip(request.patterns) {
+        registry.add_pattern(PdaPatternTemplate {
+            id: record.id,
+            program_id,
+            pattern_name: pattern.pattern_name,
+            seeds_template: pattern.seeds_template,
+            description: pattern.description,
+        });
+    }
+
+    Ok(Json(ApiResponse::success(RegisterCustomProgramResponse {
+        program,
+        patterns_registered,
+    })))
+}
+
+/// Upsert a list of programs in one transaction. Entries that don't validate are reported as
+/// failed and the rest are stored; when a program repeats, its last entry wins.
+pub async fn bulk_upsert_programs(
+    State(state): State<AppState>,
+    headers: HeaderMap,
+    Json(entries): Json<Vec<BulkProgramEntry>>,
+) -> Result<impl IntoResponse, ApiError> {
+    require_admin(&state, &headers)?;
+    if entries.is_empty() {
+        return Err(ApiError::bad_request("No programs given".to_string()));
+    }
+    if entries.len() > validation::MAX_BATCH_SIZE {
+        return Err(ApiError::bad_request(format!(
+            "At most {} programs can be imported at once, got {}",
+            validation::MAX_BATCH_SIZE,
+            entries.len()
+        )));
+    }
+
+    let validated: Vec<_> = entries.iter().map(BulkProgramEntry::to_create_request).collect();
+    let requests: Vec<_> = validated.iter().filter_map(|request| request.as_ref().ok().cloned()).collect();
+    let stored = state.database.batch_create_programs(requests).await
+        .map_err(|e| ApiError::database("Failed to store programs", e))?;
+
+    {
+        let mut analyzer = state.pda_analyzer.write().await;
+        for program in &stored {
+            if let (Some(name), Ok(program_id)) = (&program.name, program.program_id.parse::<Pubkey>()) {
+                analyzer.register_program(program_id, name.clone());
+            }
+        }
+    }
+
+    let stored_count = stored.len();
+    let mut stored = stored.into_iter();
+    let results: Vec<BulkProgramResult> = entries
+        .into_iter()
+        .zip(validated)
+        .map(|(entry, request)| match request {
+            Ok(_) => BulkProgramResult {
+                program_id: entry.program_id,
+                success: true,
+                program: stored.next(),
+                error: None,
+            },
+            Err(error) => BulkProgramResult {
+                program_id: entry.program_id,
+                success: false,
+                program: None,
+                error: Some(error),
+            },
+        })
+        .collect();
+
+    info!("Imported {} of {} programs", stored_count, results.len());
+    let meta = serde_json::json!({
+        "requested": results.len(),
+        "stored": stored_count,
+        "failed": results.len() - stored_count,
+    });
+    Ok(Json(ApiResponse::success(results).with_meta(meta)))
+}
+
 pub async fn get_program_stats(
     State(state): State<AppState>,
     Path(program_id): Path<String>,
 ) -> Result<impl IntoResponse, ApiError> {
     let program = state.database.get_program(&program_id).await
-        .map_err(|e| ApiError::internal_server_error(format!("Failed to fetch program: {}", e)))?;
+        .map_err(|e| ApiError::database("Failed to fetch program", e))?;
 
-    let pdas = state.database.get_program_pdas(&program_id).await
-        .map_err(|e| ApiError::internal_server_error(format!("Failed to fetch PDAs: {}", e)))?;
+    let (total_pdas, pattern_counts) = state.database.count_pdas_and_patterns(&program_id).await
+        .map_err(|e| ApiError::database("Failed to count PDAs", e))?;
 
     let mut stats = HashMap::new();
-    stats.insert("total_pdas".to_string(), serde_json::Value::Number(pdas.len().into()));
+    stats.insert("total_pdas".to_string(), serde_json::Value::Number(total_pdas.into()));
     
     if let Some(program) = program {
         stats.insert("program_name".to_string(), serde_json::Value::String(program.name.unwrap_or("Unknown".to_string())));
         stats.insert("last_analyzed".to_string(), serde_json::to_value(program.last_analyzed).unwrap_or(serde_json::Value::Null));
     }
 
-    // Pattern distribution
-    let mut pattern_counts = HashMap::new();
-    for pda in pdas {
-        if let Some(pattern) = pda.pattern {
-            *pattern_counts.entry(pattern).or_insert(0) += 1;
-        }
-    }
     stats.insert("pattern_distribution".to_string(), serde_json::to_value(pattern_counts).unwrap());
 
     Ok(Json(ApiResponse::success(stats)))
@@ -282,11 +1069,20 @@ pub async fn get_program_patterns(
     State(state): State<AppState>,
     Path(program_id): Path<String>,
 ) -> Result<impl IntoResponse, ApiError> {
-    let pdas = state.database.get_program_pdas(&program_id).await
-        .map_err(|e| ApiError::internal_server_error(format!("Failed to fetch PDAs: {}", e)))?;
+    let (_, pattern_counts) = state.database.count_pdas_and_patterns(&program_id).await
+        .map_err(|e| ApiError::database("Failed to fetch PDAs", e))?;
+
+    // Registered templates count even before any PDA has matched them
+    let registered: Vec<String> = match solana_pda_analyzer_core::parse_address(&program_id) {
+        Ok(program_id) => state.pattern_registry.read().await.templates(&program_id)
+            .iter()
+            .map(|template| template.pattern_name.clone())
+            .collect(),
+        Err(_) => Vec::new(),
+    };
 
-    let patterns: Vec<String> = pdas.into_iter()
-        .filter_map(|pda| pda.pattern)
+    let patterns: Vec<String> = pattern_counts.into_keys()
+        .chain(registered)
         .collect::<std::collections::HashSet<_>>()
         .into_iter()
         .collect();
@@ -299,18 +1095,77 @@ pub async fn get_program_pdas(
     Path(program_id): Path<String>,
     Query(query): Query<PdaQuery>,
 ) -> Result<impl IntoResponse, ApiError> {
-    let pdas = state.database.get_program_pdas(&program_id).await
-        .map_err(|e| ApiError::internal_server_error(format!("Failed to fetch PDAs: {}", e)))?;
+    let program = state.database.get_program(&program_id).await
+        .map_err(|e| ApiError::database("Failed to fetch program", e))?;
+
+    // A program that was never stored has no PDAs
+    let pdas = match program {
+        Some(program) => state.database.list_pdas(PdaFilter {
+            program_id: Some(program.id),
+            limit: Some(query.limit.unwrap_or(50).min(500)),
+            offset: Some(query.offset.unwrap_or(0)),
+            ..Default::default()
+        }).await
+            .map_err(|e| ApiError::database("Failed to fetch PDAs", e))?,
+        None => Vec::new(),
+    };
 
-    let limit = query.limit.unwrap_or(50).min(500) as usize;
-    let offset = query.offset.unwrap_or(0) as usize;
+    Ok(Json(ApiResponse::success(pdas)))
+}
 
-    let paginated_pdas = pdas.into_iter()
-        .skip(offset)
-        .take(limit)
-        .collect::<Vec<_>>();
+/// Stored transactions that revealed or touched the program's PDAs, newest slot first
+pub async fn get_program_transactions(
+    State(state): State<AppState>,
+    Path(program_id): Path<String>,
+    Query(query): Query<TransactionQuery>,
+) -> Result<impl IntoResponse, ApiError> {
+    validation::parse_pubkey("program_id", &program_id)?;
 
-    Ok(Json(ApiResponse::success(paginated_pdas)))
+    let slot_range = match (query.min_slot, query.max_slot) {
+        (None, None) => None,
+        (min_slot, max_slot) => Some((min_slot.unwrap_or(0), max_slot.unwrap_or(i64::MAX))),
+    };
+    let filter = TransactionFilter {
+        program_id: Some(program_id),
+        slot_range,
+        success: query.success,
+        limit: Some(query.limit.unwrap_or(50).clamp(1, 500)),
+        offset: query.offset,
+        ..Default::default()
+    };
+
+    let transactions = state.database.list_transactions(filter).await
+        .map_err(|e| ApiError::database("Failed to fetch transactions", e))?;
+
+    Ok(Json(ApiResponse::success(transactions)))
+}
+
+/// Analyze the program's accounts over RPC, skipping those already stored through
+/// `since_slot`, so repeated runs over a large program only pay for what's new
+pub async fn discover_program_pdas(
+    State(state): State<AppState>,
+    headers: HeaderMap,
+    Path(program_id): Path<String>,
+    request: Option<Json<DiscoverProgramRequest>>,
+) -> Result<impl IntoResponse, ApiError> {
+    require_admin(&state, &headers)?;
+    let program = validation::parse_pubkey("program_id", &program_id)?;
+    let request = request.map(|Json(request)| request).unwrap_or_default();
+
+    let StoredDiscovery { since_slot, outcome, stored } =
+        discover_since(&state.database, &state.solana_client, &BatchProcessor::new(), &program, request.since_slot).await
+            .map_err(|e| ApiError::database("Failed to discover PDAs", e))?;
+
+    Ok(Json(ApiResponse::success(DiscoverProgramResponse {
+        program_id,
+        since_slot,
+        scanned_through_slot: outcome.scanned_slot,
+        examined: outcome.examined,
+        skipped: outcome.skipped,
+        stored,
+        completed: outcome.completed,
+        error: outcome.error,
+    })))
 }
 
 // Transaction handlers
@@ -323,55 +1178,179 @@ pub async fn list_transactions(
 }
 
 pub async fn get_transaction(
-    State(_state): State<AppState>,
-    Path(_signature): Path<String>,
+    State(state): State<AppState>,
+    Path(signature): Path<String>,
 ) -> Result<impl IntoResponse, ApiError> {
-    // TODO: Implement transaction details
-    Err(ApiError::not_implemented("Transaction details not implemented yet".to_string()))
+    let transaction = state.database.get_transaction_by_signature(&signature).await
+        .map_err(|e| ApiError::database("Failed to fetch transaction", e))?;
+
+    match transaction {
+        Some(transaction) => Ok(Json(ApiResponse::success(transaction))),
+        None => Err(ApiError::not_found("Transaction not found".to_string())),
+    }
 }
 
 pub async fn analyze_transaction(
     State(_state): State<AppState>,
     Json(_request): Json<AnalyzeTransactionRequest>,
-) -> Result<impl IntoResponse, ApiError> {
+) -> Result<Json<ApiResponse<()>>, ApiError> {
     // TODO: Implement transaction analysis
     Err(ApiError::not_implemented("Transaction analysis not implemented yet".to_string()))
 }
 
+/// Ingest transactions by signature. Each one costs an RPC call, so large lists are best
+/// split across requests.
+pub async fn ingest_transactions(
+    State(state): State<AppState>,
+    headers: HeaderMap,
+    Json(request): Json<IngestTransactionsRequest>,
+) -> Result<impl IntoResponse, ApiError> {
+    require_admin(&state, &headers)?;
+    request.validate().map_err(ApiError::validation_failed)?;
+
+    let signatures = distinct_signatures(request.signatures.iter().map(String::as_str));
+    let report = ingest_signatures(&state.database, &state.solana_client, &signatures).await;
+
+    Ok(Json(ApiResponse::success(report)))
+}
+
 // PDA handlers
 pub async fn list_pdas(
     State(state): State<AppState>,
     Query(query): Query<PdaQuery>,
 ) -> Result<impl IntoResponse, ApiError> {
-    let limit = query.limit.unwrap_or(50).min(500) as i64;
+    let limit = query.limit.unwrap_or(50).min(500);
     let pdas = state.database.get_recent_pdas(limit).await
-        .map_err(|e| ApiError::internal_server_error(format!("Failed to fetch PDAs: {}", e)))?;
+        .map_err(|e| ApiError::database("Failed to fetch PDAs", e))?;
 
-    Ok(Json(ApiResponse::success(pdas)))
+    let entries: Vec<PdaListEntry> = pdas
+        .into_iter()
+        .map(|pda| PdaListEntry { seed_summary: pda.seed_summary(), pda })
+        .collect();
+    Ok(Json(ApiResponse::success(entries)))
 }
 
 pub async fn get_pda(
-    State(_state): State<AppState>,
-    Path(_address): Path<String>,
+    State(state): State<AppState>,
+    Path(address): Path<String>,
+) -> Result<impl IntoResponse, ApiError> {
+    // PDA addresses are unique in the repository, so the address alone identifies the record
+    let pda = state.database.get_pda_by_address(&address).await
+        .map_err(|e| ApiError::database("Failed to fetch PDA", e))?;
+
+    match pda {
+        Some(pda) => Ok(Json(ApiResponse::success(pda))),
+        None => Err(ApiError::not_found("PDA not found".to_string())),
+    }
+}
+
+pub async fn delete_pda(
+    State(state): State<AppState>,
+    headers: HeaderMap,
+    Path(address): Path<String>,
+) -> Result<impl IntoResponse, ApiError> {
+    require_admin(&state, &headers)?;
+    validation::parse_pubkey("PDA address", &address)?;
+
+    let deleted = state.database.delete_pda(&address).await
+        .map_err(|e| ApiError::database("Failed to delete PDA", e))?;
+    if !deleted {
+        return Err(ApiError::not_found("PDA not found".to_string()));
+    }
+
+    info!("Deleted PDA {}", address);
+    Ok(Json(ApiResponse::success(address)))
+}
+
+pub async fn prune_pdas(
+    State(state): State<AppState>,
+    headers: HeaderMap,
+    Json(request): Json<PrunePdasRequest>,
+) -> Result<impl IntoResponse, ApiError> {
+    require_admin(&state, &headers)?;
+
+    let cutoff = chrono::Utc::now() - chrono::Duration::days(request.older_than_days as i64);
+    let count = state.database.prune_pdas_older_than(cutoff, request.dry_run).await
+        .map_err(|e| ApiError::database("Failed to prune PDAs", e))?;
+
+    Ok(Json(ApiResponse::success(PrunePdasResponse {
+        cutoff,
+        dry_run: request.dry_run,
+        count,
+    })))
+}
+
+/// Merge program rows that store the same program id in different forms, repointing their
+/// PDAs to the surviving row
+pub async fn dedupe_programs(
+    State(state): State<AppState>,
+    headers: HeaderMap,
+    Json(request): Json<DedupeProgramsRequest>,
 ) -> Result<impl IntoResponse, ApiError> {
-    // This endpoint needs both address and program_id, but we only have address
-    // We'll need to search for any PDA with this address
-    // For now, return not implemented
-    Err(ApiError::not_implemented("PDA lookup by address only not implemented yet".to_string()))
+    require_admin(&state, &headers)?;
+
+    let report = state.database.dedupe_programs(request.dry_run).await
+        .map_err(|e| ApiError::database("Failed to merge duplicate programs", e))?;
+
+    Ok(Json(ApiResponse::success(report)))
+}
+
+/// Retry stored PDAs after heuristics or vocabulary improve. Runs on a copy of the analyzer,
+/// so analysis requests aren't held up for the length of the job.
+pub async fn reanalyze_pdas(
+    State(state): State<AppState>,
+    headers: HeaderMap,
+    Json(request): Json<ReanalyzePdasRequest>,
+) -> Result<impl IntoResponse, ApiError> {
+    require_admin(&state, &headers)?;
+
+    let analyzer = state.pda_analyzer.read().await.clone();
+    let report = reanalyze_stored_pdas(&state.database, analyzer, request.only_unknown).await
+        .map_err(|e| ApiError::database("Failed to reanalyze stored PDAs", e))?;
+
+    Ok(Json(ApiResponse::success(report)))
+}
+
+/// Reject the request unless it carries `Authorization: Bearer <admin token>`.
+/// Destructive endpoints stay disabled while no token is configured.
+fn require_admin(state: &AppState, headers: &HeaderMap) -> Result<(), ApiError> {
+    let expected = state.admin_token.as_deref().ok_or_else(|| {
+        ApiError::forbidden("Administrative endpoints are disabled; set ADMIN_API_TOKEN to enable them".to_string())
+    })?;
+
+    let provided = headers
+        .get(AUTHORIZATION)
+        .and_then(|value| value.to_str().ok())
+        .and_then(|value| value.strip_prefix("Bearer "));
+
+    match provided {
+        Some(token) if constant_time_eq(token.as_bytes(), expected.as_bytes()) => Ok(()),
+        _ => Err(ApiError::unauthorized("Missing or invalid admin token".to_string())),
+    }
+}
+
+/// Compare secrets without exiting at the first differing byte
+fn constant_time_eq(a: &[u8], b: &[u8]) -> bool {
+    a.len() == b.len() && a.iter().zip(b).fold(0u8, |diff, (x, y)| diff | (x ^ y)) == 0
 }
 
 pub async fn search_pdas(
     State(state): State<AppState>,
     Query(query): Query<PdaQuery>,
 ) -> Result<impl IntoResponse, ApiError> {
-    let limit = query.limit.unwrap_or(50).min(500) as i64;
-
-    let pdas = if let Some(pattern) = query.pattern {
-        state.database.search_pdas_by_pattern(&pattern, limit).await
-            .map_err(|e| ApiError::internal_server_error(format!("Failed to search PDAs: {}", e)))?
+    let limit = query.limit.unwrap_or(50).min(500);
+
+    let pdas = if let Some(seed) = query.seed {
+        let seed = SeedValue::parse_spec(&seed)
+            .map_err(|e| ApiError::bad_request(format!("Invalid seed filter: {}", e)))?;
+        state.database.find_pdas_by_seed_prefix(&seed, limit).await
+            .map_err(|e| ApiError::database("Failed to search PDAs by seed", e))?
+    } else if let Some(pattern) = query.pattern {
+        state.database.get_pdas_by_pattern(&pattern, limit).await
+            .map_err(|e| ApiError::database("Failed to search PDAs", e))?
     } else {
         state.database.get_recent_pdas(limit).await
-            .map_err(|e| ApiError::internal_server_error(format!("Failed to fetch PDAs: {}", e)))?
+            .map_err(|e| ApiError::database("Failed to fetch PDAs", e))?
     };
 
     Ok(Json(ApiResponse::success(pdas)))
@@ -381,9 +1360,9 @@ pub async fn get_recent_pdas(
     State(state): State<AppState>,
     Query(query): Query<PdaQuery>,
 ) -> Result<impl IntoResponse, ApiError> {
-    let limit = query.limit.unwrap_or(50).min(500) as i64;
+    let limit = query.limit.unwrap_or(50).min(500);
     let pdas = state.database.get_recent_pdas(limit).await
-        .map_err(|e| ApiError::internal_server_error(format!("Failed to fetch recent PDAs: {}", e)))?;
+        .map_err(|e| ApiError::database("Failed to fetch recent PDAs", e))?;
 
     Ok(Json(ApiResponse::success(pdas)))
 }
@@ -393,26 +1372,116 @@ pub async fn get_database_metrics(
     State(state): State<AppState>,
 ) -> Result<impl IntoResponse, ApiError> {
     let stats = state.database.get_stats().await
-        .map_err(|e| ApiError::internal_server_error(format!("Failed to fetch database stats: {}", e)))?;
+        .map_err(|e| ApiError::database("Failed to fetch database stats", e))?;
+
+    Ok(Json(ApiResponse::success(stats)))
+}
+
+pub async fn get_migration_status(
+    State(state): State<AppState>,
+) -> Result<impl IntoResponse, ApiError> {
+    let migrations = state.database.migration_status().await
+        .map_err(|e| ApiError::database("Failed to fetch migration status", e))?;
+
+    Ok(Json(ApiResponse::success(migrations)))
+}
+
+/// Database id of the program a stats query is narrowed to, or `None` for every program
+async fn resolve_program_uuid(state: &AppState, program_id: Option<String>) -> Result<Option<uuid::Uuid>, ApiError> {
+    let Some(program_id) = program_id else {
+        return Ok(None);
+    };
+    let program = state.database.get_program(&program_id).await
+        .map_err(|e| ApiError::database("Failed to fetch program", e))?
+        .ok_or_else(|| ApiError::not_found("Program not found".to_string()))?;
+    Ok(Some(program.id))
+}
+
+pub async fn get_fee_stats(
+    State(state): State<AppState>,
+    Query(query): Query<ProgramFilterQuery>,
+) -> Result<impl IntoResponse, ApiError> {
+    let program_uuid = resolve_program_uuid(&state, query.program_id).await?;
+
+    let stats = state.database.get_fee_stats(program_uuid).await
+        .map_err(|e| ApiError::database("Failed to fetch fee stats", e))?;
 
     Ok(Json(ApiResponse::success(stats)))
 }
 
+/// How many account interactions of each type were recorded, optionally for one program
+pub async fn get_interaction_distribution(
+    State(state): State<AppState>,
+    Query(query): Query<ProgramFilterQuery>,
+) -> Result<impl IntoResponse, ApiError> {
+    let program_uuid = resolve_program_uuid(&state, query.program_id).await?;
+
+    let distribution = state.database.interaction_type_distribution(program_uuid).await
+        .map_err(|e| ApiError::database("Failed to fetch interaction distribution", e))?;
+
+    Ok(Json(ApiResponse::success(distribution)))
+}
+
+/// Which of a program's stored PDAs were derived with a non-canonical bump
+pub async fn get_bump_audit(
+    State(state): State<AppState>,
+    Query(query): Query<BumpAuditQuery>,
+) -> Result<impl IntoResponse, ApiError> {
+    let program_id = validation::parse_pubkey("program_id", &query.program_id)?;
+
+    let pdas = state.database.list_program_pda_infos(&program_id).await
+        .map_err(|e| ApiError::database("Failed to fetch PDAs", e))?;
+
+    // Re-deriving can try every bump per PDA, so keep it off the async workers
+    let audit = tokio::task::spawn_blocking(move || audit_bumps(&program_id, &pdas))
+        .await
+        .map_err(|e| ApiError::internal_server_error(format!("Bump audit failed: {}", e)))?;
+
+    Ok(Json(ApiResponse::success(audit)))
+}
+
+pub async fn get_pattern_coverage(
+    State(state): State<AppState>,
+    Query(query): Query<CoverageQuery>,
+) -> Result<impl IntoResponse, ApiError> {
+    let limit = query.limit.unwrap_or(50).min(500);
+    let min_pdas = query.min_pdas.unwrap_or(1).max(1);
+
+    let coverage = state.database.list_program_coverage(min_pdas, limit).await
+        .map_err(|e| ApiError::database("Failed to fetch pattern coverage", e))?;
+
+    Ok(Json(ApiResponse::success(coverage)))
+}
+
 pub async fn get_pattern_distribution(
     State(state): State<AppState>,
 ) -> Result<impl IntoResponse, ApiError> {
-    let stats = state.database.get_stats().await
-        .map_err(|e| ApiError::internal_server_error(format!("Failed to fetch pattern distribution: {}", e)))?;
+    let distribution = state.database.count_pdas_by_pattern().await
+        .map_err(|e| ApiError::database("Failed to fetch pattern distribution", e))?;
 
-    Ok(Json(ApiResponse::success(stats.patterns_distribution)))
+    Ok(Json(ApiResponse::success(distribution)))
+}
+
+/// Patterns this analyzer has recognized, ranked with counts and percentages
+pub async fn get_pattern_histogram(
+    State(state): State<AppState>,
+) -> Result<impl IntoResponse, ApiError> {
+    let histogram = PatternHistogram::from_counts(
+        state
+            .pattern_stats
+            .snapshot()
+            .into_iter()
+            .map(|(pattern, count)| (pattern.name().into_owned(), count as i64)),
+    );
+
+    Ok(Json(ApiResponse::success(histogram)))
 }
 
 pub async fn get_performance_metrics(
     State(state): State<AppState>,
 ) -> Result<impl IntoResponse, ApiError> {
-    let analyzer = state.pda_analyzer.read().await;
-    let (cache_hits, cache_total) = analyzer.cache_stats();
-    let pattern_stats = analyzer.get_pattern_stats();
+    let (cache_hits, cache_total) = state.pda_analyzer.read().await.cache_stats();
+    let pattern_stats = state.pattern_stats.snapshot();
 
     let mut metrics = HashMap::new();
     metrics.insert("cache_hits".to_string(), serde_json::Value::Number(cache_hits.into()));
@@ -425,6 +1494,7 @@ pub async fn get_performance_metrics(
         }
     ));
     metrics.insert("pattern_stats".to_string(), serde_json::to_value(pattern_stats).unwrap());
+    metrics.insert("latency".to_string(), serde_json::to_value(state.latency.snapshot()).unwrap());
 
     Ok(Json(ApiResponse::success(metrics)))
 }
@@ -432,12 +1502,156 @@ pub async fn get_performance_metrics(
 #[cfg(test)]
 mod tests {
     use super::*;
+
+    #[test]
+    fn test_bulk_program_entries_normalize_or_explain() {
+        let entries: Vec<BulkProgramEntry> = serde_json::from_str(
+            r#"[{"program_id": "TokenkegQfeZyiNwAJbNbGKPFXCWuBvf9Ss623VQ5DA", "name": "SPL Token"}, {"program_id": "not-a-key"}]"#,
+        ).unwrap();
+
+        let request = entries[0].to_create_request().unwrap();
+        assert_eq!(request.program_id, "TokenkegQfeZyiNwAJbNbGKPFXCWuBvf9Ss623VQ5DA");
+        assert_eq!(request.name.as_deref(), Some("SPL Token"));
+        assert!(request.description.is_none());
+
+        let error = entries[1].to_create_request().unwrap_err();
+        assert!(error.starts_with("program_id "), "{}", error);
+    }
+
+    #[test]
+    fn test_prune_request_defaults_to_dry_run() {
+        let request: PrunePdasRequest = serde_json::from_str(r#"{"older_than_days": 30}"#).unwrap();
+        assert!(request.dry_run);
+
+        let request: PrunePdasRequest = serde_json::from_str(r#"{"older_than_days": 30, "dry_run": false}"#).unwrap();
+        assert!(!request.dry_run);
+    }
+
+    #[test]
+    fn test_dry_run_from_query_or_header() {
+        let mut headers = HeaderMap::new();
+        assert!(!DryRunQuery::default().requested(&headers));
+        assert!(DryRunQuery { dry_run: true }.requested(&headers));
+
+        headers.insert(DRY_RUN_HEADER, "TRUE".parse().unwrap());
+        assert!(DryRunQuery::default().requested(&headers));
+        headers.insert(DRY_RUN_HEADER, "no".parse().unwrap());
+        assert!(!DryRunQuery::default().requested(&headers));
+
+        // Batch meta is kept alongside the flag
+        let response = mark_not_persisted(ApiResponse::success(()).with_meta(serde_json::json!({"requested": 2})));
+        assert_eq!(response.meta, Some(serde_json::json!({"requested": 2, "persisted": false})));
+        assert_eq!(mark_not_persisted(ApiResponse::success(())).meta, Some(serde_json::json!({"persisted": false})));
+    }
+
+    #[test]
+    fn test_detect_patterns_request_validation() {
+        let program_id = Pubkey::new_unique();
+        let pda = |program_id: Pubkey| PdaInfo {
+            address: Pubkey::new_unique(),
+            program_id,
+            seeds: vec![SeedValue::String("vault".to_string())],
+            bump: 255,
+            first_seen_slot: None,
+            first_seen_transaction: None,
+        };
+        let request = |pdas: Vec<PdaInfo>, addresses: Vec<String>| DetectPatternsRequest {
+            program_id: program_id.to_string(),
+            pdas,
+            addresses,
+        };
+
+        assert!(request(vec![pda(program_id)], vec![Pubkey::new_unique().to_string()]).validate().is_ok());
+
+        let errors = request(Vec::new(), Vec::new()).validate().unwrap_err();
+        assert_eq!(errors[0].field, "pdas");
+
+        let errors = request(vec![pda(program_id), pda(Pubkey::new_unique())], vec!["not-a-key".to_string()])
+            .validate()
+            .unwrap_err();
+        let fields: Vec<&str> = errors.iter().map(|error| error.field.as_str()).collect();
+        assert_eq!(fields, vec!["pdas[1].program_id", "addresses[0]"]);
+    }
+
+    #[test]
+    fn test_constant_time_eq() {
+        assert!(constant_time_eq(b"secret", b"secret"));
+        assert!(!constant_time_eq(b"secret", b"secreT"));
+        assert!(!constant_time_eq(b"secret", b"secret2"));
+    }
     
     #[test]
     fn test_analyze_pda_request_deserialization() {
         let json = r#"{"address": "11111111111111111111111111111111", "program_id": "11111111111111111111111111111111"}"#;
         let request: AnalyzePdaRequest = serde_json::from_str(json).unwrap();
         assert_eq!(request.address, "11111111111111111111111111111111");
-        assert_eq!(request.program_id, "11111111111111111111111111111111");
+        assert_eq!(request.program_id.as_deref(), Some("11111111111111111111111111111111"));
+        assert!(request.program_ids.is_empty());
+    }
+
+    #[test]
+    fn test_candidate_program_ids() {
+        let json = r#"{"address": "11111111111111111111111111111111", "program_id": "11111111111111111111111111111111", "program_ids": ["11111111111111111111111111111111", "TokenkegQfeZyiNwAJbNbGKPFXCWuBvf9Ss623VQ5DA"]}"#;
+        let request: AnalyzePdaRequest = serde_json::from_str(json).unwrap();
+        assert_eq!(request.candidate_program_ids().unwrap().len(), 2);
+
+        let json = r#"{"address": "11111111111111111111111111111111"}"#;
+        let request: AnalyzePdaRequest = serde_json::from_str(json).unwrap();
+        assert!(request.candidate_program_ids().is_err());
+    }
+
+    #[test]
+    fn test_database_unavailable_maps_to_503() {
+        use solana_pda_analyzer_core::PdaAnalyzerError;
+
+        let error = ApiError::database("Failed to fetch PDAs", PdaAnalyzerError::DatabaseUnavailable("circuit breaker open".to_string()));
+        assert_eq!(error.status_code, 503);
+        assert_eq!(error.error, "DB_UNAVAILABLE");
+
+        let error = ApiError::database("Failed to fetch PDAs", PdaAnalyzerError::DatabaseError("connection reset".to_string()));
+        assert_eq!(error.status_code, 500);
+        assert!(error.message.starts_with("Failed to fetch PDAs: "));
+    }
+
+    #[test]
+    fn test_analyze_pda_query_matches_post_body() {
+        let uri: axum::http::Uri = "/api/v1/analyze/pda?address=11111111111111111111111111111111&program_ids=11111111111111111111111111111111,%20TokenkegQfeZyiNwAJbNbGKPFXCWuBvf9Ss623VQ5DA&patterns=sequential"
+            .parse()
+            .unwrap();
+        let Query(query) = Query::<AnalyzePdaQuery>::try_from_uri(&uri).unwrap();
+        let request = AnalyzePdaRequest::from(query);
+
+        assert!(request.program_id.is_none());
+        assert_eq!(request.candidate_program_ids().unwrap().len(), 2);
+        assert_eq!(request.patterns, vec!["sequential"]);
+        assert!(request.validate().is_ok());
+    }
+
+    #[test]
+    fn test_pattern_filter() {
+        let json = r#"{"address": "11111111111111111111111111111111", "program_id": "11111111111111111111111111111111", "patterns": ["sequential", "complex"]}"#;
+        let request: AnalyzePdaRequest = serde_json::from_str(json).unwrap();
+        let patterns = request.enabled_patterns().unwrap().unwrap();
+        assert!(patterns.contains(&PdaPattern::Sequential));
+        assert!(!patterns.contains(&PdaPattern::AssociatedTokenAccount));
+
+        let json = r#"{"address": "11111111111111111111111111111111", "program_id": "11111111111111111111111111111111", "patterns": ["bogus"]}"#;
+        let request: AnalyzePdaRequest = serde_json::from_str(json).unwrap();
+        let errors = request.validate().unwrap_err();
+        assert_eq!(errors[0].field, "patterns");
+    }
+
+    #[test]
+    fn test_batch_request_validation_reports_fields() {
+        let json = r#"{"pdas": [{"address": "", "program_id": "11111111111111111111111111111111"}, {"address": "invalid_address", "program_id": "11111111111111111111111111111111"}]}"#;
+        let request: BatchAnalyzePdaRequest = serde_json::from_str(json).unwrap();
+
+        let errors = request.validate().unwrap_err();
+        let fields: Vec<_> = errors.iter().map(|e| e.field.as_str()).collect();
+        assert_eq!(fields, vec!["pdas[0].address", "pdas[1].address"]);
+
+        let error = ApiError::validation_failed(errors);
+        assert_eq!(error.status_code, 400);
+        assert_eq!(error.field_errors.len(), 2);
     }
 }
\ No newline at end of file
@@ -1,13 +1,17 @@
-use crate::{ApiError, ApiResponse, AppState};
+use crate::{caching, ApiError, ApiResponse};
+use crate::routes_simple::AppState;
+use crate::validation::{self, FieldError, Validate};
 use axum::{
-    extract::{Path, State},
+    extract::{Path, Query, State},
+    http::{HeaderMap, Method, Uri},
     Json,
     response::IntoResponse,
 };
 use serde::{Deserialize, Serialize};
-use solana_sdk::pubkey::Pubkey;
-use std::str::FromStr;
+use solana_pda_analyzer_core::{PatternHistogram, PdaAnalyzer, PdaInfo, PdaPattern, SeedValue};
 use std::collections::HashMap;
+use std::hash::Hash;
+use std::time::Duration;
 use tracing::info;
 
 // Request/Response types
@@ -22,6 +26,162 @@ pub struct BatchAnalyzePdaRequest {
     pub pdas: Vec<AnalyzePdaRequest>,
 }
 
+/// Response metadata for a batch whose repeated requests were analyzed once
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct BatchDedupMeta {
+    pub requested: usize,
+    /// Distinct requests analyzed
+    pub analyzed: usize,
+    /// Requests answered from an identical one earlier in the batch
+    pub deduplicated: usize,
+}
+
+/// Where each distinct key in `keys` first appears, in order, and for every key which of
+/// those distinct keys it is, so results for the distinct keys can be fanned back out
+pub(crate) fn dedupe_batch<K: Eq + Hash>(keys: &[K]) -> (Vec<usize>, Vec<usize>) {
+    let mut positions: HashMap<&K, usize> = HashMap::new();
+    let mut firsts = Vec::new();
+    let slots = keys
+        .iter()
+        .enumerate()
+        .map(|(i, key)| {
+            *positions.entry(key).or_insert_with(|| {
+                firsts.push(i);
+                firsts.len() - 1
+            })
+        })
+        .collect();
+    (firsts, slots)
+}
+
+/// Answer every request from the results for its distinct key
+pub(crate) fn fan_out<T: Clone>(unique_results: Vec<T>, slots: &[usize]) -> (Vec<T>, BatchDedupMeta) {
+    let meta = BatchDedupMeta {
+        requested: slots.len(),
+        analyzed: unique_results.len(),
+        deduplicated: slots.len() - unique_results.len(),
+    };
+    (slots.iter().map(|&slot| unique_results[slot].clone()).collect(), meta)
+}
+
+impl Validate for AnalyzePdaRequest {
+    fn validate(&self) -> Result<(), Vec<FieldError>> {
+        let mut errors = Vec::new();
+        validation::check_pubkey("address", &self.address, &mut errors);
+        validation::check_pubkey("program_id", &self.program_id, &mut errors);
+        validation::finish(errors)
+    }
+}
+
+impl Validate for BatchAnalyzePdaRequest {
+    fn validate(&self) -> Result<(), Vec<FieldError>> {
+        let mut errors = Vec::new();
+        if self.pdas.is_empty() {
+            errors.push(FieldError::new("pdas", "must not be empty"));
+        } else if self.pdas.len() > validation::MAX_BATCH_SIZE {
+            errors.push(FieldError::new("pdas", format!("must contain at most {} entries", validation::MAX_BATCH_SIZE)));
+        }
+        for (i, pda) in self.pdas.iter().enumerate() {
+            validation::check_pubkey(&format!("pdas[{}].address", i), &pda.address, &mut errors);
+            validation::check_pubkey(&format!("pdas[{}].program_id", i), &pda.program_id, &mut errors);
+        }
+        validation::finish(errors)
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct DerivePdaRequest {
+    pub program_id: String,
+    /// Seeds in order, tagged like `PdaInfo` seeds, e.g. `{"type": "String", "value": "vault"}`
+    pub seeds: Vec<SeedValue>,
+}
+
+impl Validate for DerivePdaRequest {
+    fn validate(&self) -> Result<(), Vec<FieldError>> {
+        let mut errors = Vec::new();
+        validation::check_pubkey("program_id", &self.program_id, &mut errors);
+        validation::finish(errors)
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct DerivePdaResponse {
+    pub address: String,
+    pub program_id: String,
+    /// Canonical bump
+    pub bump: u8,
+}
+
+impl From<&PdaInfo> for DerivePdaResponse {
+    fn from(pda_info: &PdaInfo) -> Self {
+        Self {
+            address: pda_info.address.to_string(),
+            program_id: pda_info.program_id.to_string(),
+            bump: pda_info.bump,
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct PatternCapability {
+    pub name: String,
+    pub description: String,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct CapabilityLimits {
+    pub max_batch_size: usize,
+    /// Most candidate seed sets tried per pattern family; `None` tries everything configured
+    pub candidate_limit: Option<usize>,
+    pub max_seed_string_len: usize,
+    pub search_non_canonical_bumps: bool,
+    pub permute_seed_order: bool,
+    /// Per-request analysis timeout, when the server enforces one
+    pub analysis_timeout_ms: Option<u64>,
+}
+
+/// What the running analyzer supports, so clients can adapt to the server's configuration
+#[derive(Debug, Serialize, Deserialize)]
+pub struct CapabilitiesResponse {
+    pub version: String,
+    pub enabled_patterns: Vec<PatternCapability>,
+    pub known_programs: usize,
+    /// Registered seed strings, global and program-specific
+    pub vocabulary_size: usize,
+    pub candidate_pubkeys: usize,
+    pub limits: CapabilityLimits,
+}
+
+impl CapabilitiesResponse {
+    pub fn from_analyzer(analyzer: &PdaAnalyzer, analysis_timeout: Option<Duration>) -> Self {
+        let config = analyzer.config();
+        let enabled_patterns = PdaPattern::ALL
+            .iter()
+            .filter(|pattern| config.enabled_patterns.contains(*pattern))
+            .map(|pattern| PatternCapability {
+                name: pattern.as_str().to_string(),
+                description: pattern.description().to_string(),
+            })
+            .collect();
+
+        Self {
+            version: env!("CARGO_PKG_VERSION").to_string(),
+            enabled_patterns,
+            known_programs: analyzer.known_program_count(),
+            vocabulary_size: analyzer.candidates().vocabulary_size(),
+            candidate_pubkeys: analyzer.candidates().pubkeys().len(),
+            limits: CapabilityLimits {
+                max_batch_size: validation::MAX_BATCH_SIZE,
+                candidate_limit: config.candidate_limit,
+                max_seed_string_len: config.max_seed_string_len,
+                search_non_canonical_bumps: config.search_non_canonical_bumps,
+                permute_seed_order: config.permute_seed_order,
+                analysis_timeout_ms: analysis_timeout.map(|timeout| timeout.as_millis() as u64),
+            },
+        }
+    }
+}
+
 #[derive(Debug, Serialize, Deserialize)]
 pub struct HealthCheckResponse {
     pub status: String,
@@ -45,6 +205,11 @@ pub struct EndpointDoc {
     pub example: Option<String>,
 }
 
+/// Answers requests no route matches, with the same error body as every other failure
+pub async fn route_not_found(method: Method, uri: Uri) -> ApiError {
+    ApiError::not_found(format!("No endpoint for {} {}", method, uri.path()))
+}
+
 // Health check handler
 pub async fn health_check(_state: State<AppState>) -> impl IntoResponse {
     let response = HealthCheckResponse {
@@ -71,12 +236,36 @@ pub async fn api_docs() -> impl IntoResponse {
             description: "Analyze a single PDA".to_string(),
             example: Some(r#"{"address": "...", "program_id": "..."}"#.to_string()),
         },
+        EndpointDoc {
+            method: "GET".to_string(),
+            path: "/api/v1/analyze/pda".to_string(),
+            description: "Analyze a single PDA from query parameters, for sharing links".to_string(),
+            example: Some("/api/v1/analyze/pda?address=...&program_id=...".to_string()),
+        },
         EndpointDoc {
             method: "POST".to_string(),
             path: "/api/v1/analyze/pda/batch".to_string(),
             description: "Batch analyze multiple PDAs".to_string(),
             example: Some(r#"{"pdas": [{"address": "...", "program_id": "..."}]}"#.to_string()),
         },
+        EndpointDoc {
+            method: "POST".to_string(),
+            path: "/api/v1/derive".to_string(),
+            description: "Derive a PDA's address and canonical bump from its program and seeds".to_string(),
+            example: Some(r#"{"program_id": "...", "seeds": [{"type": "String", "value": "vault"}, {"type": "U64", "value": 7}]}"#.to_string()),
+        },
+        EndpointDoc {
+            method: "GET".to_string(),
+            path: "/api/v1/capabilities".to_string(),
+            description: "Enabled patterns, known program count, vocabulary size, limits and version".to_string(),
+            example: None,
+        },
+        EndpointDoc {
+            method: "GET".to_string(),
+            path: "/api/v1/analytics/patterns/histogram".to_string(),
+            description: "Recognized patterns ranked by frequency, with counts, percentages and a total".to_string(),
+            example: None,
+        },
     ];
 
     let response = ApiDocsResponse {
@@ -92,61 +281,145 @@ pub async fn api_docs() -> impl IntoResponse {
 // PDA analysis handlers
 pub async fn analyze_pda(
     State(state): State<AppState>,
+    headers: HeaderMap,
     Json(request): Json<AnalyzePdaRequest>,
 ) -> Result<impl IntoResponse, ApiError> {
+    request.validate().map_err(ApiError::validation_failed)?;
     info!("Analyzing PDA: {} for program: {}", request.address, request.program_id);
 
-    let address = Pubkey::from_str(&request.address)
-        .map_err(|e| ApiError::bad_request(format!("Invalid PDA address: {}", e)))?;
+    let address = validation::parse_pubkey("PDA address", &request.address)?;
     
-    let program_id = Pubkey::from_str(&request.program_id)
-        .map_err(|e| ApiError::bad_request(format!("Invalid program ID: {}", e)))?;
-
-    let mut analyzer = state.pda_analyzer.write().await;
-    let result = analyzer.analyze_pda(&address, &program_id)
-        .map_err(|e| ApiError::internal_server_error(format!("Analysis failed: {}", e)))?;
+    let program_id = validation::parse_pubkey("program ID", &request.program_id)?;
 
-    match result {
-        Some(analysis_result) => {
-            info!("PDA analysis successful for {}", request.address);
-            Ok(ApiResponse::success(analysis_result))
+    let analysis_result = run_analysis(&state, move |analyzer| {
+        match analyzer.analyze_pda(&address, &program_id)
+            .map_err(|e| ApiError::internal_server_error(format!("Analysis failed: {}", e)))?
+        {
+            Some(analysis_result) => Ok(analysis_result),
+            None => {
+                let unrecognized = analyzer.describe_unrecognized(&address, &program_id);
+                let message = format!("Could not analyze PDA - {}", unrecognized.message());
+                if unrecognized.is_inapplicable() {
+                    Err(ApiError::unprocessable_entity(message))
+                } else {
+                    Err(ApiError::not_found(message))
+                }
+            }
         }
-        None => Err(ApiError::not_found("Could not analyze PDA - pattern not recognized".to_string())),
-    }
+    }).await?;
+
+    info!("PDA analysis successful for {}", request.address);
+    let etag = caching::analysis_etag([&analysis_result], false);
+    Ok(caching::immutable_response(&headers, &etag, ApiResponse::success(analysis_result)))
+}
+
+/// `GET` form of `analyze_pda`, so analyses can be linked to and cached
+pub async fn analyze_pda_get(
+    State(state): State<AppState>,
+    Query(request): Query<AnalyzePdaRequest>,
+    headers: HeaderMap,
+) -> Result<impl IntoResponse, ApiError> {
+    analyze_pda(State(state), headers, Json(request)).await
 }
 
 pub async fn batch_analyze_pda(
     State(state): State<AppState>,
     Json(request): Json<BatchAnalyzePdaRequest>,
 ) -> Result<impl IntoResponse, ApiError> {
+    request.validate().map_err(ApiError::validation_failed)?;
     info!("Batch analyzing {} PDAs", request.pdas.len());
 
-    let mut results = Vec::new();
-    let mut analyzer = state.pda_analyzer.write().await;
+    let mut targets = Vec::with_capacity(request.pdas.len());
+    for pda_request in &request.pdas {
+        let address = validation::parse_pubkey("PDA address", &pda_request.address)?;
+        let program_id = validation::parse_pubkey("program ID", &pda_request.program_id)?;
+        targets.push((address, program_id));
+    }
+    let (firsts, slots) = dedupe_batch(&targets);
+    let targets: Vec<_> = firsts.into_iter().map(|i| targets[i]).collect();
 
-    for pda_request in request.pdas {
-        let address = Pubkey::from_str(&pda_request.address)
-            .map_err(|e| ApiError::bad_request(format!("Invalid PDA address: {}", e)))?;
-        
-        let program_id = Pubkey::from_str(&pda_request.program_id)
-            .map_err(|e| ApiError::bad_request(format!("Invalid program ID: {}", e)))?;
+    let results = run_analysis(&state, move |analyzer| {
+        targets
+            .iter()
+            .map(|(address, program_id)| {
+                analyzer.analyze_pda(address, program_id)
+                    .map_err(|e| ApiError::internal_server_error(format!("Analysis failed: {}", e)))
+            })
+            .collect::<Result<Vec<_>, _>>()
+    }).await?;
 
-        let result = analyzer.analyze_pda(&address, &program_id)
-            .map_err(|e| ApiError::internal_server_error(format!("Analysis failed: {}", e)))?;
+    let (results, meta) = fan_out(results, &slots);
+    Ok(ApiResponse::success(results).with_meta(meta))
+}
+
+/// Forward-derive a PDA from typed seeds, for clients that can't do the curve math
+pub async fn derive_pda(
+    State(state): State<AppState>,
+    headers: HeaderMap,
+    Json(request): Json<DerivePdaRequest>,
+) -> Result<impl IntoResponse, ApiError> {
+    request.validate().map_err(ApiError::validation_failed)?;
+    let program_id = validation::parse_pubkey("program ID", &request.program_id)?;
+
+    let seeds = request.seeds;
+    let pda_info = run_analysis(&state, move |analyzer| {
+        analyzer.derive_pda(&program_id, &seeds).map_err(ApiError::from)
+    }).await?;
+
+    let response = DerivePdaResponse::from(&pda_info);
+    let etag = caching::etag_for(&response);
+    Ok(caching::immutable_response(&headers, &etag, ApiResponse::success(response)))
+}
+
+/// Run `analyze` on a pooled analyzer off the async runtime, answering 504 once
+/// `state.analysis_timeout` passes. Waiting for a free analyzer counts towards the limit.
+/// A timed-out analysis still runs to completion before its analyzer returns to the pool.
+async fn run_analysis<T, F>(state: &AppState, analyze: F) -> Result<T, ApiError>
+where
+    T: Send + 'static,
+    F: FnOnce(&mut PdaAnalyzer) -> Result<T, ApiError> + Send + 'static,
+{
+    let analysis = async {
+        let mut analyzer = state.analyzer_pool.get().await;
+        tokio::task::spawn_blocking(move || analyze(&mut analyzer)).await
+    };
 
-        results.push(result);
+    match tokio::time::timeout(state.analysis_timeout, analysis).await {
+        Ok(Ok(result)) => result,
+        Ok(Err(e)) => Err(ApiError::internal_server_error(format!("Analysis task failed: {}", e))),
+        Err(_) => Err(ApiError::gateway_timeout(format!(
+            "Analysis did not finish within {:.1}s; try fewer PDAs per request or retry later",
+            state.analysis_timeout.as_secs_f64()
+        ))),
     }
+}
 
-    Ok(ApiResponse::success(results))
+/// The pooled analyzers' configuration; every analyzer in the pool is built alike
+pub async fn get_capabilities(State(state): State<AppState>) -> impl IntoResponse {
+    let analyzer = state.analyzer_pool.get().await;
+    ApiResponse::success(CapabilitiesResponse::from_analyzer(&analyzer, Some(state.analysis_timeout)))
+}
+
+/// Patterns recognized across the analyzer pool, ranked with counts and percentages
+pub async fn get_pattern_histogram(
+    State(state): State<AppState>,
+) -> Result<impl IntoResponse, ApiError> {
+    let pattern_stats = state.analyzer_pool.pattern_stats().await;
+    let histogram = PatternHistogram::from_counts(
+        pattern_stats
+            .into_iter()
+            .map(|(pattern, count)| (pattern.name().into_owned(), count as i64)),
+    );
+
+    Ok(ApiResponse::success(histogram))
 }
 
 // Get performance metrics
 pub async fn get_performance_metrics(
     State(state): State<AppState>,
 ) -> Result<impl IntoResponse, ApiError> {
-    let analyzer = state.pda_analyzer.read().await;
-    let (cache_hits, cache_total) = analyzer.cache_stats();
-    let pattern_stats = analyzer.get_pattern_stats();
+    let (cache_hits, cache_total) = state.analyzer_pool.cache_stats().await;
+    let pattern_stats = state.analyzer_pool.pattern_stats().await;
 
     let mut metrics = HashMap::new();
     metrics.insert("cache_hits".to_string(), serde_json::Value::Number(cache_hits.into()));
@@ -159,6 +432,9 @@ pub async fn get_performance_metrics(
         }
     ));
     metrics.insert("pattern_stats".to_string(), serde_json::to_value(pattern_stats).unwrap());
+    metrics.insert("analyzer_workers".to_string(), serde_json::Value::Number(state.analyzer_pool.size().into()));
+    metrics.insert("analyzer_workers_available".to_string(), serde_json::Value::Number(state.analyzer_pool.available().into()));
+    metrics.insert("latency".to_string(), serde_json::to_value(state.latency.snapshot()).unwrap());
 
     Ok(ApiResponse::success(metrics))
 }
@@ -178,4 +454,97 @@ pub async fn list_pdas() -> Result<ApiResponse<Vec<serde_json::Value>>, ApiError
 
 pub async fn get_database_metrics() -> Result<ApiResponse<serde_json::Value>, ApiError> {
     Err(ApiError::not_implemented("Database metrics not implemented yet - database required".to_string()))
-}
\ No newline at end of file
+}
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::pool::AnalyzerPool;
+    use std::time::Duration;
+
+    fn state_with_timeout(analysis_timeout: Duration) -> AppState {
+        AppState {
+            analyzer_pool: AnalyzerPool::new(1),
+            analysis_timeout,
+            latency: Default::default(),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_run_analysis_returns_result_within_timeout() {
+        let state = state_with_timeout(Duration::from_secs(5));
+        let total = run_analysis(&state, |analyzer| Ok(analyzer.cache_stats().1)).await.unwrap();
+        assert_eq!(total, 0);
+    }
+
+    #[tokio::test]
+    async fn test_run_analysis_times_out_with_504() {
+        let state = state_with_timeout(Duration::from_millis(10));
+        let error = run_analysis(&state, |_| {
+            std::thread::sleep(Duration::from_millis(200));
+            Ok(())
+        })
+        .await
+        .unwrap_err();
+
+        assert_eq!(error.status_code, 504);
+        assert!(error.message.contains("did not finish within"));
+    }
+
+    #[tokio::test]
+    async fn test_derive_pda_matches_find_program_address() {
+        let program_id = solana_sdk::pubkey::Pubkey::new_unique();
+        let (address, bump) = solana_sdk::pubkey::Pubkey::find_program_address(&[b"vault", &7u64.to_le_bytes()], &program_id);
+
+        let request: DerivePdaRequest = serde_json::from_value(serde_json::json!({
+            "program_id": program_id.to_string(),
+            "seeds": [{"type": "String", "value": "vault"}, {"type": "U64", "value": 7}],
+        }))
+        .unwrap();
+        let state = state_with_timeout(Duration::from_secs(5));
+        let seeds = request.seeds;
+        let pda_info = run_analysis(&state, move |analyzer| analyzer.derive_pda(&program_id, &seeds).map_err(ApiError::from))
+            .await
+            .unwrap();
+        let response = DerivePdaResponse::from(&pda_info);
+        assert_eq!(response.address, address.to_string());
+        assert_eq!(response.bump, bump);
+
+        // Over-long seeds are the caller's mistake
+        let seeds = vec![SeedValue::String("x".repeat(33))];
+        let error = run_analysis(&state, move |analyzer| analyzer.derive_pda(&program_id, &seeds).map_err(ApiError::from))
+            .await
+            .unwrap_err();
+        assert_eq!(error.status_code, 400);
+    }
+
+    #[test]
+    fn test_dedupe_batch_fans_results_out() {
+        let keys = ["a", "b", "a", "c", "b"];
+        let (firsts, slots) = dedupe_batch(&keys);
+        assert_eq!(firsts, vec![0, 1, 3]);
+        assert_eq!(slots, vec![0, 1, 0, 2, 1]);
+
+        let results: Vec<String> = firsts.iter().map(|&i| keys[i].to_uppercase()).collect();
+        let (results, meta) = fan_out(results, &slots);
+        assert_eq!(results, vec!["A", "B", "A", "C", "B"]);
+        assert_eq!(meta, BatchDedupMeta { requested: 5, analyzed: 3, deduplicated: 2 });
+    }
+
+    #[test]
+    fn test_capabilities_reflect_registrations() {
+        let mut analyzer = PdaAnalyzer::new();
+        let before = CapabilitiesResponse::from_analyzer(&analyzer, None);
+        assert_eq!(before.enabled_patterns.len(), PdaPattern::ALL.len());
+        assert_eq!(before.limits.max_batch_size, validation::MAX_BATCH_SIZE);
+        assert!(before.limits.analysis_timeout_ms.is_none());
+
+        let program_id = solana_sdk::pubkey::Pubkey::new_unique();
+        analyzer.register_program(program_id, "Custom Program".to_string());
+        analyzer.register_seed_strings(program_id, vec!["escrow".to_string(), "ledger".to_string()]);
+
+        let after = CapabilitiesResponse::from_analyzer(&analyzer, Some(Duration::from_secs(30)));
+        assert_eq!(after.known_programs, before.known_programs + 1);
+        assert_eq!(after.vocabulary_size, before.vocabulary_size + 2);
+        assert_eq!(after.limits.analysis_timeout_ms, Some(30_000));
+    }
+}
@@ -0,0 +1,141 @@
+use serde::{Deserialize, Serialize};
+use solana_pda_analyzer_analyzer::{BatchProcessor, SolanaClient};
+use solana_pda_analyzer_core::{PdaAnalyzerError, Result, TransactionAnalysis};
+use solana_pda_analyzer_database::{DatabaseRepository, StoredTransaction};
+use std::collections::HashSet;
+use tracing::{info, warn};
+
+/// A signature that could not be ingested, and why
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct IngestFailure {
+    pub signature: String,
+    pub error: String,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct IngestReport {
+    /// Distinct signatures requested
+    pub requested: usize,
+    /// Transactions fetched, analyzed and stored
+    pub stored: usize,
+    /// PDAs discovered across the stored transactions
+    pub pdas: usize,
+    /// PDA interactions stored
+    pub interactions: usize,
+    pub failed: Vec<IngestFailure>,
+}
+
+/// Trimmed signatures in order, skipping blank lines, `#` comments and repeats, so a file of
+/// signatures can be passed as `text.lines()`
+pub fn distinct_signatures<'a>(lines: impl IntoIterator<Item = &'a str>) -> Vec<String> {
+    let mut seen = HashSet::new();
+    lines
+        .into_iter()
+        .map(str::trim)
+        .filter(|line| !line.is_empty() && !line.starts_with('#'))
+        .filter(|line| seen.insert(*line))
+        .map(str::to_string)
+        .collect()
+}
+
+/// Analyzed transactions written per database transaction
+const INGEST_BATCH_SIZE: usize = 25;
+
+/// Fetch each transaction over RPC, run the transaction analyzer on it and store it with its
+/// PDAs and interactions. Writes go to the database in batches of `INGEST_BATCH_SIZE`
+/// transactions; a signature that fails to fetch, analyze or store is reported and the rest
+/// carry on.
+pub async fn ingest_signatures(
+    repository: &DatabaseRepository,
+    client: &SolanaClient,
+    signatures: &[String],
+) -> IngestReport {
+    let processor = BatchProcessor::new();
+    let mut report = IngestReport {
+        requested: signatures.len(),
+        ..IngestReport::default()
+    };
+
+    let mut batch = Vec::with_capacity(INGEST_BATCH_SIZE);
+    for signature in signatures {
+        match analyze_one(client, &processor, signature).await {
+            Ok(analyzed) => batch.push(analyzed),
+            Err(e) => report.fail(signature, e),
+        }
+        if batch.len() == INGEST_BATCH_SIZE {
+            store_batch(repository, &mut batch, &mut report).await;
+        }
+    }
+    store_batch(repository, &mut batch, &mut report).await;
+
+    info!(
+        "Ingested {} of {} transactions: {} PDAs, {} interactions",
+        report.stored, report.requested, report.pdas, report.interactions
+    );
+    report
+}
+
+impl IngestReport {
+    fn record(&mut self, stored: &StoredTransaction) {
+        self.stored += 1;
+        self.pdas += stored.pdas;
+        self.interactions += stored.interactions;
+    }
+
+    fn fail(&mut self, signature: &str, error: PdaAnalyzerError) {
+        warn!("Failed to ingest transaction {}: {}", signature, error);
+        self.failed.push(IngestFailure {
+            signature: signature.to_string(),
+            error: error.to_string(),
+        });
+    }
+}
+
+async fn analyze_one(
+    client: &SolanaClient,
+    processor: &BatchProcessor,
+    signature: &str,
+) -> Result<(TransactionAnalysis, Option<i64>)> {
+    let encoded = client.get_transaction_with_meta(signature).await?;
+    let fee = encoded.transaction.meta.as_ref().map(|meta| meta.fee as i64);
+    let analysis = processor.process_transaction(encoded).await?;
+    Ok((analysis, fee))
+}
+
+/// Store and drain `batch` in one database transaction. A failed batch is retried one
+/// transaction at a time, so only the signatures that can't be stored are reported.
+async fn store_batch(
+    repository: &DatabaseRepository,
+    batch: &mut Vec<(TransactionAnalysis, Option<i64>)>,
+    report: &mut IngestReport,
+) {
+    if batch.is_empty() {
+        return;
+    }
+
+    match repository.store_transaction_analyses(batch).await {
+        Ok(stored) => stored.iter().for_each(|stored| report.record(stored)),
+        Err(e) => {
+            warn!("Failed to store a batch of {} transactions, storing them one by one: {}", batch.len(), e);
+            for (analysis, fee) in batch.iter() {
+                match repository.store_transaction_analysis(analysis, *fee).await {
+                    Ok(stored) => report.record(&stored),
+                    Err(e) => report.fail(&analysis.signature, e),
+                }
+            }
+        }
+    }
+    batch.clear();
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_distinct_signatures() {
+        let text = "# exported from the explorer\n  sigA  \n\nsigB\nsigA\n";
+        assert_eq!(distinct_signatures(text.lines()), vec!["sigA".to_string(), "sigB".to_string()]);
+        assert!(distinct_signatures("\n# nothing\n".lines()).is_empty());
+    }
+}
@@ -0,0 +1,189 @@
+use axum::{
+    extract::{MatchedPath, State},
+    http::Request,
+    middleware::Next,
+    response::Response,
+};
+use hdrhistogram::Histogram;
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashMap};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+/// Percentiles cover between one and two windows of recent requests
+pub const DEFAULT_LATENCY_WINDOW: Duration = Duration::from_secs(300);
+
+/// Slowest latency tracked; anything longer is recorded as this
+const MAX_TRACKED_MICROS: u64 = 60_000_000;
+
+/// Latency percentiles for one route, in milliseconds
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct RouteLatency {
+    pub count: u64,
+    pub p50_ms: f64,
+    pub p90_ms: f64,
+    pub p99_ms: f64,
+    pub max_ms: f64,
+}
+
+/// Per-route request latencies over a rolling window, kept as HDR histograms
+/// (3 significant figures, fixed memory per route)
+#[derive(Debug, Clone)]
+pub struct LatencyRecorder {
+    routes: Arc<Mutex<HashMap<String, RouteWindow>>>,
+    window: Duration,
+}
+
+/// The current window's histogram and the one before it; percentiles merge both,
+/// so they never drop to nothing just after a rotation
+#[derive(Debug)]
+struct RouteWindow {
+    current: Histogram<u64>,
+    previous: Histogram<u64>,
+    started: Instant,
+}
+
+impl RouteWindow {
+    fn new(now: Instant) -> Self {
+        Self {
+            current: new_histogram(),
+            previous: new_histogram(),
+            started: now,
+        }
+    }
+
+    fn rotate_if_due(&mut self, now: Instant, window: Duration) {
+        let elapsed = now.duration_since(self.started);
+        if elapsed < window {
+            return;
+        }
+
+        if elapsed < window * 2 {
+            std::mem::swap(&mut self.previous, &mut self.current);
+        } else {
+            // Idle for over a window: everything held is stale
+            self.previous.reset();
+        }
+        self.current.reset();
+        self.started = now;
+    }
+}
+
+fn new_histogram() -> Histogram<u64> {
+    Histogram::new_with_bounds(1, MAX_TRACKED_MICROS, 3).expect("valid histogram bounds")
+}
+
+impl Default for LatencyRecorder {
+    fn default() -> Self {
+        Self::new(DEFAULT_LATENCY_WINDOW)
+    }
+}
+
+impl LatencyRecorder {
+    pub fn new(window: Duration) -> Self {
+        Self {
+            routes: Arc::new(Mutex::new(HashMap::new())),
+            window,
+        }
+    }
+
+    pub fn record(&self, route: &str, latency: Duration) {
+        let now = Instant::now();
+        let micros = (latency.as_micros() as u64).clamp(1, MAX_TRACKED_MICROS);
+
+        let mut routes = self.routes.lock().unwrap();
+        let window = routes
+            .entry(route.to_string())
+            .or_insert_with(|| RouteWindow::new(now));
+        window.rotate_if_due(now, self.window);
+        window.current.saturating_record(micros);
+    }
+
+    /// Percentiles for every route seen in the window, keyed `METHOD /path`
+    pub fn snapshot(&self) -> BTreeMap<String, RouteLatency> {
+        let now = Instant::now();
+        let mut routes = self.routes.lock().unwrap();
+
+        routes
+            .iter_mut()
+            .filter_map(|(route, window)| {
+                window.rotate_if_due(now, self.window);
+
+                let mut merged = window.previous.clone();
+                merged.add(&window.current).ok()?;
+                if merged.is_empty() {
+                    return None;
+                }
+
+                let ms = |micros: u64| micros as f64 / 1000.0;
+                Some((route.clone(), RouteLatency {
+                    count: merged.len(),
+                    p50_ms: ms(merged.value_at_quantile(0.50)),
+                    p90_ms: ms(merged.value_at_quantile(0.90)),
+                    p99_ms: ms(merged.value_at_quantile(0.99)),
+                    max_ms: ms(merged.max()),
+                }))
+            })
+            .collect()
+    }
+}
+
+/// Record each request's latency under its route template, so `/api/v1/pdas/:address`
+/// is one entry rather than one per address. Unmatched requests share a single entry.
+pub async fn latency_middleware<B>(
+    State(recorder): State<LatencyRecorder>,
+    request: Request<B>,
+    next: Next<B>,
+) -> Response {
+    let route = format!(
+        "{} {}",
+        request.method(),
+        request
+            .extensions()
+            .get::<MatchedPath>()
+            .map(MatchedPath::as_str)
+            .unwrap_or("<unmatched>")
+    );
+
+    let start = Instant::now();
+    let response = next.run(request).await;
+    recorder.record(&route, start.elapsed());
+
+    response
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_percentiles_per_route() {
+        let recorder = LatencyRecorder::default();
+        for ms in 1..=100 {
+            recorder.record("GET /health", Duration::from_millis(ms));
+        }
+        recorder.record("POST /api/v1/analyze/pda", Duration::from_millis(40));
+
+        let snapshot = recorder.snapshot();
+        let health = &snapshot["GET /health"];
+        assert_eq!(health.count, 100);
+        assert!((health.p50_ms - 50.0).abs() < 0.1);
+        assert!((health.p90_ms - 90.0).abs() < 0.1);
+        assert!((health.p99_ms - 99.0).abs() < 0.1);
+        assert!((health.max_ms - 100.0).abs() < 0.1);
+        assert_eq!(snapshot["POST /api/v1/analyze/pda"].count, 1);
+    }
+
+    #[test]
+    fn test_window_rolls_over() {
+        let recorder = LatencyRecorder::new(Duration::from_millis(20));
+        recorder.record("GET /health", Duration::from_millis(5));
+
+        std::thread::sleep(Duration::from_millis(25));
+        recorder.record("GET /health", Duration::from_millis(7));
+        assert_eq!(recorder.snapshot()["GET /health"].count, 2);
+
+        std::thread::sleep(Duration::from_millis(45));
+        assert!(recorder.snapshot().is_empty());
+    }
+}
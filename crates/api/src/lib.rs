@@ -1,21 +1,49 @@
+pub mod caching;
+pub mod client;
+pub mod config;
 pub mod handlers_simple;
+pub mod latency;
+pub mod logging;
 pub mod routes_simple;
 pub mod middleware;
+pub mod pool;
 pub mod server_simple;
 pub mod error;
+pub mod validation;
 
 // Database-enabled modules
+#[cfg(feature = "database")]
+pub mod discovery;
+#[cfg(feature = "database")]
 pub mod handlers;
+#[cfg(feature = "database")]
+pub mod ingest;
+#[cfg(feature = "database")]
 pub mod routes;
+#[cfg(feature = "database")]
 pub mod server;
 
 pub use handlers_simple::{health_check as simple_health_check};
 pub use routes_simple::{AppState as SimpleAppState, create_simple_router};
 pub use middleware::*;
+pub use latency::{LatencyRecorder, RouteLatency};
+pub use logging::{init_tracing, LogFormat};
+pub use solana_pda_analyzer_core::ColorChoice;
+pub use pool::{AnalyzerPool, PooledAnalyzer};
 pub use server_simple::{run_simple_server, SimpleServerConfig};
 pub use error::*;
+pub use validation::{FieldError, Validate};
+pub use client::{ClientConfig, ClientError, PdaAnalyzerClient};
+pub use config::{EnvVars, RECOGNIZED_VARS};
 
 // Database-enabled exports
+#[cfg(feature = "database")]
+pub use discovery::{discover_since, StoredDiscovery};
+#[cfg(feature = "database")]
 pub use handlers::*;
+#[cfg(feature = "database")]
+pub use ingest::{distinct_signatures, ingest_signatures, IngestFailure, IngestReport};
+#[cfg(feature = "database")]
 pub use routes::{AppState, create_router};
+#[cfg(feature = "database")]
 pub use server::*;
\ No newline at end of file
@@ -0,0 +1,58 @@
+use anyhow::{anyhow, Result};
+use solana_pda_analyzer_core::ColorChoice;
+use std::str::FromStr;
+use tracing::Level;
+
+/// How server logs are written
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum LogFormat {
+    /// Human-readable lines, for development
+    #[default]
+    Pretty,
+    /// One JSON object per line with every structured field, for log aggregators
+    Json,
+}
+
+impl FromStr for LogFormat {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        match s.trim().to_ascii_lowercase().as_str() {
+            "pretty" | "text" => Ok(Self::Pretty),
+            "json" => Ok(Self::Json),
+            other => Err(anyhow!("Unknown log format '{}', expected 'pretty' or 'json'", other)),
+        }
+    }
+}
+
+/// Install the global subscriber at `level` (`error` through `trace`) in `format`. Pretty
+/// logs are only colored on a terminal, and never when `NO_COLOR` is set.
+pub fn init_tracing(level: &str, format: LogFormat) -> Result<()> {
+    let level = Level::from_str(level).map_err(|_| anyhow!("Invalid log level '{}'", level))?;
+    let builder = tracing_subscriber::fmt()
+        .with_max_level(level)
+        .with_ansi(ColorChoice::Auto.for_stdout());
+
+    match format {
+        LogFormat::Pretty => tracing::subscriber::set_global_default(builder.finish())?,
+        LogFormat::Json => tracing::subscriber::set_global_default(
+            builder.json().flatten_event(true).with_current_span(false).finish(),
+        )?,
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_log_format() {
+        assert_eq!("json".parse::<LogFormat>().unwrap(), LogFormat::Json);
+        assert_eq!("JSON".parse::<LogFormat>().unwrap(), LogFormat::Json);
+        assert_eq!("pretty".parse::<LogFormat>().unwrap(), LogFormat::Pretty);
+        assert_eq!("text".parse::<LogFormat>().unwrap(), LogFormat::Pretty);
+        assert!("yaml".parse::<LogFormat>().is_err());
+    }
+}
@@ -1,23 +1,71 @@
+use crate::error::ApiError;
 use axum::{
-    http::{HeaderMap, StatusCode, Request},
+    body::HttpBody,
+    extract::MatchedPath,
+    http::{header::{CONTENT_LENGTH, CONTENT_TYPE}, HeaderMap, Method, StatusCode, Request},
     middleware::Next,
-    response::Response,
+    response::{IntoResponse, Response},
 };
 use std::time::Instant;
-use tracing::{info, warn};
+use tracing::{info, info_span, warn, Instrument};
 use uuid::Uuid;
 
+pub const REQUEST_ID_HEADER: &str = "X-Request-Id";
+
+/// Set to `true` to analyze without storing anything, like `?dry_run=true`
+pub const DRY_RUN_HEADER: &str = "X-Dry-Run";
+
+/// Correlation id assigned to every incoming request
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RequestId(pub Uuid);
+
+tokio::task_local! {
+    static CURRENT_REQUEST_ID: Uuid;
+}
+
+/// Id of the request currently being handled, if called from within one
+pub fn current_request_id() -> Option<Uuid> {
+    CURRENT_REQUEST_ID.try_with(|id| *id).ok()
+}
+
+pub async fn request_id_middleware<B>(mut request: Request<B>, next: Next<B>) -> Response {
+    let request_id = Uuid::new_v4();
+    request.extensions_mut().insert(RequestId(request_id));
+
+    // Everything logged while handling the request carries the id via the span
+    let span = info_span!("request", request_id = %request_id);
+    let mut response = CURRENT_REQUEST_ID
+        .scope(request_id, next.run(request).instrument(span))
+        .await;
+
+    response
+        .headers_mut()
+        .insert(REQUEST_ID_HEADER, request_id.to_string().parse().unwrap());
+
+    response
+}
+
 pub async fn logging_middleware<B>(request: Request<B>, next: Next<B>) -> Response {
     let start = Instant::now();
     let method = request.method().clone();
     let uri = request.uri().clone();
-    let request_id = Uuid::new_v4();
+    let route = request
+        .extensions()
+        .get::<MatchedPath>()
+        .map_or("<unmatched>", MatchedPath::as_str)
+        .to_string();
+    let request_id = request
+        .extensions()
+        .get::<RequestId>()
+        .map(|id| id.0)
+        .unwrap_or_else(Uuid::new_v4);
     
     // Log the incoming request
     info!(
         request_id = %request_id,
         method = %method,
         uri = %uri,
+        route = %route,
         "Incoming request"
     );
     
@@ -32,6 +80,7 @@ pub async fn logging_middleware<B>(request: Request<B>, next: Next<B>) -> Respon
             request_id = %request_id,
             method = %method,
             uri = %uri,
+            route = %route,
             status = %status,
             duration_ms = elapsed.as_millis(),
             "Request completed successfully"
@@ -41,6 +90,7 @@ pub async fn logging_middleware<B>(request: Request<B>, next: Next<B>) -> Respon
             request_id = %request_id,
             method = %method,
             uri = %uri,
+            route = %route,
             status = %status,
             duration_ms = elapsed.as_millis(),
             "Request completed with error"
@@ -101,13 +151,85 @@ pub async fn cors_middleware<B>(request: Request<B>, next: Next<B>) -> Response
     );
     headers.insert(
         "Access-Control-Allow-Headers",
-        "Content-Type, Authorization, X-Requested-With".parse().unwrap(),
+        format!("Content-Type, Authorization, X-Requested-With, {}", DRY_RUN_HEADER).parse().unwrap(),
     );
+    headers.insert("Access-Control-Expose-Headers", REQUEST_ID_HEADER.parse().unwrap());
     headers.insert("Access-Control-Max-Age", "86400".parse().unwrap());
     
     response
 }
 
+/// True for `application/json` and `application/*+json`, ignoring parameters such as charset
+pub fn is_json_content_type(value: &str) -> bool {
+    let essence = value.split(';').next().unwrap_or("").trim().to_ascii_lowercase();
+    essence == "application/json" || (essence.starts_with("application/") && essence.ends_with("+json"))
+}
+
+/// Every endpoint taking a body parses it as JSON. Answer anything else with a 415
+/// `ApiError`, rather than the `Json` extractor's plain-text rejection. Requests without a
+/// body pass, for endpoints whose body is optional.
+pub async fn json_content_type_middleware<B: HttpBody>(request: Request<B>, next: Next<B>) -> Response {
+    let has_body = request.body().size_hint().exact() != Some(0);
+    if has_body && matches!(*request.method(), Method::POST | Method::PUT | Method::PATCH) {
+        let message = match request.headers().get(CONTENT_TYPE) {
+            None => Some("Missing Content-Type header; send the request body as application/json".to_string()),
+            Some(value) => match value.to_str() {
+                Ok(content_type) if is_json_content_type(content_type) => None,
+                Ok(content_type) => Some(format!(
+                    "Unsupported Content-Type '{}'; send the request body as application/json",
+                    content_type
+                )),
+                Err(_) => Some("Content-Type header is not valid ASCII; send the request body as application/json".to_string()),
+            },
+        };
+
+        if let Some(message) = message {
+            warn!(uri = %request.uri(), "{}", message);
+            return ApiError::unsupported_media_type(message).into_response();
+        }
+    }
+
+    next.run(request).await
+}
+
+/// Error responses built by axum itself, such as 405 for a wrong method, 413 from the body limit
+/// or an extractor's rejection, carry plain text or nothing. Rewrap any non-JSON error as an
+/// `ApiError`, keeping its status, headers such as `Allow`, and its text as the message.
+pub async fn json_error_middleware<B>(request: Request<B>, next: Next<B>) -> Response {
+    let method = request.method().clone();
+    let path = request.uri().path().to_string();
+    let response = next.run(request).await;
+
+    let status = response.status();
+    let is_json = response
+        .headers()
+        .get(CONTENT_TYPE)
+        .and_then(|value| value.to_str().ok())
+        .is_some_and(is_json_content_type);
+    if !(status.is_client_error() || status.is_server_error()) || is_json {
+        return response;
+    }
+
+    let (mut parts, body) = response.into_parts();
+    let text = hyper::body::to_bytes(body)
+        .await
+        .map(|bytes| String::from_utf8_lossy(&bytes).trim().to_string())
+        .unwrap_or_default();
+    let message = match status {
+        _ if !text.is_empty() => text,
+        StatusCode::NOT_FOUND => format!("No endpoint for {} {}", method, path),
+        StatusCode::METHOD_NOT_ALLOWED => format!("{} is not allowed on {}", method, path),
+        StatusCode::PAYLOAD_TOO_LARGE => "Request body is too large".to_string(),
+        _ => status.canonical_reason().unwrap_or("Request failed").to_string(),
+    };
+
+    // The plain body's length no longer applies; the JSON body's Content-Type replaces its own
+    let (error_parts, error_body) = ApiError::from_status(status, message).into_response().into_parts();
+    parts.headers.remove(CONTENT_LENGTH);
+    parts.headers.extend(error_parts.headers);
+    Response::from_parts(parts, error_body)
+}
+
 pub async fn request_validation_middleware<B>(request: Request<B>, next: Next<B>) -> Result<Response, StatusCode> {
     // Basic request validation
     let headers = request.headers();
@@ -118,7 +240,7 @@ pub async fn request_validation_middleware<B>(request: Request<B>, next: Next<B>
     if method == "POST" {
         if !headers.contains_key("content-type") {
             warn!(uri = %uri, "POST request missing Content-Type header");
-            return Err(StatusCode::BAD_REQUEST);
+            return Err(StatusCode::UNSUPPORTED_MEDIA_TYPE);
         }
         
         let content_type = headers.get("content-type").unwrap().to_str().unwrap_or("");
@@ -197,4 +319,143 @@ mod tests {
         let ip = extract_client_ip(&headers);
         assert_eq!(ip, None);
     }
+
+    #[test]
+    fn test_is_json_content_type() {
+        assert!(is_json_content_type("application/json"));
+        assert!(is_json_content_type("Application/JSON; charset=utf-8"));
+        assert!(is_json_content_type("application/problem+json"));
+        assert!(!is_json_content_type("text/plain"));
+        assert!(!is_json_content_type("application/x-www-form-urlencoded"));
+        assert!(!is_json_content_type(""));
+    }
+
+    #[tokio::test]
+    async fn test_json_content_type_rejection() {
+        use axum::{body::Body, middleware, routing::post, Json, Router};
+        use tower::ServiceExt;
+
+        async fn echo(Json(value): Json<serde_json::Value>) -> Json<serde_json::Value> {
+            Json(value)
+        }
+
+        let app = Router::new()
+            .route("/echo", post(echo))
+            .layer(middleware::from_fn(json_content_type_middleware));
+        let post_with = |content_type: Option<&str>| {
+            let mut builder = Request::builder().method(Method::POST).uri("/echo");
+            if let Some(content_type) = content_type {
+                builder = builder.header(CONTENT_TYPE, content_type);
+            }
+            builder.body(Body::from("{}")).unwrap()
+        };
+
+        for content_type in [None, Some("text/plain")] {
+            let response = app.clone().oneshot(post_with(content_type)).await.unwrap();
+            assert_eq!(response.status(), StatusCode::UNSUPPORTED_MEDIA_TYPE);
+
+            let body = hyper::body::to_bytes(response.into_body()).await.unwrap();
+            let error: ApiError = serde_json::from_slice(&body).unwrap();
+            assert_eq!(error.status_code, 415);
+            assert!(error.message.contains("application/json"));
+        }
+
+        let response = app.oneshot(post_with(Some("application/json; charset=utf-8"))).await.unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+
+        // A bodyless POST reaches the handler, which decides whether it needs a body
+        async fn optional(body: Option<Json<serde_json::Value>>) -> StatusCode {
+            match body {
+                Some(_) => StatusCode::OK,
+                None => StatusCode::NO_CONTENT,
+            }
+        }
+        let app = Router::new()
+            .route("/optional", post(optional))
+            .layer(middleware::from_fn(json_content_type_middleware));
+        let request = Request::builder().method(Method::POST).uri("/optional").body(Body::empty()).unwrap();
+        assert_eq!(app.oneshot(request).await.unwrap().status(), StatusCode::NO_CONTENT);
+    }
+
+    #[tokio::test]
+    async fn test_framework_errors_get_json_bodies() {
+        use axum::{body::Body, extract::DefaultBodyLimit, http::header::ALLOW, middleware, routing::post, Json, Router};
+        use tower::ServiceExt;
+
+        async fn echo(Json(value): Json<serde_json::Value>) -> Json<serde_json::Value> {
+            Json(value)
+        }
+
+        let app = Router::new()
+            .route("/echo", post(echo))
+            .layer(DefaultBodyLimit::max(16))
+            .layer(middleware::from_fn(json_error_middleware));
+        let send = |method: Method, uri: &str, body: &str| {
+            Request::builder()
+                .method(method)
+                .uri(uri)
+                .header(CONTENT_TYPE, "application/json")
+                .body(Body::from(body.to_string()))
+                .unwrap()
+        };
+        let error_of = |response: Response| async move {
+            assert!(is_json_content_type(response.headers().get(CONTENT_TYPE).unwrap().to_str().unwrap()));
+            let body = hyper::body::to_bytes(response.into_body()).await.unwrap();
+            serde_json::from_slice::<ApiError>(&body).unwrap()
+        };
+
+        let response = app.clone().oneshot(send(Method::GET, "/missing", "")).await.unwrap();
+        let error = error_of(response).await;
+        assert_eq!(error.status_code, 404);
+        assert_eq!(error.message, "No endpoint for GET /missing");
+
+        let response = app.clone().oneshot(send(Method::GET, "/echo", "")).await.unwrap();
+        assert_eq!(response.headers().get(ALLOW).unwrap(), "POST");
+        let error = error_of(response).await;
+        assert_eq!((error.status_code, error.error.as_str()), (405, "Method Not Allowed"));
+
+        let response = app.clone().oneshot(send(Method::POST, "/echo", r#"{"padding": "well past the limit"}"#)).await.unwrap();
+        assert_eq!(error_of(response).await.status_code, 413);
+
+        // Extractor rejections keep their own explanation as the message
+        let response = app.clone().oneshot(send(Method::POST, "/echo", "{")).await.unwrap();
+        let error = error_of(response).await;
+        assert_eq!(error.status_code, 400);
+        assert!(!error.message.is_empty());
+
+        let response = app.oneshot(send(Method::POST, "/echo", "{}")).await.unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+    }
+
+    #[test]
+    fn test_current_request_id_outside_request() {
+        assert_eq!(current_request_id(), None);
+    }
+
+    #[tokio::test]
+    async fn test_request_id_header_and_error_body() {
+        use crate::error::ApiError;
+        use axum::{body::Body, middleware, routing::get, Router};
+        use tower::ServiceExt;
+
+        async fn failing_handler() -> ApiError {
+            ApiError::bad_request("boom".to_string())
+        }
+
+        let app = Router::new()
+            .route("/fail", get(failing_handler))
+            .layer(middleware::from_fn(request_id_middleware));
+
+        let response = app
+            .oneshot(Request::builder().uri("/fail").body(Body::empty()).unwrap())
+            .await
+            .unwrap();
+
+        let header = response.headers().get(REQUEST_ID_HEADER).unwrap().to_str().unwrap().to_string();
+        let body = hyper::body::to_bytes(response.into_body()).await.unwrap();
+        let error: ApiError = serde_json::from_slice(&body).unwrap();
+
+        assert!(Uuid::parse_str(&header).is_ok());
+        assert_eq!(error.request_id, Some(header));
+    }
 }
\ No newline at end of file
@@ -0,0 +1,140 @@
+use solana_pda_analyzer_core::{PatternStats, PdaAnalyzer, PdaPattern};
+use std::collections::HashMap;
+use std::ops::{Deref, DerefMut};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+use tokio::sync::{Mutex, OwnedMutexGuard, OwnedSemaphorePermit, Semaphore};
+
+/// Fixed-size pool of independent analyzers, each with its own cache, so
+/// concurrent requests don't all contend on a single lock
+#[derive(Clone)]
+pub struct AnalyzerPool {
+    analyzers: Arc<Vec<Arc<Mutex<PdaAnalyzer>>>>,
+    permits: Arc<Semaphore>,
+    next: Arc<AtomicUsize>,
+    /// Shared by every analyzer, so reading it never waits on an analysis
+    pattern_stats: PatternStats,
+}
+
+/// An analyzer checked out of the pool; returned when dropped. It owns its
+/// checkout, so it can be moved onto a blocking thread for long analyses.
+pub struct PooledAnalyzer {
+    analyzer: OwnedMutexGuard<PdaAnalyzer>,
+    _permit: OwnedSemaphorePermit,
+}
+
+impl AnalyzerPool {
+    pub fn new(workers: usize) -> Self {
+        let workers = workers.max(1);
+        let pattern_stats = PatternStats::new();
+        let analyzers = (0..workers)
+            .map(|_| Arc::new(Mutex::new(PdaAnalyzer::builder().pattern_stats(pattern_stats.clone()).build())))
+            .collect();
+
+        Self {
+            analyzers: Arc::new(analyzers),
+            permits: Arc::new(Semaphore::new(workers)),
+            next: Arc::new(AtomicUsize::new(0)),
+            pattern_stats,
+        }
+    }
+
+    /// Check out an idle analyzer, waiting if all are busy
+    pub async fn get(&self) -> PooledAnalyzer {
+        let permit = self.permits.clone().acquire_owned().await.expect("analyzer pool semaphore is never closed");
+
+        // Holding a permit means at least one analyzer is idle, unless a stats
+        // reader briefly holds it, in which case wait on the next one in turn
+        let analyzer = match self.analyzers.iter().find_map(|analyzer| analyzer.clone().try_lock_owned().ok()) {
+            Some(analyzer) => analyzer,
+            None => {
+                let index = self.next.fetch_add(1, Ordering::Relaxed) % self.analyzers.len();
+                self.analyzers[index].clone().lock_owned().await
+            }
+        };
+
+        PooledAnalyzer { analyzer, _permit: permit }
+    }
+
+    pub fn size(&self) -> usize {
+        self.analyzers.len()
+    }
+
+    pub fn available(&self) -> usize {
+        self.permits.available_permits()
+    }
+
+    /// Precompute known programs' common singleton PDAs in every analyzer's cache; see
+    /// `PdaAnalyzer::warm_up`. Returns how many each analyzer derived.
+    pub async fn warm_up(&self) -> usize {
+        let mut derived = 0;
+        for analyzer in self.analyzers.iter() {
+            derived = analyzer.lock().await.warm_up();
+        }
+        derived
+    }
+
+    /// Cache hits and totals summed over every analyzer
+    pub async fn cache_stats(&self) -> (usize, usize) {
+        let mut totals = (0, 0);
+        for analyzer in self.analyzers.iter() {
+            let (hits, total) = analyzer.lock().await.cache_stats();
+            totals.0 += hits;
+            totals.1 += total;
+        }
+        totals
+    }
+
+    /// Pattern counts over every analyzer
+    pub async fn pattern_stats(&self) -> HashMap<PdaPattern, u32> {
+        self.pattern_stats.snapshot()
+    }
+}
+
+impl Deref for PooledAnalyzer {
+    type Target = PdaAnalyzer;
+
+    fn deref(&self) -> &Self::Target {
+        &self.analyzer
+    }
+}
+
+impl DerefMut for PooledAnalyzer {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.analyzer
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_pool_hands_out_independent_analyzers() {
+        let pool = AnalyzerPool::new(2);
+        assert_eq!(pool.size(), 2);
+
+        let first = pool.get().await;
+        let second = pool.get().await;
+        assert_eq!(pool.available(), 0);
+
+        drop(first);
+        drop(second);
+        assert_eq!(pool.available(), 2);
+    }
+
+    #[tokio::test]
+    async fn test_pool_size_is_at_least_one() {
+        let pool = AnalyzerPool::new(0);
+        assert_eq!(pool.size(), 1);
+        assert_eq!(pool.cache_stats().await, (0, 0));
+    }
+
+    #[tokio::test]
+    async fn test_warm_up_fills_every_analyzer() {
+        let pool = AnalyzerPool::new(2);
+        let derived = pool.warm_up().await;
+        assert!(derived > 0);
+        assert_eq!(pool.cache_stats().await, (2 * derived, 2 * derived));
+    }
+}
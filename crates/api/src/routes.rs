@@ -1,10 +1,15 @@
 use crate::handlers::*;
+use crate::handlers_simple::route_not_found;
+use crate::latency::{latency_middleware, LatencyRecorder};
+use crate::middleware::{json_content_type_middleware, json_error_middleware};
 use axum::{
+    middleware,
     routing::{get, post},
     Router,
 };
 use tower_http::cors::CorsLayer;
-use solana_pda_analyzer_core::PdaAnalyzer;
+use solana_pda_analyzer_core::{PatternStats, PdaAnalyzer};
+use solana_pda_analyzer_analyzer::{PatternRegistry, SolanaClient};
 use solana_pda_analyzer_database::DatabaseRepository as DatabaseManager;
 use std::sync::Arc;
 use tokio::sync::RwLock;
@@ -13,6 +18,14 @@ use tokio::sync::RwLock;
 pub struct AppState {
     pub database: Arc<DatabaseManager>,
     pub pda_analyzer: Arc<RwLock<PdaAnalyzer>>,
+    /// The analyzer's pattern counters, readable without its lock
+    pub pattern_stats: PatternStats,
+    pub pattern_registry: Arc<RwLock<PatternRegistry>>,
+    /// RPC connection used to fetch transactions for ingestion
+    pub solana_client: Arc<SolanaClient>,
+    /// Bearer token required by destructive endpoints; they are disabled when unset
+    pub admin_token: Option<String>,
+    pub latency: LatencyRecorder,
 }
 
 pub fn create_router(state: AppState) -> Router {
@@ -24,32 +37,61 @@ pub fn create_router(state: AppState) -> Router {
         .route("/docs", get(api_docs))
         
         // PDA analysis routes
-        .route("/api/v1/analyze/pda", post(analyze_pda))
+        .route("/api/v1/analyze/pda", get(analyze_pda_get).post(analyze_pda))
         .route("/api/v1/analyze/pda/batch", post(batch_analyze_pda))
+        .route("/api/v1/derive", post(derive_pda))
+        .route("/api/v1/capabilities", get(get_capabilities))
+        .route("/api/v1/version", get(get_version))
+        .route("/api/v1/patterns/detect", post(detect_patterns))
         
         // Program routes
         .route("/api/v1/programs", get(list_programs))
+        .route("/api/v1/programs/custom", post(register_custom_program))
+        .route("/api/v1/programs/bulk", post(bulk_upsert_programs))
+        .route("/api/v1/programs/dedupe", post(dedupe_programs))
         .route("/api/v1/programs/:program_id", get(get_program))
         .route("/api/v1/programs/:program_id/stats", get(get_program_stats))
         .route("/api/v1/programs/:program_id/patterns", get(get_program_patterns))
         .route("/api/v1/programs/:program_id/pdas", get(get_program_pdas))
+        .route("/api/v1/programs/:program_id/transactions", get(get_program_transactions))
+        .route("/api/v1/programs/:program_id/discover", post(discover_program_pdas))
         
         // Transaction routes
         .route("/api/v1/transactions", get(list_transactions))
         .route("/api/v1/transactions/:signature", get(get_transaction))
         .route("/api/v1/transactions/analyze", post(analyze_transaction))
+        .route("/api/v1/transactions/ingest", post(ingest_transactions))
         
         // PDA routes
         .route("/api/v1/pdas", get(list_pdas))
-        .route("/api/v1/pdas/:address", get(get_pda))
+        .route("/api/v1/pdas/:address", get(get_pda).delete(delete_pda))
+        .route("/api/v1/pdas/prune", post(prune_pdas))
+        .route("/api/v1/pdas/reanalyze", post(reanalyze_pdas))
         .route("/api/v1/pdas/search", get(search_pdas))
         .route("/api/v1/pdas/recent", get(get_recent_pdas))
         
         // Analytics routes
+        .route("/api/v1/analytics/bumps", get(get_bump_audit))
+        .route("/api/v1/analytics/coverage", get(get_pattern_coverage))
         .route("/api/v1/analytics/database", get(get_database_metrics))
+        .route("/api/v1/analytics/fees", get(get_fee_stats))
+        .route("/api/v1/analytics/interactions", get(get_interaction_distribution))
+        .route("/api/v1/analytics/migrations", get(get_migration_status))
         .route("/api/v1/analytics/patterns", get(get_pattern_distribution))
+        .route("/api/v1/analytics/patterns/histogram", get(get_pattern_histogram))
         .route("/api/v1/analytics/performance", get(get_performance_metrics))
         
+        .fallback(route_not_found)
+
+        // Give 405s, 413s and extractor rejections the same JSON error body as handlers
+        .layer(middleware::from_fn(json_error_middleware))
+
+        // Reject non-JSON bodies with 415 before the extractors see them
+        .layer(middleware::from_fn(json_content_type_middleware))
+
+        // Per-route latency percentiles for /api/v1/analytics/performance
+        .layer(middleware::from_fn_with_state(state.latency.clone(), latency_middleware))
+
         // Add CORS middleware
         .layer(CorsLayer::permissive())
         
@@ -61,10 +103,21 @@ pub fn create_router(state: AppState) -> Router {
 mod tests {
     use super::*;
     
-    #[test]
-    fn test_router_creation() {
-        // Test that the router can be created without panicking
-        // This is a basic structural test
-        assert!(true);
+    #[tokio::test]
+    async fn test_router_creation() {
+        // Every route must be registered without conflicts; a lazy pool never connects
+        let pool = sqlx::postgres::PgPoolOptions::new()
+            .connect_lazy("postgresql://localhost/unused")
+            .unwrap();
+        let state = AppState {
+            database: Arc::new(DatabaseManager::new(pool)),
+            pda_analyzer: Arc::new(RwLock::new(PdaAnalyzer::new())),
+            pattern_stats: PatternStats::new(),
+            pattern_registry: Arc::new(RwLock::new(PatternRegistry::new())),
+            solana_client: Arc::new(SolanaClient::new("http://localhost:8899")),
+            admin_token: None,
+            latency: LatencyRecorder::default(),
+        };
+        let _router = create_router(state);
     }
 }
\ No newline at end of file
@@ -1,16 +1,21 @@
 use crate::handlers_simple::*;
+use crate::latency::{latency_middleware, LatencyRecorder};
+use crate::middleware::{json_content_type_middleware, json_error_middleware};
 use axum::{
+    middleware,
     routing::{get, post},
     Router,
 };
 use tower_http::cors::CorsLayer;
-use solana_pda_analyzer_core::PdaAnalyzer;
-use std::sync::Arc;
-use tokio::sync::RwLock;
+use crate::pool::AnalyzerPool;
+use std::time::Duration;
 
 #[derive(Clone)]
 pub struct AppState {
-    pub pda_analyzer: Arc<RwLock<PdaAnalyzer>>,
+    pub analyzer_pool: AnalyzerPool,
+    /// Longest a single analysis request may run before answering 504
+    pub analysis_timeout: Duration,
+    pub latency: LatencyRecorder,
 }
 
 pub fn create_simple_router(state: AppState) -> Router {
@@ -22,21 +27,67 @@ pub fn create_simple_router(state: AppState) -> Router {
         .route("/docs", get(api_docs))
         
         // PDA analysis routes
-        .route("/api/v1/analyze/pda", post(analyze_pda))
+        .route("/api/v1/analyze/pda", get(analyze_pda_get).post(analyze_pda))
         .route("/api/v1/analyze/pda/batch", post(batch_analyze_pda))
+        .route("/api/v1/derive", post(derive_pda))
+        .route("/api/v1/capabilities", get(get_capabilities))
         
         // Analytics routes
         .route("/api/v1/analytics/performance", get(get_performance_metrics))
+        .route("/api/v1/analytics/patterns/histogram", get(get_pattern_histogram))
         
         // Stub routes for future implementation
         .route("/api/v1/programs", get(list_programs))
         .route("/api/v1/programs/:program_id", get(get_program))
         .route("/api/v1/pdas", get(list_pdas))
         .route("/api/v1/analytics/database", get(get_database_metrics))
-        
+
+        .fallback(route_not_found)
+
+        // Give 405s, 413s and extractor rejections the same JSON error body as handlers
+        .layer(middleware::from_fn(json_error_middleware))
+
+        // Reject non-JSON bodies with 415 before the extractors see them
+        .layer(middleware::from_fn(json_content_type_middleware))
+
+        // Per-route latency percentiles for /api/v1/analytics/performance
+        .layer(middleware::from_fn_with_state(state.latency.clone(), latency_middleware))
+
         // Add CORS middleware
         .layer(CorsLayer::permissive())
         
         // Add state
         .with_state(state)
-}
\ No newline at end of file
+}
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::pool::AnalyzerPool;
+    use axum::{body::Body, http::{Method, Request, StatusCode}};
+    use tower::ServiceExt;
+
+    #[tokio::test]
+    async fn test_framework_errors_use_error_envelope() {
+        let app = create_simple_router(AppState {
+            analyzer_pool: AnalyzerPool::new(1),
+            analysis_timeout: Duration::from_secs(5),
+            latency: Default::default(),
+        });
+        let cases = [
+            (Method::GET, "/api/v1/nonexistent", StatusCode::NOT_FOUND),
+            (Method::DELETE, "/health", StatusCode::METHOD_NOT_ALLOWED),
+        ];
+
+        for (method, uri, status) in cases {
+            let request = Request::builder().method(method).uri(uri).body(Body::empty()).unwrap();
+            let response = app.clone().oneshot(request).await.unwrap();
+            assert_eq!(response.status(), status);
+
+            let body = hyper::body::to_bytes(response.into_body()).await.unwrap();
+            let body: serde_json::Value = serde_json::from_slice(&body).unwrap();
+            assert_eq!(body["success"], false);
+            assert_eq!(body["code"], status.as_u16());
+            assert!(body["error"].is_string());
+        }
+    }
+}
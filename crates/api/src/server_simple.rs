@@ -1,29 +1,55 @@
-use crate::{create_simple_router, AppState, middleware::*};
+use crate::{create_simple_router, init_tracing, SimpleAppState as AppState, AnalyzerPool, LatencyRecorder, LogFormat, middleware::*};
+use crate::config::{self, EnvVars};
 use axum::middleware;
-use solana_pda_analyzer_core::PdaAnalyzer;
-use std::sync::Arc;
-use tokio::sync::RwLock;
 use tower_http::services::ServeDir;
 use tracing::{info, error};
 use anyhow::Result;
+use std::time::Duration;
+
+/// Default limit on one analysis request, long enough for the full built-in search
+const DEFAULT_ANALYSIS_TIMEOUT_SECS: u64 = 30;
 
 #[derive(Debug, Clone)]
 pub struct SimpleServerConfig {
     pub host: String,
     pub port: u16,
     pub static_files_dir: Option<String>,
+    /// Number of independent analyzers serving requests concurrently
+    pub analyzer_workers: usize,
+    /// Longest an `analyze_pda` or `batch_analyze_pda` request may take before answering 504
+    pub analysis_timeout: Duration,
+    pub log_level: String,
+    /// Pass to `init_tracing` with `log_level`; `Json` suits log pipelines
+    pub log_format: LogFormat,
 }
 
 impl SimpleServerConfig {
+    /// Read and validate every setting from the process environment; `config::RECOGNIZED_VARS`
+    /// lists the variables and their defaults. `run_simple_server` logs unrecognized variables
+    /// sharing a known prefix as likely typos, once logging is set up.
     pub fn from_env() -> Result<Self> {
-        Ok(Self {
-            host: std::env::var("HOST").unwrap_or_else(|_| "127.0.0.1".to_string()),
-            port: std::env::var("PORT")
-                .unwrap_or_else(|_| "8080".to_string())
-                .parse()
-                .unwrap_or(8080),
-            static_files_dir: std::env::var("STATIC_FILES_DIR").ok(),
-        })
+        Self::from_env_vars(&EnvVars::from_process())
+    }
+
+    /// Like `from_env`, reading from `env`
+    pub fn from_env_vars(env: &EnvVars) -> Result<Self> {
+        let defaults = Self::default();
+        let analysis_timeout_secs = env.parse("ANALYSIS_TIMEOUT_SECS")?.unwrap_or(DEFAULT_ANALYSIS_TIMEOUT_SECS);
+        let config = Self {
+            host: env.get_or("HOST", &defaults.host),
+            port: env.parse("PORT")?.unwrap_or(defaults.port),
+            static_files_dir: env.get("STATIC_FILES_DIR").map(str::to_string),
+            analyzer_workers: env.parse("ANALYZER_WORKERS")?.unwrap_or(defaults.analyzer_workers),
+            analysis_timeout: Duration::from_secs(analysis_timeout_secs),
+            log_level: env.get_or("LOG_LEVEL", &defaults.log_level),
+            log_format: env.parse("LOG_FORMAT")?.unwrap_or(defaults.log_format),
+        };
+
+        config::check_nonzero("PORT", config.port.into())?;
+        config::check_nonzero("ANALYZER_WORKERS", config.analyzer_workers as u64)?;
+        config::check_nonzero("ANALYSIS_TIMEOUT_SECS", analysis_timeout_secs)?;
+        config::check_log_level(&config.log_level)?;
+        Ok(config)
     }
 
     pub fn bind_address(&self) -> String {
@@ -37,10 +63,18 @@ impl Default for SimpleServerConfig {
             host: "127.0.0.1".to_string(),
             port: 8080,
             static_files_dir: None,
+            analyzer_workers: default_analyzer_workers(),
+            analysis_timeout: Duration::from_secs(DEFAULT_ANALYSIS_TIMEOUT_SECS),
+            log_level: "info".to_string(),
+            log_format: LogFormat::default(),
         }
     }
 }
 
+fn default_analyzer_workers() -> usize {
+    std::thread::available_parallelism().map(|n| n.get()).unwrap_or(4)
+}
+
 pub struct SimpleServer {
     config: SimpleServerConfig,
     app_state: AppState,
@@ -48,11 +82,16 @@ pub struct SimpleServer {
 
 impl SimpleServer {
     pub async fn new(config: SimpleServerConfig) -> Result<Self> {
-        // Initialize PDA analyzer
-        let pda_analyzer = Arc::new(RwLock::new(PdaAnalyzer::new()));
+        // Initialize the PDA analyzer pool
+        info!("Starting {} analyzer workers", config.analyzer_workers);
+        let analyzer_pool = AnalyzerPool::new(config.analyzer_workers);
+        let warmed = analyzer_pool.warm_up().await;
+        info!("Precomputed {} common singleton PDAs of known programs", warmed);
         
         let app_state = AppState {
-            pda_analyzer,
+            analyzer_pool,
+            analysis_timeout: config.analysis_timeout,
+            latency: LatencyRecorder::default(),
         };
         
         Ok(Self {
@@ -72,7 +111,8 @@ impl SimpleServer {
         app = app
             .layer(middleware::from_fn(logging_middleware))
             .layer(middleware::from_fn(security_headers_middleware))
-            .layer(middleware::from_fn(cors_middleware));
+            .layer(middleware::from_fn(cors_middleware))
+            .layer(middleware::from_fn(request_id_middleware));
         
         // Add static file serving if configured
         if let Some(static_dir) = &self.config.static_files_dir {
@@ -105,7 +145,11 @@ impl SimpleServer {
     }
 }
 
+/// Install logging as `config` asks, then serve until the process exits
 pub async fn run_simple_server(config: SimpleServerConfig) -> Result<()> {
+    init_tracing(&config.log_level, config.log_format)?;
+    EnvVars::from_process().warn_unrecognized();
+
     let server = SimpleServer::new(config).await?;
     server.run().await
 }
@@ -120,5 +164,23 @@ mod tests {
         assert_eq!(config.host, "127.0.0.1");
         assert_eq!(config.port, 8080);
         assert_eq!(config.bind_address(), "127.0.0.1:8080");
+        assert!(config.analyzer_workers >= 1);
+        assert_eq!(config.analysis_timeout, Duration::from_secs(30));
+    }
+
+    #[test]
+    fn test_simple_server_config_from_env_vars() {
+        let env = EnvVars::from_pairs([("PORT", "9000"), ("ANALYZER_WORKERS", "2"), ("LOG_FORMAT", "json")]);
+        let config = SimpleServerConfig::from_env_vars(&env).unwrap();
+        assert_eq!(config.port, 9000);
+        assert_eq!(config.analyzer_workers, 2);
+        assert_eq!(config.log_format, LogFormat::Json);
+        assert_eq!(config.host, "127.0.0.1");
+
+        // Malformed values fail instead of quietly falling back to defaults
+        assert!(SimpleServerConfig::from_env_vars(&EnvVars::from_pairs([("PORT", "eighty")])).is_err());
+        assert!(SimpleServerConfig::from_env_vars(&EnvVars::from_pairs([("ANALYZER_WORKERS", "0")])).is_err());
+        assert!(SimpleServerConfig::from_env_vars(&EnvVars::from_pairs([("ANALYSIS_TIMEOUT_SECS", "-1")])).is_err());
+        assert!(SimpleServerConfig::from_env_vars(&EnvVars::from_pairs([("LOG_LEVEL", "loud")])).is_err());
     }
 }
\ No newline at end of file
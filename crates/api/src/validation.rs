@@ -0,0 +1,186 @@
+use crate::error::ApiError;
+use serde::{Deserialize, Serialize};
+use solana_pda_analyzer_core::{parse_address, AddressFormat, PdaAnalyzerError};
+use solana_sdk::{bs58, pubkey::Pubkey};
+
+/// Base58 encodings of 32-byte keys are between 32 and 44 characters long
+const MIN_PUBKEY_LEN: usize = 32;
+const MAX_PUBKEY_LEN: usize = 44;
+const PUBKEY_BYTES: usize = 32;
+
+/// Roughly how many base58 characters encode one byte (log 256 / log 58)
+const BASE58_CHARS_PER_BYTE: f64 = 1.3657;
+
+/// Inputs this close to the length they decode towards get a "truncated"/"extra characters" hint
+const NEAR_MISS_CHARS: usize = 2;
+
+/// Largest batch accepted in a single request
+pub const MAX_BATCH_SIZE: usize = 1000;
+
+const BASE58_ALPHABET: &str = "123456789ABCDEFGHJKLMNPQRSTUVWXYZabcdefghijkmnopqrstuvwxyz";
+
+/// A validation failure tied to a specific request field
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct FieldError {
+    pub field: String,
+    pub message: String,
+}
+
+impl FieldError {
+    pub fn new(field: impl Into<String>, message: impl Into<String>) -> Self {
+        Self {
+            field: field.into(),
+            message: message.into(),
+        }
+    }
+}
+
+/// Request bodies checked before they reach analysis
+pub trait Validate {
+    fn validate(&self) -> Result<(), Vec<FieldError>>;
+}
+
+/// Check that `value` looks like a base58 pubkey, recording a field error if not
+pub fn check_pubkey(field: &str, value: &str, errors: &mut Vec<FieldError>) {
+    if let Some(problem) = diagnose_pubkey(value) {
+        errors.push(FieldError::new(field, problem));
+    }
+}
+
+/// Parse a pubkey given as base58, hex or a byte array, explaining truncated or mis-copied input
+/// instead of returning the opaque parse error
+pub fn parse_pubkey(label: &str, value: &str) -> Result<Pubkey, ApiError> {
+    if let Some(problem) = diagnose_pubkey(value) {
+        return Err(ApiError::bad_request(format!("{} {}", label, problem)));
+    }
+    parse_address(value).map_err(|e| ApiError::bad_request(format!("Invalid {}: {}", label, e)))
+}
+
+/// Describe why `value` is not a pubkey, phrased to follow the field name
+///
+/// Hex and byte-array input is checked as such. For base58, invalid characters are reported before
+/// length, since a bad character makes the length meaningless. When the decoded size is off, the
+/// message suggests the character count a 32-byte key would need.
+pub fn diagnose_pubkey(value: &str) -> Option<String> {
+    if value.trim().is_empty() {
+        return Some("must not be empty".to_string());
+    }
+
+    if AddressFormat::detect(value) != AddressFormat::Base58 {
+        return parse_address(value).err().map(|e| match e {
+            PdaAnalyzerError::InvalidPublicKey(problem) => format!("is invalid: {}", problem),
+            e => format!("is invalid: {}", e),
+        });
+    }
+
+    if let Some((position, c)) = value.chars().enumerate().find(|(_, c)| !BASE58_ALPHABET.contains(*c)) {
+        let hint = match c {
+            '0' | 'O' | 'I' | 'l' => " (base58 never uses 0, O, I or l)",
+            c if c.is_whitespace() => " (check for stray spaces or line breaks)",
+            _ => "",
+        };
+        return Some(format!("contains non-base58 character '{}' at position {}{}", c, position + 1, hint));
+    }
+
+    let len = value.len();
+    let range_hint = format!("a pubkey is {}-{} base58 characters", MIN_PUBKEY_LEN, MAX_PUBKEY_LEN);
+    if len > MAX_PUBKEY_LEN * 2 {
+        return Some(format!("is {} characters; {}", len, range_hint));
+    }
+
+    let decoded = match bs58::decode(value).into_vec() {
+        Ok(decoded) => decoded.len(),
+        Err(e) => return Some(format!("is not valid base58: {}", e)),
+    };
+    if decoded == PUBKEY_BYTES {
+        return None;
+    }
+
+    let shortfall = (PUBKEY_BYTES as f64 - decoded as f64) * BASE58_CHARS_PER_BYTE;
+    let expected = ((len as f64 + shortfall).round() as usize).clamp(MIN_PUBKEY_LEN, MAX_PUBKEY_LEN);
+    let size = format!("decodes to {} bytes instead of {}", decoded, PUBKEY_BYTES);
+
+    Some(if expected == len {
+        format!("{}; check for a mistyped character", size)
+    } else if len.abs_diff(expected) <= NEAR_MISS_CHARS {
+        let cause = if len < expected { "truncated" } else { "pasted with extra characters" };
+        format!("is {} characters, expected {} ({}); it may have been {}", len, expected, size, cause)
+    } else {
+        format!("is {} characters ({}); {}", len, size, range_hint)
+    })
+}
+
+/// Turn collected field errors into a validation result
+pub fn finish(errors: Vec<FieldError>) -> Result<(), Vec<FieldError>> {
+    if errors.is_empty() {
+        Ok(())
+    } else {
+        Err(errors)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_check_pubkey_accepts_valid_keys() {
+        let mut errors = Vec::new();
+        check_pubkey("program_id", "11111111111111111111111111111111", &mut errors);
+        check_pubkey("program_id", "TokenkegQfeZyiNwAJbNbGKPFXCWuBvf9Ss623VQ5DA", &mut errors);
+        assert!(errors.is_empty());
+    }
+
+    #[test]
+    fn test_check_pubkey_reports_field_errors() {
+        let mut errors = Vec::new();
+        check_pubkey("address", "", &mut errors);
+        check_pubkey("address", "short", &mut errors);
+        check_pubkey("address", "0000000000000000000000000000000000000000000", &mut errors);
+
+        assert_eq!(errors.len(), 3);
+        assert_eq!(errors[0].message, "must not be empty");
+        assert!(errors[1].message.contains("base58 characters"));
+        assert!(errors[2].message.contains("'0'"));
+    }
+
+    #[test]
+    fn test_diagnose_pubkey_suggests_intended_length() {
+        let truncated = diagnose_pubkey("TokenkegQfeZyiNwAJbNbGKPFXCWuBvf9Ss623VQ5D").unwrap();
+        assert!(truncated.starts_with("is 42 characters, expected 43"), "{}", truncated);
+        assert!(truncated.contains("truncated"));
+
+        let extra = diagnose_pubkey("9WzDXwBbmkg8ZTbNMqUxvQRAyrZzDsGYdLVL9zYtAWWMx").unwrap();
+        assert!(extra.starts_with("is 45 characters, expected 44"), "{}", extra);
+        assert!(extra.contains("extra characters"));
+
+        let mistyped = diagnose_pubkey("TokenkegQfeZyiNwAJbNbGKPFXCWuBvf9Ss623VQ5lA").unwrap();
+        assert!(mistyped.contains("'l' at position 42"), "{}", mistyped);
+
+        assert_eq!(diagnose_pubkey("TokenkegQfeZyiNwAJbNbGKPFXCWuBvf9Ss623VQ5DA"), None);
+    }
+
+    #[test]
+    fn test_parse_pubkey_returns_distinct_bad_requests() {
+        let short = parse_pubkey("PDA address", "TokenkegQfeZyiNwAJbNbGKPFXCWuBvf9Ss623VQ5D").unwrap_err();
+        let bad_char = parse_pubkey("PDA address", "TokenkegQfeZyiNwAJbNbGKPFXCWuBvf9Ss623VQ50A").unwrap_err();
+
+        assert_eq!(short.status_code, 400);
+        assert!(short.message.starts_with("PDA address is 42 characters"));
+        assert!(bad_char.message.starts_with("PDA address contains non-base58 character '0'"));
+        assert!(parse_pubkey("program ID", "11111111111111111111111111111111").is_ok());
+    }
+
+    #[test]
+    fn test_parse_pubkey_accepts_hex() {
+        let key = Pubkey::new_unique();
+        let hex: String = key.to_bytes().iter().map(|byte| format!("{:02x}", byte)).collect();
+        let hex = format!("0x{}", hex);
+        assert_eq!(parse_pubkey("PDA address", &hex).unwrap(), key);
+        assert_eq!(diagnose_pubkey(&hex), None);
+
+        let short = parse_pubkey("PDA address", "0xabcd").unwrap_err();
+        assert_eq!(short.status_code, 400);
+        assert!(short.message.starts_with("PDA address is invalid: '0xabcd' is 2 bytes of hex"), "{}", short.message);
+    }
+}
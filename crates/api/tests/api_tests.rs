@@ -5,14 +5,16 @@ use axum::{
 };
 use tower::ServiceExt;
 use serde_json::{json, Value};
-use solana_pda_analyzer_api::{create_router, AppState};
-use solana_pda_analyzer_database::{DatabaseRepository, DatabaseConfig};
-use solana_pda_analyzer_core::PdaAnalyzer;
+use solana_pda_analyzer_api::{create_router, discover_since, AppState};
+use solana_pda_analyzer_database::{DatabaseConfig, DatabaseMigrator, DatabaseRepository};
+use solana_pda_analyzer_core::{PatternStats, PdaAnalyzer};
+use solana_pda_analyzer_analyzer::{BatchProcessor, PatternRegistry, SolanaClient};
+use solana_sdk::pubkey::Pubkey;
 use std::sync::Arc;
 use tokio::sync::RwLock;
 use uuid::Uuid;
 
-async fn create_test_app() -> Result<Router, Box<dyn std::error::Error>> {
+async fn create_test_database() -> Result<DatabaseRepository, Box<dyn std::error::Error>> {
     // Create in-memory or test database
     let config = DatabaseConfig {
         host: "localhost".to_string(),
@@ -27,8 +29,8 @@ async fn create_test_app() -> Result<Router, Box<dyn std::error::Error>> {
         max_lifetime: 1800,
     };
 
-    // Try to create a test database connection
-    let pool = match config.create_pool().await {
+    // Try to create and migrate a test database
+    let pool = match DatabaseMigrator::new(config.database_url()).setup_database().await {
         Ok(pool) => pool,
         Err(_) => {
             // If we can't connect to a real database, skip these tests
@@ -36,12 +38,23 @@ async fn create_test_app() -> Result<Router, Box<dyn std::error::Error>> {
         }
     };
 
-    let database = DatabaseRepository::new(pool);
-    let pda_analyzer = Arc::new(RwLock::new(PdaAnalyzer::new()));
+    Ok(DatabaseRepository::new(pool))
+}
+
+async fn create_test_app() -> Result<Router, Box<dyn std::error::Error>> {
+    let database = Arc::new(create_test_database().await?);
+    let pattern_stats = PatternStats::new();
+    let pda_analyzer = Arc::new(RwLock::new(PdaAnalyzer::builder().pattern_stats(pattern_stats.clone()).build()));
+    let pattern_registry = Arc::new(RwLock::new(PatternRegistry::new()));
 
     let state = AppState {
         database,
         pda_analyzer,
+        pattern_stats,
+        pattern_registry,
+        solana_client: Arc::new(SolanaClient::new("http://localhost:8899")),
+        admin_token: None,
+        latency: Default::default(),
     };
 
     Ok(create_router(state))
@@ -50,7 +63,7 @@ async fn create_test_app() -> Result<Router, Box<dyn std::error::Error>> {
 async fn send_request(app: &Router, request: Request<Body>) -> Result<(StatusCode, Value), Box<dyn std::error::Error>> {
     let response = app.clone().oneshot(request).await?;
     let status = response.status();
-    let body = axum::body::to_bytes(response.into_body(), usize::MAX).await?;
+    let body = hyper::body::to_bytes(response.into_body()).await?;
     let json: Value = serde_json::from_slice(&body)?;
     Ok((status, json))
 }
@@ -74,7 +87,8 @@ async fn test_health_check() {
     let (status, json) = send_request(&app, request).await.unwrap();
     assert_eq!(status, StatusCode::OK);
     assert_eq!(json["success"], true);
-    assert_eq!(json["data"], "Service is healthy");
+    assert_eq!(json["data"]["status"], "healthy");
+    assert_eq!(json["data"]["database_connected"], true);
 }
 
 #[tokio::test]
@@ -87,9 +101,11 @@ async fn test_analyze_pda_endpoint() {
         }
     };
 
+    let program_id = Pubkey::new_unique();
+    let (address, _) = Pubkey::find_program_address(&[b"state"], &program_id);
     let payload = json!({
-        "address": "11111111111111111111111111111111",
-        "program_id": "11111111111111111111111111111111"
+        "address": address.to_string(),
+        "program_id": program_id.to_string()
     });
 
     let request = Request::builder()
@@ -105,9 +121,9 @@ async fn test_analyze_pda_endpoint() {
     assert!(json["data"].is_object());
     
     let data = &json["data"];
-    assert_eq!(data["address"], "11111111111111111111111111111111");
-    assert_eq!(data["program_id"], "11111111111111111111111111111111");
-    assert!(data["derived_successfully"].is_boolean());
+    assert_eq!(data["pda_info"]["address"], json!(address));
+    assert_eq!(data["pda_info"]["program_id"], json!(program_id));
+    assert!(data["pattern"].is_string());
 }
 
 #[tokio::test]
@@ -136,6 +152,73 @@ async fn test_analyze_pda_invalid_address() {
     assert_eq!(status, StatusCode::BAD_REQUEST);
 }
 
+#[tokio::test]
+async fn test_register_custom_program() {
+    let app = match create_test_app().await {
+        Ok(app) => app,
+        Err(_) => {
+            println!("Skipping API tests - no database connection");
+            return;
+        }
+    };
+
+    let payload = json!({
+        "program_id": solana_sdk::pubkey::Pubkey::new_unique().to_string(),
+        "name": "Custom Vault Program",
+        "patterns": [{
+            "pattern_name": "Vault",
+            "seeds_template": [
+                {"name": "prefix", "seed_type": "string", "description": null, "is_variable": false},
+                {"name": "owner", "seed_type": "pubkey", "description": null, "is_variable": true}
+            ],
+            "description": "Per-user vault"
+        }]
+    });
+
+    let build_request = || {
+        Request::builder()
+            .method(Method::POST)
+            .uri("/api/v1/programs/custom")
+            .header("content-type", "application/json")
+            .body(Body::from(payload.to_string()))
+            .unwrap()
+    };
+
+    let (status, json) = send_request(&app, build_request()).await.unwrap();
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(json["data"]["patterns_registered"], 1);
+
+    // Registering the same program twice is a conflict
+    let (status, _json) = send_request(&app, build_request()).await.unwrap();
+    assert_eq!(status, StatusCode::CONFLICT);
+}
+
+#[tokio::test]
+async fn test_register_custom_program_invalid_id() {
+    let app = match create_test_app().await {
+        Ok(app) => app,
+        Err(_) => {
+            println!("Skipping API tests - no database connection");
+            return;
+        }
+    };
+
+    let payload = json!({
+        "program_id": "not_a_pubkey",
+        "name": "Broken Program"
+    });
+
+    let request = Request::builder()
+        .method(Method::POST)
+        .uri("/api/v1/programs/custom")
+        .header("content-type", "application/json")
+        .body(Body::from(payload.to_string()))
+        .unwrap();
+
+    let (status, _json) = send_request(&app, request).await.unwrap();
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+}
+
 #[tokio::test]
 async fn test_batch_analyze_pda() {
     let app = match create_test_app().await {
@@ -146,15 +229,16 @@ async fn test_batch_analyze_pda() {
         }
     };
 
+    let program_id = Pubkey::new_unique();
     let payload = json!({
-        "addresses": [
+        "pdas": [
             {
-                "address": "11111111111111111111111111111111",
-                "program_id": "11111111111111111111111111111111"
+                "address": Pubkey::find_program_address(&[b"state"], &program_id).0.to_string(),
+                "program_id": program_id.to_string()
             },
             {
-                "address": "22222222222222222222222222222222",
-                "program_id": "11111111111111111111111111111111"
+                "address": Pubkey::find_program_address(&[b"config"], &program_id).0.to_string(),
+                "program_id": program_id.to_string()
             }
         ]
     });
@@ -311,6 +395,56 @@ async fn test_get_database_metrics() {
     assert!(data["total_interactions"].is_number());
 }
 
+#[tokio::test]
+async fn test_get_fee_stats() {
+    let app = match create_test_app().await {
+        Ok(app) => app,
+        Err(_) => {
+            println!("Skipping API tests - no database connection");
+            return;
+        }
+    };
+
+    let request = Request::builder()
+        .method(Method::GET)
+        .uri("/api/v1/analytics/fees")
+        .body(Body::empty())
+        .unwrap();
+
+    let (status, json) = send_request(&app, request).await.unwrap();
+    assert_eq!(status, StatusCode::OK);
+
+    let data = &json["data"];
+    assert!(data["total_fees"].is_number());
+    assert!(data["p50_fee"].is_number());
+    assert!(data["p95_fee"].is_number());
+    assert!(data["failed_fees"].is_number());
+}
+
+#[tokio::test]
+async fn test_program_filtered_stats_reject_unknown_program() {
+    let app = match create_test_app().await {
+        Ok(app) => app,
+        Err(_) => {
+            println!("Skipping API tests - no database connection");
+            return;
+        }
+    };
+
+    let unknown = Pubkey::new_unique();
+    for path in ["/api/v1/analytics/fees", "/api/v1/analytics/interactions"] {
+        let request = Request::builder()
+            .method(Method::GET)
+            .uri(format!("{}?program_id={}", path, unknown))
+            .body(Body::empty())
+            .unwrap();
+
+        let (status, json) = send_request(&app, request).await.unwrap();
+        assert_eq!(status, StatusCode::NOT_FOUND, "{}", path);
+        assert_eq!(json["message"], "Program not found");
+    }
+}
+
 #[tokio::test]
 async fn test_invalid_endpoint() {
     let app = match create_test_app().await {
@@ -362,9 +496,11 @@ async fn test_missing_content_type() {
         }
     };
 
+    let program_id = Pubkey::new_unique();
+    let (address, _) = Pubkey::find_program_address(&[b"state"], &program_id);
     let payload = json!({
-        "address": "11111111111111111111111111111111",
-        "program_id": "11111111111111111111111111111111"
+        "address": address.to_string(),
+        "program_id": program_id.to_string()
     });
 
     let request = Request::builder()
@@ -373,9 +509,10 @@ async fn test_missing_content_type() {
         .body(Body::from(payload.to_string()))
         .unwrap();
 
-    let response = app.oneshot(request).await.unwrap();
-    // Should fail due to missing content-type
-    assert_ne!(response.status(), StatusCode::OK);
+    let (status, json) = send_request(&app, request).await.unwrap();
+    assert_eq!(status, StatusCode::UNSUPPORTED_MEDIA_TYPE);
+    assert_eq!(json["status_code"], 415);
+    assert!(json["message"].as_str().unwrap().contains("application/json"));
 }
 
 #[tokio::test]
@@ -396,8 +533,7 @@ async fn test_cors_headers() {
         .unwrap();
 
     let response = app.oneshot(request).await.unwrap();
-    let headers = response.headers();
-    
+
     // Check for CORS headers (implementation depends on tower-http configuration)
     assert!(response.status().is_success() || response.status() == StatusCode::METHOD_NOT_ALLOWED);
 }
@@ -414,15 +550,16 @@ async fn test_large_batch_request() {
 
     // Create a large batch request
     let mut addresses = Vec::new();
-    for i in 0..100 {
+    let program_id = Pubkey::new_unique();
+    for i in 0..100u64 {
         addresses.push(json!({
-            "address": format!("{:044}", i),
-            "program_id": "11111111111111111111111111111111"
+            "address": Pubkey::find_program_address(&[b"user", &i.to_le_bytes()], &program_id).0.to_string(),
+            "program_id": program_id.to_string()
         }));
     }
 
     let payload = json!({
-        "addresses": addresses
+        "pdas": addresses
     });
 
     let request = Request::builder()
@@ -497,4 +634,37 @@ async fn test_get_nonexistent_pda() {
 
     let (status, _json) = send_request(&app, request).await.unwrap();
     assert_eq!(status, StatusCode::NOT_FOUND);
-}
\ No newline at end of file
+}
+
+#[tokio::test]
+async fn test_discover_since_skips_stored_accounts() {
+    let repository = match create_test_database().await {
+        Ok(repository) => repository,
+        Err(_) => {
+            println!("Skipping API tests - no database connection");
+            return;
+        }
+    };
+
+    let program_id = Pubkey::new_unique();
+    let vaults: Vec<Pubkey> = (0..3u64)
+        .map(|i| Pubkey::find_program_address(&[b"vault", &i.to_le_bytes()], &program_id).0)
+        .collect();
+    let client = SolanaClient::offline([(program_id, vaults.clone())].into_iter().collect());
+    let processor = BatchProcessor::new();
+
+    let mut known = PdaAnalyzer::new().analyze_pda(&vaults[0], &program_id).unwrap().unwrap();
+    known.pda_info.first_seen_slot = Some(100);
+    repository.store_pda_analysis(&known).await.unwrap();
+    repository.record_discovery_high_water_mark(&program_id, 150).await.unwrap();
+
+    // Without a slot, the recorded one applies
+    let discovery = discover_since(&repository, &client, &processor, &program_id, None).await.unwrap();
+    assert_eq!(discovery.since_slot, Some(150));
+    assert_eq!((discovery.outcome.skipped, discovery.outcome.examined, discovery.stored), (1, 2, 2));
+    // An offline client can't tell the slot, so the mark stays put
+    assert_eq!(repository.discovery_high_water_mark(&program_id).await.unwrap(), Some(150));
+
+    let discovery = discover_since(&repository, &client, &processor, &program_id, Some(50)).await.unwrap();
+    assert_eq!((discovery.outcome.skipped, discovery.outcome.examined), (0, 3));
+}
@@ -1,71 +1,485 @@
-use clap::{Parser, Subcommand};
-use solana_pda_analyzer_core::PdaAnalyzer;
+use clap::{CommandFactory, Parser, Subcommand, ValueEnum};
+use clap_complete::Shell;
+use indicatif::{ProgressBar, ProgressStyle};
+use solana_pda_analyzer_core::{audit_bumps, BuildInfo, load_known_programs, AnalysisOutcome, AnchorIdl, ColorChoice, FailureSummary, parse_address, rank_results, DictionarySearch, MatchKind, PatternCategory, PdaAnalysisResult, PdaAnalyzer, PdaInfo, PdaPattern, SearchCheckpoint, SearchConfig, SearchOutcome, SeedSource, SeedValue, validate_seeds};
+use solana_pda_analyzer_analyzer::{AccountPaging, BatchProcessor, Network, SampleOutcome, SolanaClient};
+#[cfg(feature = "database")]
+use solana_pda_analyzer_api::{discover_since, distinct_signatures, ingest_signatures, StoredDiscovery};
+#[cfg(feature = "database")]
+use solana_pda_analyzer_database::{reanalyze_stored_pdas, DatabaseConfig, DatabaseRepository, MigrationStatus};
 use solana_sdk::pubkey::Pubkey;
+use std::collections::{BTreeMap, HashSet};
+use std::num::NonZeroUsize;
+use std::path::{Path, PathBuf};
 use std::str::FromStr;
-use tracing::{info, Level};
+use std::sync::Mutex;
+use std::time::Duration;
+use tracing::{info, warn, Level};
 use tracing_subscriber::FmtSubscriber;
 use anyhow::Result;
+use serde::{Deserialize, Serialize};
 
 #[derive(Parser)]
 #[clap(author, version, about, long_about = None)]
 struct Cli {
     #[clap(subcommand)]
     command: Commands,
+    /// Only try these pattern families, e.g. `sequential,complex` (ata, metaplex, singleton,
+    /// authority, self_ref, suffixed, sequential, complex, timestamp, epoch, stake, or pattern names like STRING_PUBKEY)
+    #[clap(long, global = true)]
+    only: Option<String>,
+    /// Search these pattern families first, in order, e.g. `authority,sequential` for a DEX;
+    /// takes the same names as `--only`
+    #[clap(long, global = true)]
+    priority: Option<String>,
+    /// How far each pattern family searches before giving up
+    #[clap(long, global = true, value_enum, default_value = "normal")]
+    depth: Depth,
+    /// JSON array of `{"program_id", "name"}` entries to report by name, added to or
+    /// renaming the built-in catalog
+    #[clap(long, global = true)]
+    known_programs: Option<PathBuf>,
+    /// Cluster whose public RPC endpoint commands that read chain data use: mainnet, devnet,
+    /// testnet or localhost
+    #[clap(long, global = true)]
+    network: Option<Network>,
+    /// RPC endpoint URL, overriding `--network`
+    #[clap(long, global = true)]
+    rpc_url: Option<String>,
+    /// PostgreSQL URL for commands that use the stored analyses; defaults to `DATABASE_URL`,
+    /// then the `DATABASE_*` settings the API server reads
+    #[cfg(feature = "database")]
+    #[clap(long, global = true)]
+    database_url: Option<String>,
+    /// Stop the complex-pattern search after this many seed combinations per address
+    #[clap(long, global = true)]
+    complex_max_combinations: Option<usize>,
+    /// Log at debug level and report how much of each search ran
+    #[clap(short, long, global = true)]
+    verbose: bool,
+    /// Color log output: auto (terminals only, unless NO_COLOR is set), always or never
+    #[clap(long, global = true, default_value = "auto")]
+    color: ColorChoice,
+    /// How to write log lines
+    #[clap(long, global = true, value_enum, default_value = "pretty")]
+    log_format: LogFormat,
 }
 
 #[derive(Subcommand)]
 enum Commands {
     /// Analyze a PDA
     Analyze {
-        /// PDA address to analyze
+        /// PDA address to analyze, as base58, hex or a byte array
+        #[clap(short, long)]
+        address: String,
+        /// Program ID; when omitted, the program that owns the account is looked up over RPC
+        #[clap(short, long)]
+        program_id: Option<String>,
+        /// How to print a recovered derivation
+        #[clap(long, value_enum, default_value = "text")]
+        format: OutputFormat,
+        /// Anchor IDL of the program; recovered seeds it declares are reported as confirmed
+        #[clap(long)]
+        idl: Option<PathBuf>,
+    },
+    /// Analyze many PDAs from a file of `address,program_id` lines
+    Batch {
+        /// Input file with one `address,program_id` pair per line
+        #[clap(short, long)]
+        input: PathBuf,
+        /// Show a progress bar with rate, ETA and running pattern tallies
+        #[clap(long)]
+        progress: bool,
+        /// Analyze on this many threads; defaults to one per physical core
+        #[clap(short, long)]
+        jobs: Option<NonZeroUsize>,
+        /// Write each result to `<address>.json` in this directory, plus an `index.json` listing them all
+        #[clap(long)]
+        output_dir: Option<PathBuf>,
+        /// Summarize why unrecognized addresses failed, most common reason first
+        #[clap(long)]
+        explain_failures: bool,
+    },
+    /// Analyze a program's accounts from a CSV export, e.g. from an explorer or snapshot tool, without RPC access
+    AnalyzeSnapshot {
+        /// CSV of account addresses; a header row picks the `address`, `pubkey` or `account` column,
+        /// otherwise the first column is used
+        #[clap(long)]
+        accounts: PathBuf,
+        /// Program that owns the accounts
+        #[clap(long)]
+        program: String,
+        /// Show a progress bar with rate, ETA and running pattern tallies
+        #[clap(long)]
+        progress: bool,
+        /// Analyze on this many threads; defaults to one per physical core
+        #[clap(short, long)]
+        jobs: Option<NonZeroUsize>,
+        /// Write each result to `<address>.json` in this directory, plus an `index.json` listing them all
+        #[clap(long)]
+        output_dir: Option<PathBuf>,
+        /// Summarize why unrecognized addresses failed, most common reason first
+        #[clap(long)]
+        explain_failures: bool,
+    },
+    /// Exhaustively try combinations of dictionary words as seeds
+    Search {
+        /// PDA address to recover seeds for
         #[clap(short, long)]
         address: String,
         /// Program ID
         #[clap(short, long)]
         program_id: String,
+        /// Dictionary with one seed per line; base58 pubkeys are tried as pubkey seeds
+        #[clap(short, long)]
+        words: PathBuf,
+        /// Maximum number of seeds per combination
+        #[clap(long, default_value = "2")]
+        max_seeds: usize,
+        /// File to periodically save progress to
+        #[clap(long)]
+        checkpoint: Option<PathBuf>,
+        /// Resume from a checkpoint written by an earlier run
+        #[clap(long)]
+        resume: Option<PathBuf>,
+        /// Show a progress bar with rate and ETA
+        #[clap(long)]
+        progress: bool,
+    },
+    /// Show step by step how known seeds derive an address
+    Explain {
+        /// Address the seeds are expected to derive
+        #[clap(short, long)]
+        address: String,
+        /// Program ID
+        #[clap(short, long)]
+        program_id: String,
+        /// Seeds in order as `type:value`, e.g. `str:vault`, `pubkey:<base58 or hex>`, `u64:7`, `bytes:<hex>`;
+        /// one value may hold several specs separated by whitespace, as `analyze` prints them
+        #[clap(short, long = "seed", required = true)]
+        seeds: Vec<String>,
+    },
+    /// Check that every PDA in a seeds manifest derives from its listed seeds; exits nonzero on any mismatch
+    Verify {
+        /// TOML file with `[[pda]]` entries giving `address`, `program_id` and `seeds` as `type:value` specs
+        #[clap(short, long)]
+        manifest: PathBuf,
+    },
+    /// Report which of a program's PDAs were derived with a non-canonical bump
+    AuditBumps {
+        /// Program whose PDAs to audit
+        #[clap(long)]
+        program: String,
+        /// JSON array of PDAs or analysis results, or a `batch --output-dir` directory
+        #[clap(short, long)]
+        input: PathBuf,
+    },
+    /// Retry stored PDAs with the current heuristics and vocabulary, storing those that now
+    /// resolve or match with more confidence
+    #[cfg(feature = "database")]
+    ReanalyzeAll {
+        /// Only retry PDAs stored without a recognized pattern
+        #[clap(long)]
+        only_unknown: bool,
+    },
+    /// Fetch transactions over RPC by signature, analyze them and store them with their PDAs
+    /// and interactions
+    #[cfg(feature = "database")]
+    IngestTransactions {
+        /// File with one transaction signature per line; blank lines and `#` comments are skipped
+        #[clap(short, long)]
+        input: PathBuf,
+    },
+    /// Analyze a program's accounts over RPC, every one or a random sample of them
+    Discover {
+        /// Program whose accounts to analyze
+        #[clap(long)]
+        program: String,
+        /// Analyze a random sample of this many accounts and report pattern shares
+        /// extrapolated to the whole program, for a quick read of a large one
+        #[clap(long)]
+        sample: Option<usize>,
+        /// Seed for picking the `--sample`, so a sampled run can be repeated exactly; random by default
+        #[clap(long, requires = "sample")]
+        seed: Option<u64>,
+        /// Analyze for at most this long, e.g. `300s` or `5m`, storing PDAs in the database as
+        /// they're found, then report how far it got
+        #[cfg(feature = "database")]
+        #[clap(long, value_parser = parse_duration, conflicts_with = "sample")]
+        duration: Option<Duration>,
+        /// Only analyze accounts not already stored as first seen at or before this slot, and
+        /// store the PDAs found; on its own, picks up from the last completed run
+        #[cfg(feature = "database")]
+        #[clap(long, conflicts_with_all = &["sample", "duration"])]
+        since_slot: Option<Option<u64>>,
+    },
+    /// Inspect or apply the database schema migrations
+    #[cfg(feature = "database")]
+    Migration {
+        #[clap(subcommand)]
+        command: MigrationCommand,
+    },
+    /// Keep analyzing a program's accounts as new ones appear, polling over RPC until interrupted
+    Watch {
+        /// Program whose accounts to watch
+        #[clap(long)]
+        program: String,
+        /// Time between polls, e.g. `30s` or `5m`
+        #[clap(long, value_parser = parse_duration, default_value = "30s")]
+        interval: Duration,
+        /// Show a spinner with accounts analyzed, rate and running pattern tallies
+        #[clap(long)]
+        progress: bool,
+    },
+    /// Print a shell completion script to stdout, e.g. `pda-analyzer completions --shell bash > /etc/bash_completion.d/pda-analyzer`
+    Completions {
+        #[clap(long, value_enum)]
+        shell: Shell,
+    },
+    /// List programs reported by name, including any from `--known-programs`
+    Programs {
+        /// Only list programs whose name contains this, ignoring case, e.g. `marinade`
+        #[clap(short, long)]
+        name: Option<String>,
     },
     /// Run example analyses
     Examples,
     /// Show version information
-    Version,
+    Version {
+        /// Print `{version, git_sha, build_time, rustc}` as JSON, as `GET /api/v1/version` returns it
+        #[clap(long)]
+        json: bool,
+    },
+}
+
+#[cfg(feature = "database")]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Subcommand)]
+enum MigrationCommand {
+    /// List every known migration and when it was applied, if it has been
+    Status,
+    /// Apply the migrations not yet applied
+    Run,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+enum OutputFormat {
+    /// Human-readable summary
+    Text,
+    /// Anchor `#[account(seeds = [...], bump)]` constraint
+    Anchor,
+}
+
+/// Log line format, as the API servers take it in `LOG_FORMAT`
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+enum LogFormat {
+    /// Human-readable lines
+    Pretty,
+    /// One JSON object per line with every structured field, for log aggregators
+    Json,
+}
+
+/// Speed/coverage presets for the pattern heuristics
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+enum Depth {
+    /// First 100 candidates per pattern family, for interactive use
+    Fast,
+    /// The built-in dictionaries and ranges
+    Normal,
+    /// Wider numeric ranges and every seed ordering, for batch runs
+    Deep,
+}
+
+impl Depth {
+    fn apply(self, config: &mut SearchConfig) {
+        match self {
+            Depth::Fast => config.candidate_limit = Some(100),
+            Depth::Normal => {}
+            Depth::Deep => {
+                config.permute_seed_order = true;
+                config.edition_range = 1..=100;
+                config.nonce_range = 0..=255;
+                config.bump_seed_range = 0..=255;
+                config.sequential_range = 0..=1000;
+                config.complex_numbers = 0..=10;
+            }
+        }
+    }
 }
 
 #[tokio::main]
 async fn main() -> Result<()> {
+    let cli = Cli::parse();
+    // Nothing but the script may reach stdout
+    if let Commands::Completions { shell } = cli.command {
+        clap_complete::generate(shell, &mut Cli::command(), "pda-analyzer", &mut std::io::stdout());
+        return Ok(());
+    }
+
     // Initialize tracing
-    let subscriber = FmtSubscriber::builder()
-        .with_max_level(Level::INFO)
-        .finish();
-    tracing::subscriber::set_global_default(subscriber)?;
+    let builder = FmtSubscriber::builder()
+        .with_max_level(if cli.verbose { Level::DEBUG } else { Level::INFO })
+        .with_ansi(cli.color.for_stdout());
+    match cli.log_format {
+        LogFormat::Pretty => tracing::subscriber::set_global_default(builder.finish())?,
+        LogFormat::Json => tracing::subscriber::set_global_default(
+            builder.json().flatten_event(true).with_current_span(false).finish(),
+        )?,
+    }
 
-    let cli = Cli::parse();
+    let mut config = SearchConfig::default();
+    cli.depth.apply(&mut config);
+    if cli.complex_max_combinations.is_some() {
+        config.complex_max_combinations = cli.complex_max_combinations;
+    }
+    if let Some(only) = &cli.only {
+        config.enabled_patterns = PdaPattern::parse_filter(only)?;
+    }
+    if let Some(priority) = &cli.priority {
+        config.pattern_priority = PdaPattern::parse_priority(priority)?;
+    }
+    let known_programs = match &cli.known_programs {
+        Some(path) => load_known_programs(path)?,
+        None => Vec::new(),
+    };
+    let rpc_url = Network::resolve_rpc_url(cli.network, cli.rpc_url.as_deref());
+    let build_analyzer = |config: &SearchConfig| {
+        PdaAnalyzer::builder().config(config.clone()).programs(known_programs.clone()).build()
+    };
 
     match cli.command {
-        Commands::Analyze { address, program_id } => {
-            analyze_pda(&address, &program_id).await?;
+        Commands::Analyze { address, program_id, format, idl } => {
+            let idl = idl.as_deref().map(AnchorIdl::load).transpose()?;
+            let program_id = match program_id {
+                Some(program_id) => program_id,
+                None => owner_program(&address, &SolanaClient::new(&rpc_url)).await?,
+            };
+            analyze_pda(&address, &program_id, format, cli.verbose, idl.as_ref(), build_analyzer(&config)).await?;
+        }
+        Commands::Batch { input, progress, jobs, output_dir, explain_failures } => {
+            config.batch_jobs = jobs;
+            let options = BatchOptions { progress, output_dir: output_dir.as_deref(), explain_failures };
+            run_batch(&input, options, build_analyzer(&config)).await?;
+        }
+        Commands::AnalyzeSnapshot { accounts, program, progress, jobs, output_dir, explain_failures } => {
+            config.batch_jobs = jobs;
+            let options = BatchOptions { progress, output_dir: output_dir.as_deref(), explain_failures };
+            run_snapshot(&accounts, &program, options, build_analyzer(&config)).await?;
+        }
+        Commands::Search { address, program_id, words, max_seeds, checkpoint, resume, progress } => {
+            let options = SearchOptions { max_seeds, checkpoint, resume, progress };
+            run_search(&address, &program_id, &words, options).await?;
+        }
+        Commands::Explain { address, program_id, seeds } => {
+            explain_derivation(&address, &program_id, &seeds)?;
+        }
+        Commands::Verify { manifest } => {
+            verify_manifest(&manifest)?;
+        }
+        Commands::AuditBumps { program, input } => {
+            run_bump_audit(&program, &input)?;
+        }
+        #[cfg(feature = "database")]
+        Commands::ReanalyzeAll { only_unknown } => {
+            let repository = connect_database(cli.database_url.as_deref()).await?;
+            run_reanalyze_all(&repository, build_analyzer(&config), only_unknown).await?;
+        }
+        #[cfg(feature = "database")]
+        Commands::IngestTransactions { input } => {
+            let repository = connect_database(cli.database_url.as_deref()).await?;
+            run_ingest(&repository, &SolanaClient::new(&rpc_url), &input).await?;
+        }
+        Commands::Discover {
+            program,
+            sample,
+            seed,
+            #[cfg(feature = "database")]
+            duration,
+            #[cfg(feature = "database")]
+            since_slot,
+        } => {
+            let client = SolanaClient::new(&rpc_url);
+            let mut processor = BatchProcessor::new().with_pda_analyzer(build_analyzer(&config));
+            if let Some(seed) = seed {
+                processor = processor.with_sample_seed(seed);
+            }
+            #[cfg(feature = "database")]
+            if let Some(budget) = duration {
+                let repository = connect_database(cli.database_url.as_deref()).await?;
+                return run_timed_discovery(&client, &processor, &repository, &program, budget).await;
+            }
+            #[cfg(feature = "database")]
+            if let Some(since_slot) = since_slot {
+                let repository = connect_database(cli.database_url.as_deref()).await?;
+                return run_incremental_discovery(&client, &processor, &repository, &program, since_slot).await;
+            }
+            run_discover(&client, &processor, &program, sample).await?;
+        }
+        #[cfg(feature = "database")]
+        Commands::Migration { command } => {
+            let repository = connect_database(cli.database_url.as_deref()).await?;
+            match command {
+                MigrationCommand::Status => print_migration_status(&repository.migration_status().await?),
+                MigrationCommand::Run => {
+                    repository.migrate().await?;
+                    print_migration_status(&repository.migration_status().await?);
+                }
+            }
+        }
+        Commands::Watch { program, interval, progress } => {
+            run_watch(&SolanaClient::new(&rpc_url), &program, interval, progress, build_analyzer(&config)).await?;
+        }
+        Commands::Completions { .. } => unreachable!("handled before logging starts"),
+        Commands::Programs { name } => {
+            list_programs(name.as_deref().unwrap_or(""), &build_analyzer(&config));
         }
         Commands::Examples => {
             run_examples().await?;
         }
-        Commands::Version => {
-            println!("Solana PDA Analyzer v{}", env!("CARGO_PKG_VERSION"));
-            println!("A comprehensive tool for analyzing Solana Program Derived Addresses");
+        Commands::Version { json } => {
+            let build = BuildInfo::current();
+            if json {
+                println!("{}", serde_json::to_string_pretty(&build)?);
+            } else {
+                println!("Solana PDA Analyzer v{}", build.version);
+                println!("A comprehensive tool for analyzing Solana Program Derived Addresses");
+                println!("Built from {} at {} with {}", build.git_sha, build.build_time, build.rustc);
+            }
         }
     }
 
     Ok(())
 }
 
-async fn analyze_pda(address: &str, program_id: &str) -> Result<()> {
+/// The program owning the account at `address`, for analyzing an address whose program isn't known
+async fn owner_program(address: &str, client: &SolanaClient) -> Result<String> {
+    let owner = client.owner_program(&parse_address(address)?).await?;
+    info!("{} is owned by program {}", address, owner);
+    Ok(owner.to_string())
+}
+
+async fn analyze_pda(address: &str, program_id: &str, format: OutputFormat, verbose: bool, idl: Option<&AnchorIdl>, mut analyzer: PdaAnalyzer) -> Result<()> {
     info!("Analyzing PDA: {} for program: {}", address, program_id);
     
-    let pda_address = Pubkey::from_str(address)?;
-    let program_pubkey = Pubkey::from_str(program_id)?;
+    let pda_address = parse_address(address)?;
+    let program_pubkey = parse_address(program_id)?;
     
-    let mut analyzer = PdaAnalyzer::new();
     
-    match analyzer.analyze_pda(&pda_address, &program_pubkey)? {
-        Some(analysis_result) => {
+    let mut outcome = analyzer.analyze(&pda_address, &program_pubkey)?;
+    let idl_check = match (&mut outcome, idl) {
+        (AnalysisOutcome::Recognized(result), Some(idl)) => Some(idl.confirm(result)),
+        _ => None,
+    };
+    if verbose {
+        if let Some(stats) = analyzer.complex_search_stats() {
+            info!("{}", stats);
+        }
+    }
+
+    match outcome {
+        AnalysisOutcome::Recognized(analysis_result) if format == OutputFormat::Anchor => {
+            print!("{}", render_anchor_constraint(&analysis_result.pda_info));
+        }
+        AnalysisOutcome::Recognized(analysis_result) => {
             println!("✅ PDA Analysis Successful!");
             println!("🏷️  Address: {}", analysis_result.pda_info.address);
             println!("🔧 Program ID: {}", analysis_result.pda_info.program_id);
@@ -75,10 +489,23 @@ async fn analyze_pda(address: &str, program_id: &str) -> Result<()> {
             }
             
             println!("🎯 Pattern: {} ({:.1}% confidence)", 
-                     analysis_result.pattern.as_str(), 
+                     analysis_result.pattern.name(), 
                      analysis_result.confidence * 100.0);
+            println!("📖 {}", analysis_result.pattern.description());
+            println!("🧭 Match: {}", analysis_result.match_kind.as_str());
+            match idl_check {
+                Some(Some(validation)) => {
+                    println!("📜 IDL: confirmed as `{}` in `{}`", validation.account, validation.instruction);
+                }
+                Some(None) => println!("📜 IDL: no declared PDA matches these seeds"),
+                None => {}
+            }
             println!("⏱️  Analysis Time: {}ms", analysis_result.analysis_time_ms);
-            println!("🔢 Bump: {}", analysis_result.pda_info.bump);
+            if analysis_result.bump_as_explicit_seed {
+                println!("🔢 Bump: {} (passed as the last seed, possibly non-canonical)", analysis_result.pda_info.bump);
+            } else {
+                println!("🔢 Bump: {}", analysis_result.pda_info.bump);
+            }
             
             println!("🌱 Seeds ({} total):", analysis_result.pda_info.seeds.len());
             for (i, seed) in analysis_result.pda_info.seeds.iter().enumerate() {
@@ -90,19 +517,46 @@ async fn analyze_pda(address: &str, program_id: &str) -> Result<()> {
                     solana_pda_analyzer_core::SeedValue::U16(_) |
                     solana_pda_analyzer_core::SeedValue::U8(_) => "🔢",
                     solana_pda_analyzer_core::SeedValue::Bytes(_) => "📦",
+                    solana_pda_analyzer_core::SeedValue::Hash(_) => "🔒",
                 };
-                println!("  {}. {} {:?}", i + 1, icon, seed);
+                let source = match analysis_result.seed_sources.get(i) {
+                    Some(SeedSource::ProgramVocabulary) => " (program vocabulary)",
+                    Some(SeedSource::GlobalRegistry) => " (candidate registry)",
+                    Some(SeedSource::BuiltIn) | None => "",
+                };
+                println!("  {}. {} {} {}{}", i + 1, icon, seed.seed_type(), seed, source);
             }
+            println!("🧾 Seed spec: {}", analysis_result.to_seed_spec_string());
         }
-        None => {
+        AnalysisOutcome::NotAPda => {
+            println!("❌ Not a PDA");
+            println!("{}", analyzer.describe_unrecognized(&pda_address, &program_pubkey).message());
+        }
+        AnalysisOutcome::Unrecognized { attempted } => {
+            let report = analyzer.suggest_missing_candidates(&pda_address, &program_pubkey);
+            let unrecognized = &report.unrecognized;
             println!("❌ Could not derive seeds for the given PDA");
-            println!("This could mean:");
-            println!("  - The address is not a valid PDA for this program");
-            println!("  - The seed derivation pattern is not recognized");
-            println!("  - The PDA uses an uncommon or custom pattern");
-            
-            if let Some(program_name) = analyzer.get_program_name(&program_pubkey) {
-                println!("  - Program: {}", program_name);
+            println!("{}", unrecognized.message());
+
+            if let Some(program_name) = &unrecognized.program_name {
+                println!("📝 Program: {}", program_name);
+            }
+
+            if attempted.is_empty() {
+                println!("🔍 No pattern had candidates to try for this program");
+            } else {
+                let attempted: Vec<&str> = attempted.iter().map(PdaPattern::as_str).collect();
+                println!("🔍 Tried: {}", attempted.join(", "));
+            }
+            if let Some(stats) = analyzer.complex_search_stats().filter(|stats| stats.truncated) {
+                println!("✂️  Note: {}; raise --complex-max-combinations to search further", stats);
+            }
+
+            if !report.suggestions.is_empty() {
+                println!("💡 These candidates could help recover the seeds:");
+                for suggestion in &report.suggestions {
+                    println!("  - {:?} for {}: {}", suggestion.kind, suggestion.pattern.name(), suggestion.reason);
+                }
             }
         }
     }
@@ -110,6 +564,821 @@ async fn analyze_pda(address: &str, program_id: &str) -> Result<()> {
     Ok(())
 }
 
+/// Well-known programs, named the way Anchor account structs usually name them
+const KNOWN_PROGRAM_ACCOUNTS: [(&str, &str); 4] = [
+    ("11111111111111111111111111111111", "system_program"),
+    ("TokenkegQfeZyiNwAJbNbGKPFXCWuBvf9Ss623VQ5DA", "token_program"),
+    ("ATokenGPvbdGVxr1b2hvZbsiqW5xWH25efTNsLJA8knL", "associated_token_program"),
+    ("metaqbxxUerdq28cj1RbAWkYQm3ybzjb6a8bt518x1s", "token_metadata_program"),
+];
+
+/// Render a recovered derivation as an Anchor seeds constraint. Strings become byte literals;
+/// pubkeys and integers become placeholder accounts and arguments, annotated with the observed value.
+fn render_anchor_constraint(pda: &PdaInfo) -> String {
+    let mut out = String::from("#[account(\n    seeds = [\n");
+
+    for (i, seed) in pda.seeds.iter().enumerate() {
+        let (expr, observed) = match seed {
+            // Byte string literals only take ASCII; anything else is spelled out as bytes
+            SeedValue::String(s) if s.is_ascii() => (format!("b\"{}\"", s.escape_default()), None),
+            SeedValue::String(s) => (byte_array_literal(s.as_bytes()), None),
+            SeedValue::Bytes(bytes) => (byte_array_literal(bytes), None),
+            SeedValue::Hash(digest) => (byte_array_literal(digest), None),
+            SeedValue::Pubkey(key) if *key == pda.program_id => ("crate::ID.as_ref()".to_string(), None),
+            SeedValue::Pubkey(key) => {
+                let known = KNOWN_PROGRAM_ACCOUNTS.iter().find(|(id, _)| *id == key.to_string()).map(|(_, name)| name.to_string());
+                let name = known.unwrap_or_else(|| format!("account_{}", i));
+                (format!("{}.key().as_ref()", name), Some(key.to_string()))
+            }
+            SeedValue::U64(n) => (format!("arg_{}.to_le_bytes().as_ref()", i), Some(format!("{}u64", n))),
+            SeedValue::U32(n) => (format!("arg_{}.to_le_bytes().as_ref()", i), Some(format!("{}u32", n))),
+            SeedValue::U16(n) => (format!("arg_{}.to_le_bytes().as_ref()", i), Some(format!("{}u16", n))),
+            SeedValue::U8(n) => (format!("arg_{}.to_le_bytes().as_ref()", i), Some(format!("{}u8", n))),
+        };
+
+        match observed {
+            Some(observed) => out.push_str(&format!("        {}, // {}\n", expr, observed)),
+            None => out.push_str(&format!("        {},\n", expr)),
+        }
+    }
+
+    out.push_str(&format!("    ],\n    bump, // canonical bump {}\n)]\n", pda.bump));
+    out
+}
+
+fn byte_array_literal(bytes: &[u8]) -> String {
+    let bytes: Vec<String> = bytes.iter().map(|b| format!("0x{:02x}", b)).collect();
+    format!("&[{}]", bytes.join(", "))
+}
+
+/// How `batch` and `analyze-snapshot` report their results
+struct BatchOptions<'a> {
+    progress: bool,
+    output_dir: Option<&'a Path>,
+    explain_failures: bool,
+}
+
+async fn run_batch(input: &Path, options: BatchOptions<'_>, analyzer: PdaAnalyzer) -> Result<()> {
+    let contents = std::fs::read_to_string(input)?;
+    // Parse everything up front so a typo fails fast instead of minutes into the run
+    let pdas = parse_batch_file(&contents)?;
+    info!("Batch analyzing {} PDAs from {}", pdas.len(), input.display());
+
+    analyze_and_report(&pdas, options, analyzer)
+}
+
+/// Analyze every account in a snapshot CSV against one program, reporting like `batch`
+async fn run_snapshot(accounts: &Path, program_id: &str, options: BatchOptions<'_>, analyzer: PdaAnalyzer) -> Result<()> {
+    let program_id = parse_address(program_id)?;
+    let contents = std::fs::read_to_string(accounts)?;
+    let pdas: Vec<(Pubkey, Pubkey)> = parse_snapshot_csv(&contents)?
+        .into_iter()
+        .map(|address| (address, program_id))
+        .collect();
+    info!("Analyzing {} accounts of {} from {}", pdas.len(), program_id, accounts.display());
+
+    analyze_and_report(&pdas, options, analyzer)
+}
+
+/// Analyze `pdas` in parallel, write any output directory, then print ranked results and tallies
+fn analyze_and_report(pdas: &[(Pubkey, Pubkey)], options: BatchOptions<'_>, analyzer: PdaAnalyzer) -> Result<()> {
+    let progress_bar = if options.progress {
+        let bar = ProgressBar::new(pdas.len() as u64);
+        bar.set_style(
+            ProgressStyle::with_template("[{elapsed_precise}] {bar:40} {pos}/{len} ({per_sec}, ETA {eta}) {msg}")?,
+        );
+        bar
+    } else {
+        ProgressBar::hidden()
+    };
+
+    let tallies: Mutex<BTreeMap<String, usize>> = Mutex::new(BTreeMap::new());
+
+    let results = analyzer.batch_analyze_parallel_with(pdas, |_, result| {
+        let pattern = match result {
+            Some(result) => result.pattern.name().into_owned(),
+            None => "UNRECOGNIZED".to_string(),
+        };
+
+        let mut tallies = tallies.lock().unwrap();
+        *tallies.entry(pattern).or_insert(0) += 1;
+        progress_bar.set_message(format_tallies(&tallies));
+        progress_bar.inc(1);
+    })?;
+
+    progress_bar.finish_and_clear();
+    let tallies = tallies.into_inner().unwrap();
+
+    if let Some(output_dir) = options.output_dir {
+        let written = write_batch_outputs(output_dir, pdas, &results)?;
+        info!("Wrote {} result files and an index to {}", written, output_dir.display());
+    }
+
+    // Best evidence first, so structural matches aren't buried under lucky brute-force hits
+    let (mut recognized, mut unrecognized) = (Vec::new(), Vec::new());
+    for ((address, program_id), result) in pdas.iter().zip(results) {
+        match result {
+            Some(result) => recognized.push(result),
+            None => unrecognized.push((address, program_id)),
+        }
+    }
+    rank_results(&mut recognized);
+
+    for result in &recognized {
+        println!(
+            "✅ {} -> {} ({:.1}% confidence, {})",
+            result.pda_info.address,
+            result.pattern.name(),
+            result.confidence * 100.0,
+            result.match_kind.as_str()
+        );
+    }
+    for (address, _) in &unrecognized {
+        println!("❌ {} -> unrecognized", address);
+    }
+
+    println!("\n📈 Batch Summary ({} PDAs):", pdas.len());
+    for (pattern, count) in &tallies {
+        println!("   • {}: {}", pattern, count);
+    }
+
+    let mut categories: BTreeMap<PatternCategory, usize> = BTreeMap::new();
+    for result in &recognized {
+        *categories.entry(result.pattern.category()).or_insert(0) += 1;
+    }
+    if !categories.is_empty() {
+        println!("🗂️  By category:");
+        for (category, count) in &categories {
+            println!("   • {}: {}", category.as_str(), count);
+        }
+    }
+
+    if options.explain_failures && !unrecognized.is_empty() {
+        let reports: Vec<_> = unrecognized
+            .iter()
+            .map(|(address, program_id)| analyzer.suggest_missing_candidates(address, program_id))
+            .collect();
+        print_failure_summary(&FailureSummary::from_reports(&reports));
+    }
+
+    Ok(())
+}
+
+/// Print why unrecognized addresses failed, as a to-do list for improving recovery
+fn print_failure_summary(summary: &FailureSummary) {
+    println!("🩺 Why {} PDAs went unrecognized:", summary.total);
+    for (reason, count) in &summary.reasons {
+        println!("   • {:.0}% ({}) {}", summary.share(*count) * 100.0, count, reason.describe());
+    }
+}
+
+/// One line of a batch `index.json`; `file` is `None` for unrecognized PDAs, which get no file
+#[derive(Debug, Serialize, Deserialize)]
+struct BatchIndexEntry {
+    address: String,
+    program_id: String,
+    pattern: Option<String>,
+    confidence: Option<f64>,
+    match_kind: Option<MatchKind>,
+    file: Option<String>,
+}
+
+/// Write each recognized result to `<address>.json` in `dir`, then `index.json` with every
+/// input in order. The index goes last, so a watcher that sees it can read every file it lists.
+/// Returns the number of result files written.
+fn write_batch_outputs(dir: &Path, pdas: &[(Pubkey, Pubkey)], results: &[Option<PdaAnalysisResult>]) -> Result<usize> {
+    std::fs::create_dir_all(dir)?;
+    let mut index = Vec::with_capacity(pdas.len());
+
+    for ((address, program_id), result) in pdas.iter().zip(results) {
+        let file = match result {
+            Some(result) => {
+                let file = format!("{}.json", address);
+                std::fs::write(dir.join(&file), serde_json::to_string_pretty(result)?)?;
+                Some(file)
+            }
+            None => None,
+        };
+
+        index.push(BatchIndexEntry {
+            address: address.to_string(),
+            program_id: program_id.to_string(),
+            pattern: result.as_ref().map(|result| result.pattern.name().into_owned()),
+            confidence: result.as_ref().map(|result| result.confidence),
+            match_kind: result.as_ref().map(|result| result.match_kind),
+            file,
+        });
+    }
+
+    std::fs::write(dir.join("index.json"), serde_json::to_string_pretty(&index)?)?;
+    Ok(index.iter().filter(|entry| entry.file.is_some()).count())
+}
+
+/// Parse `address,program_id` lines, skipping blanks and `#` comments
+fn parse_batch_file(contents: &str) -> Result<Vec<(Pubkey, Pubkey)>> {
+    let mut pdas = Vec::new();
+
+    for (line_number, line) in contents.lines().enumerate() {
+        let line = line.trim();
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+
+        let mut parts = line.split(|c: char| c == ',' || c.is_whitespace()).filter(|p| !p.is_empty());
+        let (Some(address), Some(program_id), None) = (parts.next(), parts.next(), parts.next()) else {
+            anyhow::bail!("line {}: expected `address,program_id`", line_number + 1);
+        };
+
+        let address = parse_address(address)
+            .map_err(|e| anyhow::anyhow!("line {}: invalid address: {}", line_number + 1, e))?;
+        let program_id = parse_address(program_id)
+            .map_err(|e| anyhow::anyhow!("line {}: invalid program ID: {}", line_number + 1, e))?;
+        pdas.push((address, program_id));
+    }
+
+    Ok(pdas)
+}
+
+/// Column names a snapshot CSV header may give the account address under
+const SNAPSHOT_ADDRESS_COLUMNS: [&str; 4] = ["address", "pubkey", "account", "account_address"];
+
+/// Account addresses from a snapshot CSV, in file order without repeats. A first row whose
+/// first field isn't an address is a header and picks the address column; blank lines and `#`
+/// comments are skipped, and fields may be double-quoted.
+fn parse_snapshot_csv(contents: &str) -> Result<Vec<Pubkey>> {
+    let mut addresses = Vec::new();
+    let mut seen = std::collections::HashSet::new();
+    let mut column = None;
+
+    for (line_number, line) in contents.lines().enumerate() {
+        let line = line.trim();
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+        let fields: Vec<&str> = line.split(',').map(|field| field.trim().trim_matches('"').trim()).collect();
+
+        let index = match column {
+            Some(index) => index,
+            None if parse_address(fields[0]).is_ok() => *column.insert(0),
+            None => {
+                let index = fields
+                    .iter()
+                    .position(|field| SNAPSHOT_ADDRESS_COLUMNS.contains(&field.to_lowercase().as_str()))
+                    .ok_or_else(|| anyhow::anyhow!(
+                        "line {}: header has no {} column",
+                        line_number + 1,
+                        SNAPSHOT_ADDRESS_COLUMNS.join(", ")
+                    ))?;
+                column = Some(index);
+                continue;
+            }
+        };
+
+        let Some(field) = fields.get(index) else {
+            anyhow::bail!("line {}: missing address column {}", line_number + 1, index + 1);
+        };
+        let address = parse_address(field)
+            .map_err(|e| anyhow::anyhow!("line {}: invalid address: {}", line_number + 1, e))?;
+        if seen.insert(address) {
+            addresses.push(address);
+        }
+    }
+
+    Ok(addresses)
+}
+
+fn format_tallies(tallies: &BTreeMap<String, usize>) -> String {
+    tallies
+        .iter()
+        .map(|(pattern, count)| format!("{}:{}", pattern, count))
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+struct SearchOptions {
+    max_seeds: usize,
+    checkpoint: Option<PathBuf>,
+    resume: Option<PathBuf>,
+    progress: bool,
+}
+
+async fn run_search(address: &str, program_id: &str, words: &Path, options: SearchOptions) -> Result<()> {
+    let pda_address = parse_address(address)?;
+    let program_pubkey = parse_address(program_id)?;
+
+    let vocabulary = parse_word_list(&std::fs::read(words)?);
+    let search = DictionarySearch::new(vocabulary, options.max_seeds);
+    let total = search.total_combinations();
+
+    let start = match &options.resume {
+        Some(path) => search.resume_index(&SearchCheckpoint::load(path)?, &pda_address, &program_pubkey)?,
+        None => 0,
+    };
+    // Keep saving to the checkpoint being resumed unless told otherwise
+    let checkpoint_path = options.checkpoint.or(options.resume);
+
+    info!(
+        "Searching {} combinations of {} words (up to {} seeds), starting at {}",
+        total,
+        search.vocabulary().len(),
+        options.max_seeds,
+        start
+    );
+
+    let progress_bar = if options.progress {
+        let bar = ProgressBar::new(total);
+        bar.set_style(ProgressStyle::with_template("[{elapsed_precise}] {bar:40} {pos}/{len} ({per_sec}, ETA {eta})")?);
+        bar.set_position(start);
+        bar
+    } else {
+        ProgressBar::hidden()
+    };
+
+    let outcome = search.run(&pda_address, &program_pubkey, start, |next_index| {
+        progress_bar.set_position(next_index);
+        match &checkpoint_path {
+            Some(path) => search.checkpoint(&pda_address, &program_pubkey, next_index).save(path),
+            None => Ok(()),
+        }
+    })?;
+
+    progress_bar.finish_and_clear();
+
+    match outcome {
+        SearchOutcome::Found { seeds, bump, index } => {
+            println!("✅ Seeds found after {} combinations!", index + 1);
+            println!("🔢 Bump: {}", bump);
+            for (i, seed) in seeds.iter().enumerate() {
+                println!("  {}. {} {}", i + 1, seed.seed_type(), seed);
+            }
+        }
+        SearchOutcome::Exhausted { tried } => {
+            println!("❌ No combination matched ({} tried)", tried);
+        }
+    }
+
+    if let Some(path) = &checkpoint_path {
+        // The search finished, so there is nothing left to resume
+        std::fs::remove_file(path).ok();
+    }
+
+    Ok(())
+}
+
+/// Parse a dictionary file, skipping blanks and `#` comments. Lines that aren't valid UTF-8
+/// or contain control characters are kept as byte seeds.
+fn parse_word_list(contents: &[u8]) -> Vec<SeedValue> {
+    contents
+        .split(|&b| b == b'\n')
+        .map(<[u8]>::trim_ascii)
+        .filter(|line| !line.is_empty() && !line.starts_with(b"#"))
+        .map(|word| match std::str::from_utf8(word).map(Pubkey::from_str) {
+            Ok(Ok(pubkey)) => SeedValue::Pubkey(pubkey),
+            _ => SeedValue::from_seed_bytes(word),
+        })
+        .collect()
+}
+
+fn explain_derivation(address: &str, program_id: &str, seed_specs: &[String]) -> Result<()> {
+    let pda_address = parse_address(address)?;
+    let program_pubkey = parse_address(program_id)?;
+    let seeds = seed_specs
+        .iter()
+        .map(|specs| SeedValue::parse_specs(specs))
+        .collect::<std::result::Result<Vec<_>, _>>()?
+        .concat();
+    let seed_bytes = validate_seeds(&seeds)?;
+
+    println!("🔧 Program ID: {}", program_pubkey);
+    println!("🌱 Seeds ({} total):", seeds.len());
+    for (i, (seed, bytes)) in seeds.iter().zip(&seed_bytes).enumerate() {
+        println!("  {}. {} {}", i + 1, seed.seed_type(), seed);
+        println!("     {} bytes: {}", bytes.len(), hex::encode(bytes));
+    }
+    println!("🔗 Concatenated seeds: {}", hex::encode(seed_bytes.concat()));
+
+    let seed_refs: Vec<&[u8]> = seed_bytes.iter().map(Vec::as_slice).collect();
+    let Some((derived, bump)) = Pubkey::try_find_program_address(&seed_refs, &program_pubkey) else {
+        anyhow::bail!("no bump produces an off-curve address for these seeds");
+    };
+    println!("🔢 Canonical bump: {} (first bump from 255 down whose address is off the ed25519 curve)", bump);
+    println!("📝 Hashed: sha256(seeds || [{}] || program_id || \"ProgramDerivedAddress\")", bump);
+    println!("🏷️  Derived address: {}", derived);
+
+    if derived == pda_address {
+        println!("✅ Matches {}", pda_address);
+    } else if let Some(bump) = find_explicit_bump(&seed_refs, &pda_address, &program_pubkey) {
+        println!("⚠️  Does not match {}, but bump {} does: the program stores a non-canonical bump", pda_address, bump);
+    } else {
+        println!("❌ Does not match {}", pda_address);
+        println!("Check the seed order, integer widths and byte order, and the program ID");
+    }
+
+    Ok(())
+}
+
+/// A program's expected PDA layout, checked by `verify`
+#[derive(Debug, Deserialize)]
+struct SeedsManifest {
+    #[serde(rename = "pda", default)]
+    entries: Vec<ManifestEntry>,
+}
+
+#[derive(Debug, Deserialize)]
+struct ManifestEntry {
+    /// Label used in the report, e.g. the account's name
+    name: Option<String>,
+    address: String,
+    program_id: String,
+    /// Seeds in order as `type:value` specs, as for `explain`
+    seeds: Vec<String>,
+    /// Bump the program stores, if it should be checked too
+    bump: Option<u8>,
+}
+
+impl ManifestEntry {
+    fn label(&self) -> &str {
+        self.name.as_deref().unwrap_or(&self.address)
+    }
+
+    /// The bump that derives the address from the seeds, or why none does
+    fn verify(&self) -> std::result::Result<u8, String> {
+        let address = parse_address(&self.address).map_err(|e| format!("invalid address: {}", e))?;
+        let program_id = parse_address(&self.program_id).map_err(|e| format!("invalid program_id: {}", e))?;
+        let seeds = self
+            .seeds
+            .iter()
+            .map(|spec| SeedValue::parse_spec(spec))
+            .collect::<std::result::Result<Vec<_>, _>>()
+            .map_err(|e| e.to_string())?;
+        let seeds = validate_seeds(&seeds).map_err(|e| e.to_string())?;
+
+        let seed_refs: Vec<&[u8]> = seeds.iter().map(Vec::as_slice).collect();
+        let canonical = Pubkey::try_find_program_address(&seed_refs, &program_id);
+        let bump = match canonical {
+            Some((derived, bump)) if derived == address => bump,
+            _ => match self.bump {
+                // Programs may store an explicit non-canonical bump
+                Some(bump) if find_explicit_bump(&seed_refs, &address, &program_id) == Some(bump) => bump,
+                _ => {
+                    let derived = canonical.map_or("nothing".to_string(), |(derived, _)| derived.to_string());
+                    return Err(format!("seeds derive {}", derived));
+                }
+            },
+        };
+
+        match self.bump {
+            Some(expected) if expected != bump => Err(format!("derives with bump {}, expected {}", bump, expected)),
+            _ => Ok(bump),
+        }
+    }
+}
+
+fn parse_manifest(contents: &str) -> Result<Vec<ManifestEntry>> {
+    let manifest: SeedsManifest = toml::from_str(contents)?;
+    if manifest.entries.is_empty() {
+        anyhow::bail!("manifest has no [[pda]] entries");
+    }
+    Ok(manifest.entries)
+}
+
+fn verify_manifest(path: &Path) -> Result<()> {
+    let entries = parse_manifest(&std::fs::read_to_string(path)?)?;
+
+    let mut failures = 0;
+    for entry in &entries {
+        match entry.verify() {
+            Ok(bump) => println!("✅ {} (bump {})", entry.label(), bump),
+            Err(reason) => {
+                failures += 1;
+                println!("❌ {}: {}", entry.label(), reason);
+            }
+        }
+    }
+
+    println!("{} of {} entries verified", entries.len() - failures, entries.len());
+    if failures > 0 {
+        anyhow::bail!("{} manifest entries did not derive", failures);
+    }
+    Ok(())
+}
+
+/// Bump, other than the canonical one, that derives `address` via `create_program_address`
+fn find_explicit_bump(seeds: &[&[u8]], address: &Pubkey, program_id: &Pubkey) -> Option<u8> {
+    (0..=u8::MAX).rev().find(|bump| {
+        let bump_seed = [*bump];
+        let mut seeds = seeds.to_vec();
+        seeds.push(&bump_seed);
+        Pubkey::create_program_address(&seeds, program_id).is_ok_and(|derived| derived == *address)
+    })
+}
+
+/// A PDA to audit as written by this tool or the API: bare, or inside an analysis result
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum AuditInput {
+    Result(Box<PdaAnalysisResult>),
+    Pda(PdaInfo),
+}
+
+impl AuditInput {
+    fn into_pda(self) -> PdaInfo {
+        match self {
+            AuditInput::Result(result) => result.pda_info,
+            AuditInput::Pda(pda) => pda,
+        }
+    }
+}
+
+/// PDAs in a JSON array file, or in the `<address>.json` results of a batch output directory
+fn load_audit_input(path: &Path) -> Result<Vec<PdaInfo>> {
+    if !path.is_dir() {
+        let entries: Vec<AuditInput> = serde_json::from_str(&std::fs::read_to_string(path)?)?;
+        return Ok(entries.into_iter().map(AuditInput::into_pda).collect());
+    }
+
+    let mut pdas = Vec::new();
+    for entry in std::fs::read_dir(path)? {
+        let file = entry?.path();
+        if file.extension().is_none_or(|extension| extension != "json") || file.ends_with("index.json") {
+            continue;
+        }
+        let entry: AuditInput = serde_json::from_str(&std::fs::read_to_string(&file)?)
+            .map_err(|e| anyhow::anyhow!("{}: {}", file.display(), e))?;
+        pdas.push(entry.into_pda());
+    }
+    Ok(pdas)
+}
+
+fn run_bump_audit(program_id: &str, input: &Path) -> Result<()> {
+    let program_id = parse_address(program_id)?;
+    let pdas = load_audit_input(input)?;
+    let audit = audit_bumps(&program_id, &pdas);
+
+    println!("🔍 Bump audit for {}", program_id);
+    println!("   PDAs checked: {}", audit.total);
+    println!("   Canonical: {}", audit.canonical);
+    println!(
+        "   Non-canonical: {} ({:.1}% of derivable)",
+        audit.non_canonical.len(),
+        audit.non_canonical_share() * 100.0
+    );
+    println!("   Underivable from stored seeds: {}", audit.underivable);
+
+    for pda in &audit.non_canonical {
+        println!("⚠️  {} uses bump {} (canonical {})", pda.address, pda.bump, pda.canonical_bump);
+    }
+    Ok(())
+}
+
+async fn run_discover(client: &SolanaClient, processor: &BatchProcessor, program_id: &str, sample: Option<usize>) -> Result<()> {
+    let program_id = parse_address(program_id)?;
+    let paging = AccountPaging::default();
+
+    match sample {
+        Some(sample_size) => {
+            let outcome = processor.sample_program_analysis(client, &program_id, &paging, sample_size).await?;
+            print_sample(&outcome);
+        }
+        None => {
+            let outcome = processor.discover_and_analyze(client, &program_id, &paging).await;
+            println!("🔍 Recognized {} PDAs owned by {}", outcome.results.len(), program_id);
+            let mut tallies = BTreeMap::new();
+            for result in &outcome.results {
+                *tallies.entry(result.pattern.name().into_owned()).or_insert(0) += 1;
+            }
+            if !tallies.is_empty() {
+                println!("📊 {}", format_tallies(&tallies));
+            }
+            if let Some(error) = &outcome.error {
+                println!("⚠️  Stopped before every account was analyzed: {}", error);
+            }
+        }
+    }
+    Ok(())
+}
+
+fn print_sample(outcome: &SampleOutcome) {
+    println!("🎲 Sampled {} of {} accounts", outcome.sample_size, outcome.population);
+    for (entry, (_, extrapolated)) in outcome.histogram.patterns.iter().zip(outcome.extrapolated_counts()) {
+        println!("   {}: {:.1}% of sample, ~{} accounts", entry.stat.pattern, entry.stat.percentage, extrapolated);
+    }
+}
+
+/// PDAs found by `discover --duration` waiting to be stored
+#[cfg(feature = "database")]
+const DISCOVERY_STORE_BUFFER: usize = 256;
+
+/// Run `discover_for_duration`, storing each PDA as it's found so an interrupted run keeps
+/// what it recovered. A storage failure stops discovery.
+#[cfg(feature = "database")]
+async fn run_timed_discovery(
+    client: &SolanaClient,
+    processor: &BatchProcessor,
+    repository: &DatabaseRepository,
+    program_id: &str,
+    budget: Duration,
+) -> Result<()> {
+    let program_id = parse_address(program_id)?;
+    let paging = AccountPaging::default();
+    let (sender, mut receiver) = tokio::sync::mpsc::channel(DISCOVERY_STORE_BUFFER);
+    // Returning drops the receiver, which ends discovery
+    let store = async move {
+        let mut stored = 0;
+        while let Some(result) = receiver.recv().await {
+            repository.store_pda_analysis(&result).await?;
+            stored += 1;
+        }
+        Ok::<_, solana_pda_analyzer_core::PdaAnalyzerError>(stored)
+    };
+
+    let (summary, stored) = tokio::join!(
+        processor.discover_for_duration(client, &program_id, &paging, budget, sender),
+        store,
+    );
+    let stored = stored?;
+
+    println!(
+        "⏱️  Ran {:.1}s: {} accounts examined ({:.1}/s), {} PDAs stored",
+        summary.elapsed.as_secs_f64(),
+        summary.examined,
+        summary.accounts_per_second(),
+        stored
+    );
+    match &summary.error {
+        Some(error) => println!("⚠️  Stopped early: {}", error),
+        None if summary.completed => println!("✅ Every account was analyzed"),
+        None => println!("⌛ Time ran out before every account was analyzed"),
+    }
+    Ok(())
+}
+
+#[cfg(feature = "database")]
+async fn run_incremental_discovery(
+    client: &SolanaClient,
+    processor: &BatchProcessor,
+    repository: &DatabaseRepository,
+    program_id: &str,
+    since_slot: Option<u64>,
+) -> Result<()> {
+    let program_id = parse_address(program_id)?;
+    let StoredDiscovery { since_slot, outcome, stored } = discover_since(repository, client, processor, &program_id, since_slot).await?;
+
+    match since_slot {
+        Some(slot) => println!("⏭️  Skipped {} accounts already stored through slot {}", outcome.skipped, slot),
+        None => println!("🆕 No earlier run recorded, so every account was analyzed"),
+    }
+    println!("🔍 {} accounts examined, {} new PDAs stored", outcome.examined, stored);
+    match (&outcome.error, outcome.scanned_slot) {
+        (Some(error), _) => println!("⚠️  Stopped early, so the next run starts from the same slot: {}", error),
+        (None, Some(slot)) => println!("📍 Next run picks up from slot {}", slot),
+        (None, None) => {}
+    }
+    Ok(())
+}
+
+/// `--duration` and `--interval` values: whole seconds, optionally suffixed with `s`, `m` or `h`
+fn parse_duration(value: &str) -> std::result::Result<Duration, String> {
+    let (count, unit) = if let Some(hours) = value.strip_suffix('h') {
+        (hours, 3600)
+    } else if let Some(minutes) = value.strip_suffix('m') {
+        (minutes, 60)
+    } else {
+        (value.strip_suffix('s').unwrap_or(value), 1)
+    };
+    count
+        .parse::<u64>()
+        .map(|count| Duration::from_secs(count * unit))
+        .map_err(|_| format!("invalid duration '{}', expected e.g. 300s, 5m or 1h", value))
+}
+
+/// Connect to `database_url`, or failing that to the database the API server is configured for
+#[cfg(feature = "database")]
+async fn connect_database(database_url: Option<&str>) -> Result<DatabaseRepository> {
+    let database_url = match database_url.map(str::to_string).or_else(|| std::env::var("DATABASE_URL").ok()) {
+        Some(url) => url,
+        None => DatabaseConfig::from_env()?.database_url(),
+    };
+    Ok(DatabaseRepository::from_url(&database_url).await?)
+}
+
+#[cfg(feature = "database")]
+async fn run_reanalyze_all(repository: &DatabaseRepository, analyzer: PdaAnalyzer, only_unknown: bool) -> Result<()> {
+    let report = reanalyze_stored_pdas(repository, analyzer, only_unknown).await?;
+
+    println!("🔁 Reanalyzed {} stored PDAs", report.scanned);
+    println!("✅ Newly resolved: {}", report.resolved);
+    println!("📈 Improved: {}", report.improved);
+    if report.failed > 0 {
+        println!("⚠️  Failed: {}", report.failed);
+    }
+    Ok(())
+}
+
+#[cfg(feature = "database")]
+async fn run_ingest(repository: &DatabaseRepository, client: &SolanaClient, input: &Path) -> Result<()> {
+    let contents = std::fs::read_to_string(input)?;
+    let signatures = distinct_signatures(contents.lines());
+    info!("Ingesting {} transactions from {}", signatures.len(), input.display());
+
+    let report = ingest_signatures(repository, client, &signatures).await;
+
+    println!("📥 Stored {} of {} transactions", report.stored, report.requested);
+    println!("🔑 PDAs: {}", report.pdas);
+    println!("🔗 Interactions: {}", report.interactions);
+    if !report.failed.is_empty() {
+        println!("⚠️  Failed: {}", report.failed.len());
+        for failure in &report.failed {
+            println!("   {}: {}", failure.signature, failure.error);
+        }
+    }
+    Ok(())
+}
+
+/// Poll `program_id`'s accounts every `interval` until Ctrl-C, analyzing each account the
+/// first time it's seen
+async fn run_watch(client: &SolanaClient, program_id: &str, interval: Duration, progress: bool, mut analyzer: PdaAnalyzer) -> Result<()> {
+    let program_id = parse_address(program_id)?;
+    let progress_bar = if progress {
+        let bar = ProgressBar::new_spinner();
+        bar.set_style(ProgressStyle::with_template("[{elapsed_precise}] {spinner} {pos} accounts ({per_sec}) {msg}")?);
+        bar.enable_steady_tick(Duration::from_millis(120));
+        bar
+    } else {
+        ProgressBar::hidden()
+    };
+
+    let mut seen = HashSet::new();
+    let mut tallies: BTreeMap<String, usize> = BTreeMap::new();
+    loop {
+        match client.fetch_program_accounts(&program_id).await {
+            Ok(accounts) => {
+                for address in new_accounts(accounts, &mut seen) {
+                    let pattern = match analyzer.analyze_pda(&address, &program_id) {
+                        Ok(Some(result)) => {
+                            progress_bar.suspend(|| {
+                                println!("✅ {} -> {} ({:.1}% confidence)", address, result.pattern.name(), result.confidence * 100.0)
+                            });
+                            result.pattern.name().into_owned()
+                        }
+                        Ok(None) => "UNRECOGNIZED".to_string(),
+                        Err(e) => {
+                            warn!("Failed to analyze account {}: {}", address, e);
+                            continue;
+                        }
+                    };
+                    *tallies.entry(pattern).or_insert(0) += 1;
+                    progress_bar.set_message(format_tallies(&tallies));
+                    progress_bar.inc(1);
+                }
+            }
+            Err(e) => warn!("Failed to fetch accounts of {}, retrying next poll: {}", program_id, e),
+        }
+
+        tokio::select! {
+            _ = tokio::time::sleep(interval) => {}
+            _ = tokio::signal::ctrl_c() => break,
+        }
+    }
+
+    progress_bar.finish_and_clear();
+    println!("\n📈 Watch Summary ({} accounts):", seen.len());
+    for (pattern, count) in &tallies {
+        println!("   • {}: {}", pattern, count);
+    }
+    Ok(())
+}
+
+/// The accounts in `accounts` not yet in `seen`, which they're added to
+fn new_accounts(accounts: Vec<Pubkey>, seen: &mut HashSet<Pubkey>) -> Vec<Pubkey> {
+    accounts.into_iter().filter(|address| seen.insert(*address)).collect()
+}
+
+#[cfg(feature = "database")]
+fn print_migration_status(migrations: &[MigrationStatus]) {
+    for migration in migrations {
+        match migration.applied_at {
+            Some(applied_at) => println!("✅ {}  applied {}", migration.version, applied_at.format("%Y-%m-%d %H:%M:%S UTC")),
+            None => println!("⏳ {}  pending", migration.version),
+        }
+    }
+    let pending = migrations.iter().filter(|migration| migration.applied_at.is_none()).count();
+    println!("{} of {} migrations applied", migrations.len() - pending, migrations.len());
+}
+
+fn list_programs(query: &str, analyzer: &PdaAnalyzer) {
+    let programs = analyzer.find_programs_by_name(query);
+    if programs.is_empty() {
+        println!("No known program matches '{}'", query);
+        return;
+    }
+
+    println!("📚 Known programs ({}):", programs.len());
+    for (program_id, name) in programs {
+        println!("  {:<44} {}", program_id, name);
+    }
+}
+
 async fn run_examples() -> Result<()> {
     println!("🚀 Running Solana PDA Analyzer Examples");
     println!("========================================");
@@ -122,32 +1391,32 @@ async fn run_examples() -> Result<()> {
     // Example 1: State PDA
     println!("\n📊 Example 1: State PDA Pattern");
     let (state_pda, _) = create_working_pda(test_program, &[b"state"])?;
-    analyze_pda(&state_pda, test_program).await?;
+    analyze_pda(&state_pda, test_program, OutputFormat::Text, false, None, PdaAnalyzer::new()).await?;
     
     // Example 2: Config PDA  
     println!("\n🔧 Example 2: Config PDA Pattern");
     let (config_pda, _) = create_working_pda(test_program, &[b"config"])?;
-    analyze_pda(&config_pda, test_program).await?;
+    analyze_pda(&config_pda, test_program, OutputFormat::Text, false, None, PdaAnalyzer::new()).await?;
     
     // Example 3: Authority PDA
     println!("\n👑 Example 3: Authority PDA Pattern");
     let (auth_pda, _) = create_working_pda(test_program, &[b"authority"])?;
-    analyze_pda(&auth_pda, test_program).await?;
+    analyze_pda(&auth_pda, test_program, OutputFormat::Text, false, None, PdaAnalyzer::new()).await?;
     
     // Example 4: Sequential PDA
     println!("\n🔢 Example 4: Sequential PDA Pattern");
     let (seq_pda, _) = create_working_pda(test_program, &[b"pool", &5u64.to_le_bytes()])?;
-    analyze_pda(&seq_pda, test_program).await?;
+    analyze_pda(&seq_pda, test_program, OutputFormat::Text, false, None, PdaAnalyzer::new()).await?;
     
     // Example 5: Associated Token Account
     println!("\n💰 Example 5: Associated Token Account Pattern");
     let (ata_pda, ata_program) = create_ata_pda(test_wallet, test_mint)?;
-    analyze_pda(&ata_pda, &ata_program).await?;
+    analyze_pda(&ata_pda, &ata_program, OutputFormat::Text, false, None, PdaAnalyzer::new()).await?;
     
     // Example 6: Metaplex Metadata
     println!("\n🎨 Example 6: Metaplex Metadata Pattern");
     let (meta_pda, meta_program) = create_metaplex_pda(test_mint)?;
-    analyze_pda(&meta_pda, &meta_program).await?;
+    analyze_pda(&meta_pda, &meta_program, OutputFormat::Text, false, None, PdaAnalyzer::new()).await?;
     
     println!("\n✅ All examples completed successfully!");
     println!("\n📈 Analysis Summary:");
@@ -209,5 +1478,347 @@ mod tests {
             "--program-id", "11111111111111111111111111111111"
         ]);
         assert!(cli.is_ok());
+
+        let cli = Cli::try_parse_from(["pda-analyzer", "analyze", "--address", "11111111111111111111111111111111"]);
+        assert!(matches!(cli.unwrap().command, Commands::Analyze { program_id: None, .. }));
+
+        let cli = Cli::try_parse_from(["pda-analyzer", "analyze", "--address", "11111111111111111111111111111111", "--network", "devnet"]).unwrap();
+        assert_eq!(Network::resolve_rpc_url(cli.network, cli.rpc_url.as_deref()), "https://api.devnet.solana.com");
+        let cli = Cli::try_parse_from(["pda-analyzer", "--network", "devnet", "--rpc-url", "http://10.0.0.5:8899", "examples"]).unwrap();
+        assert_eq!(Network::resolve_rpc_url(cli.network, cli.rpc_url.as_deref()), "http://10.0.0.5:8899");
+        assert!(Cli::try_parse_from(["pda-analyzer", "--network", "mainnet-alpha", "examples"]).is_err());
+
+        #[cfg(feature = "database")]
+        {
+            let cli = Cli::try_parse_from(["pda-analyzer", "reanalyze-all", "--only-unknown", "--database-url", "postgresql://localhost/pdas"]).unwrap();
+            assert!(matches!(cli.command, Commands::ReanalyzeAll { only_unknown: true }));
+            assert_eq!(cli.database_url.as_deref(), Some("postgresql://localhost/pdas"));
+
+            let cli = Cli::try_parse_from(["pda-analyzer", "migration", "status"]).unwrap();
+            assert!(matches!(cli.command, Commands::Migration { command: MigrationCommand::Status }));
+            assert!(Cli::try_parse_from(["pda-analyzer", "migration", "rollback"]).is_err());
+
+            let cli = Cli::try_parse_from(["pda-analyzer", "ingest-transactions", "--input", "signatures.txt"]).unwrap();
+            assert!(matches!(cli.command, Commands::IngestTransactions { input } if input == Path::new("signatures.txt")));
+        }
+
+        let cli = Cli::try_parse_from(["pda-analyzer", "batch", "--input", "pdas.csv", "--progress"]);
+        assert!(cli.is_ok());
+
+        let cli = Cli::try_parse_from(["pda-analyzer", "batch", "--input", "pdas.csv", "--jobs", "2"]);
+        assert!(matches!(cli.unwrap().command, Commands::Batch { jobs: Some(jobs), .. } if jobs.get() == 2));
+        assert!(Cli::try_parse_from(["pda-analyzer", "batch", "--input", "pdas.csv", "--jobs", "0"]).is_err());
+
+        let cli = Cli::try_parse_from(["pda-analyzer", "completions", "--shell", "zsh"]);
+        assert!(matches!(cli.unwrap().command, Commands::Completions { shell: Shell::Zsh }));
+        assert!(Cli::try_parse_from(["pda-analyzer", "completions", "--shell", "tcsh"]).is_err());
+
+        let cli = Cli::try_parse_from(["pda-analyzer", "--log-format", "json", "batch", "--input", "pdas.csv"]);
+        assert_eq!(cli.unwrap().log_format, LogFormat::Json);
+        assert!(Cli::try_parse_from(["pda-analyzer", "--log-format", "yaml", "examples"]).is_err());
+
+        let cli = Cli::try_parse_from(["pda-analyzer", "batch", "--input", "pdas.csv", "--explain-failures"]);
+        assert!(matches!(cli.unwrap().command, Commands::Batch { explain_failures: true, .. }));
+
+        let cli = Cli::try_parse_from([
+            "pda-analyzer", "search",
+            "--address", "11111111111111111111111111111111",
+            "--program-id", "11111111111111111111111111111111",
+            "--words", "words.txt",
+            "--resume", "search.checkpoint",
+        ]);
+        assert!(cli.is_ok());
+
+        let cli = Cli::try_parse_from([
+            "pda-analyzer", "explain",
+            "--address", "11111111111111111111111111111111",
+            "--program-id", "11111111111111111111111111111111",
+            "--seed", "str:vault", "--seed", "u64:7",
+        ]);
+        assert!(cli.is_ok());
+
+        let cli = Cli::try_parse_from(["pda-analyzer", "batch", "--input", "pdas.csv", "--only", "sequential,complex"]).unwrap();
+        assert_eq!(cli.only.as_deref(), Some("sequential,complex"));
+
+        let cli = Cli::try_parse_from([
+            "pda-analyzer", "analyze-snapshot",
+            "--accounts", "accounts.csv",
+            "--program", "11111111111111111111111111111111",
+        ]);
+        assert!(matches!(cli.unwrap().command, Commands::AnalyzeSnapshot { jobs: None, .. }));
+
+        let cli = Cli::try_parse_from(["pda-analyzer", "version", "--json"]);
+        assert!(matches!(cli.unwrap().command, Commands::Version { json: true }));
+
+        let cli = Cli::try_parse_from(["pda-analyzer", "programs", "--name", "marinade"]);
+        assert!(matches!(cli.unwrap().command, Commands::Programs { name: Some(name) } if name == "marinade"));
+
+        let cli = Cli::try_parse_from(["pda-analyzer", "batch", "--input", "pdas.csv", "--depth", "fast"]).unwrap();
+        assert_eq!(cli.depth, Depth::Fast);
+        assert!(Cli::try_parse_from(["pda-analyzer", "examples", "--depth", "bottomless"]).is_err());
+    }
+
+    #[test]
+    fn test_parse_batch_file() {
+        let contents = "# address,program_id\n\n11111111111111111111111111111111,TokenkegQfeZyiNwAJbNbGKPFXCWuBvf9Ss623VQ5DA\n11111111111111111111111111111111 11111111111111111111111111111111\n";
+        let pdas = parse_batch_file(contents).unwrap();
+        assert_eq!(pdas.len(), 2);
+
+        let err = parse_batch_file("11111111111111111111111111111111\n").unwrap_err();
+        assert!(err.to_string().contains("line 1"));
     }
-}
\ No newline at end of file
+
+    #[test]
+    fn test_parse_snapshot_csv() {
+        let first = Pubkey::new_unique();
+        let second = Pubkey::new_unique();
+
+        // Explorer exports lead with a header and quote their fields
+        let contents = format!("\"Slot\",\"Account\",\"Lamports\"\n1,\"{}\",10\n2,\"{}\",20\n3,\"{}\",30\n", first, second, first);
+        assert_eq!(parse_snapshot_csv(&contents).unwrap(), vec![first, second]);
+
+        // A bare address list has no header
+        let contents = format!("# accounts\n{}\n\n{},extra\n", first, second);
+        assert_eq!(parse_snapshot_csv(&contents).unwrap(), vec![first, second]);
+
+        let err = parse_snapshot_csv("slot,lamports\n1,10\n").unwrap_err();
+        assert!(err.to_string().contains("line 1"));
+        let err = parse_snapshot_csv(&format!("{}\nnot-an-address\n", first)).unwrap_err();
+        assert!(err.to_string().contains("line 2"));
+    }
+
+    #[test]
+    fn test_write_batch_outputs() {
+        let program_id = Pubkey::new_unique();
+        let (config, _) = Pubkey::find_program_address(&[b"config"], &program_id);
+        let unknown = Pubkey::new_unique();
+        let pdas = vec![(config, program_id), (unknown, program_id)];
+        let results = PdaAnalyzer::new().batch_analyze(&pdas).unwrap();
+
+        let dir = std::env::temp_dir().join(format!("pda-batch-{}", program_id));
+        assert_eq!(write_batch_outputs(&dir, &pdas, &results).unwrap(), 1);
+
+        let written: PdaAnalysisResult =
+            serde_json::from_str(&std::fs::read_to_string(dir.join(format!("{}.json", config))).unwrap()).unwrap();
+        assert_eq!(written.pda_info.address, config);
+
+        let index: Vec<BatchIndexEntry> = serde_json::from_str(&std::fs::read_to_string(dir.join("index.json")).unwrap()).unwrap();
+        assert_eq!(index.len(), 2);
+        assert_eq!(index[0].pattern.as_deref(), Some("STRING_SINGLETON"));
+        assert_eq!(index[0].file, Some(format!("{}.json", config)));
+        assert_eq!(index[1].address, unknown.to_string());
+        assert!(index[1].file.is_none() && index[1].pattern.is_none());
+
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn test_render_anchor_constraint() {
+        let program_id = Pubkey::new_unique();
+        let authority = Pubkey::new_unique();
+        let token_program = Pubkey::from_str("TokenkegQfeZyiNwAJbNbGKPFXCWuBvf9Ss623VQ5DA").unwrap();
+        let pda = PdaInfo {
+            address: Pubkey::new_unique(),
+            program_id,
+            seeds: vec![
+                SeedValue::String("metadata".to_string()),
+                SeedValue::Pubkey(program_id),
+                SeedValue::Pubkey(authority),
+                SeedValue::Pubkey(token_program),
+                SeedValue::U64(7),
+                SeedValue::Bytes(vec![0, 1]),
+            ],
+            bump: 254,
+            first_seen_slot: None,
+            first_seen_transaction: None,
+        };
+
+        let expected = format!(
+            "#[account(\n    seeds = [\n        b\"metadata\",\n        crate::ID.as_ref(),\n        account_2.key().as_ref(), // {}\n        token_program.key().as_ref(), // {}\n        arg_4.to_le_bytes().as_ref(), // 7u64\n        &[0x00, 0x01],\n    ],\n    bump, // canonical bump 254\n)]\n",
+            authority, token_program
+        );
+        assert_eq!(render_anchor_constraint(&pda), expected);
+
+        let cli = Cli::try_parse_from([
+            "pda-analyzer", "analyze",
+            "--address", "11111111111111111111111111111111",
+            "--program-id", "11111111111111111111111111111111",
+            "--format", "anchor",
+        ]).unwrap();
+        assert!(matches!(cli.command, Commands::Analyze { format: OutputFormat::Anchor, .. }));
+    }
+
+    #[test]
+    fn test_parse_word_list_keeps_unprintable_lines_as_bytes() {
+        let words = parse_word_list(b"vault\n# comment\n\nbad\x01seed\n\xff\xfe\n  11111111111111111111111111111111  \n");
+        assert_eq!(words.len(), 4);
+        assert_eq!(words[0], SeedValue::String("vault".to_string()));
+        assert_eq!(words[1], SeedValue::Bytes(b"bad\x01seed".to_vec()));
+        assert_eq!(words[2], SeedValue::Bytes(vec![0xff, 0xfe]));
+        assert!(matches!(words[3], SeedValue::Pubkey(_)));
+
+        let pda = PdaInfo {
+            address: Pubkey::new_unique(),
+            program_id: Pubkey::new_unique(),
+            seeds: vec![SeedValue::String("tab\there".to_string()), SeedValue::String("é".to_string())],
+            bump: 255,
+            first_seen_slot: None,
+            first_seen_transaction: None,
+        };
+        let rendered = render_anchor_constraint(&pda);
+        assert!(rendered.contains("b\"tab\\there\","));
+        assert!(rendered.contains("&[0xc3, 0xa9],"));
+    }
+
+    #[test]
+    fn test_verify_manifest_entries() {
+        let program_id = Pubkey::new_unique();
+        let owner = Pubkey::new_unique();
+        let (vault, bump) = Pubkey::find_program_address(&[b"vault", owner.as_ref()], &program_id);
+        let (counter, _) = Pubkey::find_program_address(&[b"counter", &7u64.to_le_bytes()], &program_id);
+
+        let manifest = format!(
+            r#"
+            [[pda]]
+            name = "vault"
+            address = "{vault}"
+            program_id = "{program_id}"
+            seeds = ["str:vault", "pubkey:{owner}"]
+            bump = {bump}
+
+            [[pda]]
+            address = "{counter}"
+            program_id = "{program_id}"
+            seeds = ["str:counter", "u32:7"]
+
+            [[pda]]
+            name = "wrong bump"
+            address = "{vault}"
+            program_id = "{program_id}"
+            seeds = ["str:vault", "pubkey:{owner}"]
+            bump = {wrong_bump}
+            "#,
+            wrong_bump = bump.wrapping_sub(1),
+        );
+        let entries = parse_manifest(&manifest).unwrap();
+        assert_eq!(entries.len(), 3);
+
+        assert_eq!(entries[0].verify(), Ok(bump));
+        // u32 instead of u64 derives a different address
+        assert!(entries[1].verify().unwrap_err().starts_with("seeds derive"));
+        assert_eq!(entries[1].label(), counter.to_string());
+        assert!(entries[2].verify().is_err());
+
+        assert!(parse_manifest("").is_err());
+        assert!(Cli::try_parse_from(["pda-analyzer", "verify", "--manifest", "pdas.toml"]).is_ok());
+    }
+
+    #[test]
+    fn test_find_explicit_bump() {
+        let program_id = Pubkey::new_unique();
+        let seeds: &[&[u8]] = &[b"vault"];
+        let (address, bump) = Pubkey::find_program_address(seeds, &program_id);
+        assert_eq!(find_explicit_bump(seeds, &address, &program_id), Some(bump));
+        assert_eq!(find_explicit_bump(seeds, &Pubkey::new_unique(), &program_id), None);
+    }
+
+    #[test]
+    fn test_load_audit_input_reads_batch_output_dir() {
+        let program_id = Pubkey::new_unique();
+        let (config, _) = Pubkey::find_program_address(&[b"config"], &program_id);
+        let pdas = vec![(config, program_id), (Pubkey::new_unique(), program_id)];
+        let results = PdaAnalyzer::new().batch_analyze(&pdas).unwrap();
+
+        let dir = std::env::temp_dir().join(format!("pda-audit-{}", program_id));
+        write_batch_outputs(&dir, &pdas, &results).unwrap();
+        let loaded = load_audit_input(&dir).unwrap();
+        assert_eq!(loaded.len(), 1);
+        assert_eq!(loaded[0].address, config);
+        std::fs::remove_dir_all(&dir).unwrap();
+
+        let file = std::env::temp_dir().join(format!("pda-audit-{}.json", program_id));
+        std::fs::write(&file, serde_json::to_string(&[&loaded[0]]).unwrap()).unwrap();
+        assert_eq!(load_audit_input(&file).unwrap()[0].address, config);
+        std::fs::remove_file(&file).unwrap();
+
+        assert!(Cli::try_parse_from(["pda-analyzer", "audit-bumps", "--program", "11111111111111111111111111111111", "--input", "pdas.json"]).is_ok());
+    }
+
+    #[tokio::test]
+    async fn test_owner_program_for_analyze_without_program_id() {
+        let program_id = Pubkey::new_unique();
+        let (vault, _) = Pubkey::find_program_address(&[b"vault"], &program_id);
+        let client = SolanaClient::offline([(program_id, vec![vault])].into_iter().collect());
+
+        assert_eq!(owner_program(&vault.to_string(), &client).await.unwrap(), program_id.to_string());
+        assert!(owner_program(&Pubkey::new_unique().to_string(), &client).await.is_err());
+    }
+
+    #[tokio::test]
+    async fn test_discover_sample() {
+        let program_id = Pubkey::new_unique();
+        let accounts: Vec<Pubkey> = (0..10u64)
+            .map(|i| Pubkey::find_program_address(&[b"vault", &i.to_le_bytes()], &program_id).0)
+            .collect();
+        let client = SolanaClient::offline([(program_id, accounts)].into_iter().collect());
+        let processor = BatchProcessor::new();
+
+        let outcome = processor.sample_program_analysis(&client, &program_id, &AccountPaging::default(), 4).await.unwrap();
+        assert_eq!((outcome.sample_size, outcome.population), (4, 10));
+        assert!(run_discover(&client, &processor, &program_id.to_string(), Some(4)).await.is_ok());
+        assert!(run_discover(&client, &processor, &program_id.to_string(), None).await.is_ok());
+
+        let sampled = |seed| {
+            let processor = BatchProcessor::new().with_sample_seed(seed);
+            let client = client.clone();
+            async move {
+                let outcome = processor.sample_program_analysis(&client, &program_id, &AccountPaging::default(), 4).await.unwrap();
+                outcome.results.into_iter().map(|result| result.pda_info.address).collect::<Vec<_>>()
+            }
+        };
+        assert_eq!(sampled(42).await, sampled(42).await);
+
+        let cli = Cli::try_parse_from(["pda-analyzer", "discover", "--program", "11111111111111111111111111111111", "--sample", "500"]).unwrap();
+        assert!(matches!(cli.command, Commands::Discover { sample: Some(500), seed: None, .. }));
+        let cli = Cli::try_parse_from(["pda-analyzer", "discover", "--program", "11111111111111111111111111111111", "--sample", "500", "--seed", "42"]).unwrap();
+        assert!(matches!(cli.command, Commands::Discover { seed: Some(42), .. }));
+        assert!(Cli::try_parse_from(["pda-analyzer", "discover", "--program", "11111111111111111111111111111111", "--seed", "42"]).is_err());
+
+        #[cfg(feature = "database")]
+        {
+            let cli = Cli::try_parse_from(["pda-analyzer", "discover", "--program", "11111111111111111111111111111111", "--duration", "5m"]).unwrap();
+            assert!(matches!(cli.command, Commands::Discover { duration: Some(duration), .. } if duration == Duration::from_secs(300)));
+            assert!(Cli::try_parse_from(["pda-analyzer", "discover", "--program", "11111111111111111111111111111111", "--duration", "5m", "--sample", "10"]).is_err());
+
+            let cli = Cli::try_parse_from(["pda-analyzer", "discover", "--program", "11111111111111111111111111111111", "--since-slot", "250000000"]).unwrap();
+            assert!(matches!(cli.command, Commands::Discover { since_slot: Some(Some(250_000_000)), .. }));
+            let cli = Cli::try_parse_from(["pda-analyzer", "discover", "--program", "11111111111111111111111111111111", "--since-slot"]).unwrap();
+            assert!(matches!(cli.command, Commands::Discover { since_slot: Some(None), .. }));
+            let cli = Cli::try_parse_from(["pda-analyzer", "discover", "--program", "11111111111111111111111111111111"]).unwrap();
+            assert!(matches!(cli.command, Commands::Discover { since_slot: None, .. }));
+            assert!(Cli::try_parse_from(["pda-analyzer", "discover", "--program", "11111111111111111111111111111111", "--since-slot", "--duration", "5m"]).is_err());
+        }
+    }
+
+    #[test]
+    fn test_parse_duration() {
+        assert_eq!(parse_duration("300s"), Ok(Duration::from_secs(300)));
+        assert_eq!(parse_duration("300"), Ok(Duration::from_secs(300)));
+        assert_eq!(parse_duration("5m"), Ok(Duration::from_secs(300)));
+        assert_eq!(parse_duration("1h"), Ok(Duration::from_secs(3600)));
+        assert!(parse_duration("5 minutes").is_err());
+        assert!(parse_duration("").is_err());
+    }
+
+    #[test]
+    fn test_watch_analyzes_each_account_once() {
+        let (first, second, third) = (Pubkey::new_unique(), Pubkey::new_unique(), Pubkey::new_unique());
+        let mut seen = HashSet::new();
+        assert_eq!(new_accounts(vec![first, second], &mut seen), vec![first, second]);
+        assert_eq!(new_accounts(vec![second, third, first], &mut seen), vec![third]);
+        assert!(new_accounts(vec![third], &mut seen).is_empty());
+
+        let cli = Cli::try_parse_from(["pda-analyzer", "watch", "--program", "11111111111111111111111111111111", "--progress"]).unwrap();
+        assert!(matches!(cli.command, Commands::Watch { interval, progress: true, .. } if interval == Duration::from_secs(30)));
+    }
+}
@@ -0,0 +1,80 @@
+use crate::{PdaAnalyzerError, Result};
+use solana_sdk::pubkey::{Pubkey, PUBKEY_BYTES};
+use std::str::FromStr;
+
+/// How an address was written. Base58 keys are at most 44 characters and hex keys exactly 64
+/// digits, so no input is valid as both.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AddressFormat {
+    Base58,
+    /// 64 hex digits, with or without `0x`
+    Hex,
+    /// 32 comma-separated bytes in brackets, e.g. `[12, 0, 255, ...]`
+    ByteArray,
+}
+
+impl AddressFormat {
+    /// The format `input` is written in, judged by its shape alone
+    pub fn detect(input: &str) -> Self {
+        let input = input.trim();
+        if input.starts_with('[') {
+            AddressFormat::ByteArray
+        } else if strip_hex_prefix(input).is_some()
+            || (input.len() == PUBKEY_BYTES * 2 && input.chars().all(|c| c.is_ascii_hexdigit()))
+        {
+            AddressFormat::Hex
+        } else {
+            AddressFormat::Base58
+        }
+    }
+}
+
+/// Parse an address written as base58, hex or a byte array, detecting which
+pub fn parse_address(input: &str) -> Result<Pubkey> {
+    let input = input.trim();
+    let invalid = |problem: String| PdaAnalyzerError::InvalidPublicKey(format!("'{}' {}", input, problem));
+
+    match AddressFormat::detect(input) {
+        AddressFormat::Base58 => Pubkey::from_str(input).map_err(|e| {
+            // All-hex input of the wrong length was most likely meant as hex
+            if !input.is_empty() && input.chars().all(|c| c.is_ascii_hexdigit()) {
+                invalid(format!(
+                    "is neither a base58 pubkey ({}) nor hex: hex addresses are {} digits, got {}",
+                    e,
+                    PUBKEY_BYTES * 2,
+                    input.len()
+                ))
+            } else {
+                invalid(format!("is not a base58 pubkey: {}", e))
+            }
+        }),
+        AddressFormat::Hex => {
+            let digits = strip_hex_prefix(input).unwrap_or(input);
+            let bytes = hex::decode(digits).map_err(|e| invalid(format!("is not valid hex: {}", e)))?;
+            to_pubkey(bytes).map_err(|len| invalid(format!("is {} bytes of hex, expected {}", len, PUBKEY_BYTES)))
+        }
+        AddressFormat::ByteArray => {
+            let inner = input
+                .strip_prefix('[')
+                .and_then(|rest| rest.strip_suffix(']'))
+                .ok_or_else(|| invalid("is missing its closing ']'".to_string()))?;
+            let bytes = inner
+                .split(',')
+                .map(str::trim)
+                .filter(|byte| !byte.is_empty())
+                .map(|byte| byte.parse::<u8>().map_err(|_| invalid(format!("has '{}', which is not a byte", byte))))
+                .collect::<Result<Vec<u8>>>()?;
+            to_pubkey(bytes).map_err(|len| invalid(format!("has {} bytes, expected {}", len, PUBKEY_BYTES)))
+        }
+    }
+}
+
+fn strip_hex_prefix(input: &str) -> Option<&str> {
+    input.strip_prefix("0x").or_else(|| input.strip_prefix("0X"))
+}
+
+/// The key for exactly 32 bytes, or how many bytes there were instead
+fn to_pubkey(bytes: Vec<u8>) -> std::result::Result<Pubkey, usize> {
+    let len = bytes.len();
+    <[u8; PUBKEY_BYTES]>::try_from(bytes).map(Pubkey::new_from_array).map_err(|_| len)
+}
@@ -0,0 +1,114 @@
+use crate::{validate_seeds, PdaInfo, SeedValue};
+use serde::{Deserialize, Serialize};
+use solana_sdk::pubkey::Pubkey;
+
+/// How a PDA's bump compares to the one `find_program_address` picks for its seeds
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "status", rename_all = "snake_case")]
+pub enum BumpCheck {
+    Canonical { bump: u8 },
+    /// Derived with a lower bump than the canonical one. Programs that accept such addresses
+    /// let several accounts stand in for the same seeds.
+    NonCanonical { bump: u8, canonical_bump: u8 },
+    /// No bump derives the address from the stored seeds, e.g. seeds were never recovered
+    Underivable,
+}
+
+/// Re-derive `pda` from its seeds and classify its bump. A trailing `U8` seed that is the bump
+/// itself, as `bump_is_last_seed` reports, is checked as the bump rather than as a seed.
+pub fn check_bump(pda: &PdaInfo) -> BumpCheck {
+    let (seeds, stored_bump) = match pda.seeds.split_last() {
+        Some((SeedValue::U8(bump), seeds)) if pda.bump_is_last_seed() => (seeds, *bump),
+        _ => (pda.seeds.as_slice(), pda.bump),
+    };
+    let Ok(seed_bytes) = validate_seeds(seeds) else {
+        return BumpCheck::Underivable;
+    };
+    let seed_refs: Vec<&[u8]> = seed_bytes.iter().map(Vec::as_slice).collect();
+
+    let Some((canonical_address, canonical_bump)) = Pubkey::try_find_program_address(&seed_refs, &pda.program_id) else {
+        return BumpCheck::Underivable;
+    };
+    if canonical_address == pda.address {
+        return BumpCheck::Canonical { bump: canonical_bump };
+    }
+
+    let derives = |bump: u8| {
+        let bump_seed = [bump];
+        let mut seeds = seed_refs.clone();
+        seeds.push(&bump_seed);
+        Pubkey::create_program_address(&seeds, &pda.program_id).is_ok_and(|derived| derived == pda.address)
+    };
+    // The stored bump is almost always the right one; scan the rest only when it isn't
+    let bump = if stored_bump < canonical_bump && derives(stored_bump) {
+        Some(stored_bump)
+    } else {
+        (0..canonical_bump).rev().find(|bump| derives(*bump))
+    };
+
+    match bump {
+        Some(bump) => BumpCheck::NonCanonical { bump, canonical_bump },
+        None => BumpCheck::Underivable,
+    }
+}
+
+/// A PDA found at a non-canonical bump
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct NonCanonicalBump {
+    pub address: Pubkey,
+    pub bump: u8,
+    pub canonical_bump: u8,
+}
+
+/// How many of a program's PDAs use canonical bumps
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BumpAudit {
+    pub program_id: Pubkey,
+    /// PDAs of this program that were checked
+    pub total: usize,
+    pub canonical: usize,
+    pub non_canonical: Vec<NonCanonicalBump>,
+    /// PDAs whose stored seeds derive no matching address under any bump
+    pub underivable: usize,
+}
+
+impl BumpAudit {
+    /// Share of the PDAs that re-derived at all which use a non-canonical bump, in `[0, 1]`
+    pub fn non_canonical_share(&self) -> f64 {
+        let derivable = self.canonical + self.non_canonical.len();
+        if derivable == 0 {
+            return 0.0;
+        }
+
+        self.non_canonical.len() as f64 / derivable as f64
+    }
+}
+
+/// Check the bump of every PDA in `pdas` owned by `program_id`; others are ignored
+pub fn audit_bumps<'a, I>(program_id: &Pubkey, pdas: I) -> BumpAudit
+where
+    I: IntoIterator<Item = &'a PdaInfo>,
+{
+    let mut audit = BumpAudit {
+        program_id: *program_id,
+        total: 0,
+        canonical: 0,
+        non_canonical: Vec::new(),
+        underivable: 0,
+    };
+
+    for pda in pdas.into_iter().filter(|pda| pda.program_id == *program_id) {
+        audit.total += 1;
+        match check_bump(pda) {
+            BumpCheck::Canonical { .. } => audit.canonical += 1,
+            BumpCheck::NonCanonical { bump, canonical_bump } => audit.non_canonical.push(NonCanonicalBump {
+                address: pda.address,
+                bump,
+                canonical_bump,
+            }),
+            BumpCheck::Underivable => audit.underivable += 1,
+        }
+    }
+
+    audit
+}
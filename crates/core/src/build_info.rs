@@ -0,0 +1,26 @@
+use serde::{Deserialize, Serialize};
+
+/// Which build is running, for deployments to confirm exactly what they're serving.
+/// Recorded by the core crate's build script; fields it couldn't determine read `unknown`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct BuildInfo {
+    /// Crate version; every crate in the workspace shares it
+    pub version: String,
+    /// Abbreviated commit the build was made from
+    pub git_sha: String,
+    /// RFC 3339 UTC time of the build, or `SOURCE_DATE_EPOCH` when set
+    pub build_time: String,
+    /// `rustc --version` of the compiler that built it
+    pub rustc: String,
+}
+
+impl BuildInfo {
+    pub fn current() -> Self {
+        Self {
+            version: env!("CARGO_PKG_VERSION").to_string(),
+            git_sha: env!("PDA_ANALYZER_GIT_SHA").to_string(),
+            build_time: env!("PDA_ANALYZER_BUILD_TIME").to_string(),
+            rustc: env!("PDA_ANALYZER_RUSTC").to_string(),
+        }
+    }
+}
@@ -0,0 +1,154 @@
+use crate::SeedValue;
+use solana_sdk::pubkey::Pubkey;
+use std::collections::HashMap;
+
+/// Shortest ASCII run considered a plausible seed string
+const MIN_SEED_STRING_LEN: usize = 3;
+
+/// Seeds are at most 32 bytes, so longer runs can't be a single seed
+const MAX_SEED_STRING_LEN: usize = 32;
+
+/// Runtime words that show up in every program's logs and are never seeds
+const LOG_STOPWORDS: &[&str] = &[
+    "Program", "program", "invoke", "success", "failed", "consumed", "compute",
+    "units", "log", "data", "return", "Instruction", "instruction", "error",
+];
+
+/// Where a recovered seed came from. When several sources hold the same value, the most
+/// specific one is recorded: a program's own vocabulary, then the global registry.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SeedSource {
+    /// Strings registered for the analyzed program alone, e.g. harvested from its logs or IDL
+    ProgramVocabulary,
+    /// Strings, pubkeys and vote accounts registered for every program
+    GlobalRegistry,
+    /// The configured dictionaries, known layouts, scanned numbers and program IDs
+    BuiltIn,
+}
+
+/// Seed candidates learned at runtime, tried alongside the built-in dictionaries
+#[derive(Debug, Clone, Default)]
+pub struct CandidateRegistry {
+    strings: Vec<String>,
+    pubkeys: Vec<Pubkey>,
+    vote_accounts: Vec<Pubkey>,
+    program_strings: HashMap<Pubkey, Vec<String>>,
+}
+
+impl CandidateRegistry {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Add a seed string tried for every program
+    pub fn add_string(&mut self, string: String) {
+        if !self.strings.contains(&string) {
+            self.strings.push(string);
+        }
+    }
+
+    /// Add a pubkey tried as a seed for every program
+    pub fn add_pubkey(&mut self, pubkey: Pubkey) {
+        if !self.pubkeys.contains(&pubkey) {
+            self.pubkeys.push(pubkey);
+        }
+    }
+
+    /// Add a validator vote account, tried in validator record and stake pool seeds
+    pub fn add_vote_account(&mut self, vote_account: Pubkey) {
+        if !self.vote_accounts.contains(&vote_account) {
+            self.vote_accounts.push(vote_account);
+        }
+    }
+
+    /// Add seed strings only tried for a single program
+    pub fn add_program_strings<I>(&mut self, program_id: Pubkey, strings: I) -> usize
+    where
+        I: IntoIterator<Item = String>,
+    {
+        let vocabulary = self.program_strings.entry(program_id).or_default();
+        let before = vocabulary.len();
+        for string in strings {
+            if !vocabulary.contains(&string) {
+                vocabulary.push(string);
+            }
+        }
+        vocabulary.len() - before
+    }
+
+    /// Global and program-specific strings to try for `program_id`
+    pub fn strings_for<'a>(&'a self, program_id: &Pubkey) -> impl Iterator<Item = &'a str> + 'a {
+        self.strings
+            .iter()
+            .chain(self.program_strings.get(program_id).into_iter().flatten())
+            .map(String::as_str)
+    }
+
+    /// Strings registered for `program_id` alone, not counting global ones
+    pub fn program_string_count(&self, program_id: &Pubkey) -> usize {
+        self.program_strings.get(program_id).map_or(0, Vec::len)
+    }
+
+    /// Which registry, if any, could have supplied `seed` when analyzing under `program_id`
+    pub fn source_of(&self, program_id: &Pubkey, seed: &SeedValue) -> Option<SeedSource> {
+        match seed {
+            SeedValue::String(s) if self.program_strings.get(program_id).is_some_and(|strings| strings.contains(s)) => {
+                Some(SeedSource::ProgramVocabulary)
+            }
+            SeedValue::String(s) if self.strings.contains(s) => Some(SeedSource::GlobalRegistry),
+            SeedValue::Pubkey(pubkey) if self.pubkeys.contains(pubkey) || self.vote_accounts.contains(pubkey) => {
+                Some(SeedSource::GlobalRegistry)
+            }
+            _ => None,
+        }
+    }
+
+    /// Pubkeys to try as seeds
+    pub fn pubkeys(&self) -> &[Pubkey] {
+        &self.pubkeys
+    }
+
+    /// Vote accounts to try in staking seeds
+    pub fn vote_accounts(&self) -> &[Pubkey] {
+        &self.vote_accounts
+    }
+
+    /// Global strings plus every program's own strings
+    pub fn vocabulary_size(&self) -> usize {
+        self.strings.len() + self.program_strings.values().map(Vec::len).sum::<usize>()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.strings.is_empty() && self.pubkeys.is_empty() && self.vote_accounts.is_empty() && self.program_strings.is_empty()
+    }
+}
+
+/// Extract printable ASCII runs from account data or log lines that could be seed strings
+pub fn extract_seed_strings(data: &[u8]) -> Vec<String> {
+    let mut strings = Vec::new();
+
+    for run in data.split(|b| !(b.is_ascii_alphanumeric() || *b == b'_' || *b == b'-')) {
+        if run.len() < MIN_SEED_STRING_LEN || run.len() > MAX_SEED_STRING_LEN {
+            continue;
+        }
+        if !run.iter().any(u8::is_ascii_alphabetic) {
+            continue;
+        }
+
+        let string = String::from_utf8_lossy(run).into_owned();
+        if LOG_STOPWORDS.contains(&string.as_str()) || strings.contains(&string) {
+            continue;
+        }
+
+        // Logs print instruction names in CamelCase while seeds are usually lowercase
+        let lowercase = string.to_lowercase();
+        strings.push(string);
+        if !strings.contains(&lowercase) {
+            strings.push(lowercase);
+        }
+    }
+
+    strings
+}
+
@@ -0,0 +1,77 @@
+use crate::{PdaAnalyzerError, Result};
+use std::io::IsTerminal;
+use std::str::FromStr;
+
+/// When decorated output may use ANSI color codes
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum ColorChoice {
+    /// Color terminals, unless `NO_COLOR` is set to a non-empty value
+    #[default]
+    Auto,
+    Always,
+    Never,
+}
+
+impl FromStr for ColorChoice {
+    type Err = PdaAnalyzerError;
+
+    fn from_str(s: &str) -> Result<Self> {
+        match s.trim().to_ascii_lowercase().as_str() {
+            "auto" => Ok(Self::Auto),
+            "always" => Ok(Self::Always),
+            "never" => Ok(Self::Never),
+            other => Err(PdaAnalyzerError::ConfigurationError(format!(
+                "Unknown color choice '{}', expected auto, always or never",
+                other
+            ))),
+        }
+    }
+}
+
+impl ColorChoice {
+    /// The choice given as `--color <when>` or `--color=<when>` among `args`, for binaries
+    /// without an argument parser; `Auto` if absent
+    pub fn from_args<I>(args: I) -> Result<Self>
+    where
+        I: IntoIterator<Item = String>,
+    {
+        let mut args = args.into_iter();
+        while let Some(arg) = args.next() {
+            if let Some(value) = arg.strip_prefix("--color=") {
+                return value.parse();
+            }
+            if arg == "--color" {
+                let value = args.next().ok_or_else(|| {
+                    PdaAnalyzerError::ConfigurationError("--color needs a value: auto, always or never".to_string())
+                })?;
+                return value.parse();
+            }
+        }
+        Ok(Self::Auto)
+    }
+
+    /// Whether to color a stream, given whether `NO_COLOR` is set and the stream is a terminal.
+    /// An explicit `Always` or `Never` wins over `NO_COLOR`.
+    pub fn enabled(self, no_color: bool, is_terminal: bool) -> bool {
+        match self {
+            Self::Always => true,
+            Self::Never => false,
+            Self::Auto => !no_color && is_terminal,
+        }
+    }
+
+    /// Whether to color standard output, reading `NO_COLOR` from the environment
+    pub fn for_stdout(self) -> bool {
+        self.enabled(no_color_set(), std::io::stdout().is_terminal())
+    }
+
+    /// Whether to color standard error, reading `NO_COLOR` from the environment
+    pub fn for_stderr(self) -> bool {
+        self.enabled(no_color_set(), std::io::stderr().is_terminal())
+    }
+}
+
+/// `NO_COLOR` counts when set to anything but the empty string, per no-color.org
+fn no_color_set() -> bool {
+    std::env::var_os("NO_COLOR").is_some_and(|value| !value.is_empty())
+}
@@ -0,0 +1,308 @@
+use crate::pda::PatternFamily;
+use crate::{PdaAnalyzerError, PdaPattern, Result, SeedValue};
+use solana_sdk::pubkey::{Pubkey, MAX_SEED_LEN};
+use std::collections::HashSet;
+use std::num::NonZeroUsize;
+use std::ops::RangeInclusive;
+use std::str::FromStr;
+
+/// Upper bound on timestamps scanned per address, so a typo in the window can't run for days
+pub const MAX_TIMESTAMP_CANDIDATES: u64 = 10_000_000;
+
+/// Slots tried either side of a `ChainContext`'s slot by default, about a minute of blocks
+pub const DEFAULT_SLOT_RADIUS: u64 = 150;
+
+/// Epochs tried either side of a `ChainContext`'s epoch by default
+pub const DEFAULT_EPOCH_RADIUS: u64 = 2;
+
+/// Shortest single string seed matched at full confidence by default
+pub const DEFAULT_MIN_CONFIDENT_STRING_LEN: usize = 3;
+
+/// Byte order(s) tried when encoding integer seeds
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Endianness {
+    /// `to_le_bytes`, what Anchor and most native programs use
+    #[default]
+    Little,
+    Big,
+    /// Little-endian first, then big-endian
+    Both,
+}
+
+/// Every tunable the `PdaAnalyzer` heuristics read: dictionaries, numeric ranges,
+/// bump scanning and byte order. Defaults reproduce the built-in search.
+#[derive(Debug, Clone)]
+pub struct SearchConfig {
+    /// Also try all 256 bumps via `create_program_address` for authority and complex seed sets.
+    /// Recovers PDAs stored with explicit non-canonical bumps at ~256x the cost.
+    pub search_non_canonical_bumps: bool,
+    /// Also try every ordering of small candidate seed sets, for programs that order seeds unusually.
+    pub permute_seed_order: bool,
+    /// Patterns the analyzer may report; families with no enabled pattern are skipped entirely.
+    pub enabled_patterns: HashSet<PdaPattern>,
+    /// Patterns whose families are searched first, in this order, e.g. `PubkeyU64` for a DEX;
+    /// the remaining families follow in the built-in order. Empty keeps the built-in order.
+    pub pattern_priority: Vec<PdaPattern>,
+    /// Byte order of integer seeds in sequential, nonce, complex and timestamp patterns.
+    /// Big-endian matches are reported as `SeedValue::Bytes`.
+    pub endianness: Endianness,
+    /// Most candidate seed sets each pattern family tries before giving up, in the order it
+    /// would try them. `None` tries everything configured.
+    pub candidate_limit: Option<usize>,
+    /// Worker threads for `batch_analyze_parallel`, in a pool of its own rather than rayon's
+    /// global one. `None` uses one per physical core.
+    pub batch_jobs: Option<NonZeroUsize>,
+
+    /// Wallets and mints combined into associated token account seeds
+    pub ata_wallets: Vec<Pubkey>,
+    pub ata_mints: Vec<Pubkey>,
+    /// Mints tried in Metaplex metadata and edition seeds
+    pub metaplex_mints: Vec<Pubkey>,
+    /// Edition numbers tried for numbered Metaplex editions
+    pub edition_range: RangeInclusive<u64>,
+    /// Single-string seeds such as `state` or `config`
+    pub singleton_strings: Vec<String>,
+    /// Longest string tried as a single seed, in bytes. Longer vocabulary entries are skipped
+    /// with a warning; values above `MAX_SEED_LEN` are treated as `MAX_SEED_LEN`.
+    pub max_seed_string_len: usize,
+    /// Single string seeds shorter than this, in bytes, are trusted less: their matches are
+    /// capped in confidence and flagged as `short_string_match`
+    pub min_confident_string_len: usize,
+    /// Pubkeys tried as authorities, in addition to registered candidates
+    pub authority_pubkeys: Vec<Pubkey>,
+    /// Nonces tried after an authority pubkey
+    pub nonce_range: RangeInclusive<u64>,
+    /// Single-byte bump seeds tried after an authority pubkey
+    pub bump_seed_range: RangeInclusive<u8>,
+    /// Suffixes such as version tags tried in `[prefix, pubkey, suffix]` seeds, alongside
+    /// the program's vocabulary
+    pub suffix_strings: Vec<String>,
+    /// Prefixes and indices for `[prefix, index]` sequential accounts
+    pub sequential_prefixes: Vec<String>,
+    pub sequential_range: RangeInclusive<u64>,
+    /// Strings, pubkeys and numbers for `[string, pubkey, string, number]` seeds
+    pub complex_strings: Vec<String>,
+    pub complex_pubkeys: Vec<Pubkey>,
+    pub complex_numbers: RangeInclusive<u32>,
+    /// Most complex combinations derived per address. The search stops there and reports
+    /// itself truncated in `PdaAnalyzer::complex_search_stats`. `None` tries them all.
+    pub complex_max_combinations: Option<usize>,
+    /// Prefixes used by escrow/auction style programs that seed accounts with a creation time
+    pub timestamp_prefixes: Vec<String>,
+    /// Unix time window scanned for timestamp-seeded PDAs. Timestamp patterns are only tried when set.
+    pub timestamp_range: Option<TimestampRange>,
+    /// Stake pools whose per-validator accounts are tried, alongside candidate pubkeys
+    pub stake_pools: Vec<Pubkey>,
+    /// Prefixes of accounts seeded with an epoch or slot number, e.g. per-epoch reward accounts
+    pub epoch_slot_prefixes: Vec<String>,
+    /// Slot and epoch around when the account appeared. Epoch and slot patterns are only
+    /// tried when set, usually per account via `PdaAnalyzer::analyze_near`.
+    pub chain_context: Option<ChainContext>,
+}
+
+/// Earlier name for `SearchConfig`
+pub type PdaAnalyzerConfig = SearchConfig;
+
+impl Default for SearchConfig {
+    fn default() -> Self {
+        Self {
+            search_non_canonical_bumps: false,
+            permute_seed_order: false,
+            enabled_patterns: PdaPattern::ALL.iter().cloned().collect(),
+            pattern_priority: Vec::new(),
+            endianness: Endianness::Little,
+            candidate_limit: None,
+            batch_jobs: None,
+            ata_wallets: pubkeys(&[
+                "9WzDXwBbmkg8ZTbNMqUxvQRAyrZzDsGYdLVL9zYtAWWM",
+                "7gXKKGLQs2HpzrPTtBP7kkQ3LktDShQPE8VV9PYW9RSh",
+                "5Q544fKrFoe6tsEbD7S8EmxGTJYAKtTVhAW5Q5pge4j1",
+                "8szGkuLTAux9XMgZ2vtY39jVSowEcpBfFfD8hXSEqdGC",
+            ]),
+            ata_mints: pubkeys(&[
+                "EPjFWdd5AufqSSqeM2qN1xzybapC8G4wEGGkZwyTDt1v", // USDC
+                "So11111111111111111111111111111111111111112",  // SOL
+                "Es9vMFrzaCERmJfrF4H2FYD4KCoNkY11McCe8BenwNYB", // USDT
+                "7gXKKGLQs2HpzrPTtBP7kkQ3LktDShQPE8VV9PYW9RSh", // Example NFT
+            ]),
+            metaplex_mints: pubkeys(&[
+                "7gXKKGLQs2HpzrPTtBP7kkQ3LktDShQPE8VV9PYW9RSh",
+                "8HYrKZBRZk9CgGfVv5u3r5G4W3dP2Qe2Y7rZRzMhQKkx",
+                "So11111111111111111111111111111111111111112",
+            ]),
+            edition_range: 1..=10,
+            singleton_strings: strings(&[
+                "state", "config", "authority", "vault", "pool", "market",
+                "escrow", "registry", "governance", "proposal", "metadata",
+                "treasury", "rewards", "staking", "lending", "farming",
+                "oracle", "price_feed", "liquidity", "swap", "mint_authority",
+                "global", "settings", "admin", "owner", "controller",
+            ]),
+            max_seed_string_len: MAX_SEED_LEN,
+            min_confident_string_len: DEFAULT_MIN_CONFIDENT_STRING_LEN,
+            authority_pubkeys: pubkeys(&[
+                "11111111111111111111111111111112",
+                "TokenkegQfeZyiNwAJbNbGKPFXCWuBvf9Ss623VQ5DA",
+                "9WzDXwBbmkg8ZTbNMqUxvQRAyrZzDsGYdLVL9zYtAWWM",
+                "7gXKKGLQs2HpzrPTtBP7kkQ3LktDShQPE8VV9PYW9RSh",
+            ]),
+            nonce_range: 0..=10,
+            bump_seed_range: 250..=255,
+            suffix_strings: strings(&["v1", "v2", "v3", "v4"]),
+            sequential_prefixes: strings(&["account", "user", "pool", "vault", "market", "index", "item"]),
+            sequential_range: 0..=50,
+            complex_strings: strings(&["governance", "proposal", "vote", "realm", "council"]),
+            complex_pubkeys: pubkeys(&[
+                "11111111111111111111111111111112",
+                "DPiH3H3c7t47BMxqTxLsuPQpEC6Kne8GA9VXbxpnZxFE",
+                "7gXKKGLQs2HpzrPTtBP7kkQ3LktDShQPE8VV9PYW9RSh",
+            ]),
+            complex_numbers: 0..=2,
+            complex_max_combinations: None,
+            timestamp_prefixes: strings(&["escrow", "auction", "vesting", "lock", "order", "offer", "bid", "listing"]),
+            timestamp_range: None,
+            stake_pools: Vec::new(),
+            epoch_slot_prefixes: strings(&["epoch", "reward", "rewards", "epoch_rewards", "snapshot", "slot", "round", "distribution"]),
+            chain_context: None,
+        }
+    }
+}
+
+impl SearchConfig {
+    pub fn is_enabled(&self, pattern: &PdaPattern) -> bool {
+        self.enabled_patterns.contains(pattern)
+    }
+
+    pub(crate) fn any_enabled(&self, patterns: &[PdaPattern]) -> bool {
+        patterns.iter().any(|pattern| self.is_enabled(pattern))
+    }
+
+    /// Families in the order `analyze` searches them: those of `pattern_priority` first,
+    /// then the rest in the built-in order
+    pub(crate) fn family_order(&self) -> Vec<PatternFamily> {
+        let mut order: Vec<PatternFamily> = Vec::with_capacity(PatternFamily::DEFAULT_ORDER.len());
+        let prioritized = self.pattern_priority.iter().filter_map(PatternFamily::of);
+        for family in prioritized.chain(PatternFamily::DEFAULT_ORDER) {
+            if !order.contains(&family) {
+                order.push(family);
+            }
+        }
+        order
+    }
+
+    /// Encodings of `value` as a `width`-byte integer seed in the configured byte order(s).
+    /// Values too wide for `width` have no encoding rather than a truncated one.
+    pub(crate) fn integer_seeds(&self, value: u64, width: usize) -> Vec<(Vec<u8>, SeedValue)> {
+        if width < 8 && value >> (width * 8) != 0 {
+            return Vec::new();
+        }
+
+        let mut seeds = Vec::with_capacity(2);
+
+        if matches!(self.endianness, Endianness::Little | Endianness::Both) {
+            let bytes = value.to_le_bytes()[..width].to_vec();
+            let seed = match width {
+                1 => SeedValue::U8(value as u8),
+                2 => SeedValue::U16(value as u16),
+                4 => SeedValue::U32(value as u32),
+                _ => SeedValue::U64(value),
+            };
+            seeds.push((bytes, seed));
+        }
+        // Single bytes read the same either way
+        if matches!(self.endianness, Endianness::Big | Endianness::Both) && width > 1 {
+            let bytes = value.to_be_bytes()[8 - width..].to_vec();
+            seeds.push((bytes.clone(), SeedValue::Bytes(bytes)));
+        }
+
+        seeds
+    }
+}
+
+/// Inclusive window of unix timestamps tried as `u64` seeds, every `step` seconds
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TimestampRange {
+    from: u64,
+    to: u64,
+    step: u64,
+}
+
+impl TimestampRange {
+    pub fn new(from: u64, to: u64, step: u64) -> Result<Self> {
+        if from > to {
+            return Err(PdaAnalyzerError::ConfigurationError(format!(
+                "Timestamp range start {} is after its end {}",
+                from, to
+            )));
+        }
+        if step == 0 {
+            return Err(PdaAnalyzerError::ConfigurationError("Timestamp step must be at least 1 second".to_string()));
+        }
+
+        let range = Self { from, to, step };
+        if range.count() > MAX_TIMESTAMP_CANDIDATES {
+            return Err(PdaAnalyzerError::ConfigurationError(format!(
+                "Timestamp range covers {} candidates, more than the limit of {}; narrow the window or raise the step",
+                range.count(),
+                MAX_TIMESTAMP_CANDIDATES
+            )));
+        }
+        Ok(range)
+    }
+
+    /// Number of timestamps in the window
+    pub fn count(&self) -> u64 {
+        ((self.to - self.from) / self.step).saturating_add(1)
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = u64> {
+        (self.from..=self.to).step_by(self.step as usize)
+    }
+}
+
+/// Where on chain an account appeared, for seeds taken from the epoch or slot at the time
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ChainContext {
+    pub slot: u64,
+    pub epoch: u64,
+    /// Slots tried either side of `slot`
+    pub slot_radius: u64,
+    /// Epochs tried either side of `epoch`
+    pub epoch_radius: u64,
+}
+
+impl ChainContext {
+    pub fn new(slot: u64, epoch: u64) -> Self {
+        Self {
+            slot,
+            epoch,
+            slot_radius: DEFAULT_SLOT_RADIUS,
+            epoch_radius: DEFAULT_EPOCH_RADIUS,
+        }
+    }
+
+    /// Epochs to try, nearest first
+    pub fn epochs(&self) -> impl Iterator<Item = u64> {
+        nearest_first(self.epoch, self.epoch_radius)
+    }
+
+    /// Slots to try, nearest first
+    pub fn slots(&self) -> impl Iterator<Item = u64> {
+        nearest_first(self.slot, self.slot_radius)
+    }
+}
+
+/// `center`, then alternately below and above it out to `radius`, without leaving `u64`
+fn nearest_first(center: u64, radius: u64) -> impl Iterator<Item = u64> {
+    std::iter::once(center).chain((1..=radius).flat_map(move |offset| {
+        [center.checked_sub(offset), center.checked_add(offset)].into_iter().flatten()
+    }))
+}
+
+fn pubkeys(keys: &[&str]) -> Vec<Pubkey> {
+    keys.iter().filter_map(|key| Pubkey::from_str(key).ok()).collect()
+}
+
+fn strings(words: &[&str]) -> Vec<String> {
+    words.iter().map(|word| word.to_string()).collect()
+}
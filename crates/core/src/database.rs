@@ -1,4 +1,4 @@
-use crate::{Result, PdaInfo, PdaAnalysisResult, PdaPattern, TransactionAnalysis, PdaAnalyzerError};
+use crate::{Result, PdaInfo, PdaAnalysisResult, PdaPattern, PatternStat, TransactionAnalysis, PdaAnalyzerError};
 use sqlx::{PgPool, Row};
 use chrono::{DateTime, Utc};
 use uuid::Uuid;
@@ -59,13 +59,6 @@ pub struct DatabaseStats {
     pub recent_analyses: i64,
 }
 
-#[derive(Debug, Serialize, Deserialize)]
-pub struct PatternStat {
-    pub pattern: String,
-    pub count: i64,
-    pub percentage: f64,
-}
-
 impl DatabaseManager {
     pub async fn new(database_url: &str) -> Result<Self> {
         let pool = PgPool::connect(database_url)
@@ -110,7 +103,7 @@ impl DatabaseManager {
             result.pda_info.program_id.to_string(),
             seeds_json,
             result.pda_info.bump as i16,
-            result.pattern.as_str(),
+            result.pattern.name().as_ref(),
             result.confidence,
             result.analysis_time_ms as i64,
             result.pda_info.first_seen_slot,
@@ -7,6 +7,9 @@ pub enum PdaAnalyzerError {
     
     #[error("PDA derivation failed: {0}")]
     PdaDerivationFailed(String),
+
+    #[error("Seed collision: {0}")]
+    SeedCollision(String),
     
     #[error("Invalid program ID: {0}")]
     InvalidProgramId(String),
@@ -28,6 +31,16 @@ pub enum PdaAnalyzerError {
     
     #[error("Configuration error: {0}")]
     ConfigurationError(String),
+
+    #[error("Database unavailable: {0}")]
+    DatabaseUnavailable(String),
+
+    #[cfg(feature = "database")]
+    #[error("Database error: {0}")]
+    Sqlx(#[from] sqlx::Error),
+
+    #[error("Invalid public key: {0}")]
+    ParsePubkey(#[from] solana_sdk::pubkey::ParsePubkeyError),
 }
 
 pub type Result<T> = std::result::Result<T, PdaAnalyzerError>;
@@ -36,10 +49,4 @@ impl From<serde_json::Error> for PdaAnalyzerError {
     fn from(err: serde_json::Error) -> Self {
         PdaAnalyzerError::SerializationError(err.to_string())
     }
-}
-
-impl From<solana_sdk::pubkey::ParsePubkeyError> for PdaAnalyzerError {
-    fn from(err: solana_sdk::pubkey::ParsePubkeyError) -> Self {
-        PdaAnalyzerError::InvalidPublicKey(err.to_string())
-    }
 }
\ No newline at end of file
@@ -0,0 +1,340 @@
+use crate::{MatchKind, PdaAnalysisResult, PdaAnalyzerError, PdaInfo, Result, SeedValue};
+use serde_json::Value;
+use solana_sdk::pubkey::Pubkey;
+use std::collections::HashMap;
+use std::path::Path;
+use std::str::FromStr;
+
+/// Confidence of a recovery whose seeds agree with the program's own IDL
+pub const IDL_CONFIRMED_CONFIDENCE: f64 = 0.99;
+
+/// What an IDL says one seed slot holds
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum IdlSeedType {
+    Pubkey,
+    String,
+    /// A little-endian integer of this many bytes; signed and `bool` seeds encode the same way
+    Integer(usize),
+    /// Raw bytes, of a fixed length for `[u8; N]` arrays
+    Bytes(Option<usize>),
+}
+
+impl IdlSeedType {
+    /// Read an IDL type such as `"u64"`, `"publicKey"` or `{"array": ["u8", 32]}`; `None`
+    /// for user-defined types, whose encoding the IDL doesn't spell out
+    fn from_idl(value: &Value) -> Option<Self> {
+        if let Some(name) = value.as_str() {
+            return match name {
+                "publicKey" | "pubkey" => Some(IdlSeedType::Pubkey),
+                "string" => Some(IdlSeedType::String),
+                "bytes" => Some(IdlSeedType::Bytes(None)),
+                "u8" | "i8" | "bool" => Some(IdlSeedType::Integer(1)),
+                "u16" | "i16" => Some(IdlSeedType::Integer(2)),
+                "u32" | "i32" => Some(IdlSeedType::Integer(4)),
+                "u64" | "i64" => Some(IdlSeedType::Integer(8)),
+                "u128" | "i128" => Some(IdlSeedType::Integer(16)),
+                _ => None,
+            };
+        }
+
+        let array = value.get("array")?.as_array()?;
+        match array.as_slice() {
+            [element, len] if element.as_str() == Some("u8") => Some(IdlSeedType::Bytes(Some(len.as_u64()? as usize))),
+            _ => None,
+        }
+    }
+
+    /// Whether the recovered `seed` has the shape this slot declares
+    fn accepts(&self, seed: &SeedValue) -> bool {
+        match self {
+            IdlSeedType::Pubkey => matches!(seed, SeedValue::Pubkey(_)),
+            IdlSeedType::String => matches!(seed, SeedValue::String(_)),
+            // Anchor encodes integers little-endian; a big-endian match comes back as `Bytes`
+            IdlSeedType::Integer(width) => {
+                matches!(seed, SeedValue::U8(_) | SeedValue::U16(_) | SeedValue::U32(_) | SeedValue::U64(_))
+                    && seed.as_bytes().len() == *width
+            }
+            IdlSeedType::Bytes(len) => len.is_none_or(|len| seed.as_bytes().len() == len),
+        }
+    }
+}
+
+impl std::fmt::Display for IdlSeedType {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            IdlSeedType::Pubkey => write!(f, "pubkey"),
+            IdlSeedType::String => write!(f, "string"),
+            IdlSeedType::Integer(width) => write!(f, "{}-bit integer", width * 8),
+            IdlSeedType::Bytes(Some(len)) => write!(f, "[u8; {}]", len),
+            IdlSeedType::Bytes(None) => write!(f, "bytes"),
+        }
+    }
+}
+
+/// One seed an IDL declares for a PDA account
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum IdlSeed {
+    /// Fixed bytes, e.g. `b"vault"`
+    Const(Vec<u8>),
+    /// An account's key, or a field of an account; `seed_type` is `None` when the IDL
+    /// leaves a field's type out
+    Account { path: String, seed_type: Option<IdlSeedType> },
+    /// An instruction argument, or a field of one
+    Arg { path: String, seed_type: Option<IdlSeedType> },
+}
+
+/// A PDA account as one instruction of the IDL declares it
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct IdlPdaAccount {
+    pub instruction: String,
+    pub name: String,
+    pub seeds: Vec<IdlSeed>,
+}
+
+/// How a recovered seed set differs from an IDL declaration
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SeedMismatch {
+    /// The IDL belongs to another program
+    Program { expected: Pubkey, found: Pubkey },
+    SeedCount { declared: usize, recovered: usize },
+    Constant { index: usize, expected: Vec<u8>, found: SeedValue },
+    Type { index: usize, path: String, expected: IdlSeedType, found: &'static str },
+}
+
+impl std::fmt::Display for SeedMismatch {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            SeedMismatch::Program { expected, found } => {
+                write!(f, "the IDL is for program {}, not {}", expected, found)
+            }
+            SeedMismatch::SeedCount { declared, recovered } => {
+                write!(f, "the IDL declares {} seeds, {} were recovered", declared, recovered)
+            }
+            SeedMismatch::Constant { index, expected, found } => write!(
+                f,
+                "seed {} should be the constant {}, found {}",
+                index + 1,
+                SeedValue::from_seed_bytes(expected),
+                found
+            ),
+            SeedMismatch::Type { index, path, expected, found } => {
+                write!(f, "seed {} ({}) should be a {}, found a {}", index + 1, path, expected, found)
+            }
+        }
+    }
+}
+
+/// A recovered PDA checked against one IDL declaration
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct IdlValidation {
+    pub instruction: String,
+    pub account: String,
+    pub mismatches: Vec<SeedMismatch>,
+}
+
+impl IdlValidation {
+    pub fn agrees(&self) -> bool {
+        self.mismatches.is_empty()
+    }
+}
+
+/// The PDA seed declarations of an Anchor IDL, in either the legacy (pre-0.30) layout or the
+/// current one. PDAs derived under another program (`pda.program`) are left out.
+#[derive(Debug, Clone, Default)]
+pub struct AnchorIdl {
+    /// The program the IDL describes, when it says
+    pub program_id: Option<Pubkey>,
+    pub pda_accounts: Vec<IdlPdaAccount>,
+}
+
+impl AnchorIdl {
+    pub fn load(path: &Path) -> Result<Self> {
+        let contents = std::fs::read_to_string(path).map_err(|e| {
+            PdaAnalyzerError::ConfigurationError(format!("Failed to read IDL {}: {}", path.display(), e))
+        })?;
+        Self::parse(&contents).map_err(|e| PdaAnalyzerError::ConfigurationError(format!("{}: {}", path.display(), e)))
+    }
+
+    pub fn parse(json: &str) -> Result<Self> {
+        let idl: Value = serde_json::from_str(json)?;
+        let address = idl
+            .get("address")
+            .or_else(|| idl.pointer("/metadata/address"))
+            .and_then(Value::as_str);
+        let program_id = address.map(Pubkey::from_str).transpose()?;
+
+        let mut pda_accounts = Vec::new();
+        for instruction in idl.get("instructions").and_then(Value::as_array).into_iter().flatten() {
+            let name = instruction.get("name").and_then(Value::as_str).unwrap_or_default();
+            let arg_types: HashMap<&str, &Value> = instruction
+                .get("args")
+                .and_then(Value::as_array)
+                .into_iter()
+                .flatten()
+                .filter_map(|arg| Some((arg.get("name")?.as_str()?, arg.get("type")?)))
+                .collect();
+            let accounts = instruction.get("accounts").and_then(Value::as_array).map(Vec::as_slice).unwrap_or_default();
+            collect_pda_accounts(name, accounts, &arg_types, &mut pda_accounts)?;
+        }
+
+        Ok(Self { program_id, pda_accounts })
+    }
+
+    /// Declarations of the account named `name`, one per instruction using it
+    pub fn declarations<'a>(&'a self, name: &'a str) -> impl Iterator<Item = &'a IdlPdaAccount> + 'a {
+        self.pda_accounts.iter().filter(move |account| account.name == name)
+    }
+
+    /// Check `pda` against the account named `name`, keeping the closest of its declarations;
+    /// `None` if the IDL declares no PDA by that name
+    pub fn validate(&self, pda: &PdaInfo, name: &str) -> Option<IdlValidation> {
+        self.declarations(name)
+            .map(|declared| self.check(pda, declared))
+            .min_by_key(|validation| validation.mismatches.len())
+    }
+
+    /// The first declaration `pda`'s recovered seeds agree with, if any
+    pub fn find_declaration(&self, pda: &PdaInfo) -> Option<IdlValidation> {
+        self.pda_accounts
+            .iter()
+            .map(|declared| self.check(pda, declared))
+            .find(IdlValidation::agrees)
+    }
+
+    /// Raise `result` to `IDL_CONFIRMED_CONFIDENCE` as a structural match when its seeds agree
+    /// with a declaration, returning that declaration
+    pub fn confirm(&self, result: &mut PdaAnalysisResult) -> Option<IdlValidation> {
+        let validation = self.find_declaration(&result.pda_info)?;
+        result.confidence = result.confidence.max(IDL_CONFIRMED_CONFIDENCE);
+        result.match_kind = MatchKind::Structural;
+        Some(validation)
+    }
+
+    /// Compare `pda`'s seeds, in order, with one declaration
+    pub fn check(&self, pda: &PdaInfo, declared: &IdlPdaAccount) -> IdlValidation {
+        let mut mismatches = Vec::new();
+
+        if let Some(program_id) = self.program_id.filter(|program_id| *program_id != pda.program_id) {
+            mismatches.push(SeedMismatch::Program { expected: program_id, found: pda.program_id });
+        }
+        if declared.seeds.len() != pda.seeds.len() {
+            mismatches.push(SeedMismatch::SeedCount {
+                declared: declared.seeds.len(),
+                recovered: pda.seeds.len(),
+            });
+        }
+
+        for (index, (slot, seed)) in declared.seeds.iter().zip(&pda.seeds).enumerate() {
+            match slot {
+                IdlSeed::Const(expected) if seed.as_bytes() != *expected => {
+                    mismatches.push(SeedMismatch::Constant { index, expected: expected.clone(), found: seed.clone() });
+                }
+                IdlSeed::Account { path, seed_type: Some(expected) } | IdlSeed::Arg { path, seed_type: Some(expected) }
+                    if !expected.accepts(seed) =>
+                {
+                    mismatches.push(SeedMismatch::Type {
+                        index,
+                        path: path.clone(),
+                        expected: expected.clone(),
+                        found: seed.seed_type(),
+                    });
+                }
+                _ => {}
+            }
+        }
+
+        IdlValidation {
+            instruction: declared.instruction.clone(),
+            account: declared.name.clone(),
+            mismatches,
+        }
+    }
+}
+
+/// Gather accounts with `pda` seeds, descending into the legacy layout's nested account groups
+fn collect_pda_accounts(
+    instruction: &str,
+    accounts: &[Value],
+    arg_types: &HashMap<&str, &Value>,
+    out: &mut Vec<IdlPdaAccount>,
+) -> Result<()> {
+    for account in accounts {
+        if let Some(nested) = account.get("accounts").and_then(Value::as_array) {
+            collect_pda_accounts(instruction, nested, arg_types, out)?;
+            continue;
+        }
+        let Some(pda) = account.get("pda") else {
+            continue;
+        };
+        if pda.get("program").is_some() {
+            continue;
+        }
+
+        let name = account.get("name").and_then(Value::as_str).unwrap_or_default();
+        let seeds = pda
+            .get("seeds")
+            .and_then(Value::as_array)
+            .into_iter()
+            .flatten()
+            .map(|seed| parse_seed(seed, arg_types))
+            .collect::<Result<Vec<_>>>()
+            .map_err(|e| PdaAnalyzerError::InvalidSeedData(format!("{}.{}: {}", instruction, name, e)))?;
+
+        out.push(IdlPdaAccount {
+            instruction: instruction.to_string(),
+            name: name.to_string(),
+            seeds,
+        });
+    }
+    Ok(())
+}
+
+fn parse_seed(seed: &Value, arg_types: &HashMap<&str, &Value>) -> Result<IdlSeed> {
+    let kind = seed.get("kind").and_then(Value::as_str).unwrap_or_default();
+    let path = seed.get("path").and_then(Value::as_str).unwrap_or_default().to_string();
+    let declared_type = seed.get("type").and_then(IdlSeedType::from_idl);
+
+    match kind {
+        "const" => {
+            let value = seed
+                .get("value")
+                .ok_or_else(|| PdaAnalyzerError::InvalidSeedData("constant seed without a value".to_string()))?;
+            const_bytes(value, declared_type.as_ref()).map(IdlSeed::Const)
+        }
+        // The current layout omits account types; a whole account (no field path) is its key
+        "account" => Ok(IdlSeed::Account {
+            seed_type: declared_type.or_else(|| (!path.contains('.')).then_some(IdlSeedType::Pubkey)),
+            path,
+        }),
+        "arg" => Ok(IdlSeed::Arg {
+            seed_type: declared_type.or_else(|| arg_types.get(path.as_str()).and_then(|ty| IdlSeedType::from_idl(ty))),
+            path,
+        }),
+        other => Err(PdaAnalyzerError::InvalidSeedData(format!("unknown seed kind '{}'", other))),
+    }
+}
+
+/// Bytes of a constant seed: a byte array in the current layout, a typed value in the legacy one
+fn const_bytes(value: &Value, seed_type: Option<&IdlSeedType>) -> Result<Vec<u8>> {
+    let invalid = || PdaAnalyzerError::InvalidSeedData(format!("invalid constant seed {}", value));
+
+    match (value, seed_type) {
+        (Value::Array(bytes), _) => bytes
+            .iter()
+            .map(|byte| byte.as_u64().and_then(|byte| u8::try_from(byte).ok()).ok_or_else(invalid))
+            .collect(),
+        (Value::String(key), Some(IdlSeedType::Pubkey)) => Ok(Pubkey::from_str(key)?.to_bytes().to_vec()),
+        (Value::String(s), _) => Ok(s.as_bytes().to_vec()),
+        (Value::Number(n), Some(IdlSeedType::Integer(width))) => {
+            // Negative values sign-extend into 128-bit seeds
+            let (n, fill) = match n.as_u64() {
+                Some(n) => (n, 0),
+                None => n.as_i64().map(|n| (n as u64, if n < 0 { 0xff } else { 0 })).ok_or_else(invalid)?,
+            };
+            let mut bytes = n.to_le_bytes().to_vec();
+            bytes.resize(*width, fill);
+            Ok(bytes)
+        }
+        _ => Err(invalid()),
+    }
+}
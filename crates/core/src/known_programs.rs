@@ -0,0 +1,55 @@
+use crate::{PdaAnalyzerError, Result};
+use serde::{Deserialize, Serialize};
+use solana_sdk::pubkey::Pubkey;
+use std::path::Path;
+use std::str::FromStr;
+
+/// The catalog every analyzer starts from, maintained as data in `data/known_programs.json`
+const BUILTIN_KNOWN_PROGRAMS: &str = include_str!("../data/known_programs.json");
+
+/// One entry of a known-programs file: a JSON array of `{"program_id": ..., "name": ...}`
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct KnownProgramEntry {
+    pub program_id: String,
+    pub name: String,
+}
+
+/// Programs reported by name out of the box
+pub fn builtin_known_programs() -> Vec<(Pubkey, String)> {
+    parse_known_programs(BUILTIN_KNOWN_PROGRAMS).expect("bundled known_programs.json is valid")
+}
+
+/// Read a known-programs file. Register its entries after the built-in ones, e.g. with
+/// `PdaAnalyzerBuilder::programs`, so they rename or add to the catalog.
+pub fn load_known_programs(path: &Path) -> Result<Vec<(Pubkey, String)>> {
+    let contents = std::fs::read_to_string(path).map_err(|e| {
+        PdaAnalyzerError::ConfigurationError(format!("Failed to read known programs {}: {}", path.display(), e))
+    })?;
+    parse_known_programs(&contents)
+        .map_err(|e| PdaAnalyzerError::ConfigurationError(format!("{}: {}", path.display(), e)))
+}
+
+/// Parse a known-programs JSON array, rejecting entries whose program ID isn't a pubkey or
+/// whose name is blank
+pub fn parse_known_programs(json: &str) -> Result<Vec<(Pubkey, String)>> {
+    let entries: Vec<KnownProgramEntry> =
+        serde_json::from_str(json).map_err(|e| PdaAnalyzerError::SerializationError(e.to_string()))?;
+
+    entries
+        .into_iter()
+        .enumerate()
+        .map(|(i, entry)| {
+            let program_id = Pubkey::from_str(entry.program_id.trim()).map_err(|e| {
+                PdaAnalyzerError::InvalidProgramId(format!("entry {}: '{}': {}", i, entry.program_id, e))
+            })?;
+            let name = entry.name.trim();
+            if name.is_empty() {
+                return Err(PdaAnalyzerError::ConfigurationError(format!(
+                    "entry {}: program {} has an empty name",
+                    i, program_id
+                )));
+            }
+            Ok((program_id, name.to_string()))
+        })
+        .collect()
+}
@@ -1,13 +1,34 @@
+pub mod address;
+pub mod audit;
+pub mod build_info;
 pub mod pda;
+pub mod candidates;
+pub mod color;
+pub mod config;
+pub mod idl;
+pub mod known_programs;
+pub mod prelude;
+pub mod sampling;
+pub mod search;
 pub mod transaction;
 pub mod error;
 pub mod types;
 // pub mod database;
 
-pub use pda::{PdaPattern, PdaAnalysisResult, PdaAnalyzer};
+pub use address::{parse_address, AddressFormat};
+pub use audit::{audit_bumps, check_bump, BumpAudit, BumpCheck, NonCanonicalBump};
+pub use build_info::BuildInfo;
+pub use pda::{rank_results, validate_seeds, AddressKind, AnalysisOutcome, CandidateKind, ComplexSearchStats, CandidateSuggestion, FailureReason, FailureSummary, MatchKind, PatternCategory, SuggestionReport, PdaPattern, PdaAnalysisResult, PdaAnalyzer, PdaAnalyzerBuilder, PdaDeriver, PatternStats, TargetSetOutcome, UnrecognizedPda};
+pub use idl::{AnchorIdl, IdlPdaAccount, IdlSeed, IdlSeedType, IdlValidation, SeedMismatch, IDL_CONFIRMED_CONFIDENCE};
+pub use config::{ChainContext, Endianness, PdaAnalyzerConfig, SearchConfig, TimestampRange};
+pub use color::ColorChoice;
+pub use candidates::{CandidateRegistry, SeedSource, extract_seed_strings};
+pub use known_programs::{builtin_known_programs, load_known_programs, parse_known_programs, KnownProgramEntry};
+pub use sampling::Reservoir;
+pub use search::{DictionarySearch, SearchCheckpoint, SearchOutcome};
 pub use transaction::*;
 pub use error::*;
-pub use types::{PdaInfo, SeedValue, PdaPatternTemplate, SeedTemplate, TransactionAnalysis, PdaInteraction, InteractionType, ProgramInfo, SeedDerivationAttempt};
+pub use types::{seeds_fingerprint, PdaInfo, SeedValue, PdaPatternTemplate, SeedTemplate, TransactionAnalysis, TransactionAnalysisBuilder, PdaInteraction, InteractionType, ProgramInfo, SeedDerivationAttempt, PatternStat, PatternHistogram, RankedPatternStat};
 // pub use database::*;
 
 // Export database types for API compatibility
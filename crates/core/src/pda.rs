@@ -1,11 +1,49 @@
-use crate::{Result, PdaInfo, SeedValue};
-use solana_sdk::pubkey::Pubkey;
-use std::collections::HashMap;
+use crate::{builtin_known_programs, CandidateRegistry, ChainContext, PdaAnalyzerError, Result, PdaInfo, SearchConfig, SeedDerivationAttempt, SeedSource, SeedValue};
+use chrono::Utc;
+use solana_sdk::pubkey::{Pubkey, MAX_SEEDS, MAX_SEED_LEN};
+use dashmap::DashMap;
+use rayon::prelude::*;
+use std::borrow::Cow;
+use std::collections::{HashMap, HashSet};
 use std::str::FromStr;
+use std::sync::atomic::{AtomicU32, AtomicU64, AtomicUsize, Ordering};
+use std::sync::{Arc, OnceLock};
+// `std::time::Instant` panics on wasm32-unknown-unknown; `web-time` reads the browser's clock there
+#[cfg(not(target_arch = "wasm32"))]
+use std::time::Instant;
+#[cfg(target_arch = "wasm32")]
+use web_time::Instant;
 
-/// Caches PDA analysis results for performance
+/// Caches PDA analysis results for performance, keyed by seed bytes so equivalent seed
+/// expressions share one entry. Entries hold the seeds in canonical form (`SeedValue::normalized`).
 type PdaCache = HashMap<(Pubkey, Vec<Vec<u8>>), Option<PdaInfo>>;
 
+/// Seed sets larger than this are only tried in their given order (4! = 24 orderings)
+const MAX_PERMUTED_SEEDS: usize = 4;
+
+/// Confidence lost per tenfold increase in combinations tried before a brute-forced match
+const DEPTH_DECAY_PER_DECADE: f64 = 0.05;
+
+/// Brute-forced matches never drop below this, however deep they were found
+const MIN_DEPTH_CONFIDENCE: f64 = 0.50;
+
+/// Singleton seeds `PdaAnalyzer::warm_up` derives for every known program
+const WARM_UP_SINGLETONS: [&str; 3] = ["state", "config", "authority"];
+
+/// Share of a match's confidence that rides on seed plausibility; wholly implausible seeds lose this much
+const PLAUSIBILITY_WEIGHT: f64 = 0.25;
+
+/// Share of a match's confidence that rides on seed length typicality; kept small, as it only
+/// separates otherwise equal matches
+const SEED_LENGTH_WEIGHT: f64 = 0.05;
+
+/// Highest confidence a singleton match on a string shorter than `min_confident_string_len` gets
+const SHORT_STRING_MAX_CONFIDENCE: f64 = 0.50;
+
+/// Share of the remaining doubt a match drawing a seed from the program's own vocabulary
+/// removes; a documented program string is far stronger evidence than a generic word
+const PROGRAM_VOCABULARY_WEIGHT: f64 = 0.30;
+
 /// Pattern types detected by the analyzer
 #[derive(Debug, Clone, PartialEq, Eq, Hash, serde::Serialize, serde::Deserialize)]
 pub enum PdaPattern {
@@ -17,14 +55,202 @@ pub enum PdaPattern {
     StringAuthority,
     StringPubkey,
     StringPubkeyString,
+    /// A pubkey between a vocabulary prefix and suffix, e.g. versioned `["position", user, "v2"]`
+    StringPubkeyStringSuffix,
     PubkeyU64,
     PubkeyU8,
+    /// Two pubkeys, e.g. `[pool, user]` position accounts
+    PubkeyPubkey,
+    /// The program's own ID among the seeds, e.g. `["config", program_id]`
+    ProgramSelfRef,
     Sequential,
     Complex,
+    Timestamp,
+    /// A number taken from the chain when the account was made, e.g. `["rewards", epoch]`
+    EpochSlot,
+    /// A staking program's record for one validator: `["validator", vote_account]`
+    ValidatorRecord,
+    /// A stake pool's account for one validator, e.g. `["stake", pool, vote_account]` or the
+    /// SPL stake pool's `[vote_account, pool]`
+    StakePoolValidator,
+    /// A position keyed by a string, a market and a user, e.g. lending and perps
+    /// `["position", market, user]`; recovered for whole target sets by
+    /// `PdaAnalyzer::solve_market_user_positions`
+    StringMarketUser,
     Unknown,
+    /// A program-specific pattern the built-in variants don't name, e.g. from an IDL
+    Custom(String),
+}
+
+/// Patterns produced by the authority family, which tries several seed shapes around known pubkeys
+const AUTHORITY_PATTERNS: [PdaPattern; 5] = [
+    PdaPattern::StringAuthority,
+    PdaPattern::StringPubkey,
+    PdaPattern::StringPubkeyString,
+    PdaPattern::PubkeyU64,
+    PdaPattern::PubkeyU8,
+];
+
+const METAPLEX_PATTERNS: [PdaPattern; 3] = [
+    PdaPattern::MetaplexMetadata,
+    PdaPattern::MetaplexMasterEdition,
+    PdaPattern::MetaplexEdition,
+];
+
+/// Patterns produced by the staking family, which tries validator layouts around vote accounts
+const STAKE_PATTERNS: [PdaPattern; 2] = [
+    PdaPattern::ValidatorRecord,
+    PdaPattern::StakePoolValidator,
+];
+
+/// One of `analyze`'s searches; a family may recover several patterns
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum PatternFamily {
+    Ata,
+    Metaplex,
+    Singleton,
+    Authority,
+    SelfRef,
+    PubkeyPair,
+    Stake,
+    Suffixed,
+    Sequential,
+    Complex,
+    Timestamp,
+    EpochSlot,
+}
+
+impl PatternFamily {
+    /// Built-in search order: most common and most specific first, numeric scans last
+    pub(crate) const DEFAULT_ORDER: [PatternFamily; 12] = [
+        // Associated token accounts are the most common PDAs on Solana
+        PatternFamily::Ata,
+        // Metaplex accounts are very common for NFTs
+        PatternFamily::Metaplex,
+        PatternFamily::Singleton,
+        PatternFamily::Authority,
+        // Seeds that include the program's own ID
+        PatternFamily::SelfRef,
+        // Pairs of candidate pubkeys
+        PatternFamily::PubkeyPair,
+        // Staking layouts around candidate vote accounts
+        PatternFamily::Stake,
+        // A pubkey between a prefix and a suffix string, e.g. versioned accounts
+        PatternFamily::Suffixed,
+        // Numbered accounts
+        PatternFamily::Sequential,
+        PatternFamily::Complex,
+        // Only searched when a time window is configured
+        PatternFamily::Timestamp,
+        // Only searched when the chain context is known
+        PatternFamily::EpochSlot,
+    ];
+
+    /// The patterns this family's search can report
+    pub(crate) fn patterns(self) -> &'static [PdaPattern] {
+        match self {
+            PatternFamily::Ata => &[PdaPattern::AssociatedTokenAccount],
+            PatternFamily::Metaplex => &METAPLEX_PATTERNS,
+            PatternFamily::Singleton => &[PdaPattern::StringSingleton],
+            PatternFamily::Authority => &AUTHORITY_PATTERNS,
+            PatternFamily::SelfRef => &[PdaPattern::ProgramSelfRef],
+            PatternFamily::PubkeyPair => &[PdaPattern::PubkeyPubkey],
+            PatternFamily::Stake => &STAKE_PATTERNS,
+            PatternFamily::Suffixed => &[PdaPattern::StringPubkeyStringSuffix],
+            PatternFamily::Sequential => &[PdaPattern::Sequential],
+            PatternFamily::Complex => &[PdaPattern::Complex],
+            PatternFamily::Timestamp => &[PdaPattern::Timestamp],
+            PatternFamily::EpochSlot => &[PdaPattern::EpochSlot],
+        }
+    }
+
+    /// The family whose search reports `pattern`; `None` for patterns `analyze` never
+    /// searches for, such as `Unknown` or custom ones
+    pub(crate) fn of(pattern: &PdaPattern) -> Option<PatternFamily> {
+        Self::DEFAULT_ORDER.into_iter().find(|family| family.patterns().contains(pattern))
+    }
 }
 
 impl PdaPattern {
+    /// Every built-in pattern; `Custom` patterns are open-ended and not listed
+    pub const ALL: [PdaPattern; 21] = [
+        PdaPattern::AssociatedTokenAccount,
+        PdaPattern::MetaplexMetadata,
+        PdaPattern::MetaplexMasterEdition,
+        PdaPattern::MetaplexEdition,
+        PdaPattern::StringSingleton,
+        PdaPattern::StringAuthority,
+        PdaPattern::StringPubkey,
+        PdaPattern::StringPubkeyString,
+        PdaPattern::StringPubkeyStringSuffix,
+        PdaPattern::PubkeyU64,
+        PdaPattern::PubkeyU8,
+        PdaPattern::PubkeyPubkey,
+        PdaPattern::ProgramSelfRef,
+        PdaPattern::Sequential,
+        PdaPattern::Complex,
+        PdaPattern::Timestamp,
+        PdaPattern::EpochSlot,
+        PdaPattern::ValidatorRecord,
+        PdaPattern::StakePoolValidator,
+        PdaPattern::StringMarketUser,
+        PdaPattern::Unknown,
+    ];
+
+    /// Parse a comma-separated filter such as `sequential,complex` into a pattern set.
+    /// Accepts family names (`ata`, `metaplex`, `singleton`, `authority`, `self_ref`,
+    /// `suffixed`, `sequential`, `complex`, `timestamp`, `epoch`, `stake`) as well as individual pattern names like `STRING_PUBKEY`.
+    pub fn parse_filter(filter: &str) -> Result<HashSet<PdaPattern>> {
+        let mut patterns = HashSet::new();
+        for name in filter.split(',').map(str::trim).filter(|name| !name.is_empty()) {
+            patterns.extend(Self::resolve_filter_name(name)?.iter().cloned());
+        }
+
+        if patterns.is_empty() {
+            return Err(PdaAnalyzerError::ConfigurationError("Pattern filter is empty".to_string()));
+        }
+        Ok(patterns)
+    }
+
+    /// Parse a comma-separated priority list such as `authority,sequential` for
+    /// `SearchConfig::pattern_priority`, keeping its order. Takes the same names as `parse_filter`.
+    pub fn parse_priority(priority: &str) -> Result<Vec<PdaPattern>> {
+        let mut patterns = Vec::new();
+        for name in priority.split(',').map(str::trim).filter(|name| !name.is_empty()) {
+            for pattern in Self::resolve_filter_name(name)? {
+                if !patterns.contains(pattern) {
+                    patterns.push(pattern.clone());
+                }
+            }
+        }
+        Ok(patterns)
+    }
+
+    /// The patterns a family name or single pattern name stands for
+    fn resolve_filter_name(name: &str) -> Result<&'static [PdaPattern]> {
+        Ok(match name.to_ascii_lowercase().as_str() {
+            "ata" | "associated_token_account" => &[PdaPattern::AssociatedTokenAccount],
+            "metaplex" => &METAPLEX_PATTERNS,
+            "singleton" => &[PdaPattern::StringSingleton],
+            "authority" => &AUTHORITY_PATTERNS,
+            "self_ref" | "self_referential" => &[PdaPattern::ProgramSelfRef],
+            "suffixed" | "versioned" => &[PdaPattern::StringPubkeyStringSuffix],
+            "sequential" => &[PdaPattern::Sequential],
+            "complex" => &[PdaPattern::Complex],
+            "timestamp" => &[PdaPattern::Timestamp],
+            "epoch" | "slot" => &[PdaPattern::EpochSlot],
+            "stake" => &STAKE_PATTERNS,
+            _ => match Self::ALL.iter().find(|p| p.as_str().eq_ignore_ascii_case(name)) {
+                Some(pattern) => std::slice::from_ref(pattern),
+                None => {
+                    return Err(PdaAnalyzerError::ConfigurationError(format!("Unknown pattern '{}'", name)));
+                }
+            },
+        })
+    }
+
+    /// The built-in pattern's name; every `Custom` pattern is `CUSTOM`. Use `name` for the
+    /// name a result is stored and shown under.
     pub fn as_str(&self) -> &'static str {
         match self {
             PdaPattern::AssociatedTokenAccount => "WALLET_TOKEN_MINT",
@@ -35,12 +261,182 @@ impl PdaPattern {
             PdaPattern::StringAuthority => "STRING_AUTHORITY",
             PdaPattern::StringPubkey => "STRING_PUBKEY",
             PdaPattern::StringPubkeyString => "STRING_PUBKEY_STRING",
+            PdaPattern::StringPubkeyStringSuffix => "STRING_PUBKEY_STRING_SUFFIX",
             PdaPattern::PubkeyU64 => "PUBKEY_U64",
             PdaPattern::PubkeyU8 => "PUBKEY_U8",
+            PdaPattern::PubkeyPubkey => "PUBKEY_PUBKEY",
+            PdaPattern::ProgramSelfRef => "PROGRAM_SELF_REF",
             PdaPattern::Sequential => "SEQUENTIAL",
             PdaPattern::Complex => "COMPLEX",
+            PdaPattern::Timestamp => "TIMESTAMP",
+            PdaPattern::EpochSlot => "EPOCH_SLOT",
+            PdaPattern::ValidatorRecord => "VALIDATOR_RECORD",
+            PdaPattern::StakePoolValidator => "STAKE_POOL_VALIDATOR",
+            PdaPattern::StringMarketUser => "STRING_MARKET_USER",
             PdaPattern::Unknown => "UNKNOWN",
+            PdaPattern::Custom(_) => "CUSTOM",
+        }
+    }
+
+    /// The name results are stored and shown under: a custom pattern's own name, otherwise `as_str`
+    pub fn name(&self) -> Cow<'_, str> {
+        match self {
+            PdaPattern::Custom(name) => Cow::Borrowed(name),
+            pattern => Cow::Borrowed(pattern.as_str()),
+        }
+    }
+
+    /// What the pattern's seeds look like and where it's typically used
+    pub fn description(&self) -> &'static str {
+        match self {
+            PdaPattern::AssociatedTokenAccount => "Associated token account: [wallet, token program, mint]",
+            PdaPattern::MetaplexMetadata => "Metaplex metadata: [\"metadata\", program, mint]",
+            PdaPattern::MetaplexMasterEdition => "Metaplex master edition: [\"metadata\", program, mint, \"edition\"]",
+            PdaPattern::MetaplexEdition => "Numbered Metaplex edition: [\"metadata\", program, mint, \"edition\", number]",
+            PdaPattern::StringSingleton => "Global program state under a single string seed, e.g. [\"config\"]",
+            PdaPattern::StringAuthority => "Account controlled by an authority: [authority]",
+            PdaPattern::StringPubkey => "Prefixed authority: [\"authority\", pubkey]",
+            PdaPattern::StringPubkeyString => "Pubkey between two strings: [prefix, pubkey, suffix]",
+            PdaPattern::StringPubkeyStringSuffix => "Versioned or suffixed accounts: [\"position\", pubkey, \"v2\"]",
+            PdaPattern::PubkeyU64 => "Market or pool nonce, common in DEXes and AMMs: [pubkey, u64]",
+            PdaPattern::PubkeyU8 => "Pubkey with a one-byte seed: [pubkey, u8]",
+            PdaPattern::PubkeyPubkey => "Pair of pubkeys, e.g. pool and user positions: [pubkey, pubkey]",
+            PdaPattern::ProgramSelfRef => "Namespaced by the owning program's own ID: [string?, program, pubkey?]",
+            PdaPattern::Sequential => "Numbered accounts: [prefix, index]",
+            PdaPattern::Complex => "Multi-parameter accounts: [string, pubkey, string, number]",
+            PdaPattern::Timestamp => "Accounts seeded with a creation time: [prefix, pubkeys..., unix timestamp]",
+            PdaPattern::EpochSlot => "Per-epoch or per-slot accounts such as reward snapshots: [prefix, pubkey?, epoch or slot]",
+            PdaPattern::ValidatorRecord => "Staking program validator records: [\"validator\", vote_account]",
+            PdaPattern::StakePoolValidator => "Stake pool per-validator accounts: [\"stake\", pool, vote_account] or [vote_account, pool]",
+            PdaPattern::StringMarketUser => "Lending and perps positions: [\"position\", market, user]",
+            PdaPattern::Unknown => "Seeds no heuristic recognized",
+            PdaPattern::Custom(_) => "Program-specific pattern outside the built-in set",
+        }
+    }
+
+    /// How strong a match on this pattern is as evidence, independent of its confidence
+    pub fn match_kind(&self) -> MatchKind {
+        match self {
+            PdaPattern::AssociatedTokenAccount
+            | PdaPattern::MetaplexMetadata
+            | PdaPattern::MetaplexMasterEdition
+            | PdaPattern::MetaplexEdition
+            | PdaPattern::Custom(_) => MatchKind::Structural,
+            PdaPattern::StringSingleton
+            | PdaPattern::StringAuthority
+            | PdaPattern::StringPubkey
+            | PdaPattern::StringPubkeyString
+            | PdaPattern::StringPubkeyStringSuffix
+            | PdaPattern::PubkeyPubkey
+            | PdaPattern::ProgramSelfRef
+            | PdaPattern::ValidatorRecord
+            | PdaPattern::StakePoolValidator
+            | PdaPattern::StringMarketUser => MatchKind::DictionaryHit,
+            PdaPattern::PubkeyU64
+            | PdaPattern::PubkeyU8
+            | PdaPattern::Sequential
+            | PdaPattern::Complex
+            | PdaPattern::Timestamp
+            | PdaPattern::EpochSlot
+            | PdaPattern::Unknown => MatchKind::BruteForce,
+        }
+    }
+
+    /// The kind of account this pattern belongs to, for rolling pattern counts up
+    pub fn category(&self) -> PatternCategory {
+        match self {
+            PdaPattern::AssociatedTokenAccount => PatternCategory::Token,
+            PdaPattern::MetaplexMetadata | PdaPattern::MetaplexMasterEdition | PdaPattern::MetaplexEdition => {
+                PatternCategory::Nft
+            }
+            PdaPattern::ValidatorRecord | PdaPattern::StakePoolValidator => PatternCategory::Staking,
+            PdaPattern::StringSingleton
+            | PdaPattern::StringAuthority
+            | PdaPattern::StringPubkey
+            | PdaPattern::StringPubkeyString
+            | PdaPattern::StringPubkeyStringSuffix
+            | PdaPattern::PubkeyU64
+            | PdaPattern::PubkeyU8
+            | PdaPattern::PubkeyPubkey
+            | PdaPattern::ProgramSelfRef
+            | PdaPattern::Sequential
+            | PdaPattern::Complex
+            | PdaPattern::Timestamp
+            | PdaPattern::EpochSlot
+            | PdaPattern::StringMarketUser
+            | PdaPattern::Custom(_) => PatternCategory::Generic,
+            PdaPattern::Unknown => PatternCategory::Unknown,
+        }
+    }
+}
+
+/// Broad kind of account a pattern derives, coarser than the pattern itself
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum PatternCategory {
+    /// Associated token accounts
+    Token,
+    /// Metaplex metadata and edition accounts
+    Nft,
+    /// Validator and stake pool records
+    Staking,
+    /// Program-defined layouts built from strings, pubkeys and numbers
+    Generic,
+    Unknown,
+}
+
+impl PatternCategory {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            PatternCategory::Token => "token",
+            PatternCategory::Nft => "nft",
+            PatternCategory::Staking => "staking",
+            PatternCategory::Generic => "generic",
+            PatternCategory::Unknown => "unknown",
+        }
+    }
+}
+
+/// How a derivation was recovered, strongest evidence first. A recognized layout such as an
+/// ATA is stronger evidence than a lucky hit in a numeric scan, whatever their confidences.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord, Hash, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum MatchKind {
+    /// A well-known account layout (ATA, Metaplex) or a pattern declared for the program
+    Structural,
+    /// Seeds taken from the vocabulary or registered candidate pubkeys
+    DictionaryHit,
+    /// Found by scanning numbers or combinations
+    #[default]
+    BruteForce,
+}
+
+impl MatchKind {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            MatchKind::Structural => "structural",
+            MatchKind::DictionaryHit => "dictionary_hit",
+            MatchKind::BruteForce => "brute_force",
+        }
+    }
+}
+
+impl FromStr for PdaPattern {
+    type Err = PdaAnalyzerError;
+
+    /// Inverse of `name`: built-in names map to their variant and any other name is
+    /// `Custom`, so stored patterns read back as they were written. A custom name that
+    /// matches a built-in one reads back as the built-in pattern.
+    fn from_str(name: &str) -> Result<Self> {
+        if name.is_empty() {
+            return Err(PdaAnalyzerError::ConfigurationError("Pattern name is empty".to_string()));
         }
+
+        Ok(Self::ALL
+            .iter()
+            .find(|pattern| pattern.as_str() == name)
+            .cloned()
+            .unwrap_or_else(|| PdaPattern::Custom(name.to_string())))
     }
 }
 
@@ -49,308 +445,973 @@ pub struct PdaAnalysisResult {
     pub pda_info: PdaInfo,
     pub pattern: PdaPattern,
     pub confidence: f64,
+    /// Results from before match kinds were recorded read back as `BruteForce`
+    #[serde(default)]
+    pub match_kind: MatchKind,
     pub analysis_time_ms: u64,
+    /// The last seed is the bump the program passed, not a seed followed by a found bump; see
+    /// `PdaInfo::bump_is_last_seed`
+    #[serde(default)]
+    pub bump_as_explicit_seed: bool,
+    /// Whether the account is instantiated on chain; `None` when nobody checked. A result
+    /// with `Some(false)` is still a valid derivation, just not a live account.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub account_exists: Option<bool>,
+    /// Matched a single string seed shorter than `SearchConfig::min_confident_string_len`,
+    /// which is more likely a dictionary accident than the program's real seed
+    #[serde(default)]
+    pub short_string_match: bool,
+    /// The address is off the ed25519 curve, as every PDA must be; `false` means the recovery
+    /// is wrong. Results saved before this was recorded read back as `true`.
+    #[serde(default = "default_off_curve")]
+    pub off_curve: bool,
+    /// Where each of `pda_info.seeds` came from, in the same order. Empty for results saved
+    /// before provenance was recorded.
+    #[serde(default)]
+    pub seed_sources: Vec<SeedSource>,
 }
 
-#[derive(Debug, Clone)]
-pub struct PdaAnalyzer {
-    cache: PdaCache,
-    known_programs: HashMap<Pubkey, String>,
-    pattern_stats: HashMap<PdaPattern, u32>,
+fn default_off_curve() -> bool {
+    true
 }
 
-impl PdaAnalyzer {
-    pub fn new() -> Self {
-        let mut known_programs = HashMap::new();
-        
-        // System Programs
-        known_programs.insert(solana_sdk::system_program::id(), "System Program".to_string());
-        
-        // SPL Programs
-        if let Ok(spl_token_id) = Pubkey::from_str("TokenkegQfeZyiNwAJbNbGKPFXCWuBvf9Ss623VQ5DA") {
-            known_programs.insert(spl_token_id, "SPL Token".to_string());
-        }
-        if let Ok(ata_id) = Pubkey::from_str("ATokenGPvbdGVxr1b2hvZbsiqW5xWH25efTNsLJA8knL") {
-            known_programs.insert(ata_id, "SPL Associated Token Account".to_string());
-        }
-        
-        // Metaplex Programs
-        if let Ok(metadata_id) = Pubkey::from_str("metaqbxxUerdq28cj1RbAWkYQm3ybzjb6a8bt518x1s") {
-            known_programs.insert(metadata_id, "Metaplex Token Metadata".to_string());
-        }
-        if let Ok(candy_machine_id) = Pubkey::from_str("CndyV3LdqHUfDLmE5naZjVN8rBZz4tqhdefbAnjHG3JR") {
-            known_programs.insert(candy_machine_id, "Metaplex Candy Machine".to_string());
-        }
-        if let Ok(auction_house_id) = Pubkey::from_str("hausS13jsjafwWwGqZTUQRmWyvyxn9EQpqMwV1PBBmk") {
-            known_programs.insert(auction_house_id, "Metaplex Auction House".to_string());
-        }
-        
-        // DeFi Programs
-        if let Ok(serum_id) = Pubkey::from_str("9xQeWvG816bUx9EPjHmaT23yvVM2ZWbrrpZb9PusVFin") {
-            known_programs.insert(serum_id, "Serum DEX".to_string());
-        }
-        if let Ok(raydium_id) = Pubkey::from_str("675kPX9MHTjS2zt1qfr1NYHuzeLXfQM9H24wFSUt1Mp8") {
-            known_programs.insert(raydium_id, "Raydium AMM".to_string());
-        }
-        if let Ok(marinade_id) = Pubkey::from_str("MarBmsSgKXdrN1egZf5sqe1TMai9K1rChYNDJgjq7aD") {
-            known_programs.insert(marinade_id, "Marinade Finance".to_string());
+impl PdaAnalysisResult {
+    /// Ranking order: structural matches before dictionary hits before brute-forced ones,
+    /// then higher confidence first
+    pub fn rank_cmp(&self, other: &Self) -> std::cmp::Ordering {
+        self.match_kind
+            .cmp(&other.match_kind)
+            .then_with(|| other.confidence.partial_cmp(&self.confidence).unwrap_or(std::cmp::Ordering::Equal))
+    }
+
+    /// The recovered seeds as space-separated specs, e.g. `str:metadata pubkey:<base58> u64:5`,
+    /// ready to pass to `explain --seed` or a `verify` manifest and read back by
+    /// `SeedValue::parse_specs`. A bump passed as the last seed is left out, as `explain` and
+    /// `verify` search for the bump themselves.
+    pub fn to_seed_spec_string(&self) -> String {
+        let seeds = &self.pda_info.seeds;
+        let seeds = if self.bump_as_explicit_seed { &seeds[..seeds.len().saturating_sub(1)] } else { &seeds[..] };
+        seeds.iter().map(SeedValue::to_spec).collect::<Vec<_>>().join(" ")
+    }
+}
+
+/// What `PdaAnalyzer::analyze` found, keeping apart the cases `analyze_pda` reports as `None`
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+#[serde(tag = "outcome", rename_all = "snake_case")]
+pub enum AnalysisOutcome {
+    Recognized(PdaAnalysisResult),
+    /// A valid PDA no pattern derived; `attempted` lists the patterns that tried at least
+    /// one seed set, in the order they ran
+    Unrecognized { attempted: Vec<PdaPattern> },
+    /// On the curve or owned by the System Program, so no seeds could derive it;
+    /// `PdaAnalyzer::describe_unrecognized` says which
+    NotAPda,
+}
+
+impl AnalysisOutcome {
+    pub fn into_result(self) -> Option<PdaAnalysisResult> {
+        match self {
+            AnalysisOutcome::Recognized(result) => Some(result),
+            AnalysisOutcome::Unrecognized { .. } | AnalysisOutcome::NotAPda => None,
         }
-        
-        // Infrastructure Programs
-        if let Ok(name_service_id) = Pubkey::from_str("namesLPneVptA9Z5rqUDD9tMTWEJwofgaYwp8cawRkX") {
-            known_programs.insert(name_service_id, "Solana Name Service".to_string());
+    }
+}
+
+/// Sort `results` best first, by `PdaAnalysisResult::rank_cmp`
+pub fn rank_results(results: &mut [PdaAnalysisResult]) {
+    results.sort_by(PdaAnalysisResult::rank_cmp);
+}
+
+/// Whether an address can be a PDA at all. PDAs are deliberately off the ed25519 curve,
+/// so an on-curve address is a wallet-style key for every program, not an unrecognized PDA.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum AddressKind {
+    OffCurve,
+    OnCurve,
+}
+
+impl AddressKind {
+    pub fn of(address: &Pubkey) -> Self {
+        if address.is_on_curve() {
+            AddressKind::OnCurve
+        } else {
+            AddressKind::OffCurve
         }
-        if let Ok(governance_id) = Pubkey::from_str("GovER5Lthms3bLBqWub97yVrMmEogzX7xNjdXpPPCVZw") {
-            known_programs.insert(governance_id, "SPL Governance".to_string());
+    }
+}
+
+/// What is still known about an address when no seed pattern matched
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+pub struct UnrecognizedPda {
+    pub address: Pubkey,
+    pub program_id: Pubkey,
+    pub program_name: Option<String>,
+    pub address_kind: AddressKind,
+}
+
+impl UnrecognizedPda {
+    /// True when no seed search could have succeeded: the address is on-curve,
+    /// or the program is the System Program, which owns no PDAs
+    pub fn is_inapplicable(&self) -> bool {
+        self.address_kind == AddressKind::OnCurve || self.program_id == solana_sdk::system_program::id()
+    }
+
+    /// One-line explanation suitable for showing to a user
+    pub fn message(&self) -> String {
+        if self.program_id == solana_sdk::system_program::id() {
+            return format!(
+                "the System Program owns wallets and other on-curve accounts, never PDAs; \
+                 analyze {} against the program that owns it instead",
+                self.address
+            );
         }
 
-        Self {
-            cache: HashMap::new(),
-            known_programs,
-            pattern_stats: HashMap::new(),
+        match self.address_kind {
+            AddressKind::OnCurve => format!(
+                "{} is on the ed25519 curve, so it cannot be a PDA for any program",
+                self.address
+            ),
+            AddressKind::OffCurve => format!(
+                "{} is a valid PDA, but no known seed pattern derives it for {}",
+                self.address,
+                self.program_name.as_deref().unwrap_or("this program")
+            ),
         }
     }
+}
 
-    /// Analyze a PDA to determine its seed derivation pattern with confidence scoring
-    pub fn analyze_pda(&mut self, address: &Pubkey, program_id: &Pubkey) -> Result<Option<PdaAnalysisResult>> {
-        let start_time = std::time::Instant::now();
-        
-        // Try different PDA patterns in order of likelihood and specificity
-        
-        // 1. Try Associated Token Account pattern (most common on Solana)
-        if let Some((pda_info, confidence)) = self.try_associated_token_account(address, program_id)? {
-            let result = PdaAnalysisResult {
-                pda_info,
-                pattern: PdaPattern::AssociatedTokenAccount,
-                confidence,
-                analysis_time_ms: start_time.elapsed().as_millis() as u64,
-            };
-            self.update_pattern_stats(&result.pattern);
-            return Ok(Some(result));
+/// Kind of candidate value a failed analysis was missing
+#[derive(Debug, Clone, Copy, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum CandidateKind {
+    /// The wallet that owns a token account
+    Wallet,
+    /// A token or NFT mint
+    Mint,
+    /// A string the program uses as a seed, e.g. from its IDL or source
+    SeedString,
+    /// An authority, user or other account pubkey the PDA belongs to
+    Pubkey,
+    /// A validator's vote account, for staking programs
+    VoteAccount,
+    /// An index or nonce outside the range already scanned
+    Number,
+    /// The window of unix times the account could have been created in
+    TimeWindow,
+}
+
+/// One kind of hint that would let a pattern family search where it couldn't
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+pub struct CandidateSuggestion {
+    pub kind: CandidateKind,
+    /// The pattern the candidate would most likely recover
+    pub pattern: PdaPattern,
+    pub reason: String,
+}
+
+/// What to provide so a failed analysis can succeed, most promising first
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+pub struct SuggestionReport {
+    pub unrecognized: UnrecognizedPda,
+    /// Empty when no candidate could help, e.g. for on-curve addresses
+    pub suggestions: Vec<CandidateSuggestion>,
+}
+
+impl SuggestionReport {
+    /// The single most actionable reason the analysis failed
+    pub fn failure_reason(&self) -> FailureReason {
+        let unrecognized = &self.unrecognized;
+        if unrecognized.program_id == solana_sdk::system_program::id() {
+            FailureReason::SystemProgram
+        } else if unrecognized.address_kind == AddressKind::OnCurve {
+            FailureReason::OnCurve
+        } else if unrecognized.program_name.is_none() {
+            FailureReason::UnknownProgram
+        } else if let Some(suggestion) = self.suggestions.first() {
+            FailureReason::MissingCandidate(suggestion.kind)
+        } else {
+            FailureReason::Exhausted
         }
+    }
+}
 
-        // 2. Try Metaplex patterns (very common for NFTs)
-        if let Some((pda_info, pattern, confidence)) = self.try_metaplex_patterns(address, program_id)? {
-            let result = PdaAnalysisResult {
-                pda_info,
-                pattern,
-                confidence,
-                analysis_time_ms: start_time.elapsed().as_millis() as u64,
-            };
-            self.update_pattern_stats(&result.pattern);
-            return Ok(Some(result));
+/// Why an address went unrecognized, coarse enough to count across a batch
+#[derive(Debug, Clone, Copy, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum FailureReason {
+    /// Analyzed against the System Program, which owns no PDAs
+    SystemProgram,
+    /// On the ed25519 curve, so not a PDA for any program
+    OnCurve,
+    /// The program isn't in the known-programs catalog, so only generic patterns were tried
+    UnknownProgram,
+    /// A known program whose most promising next step is a candidate of this kind
+    MissingCandidate(CandidateKind),
+    /// Every enabled pattern was tried and nothing is left to suggest
+    Exhausted,
+}
+
+impl FailureReason {
+    /// Reads after a share of failures, e.g. "40% failed because the program is unknown"
+    pub fn describe(&self) -> &'static str {
+        match self {
+            FailureReason::SystemProgram => "were analyzed against the System Program, which owns no PDAs",
+            FailureReason::OnCurve => "are on-curve, so they can't be PDAs",
+            FailureReason::UnknownProgram => "failed because the program is unknown",
+            FailureReason::MissingCandidate(CandidateKind::Wallet) => "likely need a wallet hint",
+            FailureReason::MissingCandidate(CandidateKind::Mint) => "likely need a mint hint",
+            FailureReason::MissingCandidate(CandidateKind::SeedString) => "likely need the program's seed strings",
+            FailureReason::MissingCandidate(CandidateKind::Pubkey) => "likely need an authority or user pubkey hint",
+            FailureReason::MissingCandidate(CandidateKind::VoteAccount) => "likely need a vote account hint",
+            FailureReason::MissingCandidate(CandidateKind::Number) => "likely use an index outside the scanned range",
+            FailureReason::MissingCandidate(CandidateKind::TimeWindow) => "likely need a time window or slot hint",
+            FailureReason::Exhausted => "exhausted every enabled pattern",
         }
+    }
+}
 
-        // 3. Try common string singleton patterns
-        if let Some((pda_info, confidence)) = self.try_string_singleton_patterns(address, program_id)? {
-            let result = PdaAnalysisResult {
-                pda_info,
-                pattern: PdaPattern::StringSingleton,
-                confidence,
-                analysis_time_ms: start_time.elapsed().as_millis() as u64,
-            };
-            self.update_pattern_stats(&result.pattern);
-            return Ok(Some(result));
+/// Failure reasons tallied over a batch, most common first
+#[derive(Debug, Clone, Default, serde::Serialize, serde::Deserialize)]
+pub struct FailureSummary {
+    pub total: usize,
+    pub reasons: Vec<(FailureReason, usize)>,
+}
+
+impl FailureSummary {
+    /// Tally `reports`; reasons with equal counts keep the order they were first seen in
+    pub fn from_reports<'a, I>(reports: I) -> Self
+    where
+        I: IntoIterator<Item = &'a SuggestionReport>,
+    {
+        let mut summary = FailureSummary::default();
+        for report in reports {
+            let reason = report.failure_reason();
+            match summary.reasons.iter_mut().find(|(seen, _)| *seen == reason) {
+                Some((_, count)) => *count += 1,
+                None => summary.reasons.push((reason, 1)),
+            }
+            summary.total += 1;
         }
+        summary.reasons.sort_by_key(|(_, count)| std::cmp::Reverse(*count));
+        summary
+    }
 
-        // 4. Try authority patterns
-        if let Some((pda_info, pattern, confidence)) = self.try_authority_patterns(address, program_id)? {
-            let result = PdaAnalysisResult {
-                pda_info,
-                pattern,
-                confidence,
-                analysis_time_ms: start_time.elapsed().as_millis() as u64,
-            };
-            self.update_pattern_stats(&result.pattern);
-            return Ok(Some(result));
+    /// Fraction of all failures `count` stands for
+    pub fn share(&self, count: usize) -> f64 {
+        if self.total == 0 {
+            0.0
+        } else {
+            count as f64 / self.total as f64
         }
+    }
+}
 
-        // 5. Try sequential patterns (numbered accounts)
-        if let Some((pda_info, confidence)) = self.try_sequential_patterns(address, program_id)? {
-            let result = PdaAnalysisResult {
-                pda_info,
-                pattern: PdaPattern::Sequential,
-                confidence,
-                analysis_time_ms: start_time.elapsed().as_millis() as u64,
-            };
-            self.update_pattern_stats(&result.pattern);
-            return Ok(Some(result));
+/// Derives PDAs from explicit seeds, caching results per program and seed set
+#[derive(Debug, Clone, Default)]
+pub struct PdaDeriver {
+    cache: PdaCache,
+}
+
+impl PdaDeriver {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Derive the canonical PDA for `seeds`. Zero-length seeds are allowed, but
+    /// Solana caps a derivation at `MAX_SEEDS` seeds including the bump and each seed at `MAX_SEED_LEN` bytes.
+    pub fn derive_pda(&mut self, program_id: &Pubkey, seeds: &[SeedValue]) -> Result<PdaInfo> {
+        let seed_bytes = validate_seeds(seeds)?;
+        let cache_key = (*program_id, seed_bytes);
+
+        // Seed sets that encode alike share an entry; report the seeds as this caller typed them
+        if let Some(Some(pda_info)) = self.cache.get(&cache_key) {
+            return Ok(PdaInfo {
+                seeds: seeds.to_vec(),
+                ..pda_info.clone()
+            });
         }
 
-        // 6. Try complex multi-seed patterns
-        if let Some((pda_info, confidence)) = self.try_complex_patterns(address, program_id)? {
-            let result = PdaAnalysisResult {
-                pda_info,
-                pattern: PdaPattern::Complex,
-                confidence,
-                analysis_time_ms: start_time.elapsed().as_millis() as u64,
-            };
-            self.update_pattern_stats(&result.pattern);
-            return Ok(Some(result));
+        let seed_refs: Vec<&[u8]> = cache_key.1.iter().map(|s| s.as_slice()).collect();
+
+        match Pubkey::try_find_program_address(&seed_refs, program_id) {
+            Some((address, bump)) => {
+                let pda_info = PdaInfo {
+                    address,
+                    program_id: *program_id,
+                    seeds: SeedValue::normalize_all(seeds),
+                    bump,
+                    first_seen_slot: None,
+                    first_seen_transaction: None,
+                };
+
+                self.cache.insert(cache_key, Some(pda_info.clone()));
+                Ok(PdaInfo {
+                    seeds: seeds.to_vec(),
+                    ..pda_info
+                })
+            }
+            None => {
+                self.cache.insert(cache_key, None);
+                Err(PdaAnalyzerError::PdaDerivationFailed(
+                    "No bump produces an off-curve address for these seeds".to_string(),
+                ))
+            }
         }
+    }
 
-        // 7. If no pattern matches, return None
-        // Future: implement general brute force analysis as fallback
+    /// Whether `seeds` derive `address` under `program_id` with the canonical bump
+    pub fn verify_pda(&self, address: &Pubkey, program_id: &Pubkey, seeds: &[SeedValue]) -> Result<bool> {
+        let seed_bytes = validate_seeds(seeds)?;
+        let seed_refs: Vec<&[u8]> = seed_bytes.iter().map(|s| s.as_slice()).collect();
 
-        Ok(None)
+        Ok(Pubkey::try_find_program_address(&seed_refs, program_id)
+            .is_some_and(|(derived_address, _)| derived_address == *address))
     }
 
-    /// Try Associated Token Account pattern: [wallet, token_program, mint]
-    fn try_associated_token_account(&mut self, address: &Pubkey, program_id: &Pubkey) -> Result<Option<(PdaInfo, f64)>> {
-        let ata_program_id = Pubkey::from_str("ATokenGPvbdGVxr1b2hvZbsiqW5xWH25efTNsLJA8knL")?;
-        
-        if *program_id != ata_program_id {
-            return Ok(None);
+    pub fn clear_cache(&mut self) {
+        self.cache.clear();
+    }
+
+    /// The cached derivation of already-encoded seeds, without deriving on a miss; `Some(None)`
+    /// records seeds no bump derives an address from
+    fn cached(&self, program_id: &Pubkey, seeds: &[&[u8]]) -> Option<Option<(Pubkey, u8)>> {
+        let key = (*program_id, seeds.iter().map(|seed| seed.to_vec()).collect());
+        self.cache.get(&key).map(|entry| entry.as_ref().map(|pda_info| (pda_info.address, pda_info.bump)))
+    }
+
+    /// Successful derivations and total cached entries
+    pub fn cache_stats(&self) -> (usize, usize) {
+        let hits = self.cache.values().filter(|v| v.is_some()).count();
+        (hits, self.cache.len())
+    }
+}
+
+/// Seed bytes for derivation, rejecting seed sets Solana would refuse
+pub fn validate_seeds(seeds: &[SeedValue]) -> Result<Vec<Vec<u8>>> {
+    // The bump is appended as one more seed
+    if seeds.len() >= MAX_SEEDS {
+        return Err(PdaAnalyzerError::InvalidSeedData(format!(
+            "{} seeds given, but at most {} fit alongside the bump seed (limit {})",
+            seeds.len(),
+            MAX_SEEDS - 1,
+            MAX_SEEDS
+        )));
+    }
+
+    let seed_bytes: Vec<Vec<u8>> = seeds.iter().map(|s| s.as_bytes()).collect();
+    if let Some((i, bytes)) = seed_bytes.iter().enumerate().find(|(_, bytes)| bytes.len() > MAX_SEED_LEN) {
+        return Err(PdaAnalyzerError::InvalidSeedData(format!(
+            "Seed {} is {} bytes, more than the {} byte limit",
+            i,
+            bytes.len(),
+            MAX_SEED_LEN
+        )));
+    }
+
+    Ok(seed_bytes)
+}
+
+/// Bump and seed ordering that reproduced an address
+struct SeedMatch {
+    bump: u8,
+    order: Vec<usize>,
+}
+
+impl SeedMatch {
+    /// Arrange candidate seeds in the order that matched
+    fn arrange(&self, seeds: Vec<SeedValue>) -> Vec<SeedValue> {
+        self.order.iter().map(|&i| seeds[i].clone()).collect()
+    }
+
+    /// Reordered seeds are a less expected shape, so trust them slightly less
+    fn confidence(&self, base: f64) -> f64 {
+        if self.order.iter().enumerate().all(|(i, &j)| i == j) {
+            base
+        } else {
+            base - 0.05
         }
+    }
+}
 
-        // Common wallets and mints for testing
-        let test_wallets = [
-            "9WzDXwBbmkg8ZTbNMqUxvQRAyrZzDsGYdLVL9zYtAWWM",
-            "7gXKKGLQs2HpzrPTtBP7kkQ3LktDShQPE8VV9PYW9RSh", 
-            "5Q544fKrFoe6tsEbD7S8EmxGTJYAKtTVhAW5Q5pge4j1",
-            "8szGkuLTAux9XMgZ2vtY39jVSowEcpBfFfD8hXSEqdGC",
-        ];
-        
-        let test_mints = [
-            "EPjFWdd5AufqSSqeM2qN1xzybapC8G4wEGGkZwyTDt1v", // USDC
-            "So11111111111111111111111111111111111111112",   // SOL
-            "Es9vMFrzaCERmJfrF4H2FYD4KCoNkY11McCe8BenwNYB",   // USDT
-            "7gXKKGLQs2HpzrPTtBP7kkQ3LktDShQPE8VV9PYW9RSh", // Example NFT
-        ];
+/// Seed sets one pattern family may still try under `SearchConfig::candidate_limit`
+#[derive(Debug, Clone, Copy)]
+struct CandidateBudget {
+    remaining: Option<usize>,
+}
 
-        let spl_token_program = Pubkey::from_str("TokenkegQfeZyiNwAJbNbGKPFXCWuBvf9Ss623VQ5DA")?;
+impl CandidateBudget {
+    fn new(limit: Option<usize>) -> Self {
+        Self { remaining: limit }
+    }
 
-        for wallet_str in &test_wallets {
-            if let Ok(wallet) = Pubkey::from_str(wallet_str) {
-                for mint_str in &test_mints {
-                    if let Ok(mint) = Pubkey::from_str(mint_str) {
-                        let seeds = &[
-                            wallet.as_ref(),
-                            spl_token_program.as_ref(),
-                            mint.as_ref(),
-                        ];
-                        
-                        if let Some((derived_address, bump)) = Pubkey::try_find_program_address(seeds, program_id) {
-                            if derived_address == *address {
-                                let pda_info = PdaInfo {
-                                    address: *address,
-                                    program_id: *program_id,
-                                    seeds: vec![
-                                        SeedValue::Pubkey(wallet),
-                                        SeedValue::Pubkey(spl_token_program),
-                                        SeedValue::Pubkey(mint),
-                                    ],
-                                    bump,
-                                    first_seen_slot: None,
-                                    first_seen_transaction: None,
-                                };
-                                return Ok(Some((pda_info, 0.98))); // High confidence for ATA pattern
-                            }
-                        }
-                    }
-                }
+    /// Spend one candidate, or report the family is out of budget
+    fn take(&mut self) -> bool {
+        match &mut self.remaining {
+            None => true,
+            Some(0) => false,
+            Some(remaining) => {
+                *remaining -= 1;
+                true
             }
         }
+    }
+}
 
-        Ok(None)
+/// Seed sets already derived in one `analyze_pda` call, so overlapping pattern families
+/// and candidate lists don't repeat the same curve operations. `match_seeds` searches
+/// are kept apart from plain derivations, since they also try other orders and bumps.
+#[derive(Debug, Clone, Default)]
+struct TriedSeeds {
+    derived: HashSet<Vec<Vec<u8>>>,
+    searched: HashSet<Vec<Vec<u8>>>,
+}
+
+impl TriedSeeds {
+    /// Note `seeds` as derived, or report they already were
+    fn first_attempt(&mut self, seeds: &[&[u8]]) -> bool {
+        self.derived.insert(seeds.iter().map(|seed| seed.to_vec()).collect())
     }
 
-    /// Try Metaplex metadata patterns
-    fn try_metaplex_patterns(&mut self, address: &Pubkey, program_id: &Pubkey) -> Result<Option<(PdaInfo, PdaPattern, f64)>> {
-        let metaplex_program_id = Pubkey::from_str("metaqbxxUerdq28cj1RbAWkYQm3ybzjb6a8bt518x1s")?;
-        
-        if *program_id != metaplex_program_id {
-            return Ok(None);
+    /// Note `seeds` as searched with `match_seeds`, or report they already were
+    fn first_search(&mut self, seeds: &[&[u8]]) -> bool {
+        self.searched.insert(seeds.iter().map(|seed| seed.to_vec()).collect())
+    }
+}
+
+/// How much of the complex-pattern search one analysis got through
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct ComplexSearchStats {
+    /// `[string, pubkey, string, number]` combinations derived
+    pub combinations: u64,
+    /// Whether `SearchConfig::complex_max_combinations` stopped the search early
+    pub truncated: bool,
+}
+
+impl std::fmt::Display for ComplexSearchStats {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        if self.truncated {
+            write!(f, "complex search truncated after {} combinations", self.combinations)
+        } else {
+            write!(f, "complex search evaluated {} combinations", self.combinations)
         }
+    }
+}
 
-        let test_mints = [
-            "7gXKKGLQs2HpzrPTtBP7kkQ3LktDShQPE8VV9PYW9RSh",
-            "8HYrKZBRZk9CgGfVv5u3r5G4W3dP2Qe2Y7rZRzMhQKkx",
-            "So11111111111111111111111111111111111111112",
-        ];
+/// What `PdaAnalyzer::solve_market_user_positions` recovered from a set of target addresses
+#[derive(Debug, Clone)]
+pub struct TargetSetOutcome {
+    /// One result per solved target, in the order the targets were given
+    pub solved: Vec<PdaAnalysisResult>,
+    /// Targets no combination derived, on-curve ones included
+    pub unsolved: Vec<Pubkey>,
+    /// Combinations derived before every target was solved or the candidates ran out
+    pub combinations: u64,
+}
 
-        for mint_str in &test_mints {
-            if let Ok(mint) = Pubkey::from_str(mint_str) {
-                // Try metadata pattern: ["metadata", program_id, mint]
-                let metadata_seeds = &[
-                    b"metadata",
-                    program_id.as_ref(),
-                    mint.as_ref(),
-                ];
-                
-                if let Some((derived_address, bump)) = Pubkey::try_find_program_address(metadata_seeds, program_id) {
-                    if derived_address == *address {
-                        let pda_info = PdaInfo {
-                            address: *address,
-                            program_id: *program_id,
-                            seeds: vec![
-                                SeedValue::String("metadata".to_string()),
-                                SeedValue::Pubkey(*program_id),
-                                SeedValue::Pubkey(mint),
-                            ],
+/// Patterns tried during one analysis and, while tracing, every seed set they tried
+#[derive(Debug, Clone, Default)]
+struct AttemptTrace {
+    patterns: Vec<PdaPattern>,
+    complex: Option<ComplexSearchStats>,
+    attempts: Option<Vec<SeedDerivationAttempt>>,
+}
+
+impl AttemptTrace {
+    /// Record one candidate seed set. `seeds` is only built while tracing.
+    fn record(
+        &mut self,
+        pattern: PdaPattern,
+        address: &Pubkey,
+        program_id: &Pubkey,
+        seeds: impl FnOnce() -> Vec<SeedValue>,
+        success: bool,
+    ) {
+        if !self.patterns.contains(&pattern) {
+            self.patterns.push(pattern.clone());
+        }
+        if let Some(attempts) = &mut self.attempts {
+            attempts.push(SeedDerivationAttempt {
+                pda_address: *address,
+                program_id: *program_id,
+                pattern,
+                attempted_seeds: seeds(),
+                success,
+                attempted_at: Utc::now(),
+            });
+        }
+    }
+}
+
+/// How often each pattern has been recognized. Counting and reading only need `&self`,
+/// and clones share the same counters, so several analyzers can report into one set.
+#[derive(Debug, Clone, Default)]
+pub struct PatternStats {
+    counts: Arc<DashMap<PdaPattern, AtomicU32>>,
+}
+
+impl PatternStats {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn record(&self, pattern: &PdaPattern) {
+        // Patterns seen before only need the shard's read lock
+        if let Some(count) = self.counts.get(pattern) {
+            count.fetch_add(1, Ordering::Relaxed);
+            return;
+        }
+        self.counts.entry(pattern.clone()).or_default().fetch_add(1, Ordering::Relaxed);
+    }
+
+    pub fn get(&self, pattern: &PdaPattern) -> u32 {
+        self.counts.get(pattern).map_or(0, |count| count.load(Ordering::Relaxed))
+    }
+
+    /// Current counts for every pattern recognized at least once
+    pub fn snapshot(&self) -> HashMap<PdaPattern, u32> {
+        self.counts
+            .iter()
+            .map(|entry| (entry.key().clone(), entry.value().load(Ordering::Relaxed)))
+            .collect()
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct PdaAnalyzer {
+    deriver: PdaDeriver,
+    // Read-only while analyzing, so batch workers share them instead of copying
+    known_programs: Arc<HashMap<Pubkey, String>>,
+    pattern_stats: PatternStats,
+    config: Arc<SearchConfig>,
+    candidates: Arc<CandidateRegistry>,
+    /// Built on first use by `batch_pool` and shared by clones
+    batch_pool: Arc<OnceLock<rayon::ThreadPool>>,
+    trace: AttemptTrace,
+    tried: TriedSeeds,
+    /// Over-long vocabulary strings already warned about
+    skipped_strings: HashSet<String>,
+    /// Singleton matches on strings shorter than `min_confident_string_len`
+    short_string_matches: u64,
+}
+
+impl Default for PdaAnalyzer {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Step-by-step construction of a `PdaAnalyzer`; start with `PdaAnalyzer::builder()`
+#[derive(Debug, Clone, Default)]
+pub struct PdaAnalyzerBuilder {
+    config: SearchConfig,
+    programs: Vec<(Pubkey, String)>,
+    candidates: CandidateRegistry,
+    pattern_stats: Option<PatternStats>,
+}
+
+impl PdaAnalyzerBuilder {
+    pub fn config(mut self, config: SearchConfig) -> Self {
+        self.config = config;
+        self
+    }
+
+    /// Programs to report by name, alongside the built-in ones; an entry for a built-in
+    /// program renames it
+    pub fn programs<I>(mut self, programs: I) -> Self
+    where
+        I: IntoIterator<Item = (Pubkey, String)>,
+    {
+        self.programs.extend(programs);
+        self
+    }
+
+    /// Seed strings to try for every program, added to the candidate registry
+    pub fn vocabulary<I>(mut self, words: I) -> Self
+    where
+        I: IntoIterator<Item = String>,
+    {
+        for word in words {
+            self.candidates.add_string(word);
+        }
+        self
+    }
+
+    /// Start from an existing registry, replacing any vocabulary added so far
+    pub fn candidate_registry(mut self, candidates: CandidateRegistry) -> Self {
+        self.candidates = candidates;
+        self
+    }
+
+    /// Count matches into shared counters, e.g. one set for a pool of analyzers
+    pub fn pattern_stats(mut self, pattern_stats: PatternStats) -> Self {
+        self.pattern_stats = Some(pattern_stats);
+        self
+    }
+
+    pub fn build(self) -> PdaAnalyzer {
+        let mut analyzer = PdaAnalyzer::with_config(self.config);
+        analyzer.candidates = Arc::new(self.candidates);
+        if let Some(pattern_stats) = self.pattern_stats {
+            analyzer.pattern_stats = pattern_stats;
+        }
+        for (program_id, name) in self.programs {
+            analyzer.register_program(program_id, name);
+        }
+        analyzer
+    }
+}
+
+impl PdaAnalyzer {
+    pub fn new() -> Self {
+        Self::with_config(SearchConfig::default())
+    }
+
+    pub fn builder() -> PdaAnalyzerBuilder {
+        PdaAnalyzerBuilder::default()
+    }
+
+    pub fn with_config(config: SearchConfig) -> Self {
+        let known_programs = builtin_known_programs().into_iter().collect();
+
+        Self {
+            deriver: PdaDeriver::new(),
+            known_programs: Arc::new(known_programs),
+            pattern_stats: PatternStats::new(),
+            config: Arc::new(config),
+            candidates: Arc::new(CandidateRegistry::new()),
+            batch_pool: Arc::new(OnceLock::new()),
+            trace: AttemptTrace::default(),
+            tried: TriedSeeds::default(),
+            skipped_strings: HashSet::new(),
+            short_string_matches: 0,
+        }
+    }
+
+    /// Get the active analyzer configuration
+    pub fn config(&self) -> &SearchConfig {
+        &self.config
+    }
+
+    /// Run `f` with only `patterns` enabled, restoring the configured set afterwards
+    pub fn with_enabled_patterns<R>(&mut self, patterns: HashSet<PdaPattern>, f: impl FnOnce(&mut Self) -> R) -> R {
+        let previous = std::mem::replace(&mut Arc::make_mut(&mut self.config).enabled_patterns, patterns);
+        let result = f(self);
+        Arc::make_mut(&mut self.config).enabled_patterns = previous;
+        result
+    }
+
+    /// Analyze a PDA to determine its seed derivation pattern with confidence scoring;
+    /// `None` both for unrecognized PDAs and for addresses that can't be PDAs
+    pub fn analyze_pda(&mut self, address: &Pubkey, program_id: &Pubkey) -> Result<Option<PdaAnalysisResult>> {
+        self.analyze(address, program_id).map(AnalysisOutcome::into_result)
+    }
+
+    /// `analyze` with epoch and slot seeds near `context` also tried, for accounts whose
+    /// creation slot or epoch is known
+    pub fn analyze_near(&mut self, address: &Pubkey, program_id: &Pubkey, context: ChainContext) -> Result<AnalysisOutcome> {
+        let previous = Arc::make_mut(&mut self.config).chain_context.replace(context);
+        let outcome = self.analyze(address, program_id);
+        Arc::make_mut(&mut self.config).chain_context = previous;
+        outcome
+    }
+
+    /// Analyze a PDA, saying whether it was recognized, searched without a match, or
+    /// could never have been derived
+    pub fn analyze(&mut self, address: &Pubkey, program_id: &Pubkey) -> Result<AnalysisOutcome> {
+        let start_time = Instant::now();
+
+        // No seeds derive an on-curve address, and the System Program owns no PDAs;
+        // `describe_unrecognized` explains either case
+        if address.is_on_curve() || *program_id == solana_sdk::system_program::id() {
+            return Ok(AnalysisOutcome::NotAPda);
+        }
+        self.tried = TriedSeeds::default();
+        self.trace.patterns.clear();
+        self.trace.complex = None;
+        
+        // Try each pattern family in turn, any configured priorities first
+        for family in self.config.family_order() {
+            if !self.config.any_enabled(family.patterns()) {
+                continue;
+            }
+            if let Some((pda_info, pattern, confidence)) = self.try_family(family, address, program_id)? {
+                // A multi-pattern family may match a pattern filtered out on its own
+                if self.config.is_enabled(&pattern) {
+                    return Ok(AnalysisOutcome::Recognized(self.finish(pda_info, pattern, confidence, start_time)));
+                }
+            }
+        }
+
+        // If no pattern matches, report what was tried
+        // Future: implement general brute force analysis as fallback
+
+        Ok(AnalysisOutcome::Unrecognized {
+            attempted: self.trace.patterns.clone(),
+        })
+    }
+
+    /// Build the result for a match, recording where each seed came from and weighting the
+    /// pattern's confidence by that provenance, how plausible the recovered seeds are and how
+    /// conventional their lengths, and count it in the pattern stats
+    fn finish(
+        &mut self,
+        pda_info: PdaInfo,
+        pattern: PdaPattern,
+        confidence: f64,
+        start_time: Instant,
+    ) -> PdaAnalysisResult {
+        let seed_sources: Vec<SeedSource> = pda_info.seeds.iter().map(|seed| self.seed_source(&pda_info.program_id, seed)).collect();
+        let short_string_match = pattern == PdaPattern::StringSingleton && self.is_short_string(&pda_info.seeds);
+        // A short string stays capped even when the program registered it
+        let confidence = if seed_sources.contains(&SeedSource::ProgramVocabulary) && !short_string_match {
+            confidence + PROGRAM_VOCABULARY_WEIGHT * (1.0 - confidence)
+        } else {
+            confidence
+        };
+        let confidence = confidence
+            * (1.0 - PLAUSIBILITY_WEIGHT * (1.0 - pda_info.plausibility_score()))
+            * (1.0 - SEED_LENGTH_WEIGHT * (1.0 - pda_info.seed_length_typicality()));
+        self.update_pattern_stats(&pattern);
+
+        PdaAnalysisResult {
+            short_string_match,
+            off_curve: !pda_info.address.is_on_curve(),
+            bump_as_explicit_seed: pda_info.bump_is_last_seed(),
+            pda_info,
+            match_kind: pattern.match_kind(),
+            pattern,
+            confidence,
+            analysis_time_ms: start_time.elapsed().as_millis() as u64,
+            account_exists: None,
+            seed_sources,
+        }
+    }
+
+    /// The most specific source that could have supplied `seed`; the registries are checked
+    /// first, since a value there may also be in a built-in dictionary
+    fn seed_source(&self, program_id: &Pubkey, seed: &SeedValue) -> SeedSource {
+        self.candidates.source_of(program_id, seed).unwrap_or(SeedSource::BuiltIn)
+    }
+
+    /// Whether `seeds` is a single string shorter than `min_confident_string_len`
+    fn is_short_string(&self, seeds: &[SeedValue]) -> bool {
+        matches!(seeds, [SeedValue::String(s)] if s.len() < self.config.min_confident_string_len)
+    }
+
+    /// How many singleton matches so far were on strings too short to trust fully
+    pub fn short_string_matches(&self) -> u64 {
+        self.short_string_matches
+    }
+
+    /// How far the last analysis got through the complex-pattern search; `None` if it
+    /// never reached it, e.g. because an earlier pattern matched
+    pub fn complex_search_stats(&self) -> Option<ComplexSearchStats> {
+        self.trace.complex
+    }
+
+    /// Like `analyze_pda`, but also returns every seed set tried, in order. A successful
+    /// analysis ends with the matching attempt.
+    pub fn analyze_pda_traced(
+        &mut self,
+        address: &Pubkey,
+        program_id: &Pubkey,
+    ) -> Result<(Option<PdaAnalysisResult>, Vec<SeedDerivationAttempt>)> {
+        self.trace.attempts = Some(Vec::new());
+        let result = self.analyze_pda(address, program_id);
+        let attempts = self.trace.attempts.take().unwrap_or_default();
+        Ok((result?, attempts))
+    }
+
+    /// Run one pattern family's search, reporting which of its patterns matched
+    fn try_family(&mut self, family: PatternFamily, address: &Pubkey, program_id: &Pubkey) -> Result<Option<(PdaInfo, PdaPattern, f64)>> {
+        let single = |pattern: PdaPattern, found: Option<(PdaInfo, f64)>| found.map(|(pda_info, confidence)| (pda_info, pattern, confidence));
+        Ok(match family {
+            PatternFamily::Ata => single(PdaPattern::AssociatedTokenAccount, self.try_associated_token_account(address, program_id)?),
+            PatternFamily::Metaplex => self.try_metaplex_patterns(address, program_id)?,
+            PatternFamily::Singleton => single(PdaPattern::StringSingleton, self.try_string_singleton_patterns(address, program_id)?),
+            PatternFamily::Authority => self.try_authority_patterns(address, program_id)?,
+            PatternFamily::SelfRef => single(PdaPattern::ProgramSelfRef, self.try_self_referential_patterns(address, program_id)?),
+            PatternFamily::PubkeyPair => single(PdaPattern::PubkeyPubkey, self.try_pubkey_pubkey_patterns(address, program_id)?),
+            PatternFamily::Stake => self.try_stake_patterns(address, program_id)?,
+            PatternFamily::Suffixed => single(PdaPattern::StringPubkeyStringSuffix, self.try_suffixed_pubkey_patterns(address, program_id)?),
+            PatternFamily::Sequential => single(PdaPattern::Sequential, self.try_sequential_patterns(address, program_id)?),
+            PatternFamily::Complex => single(PdaPattern::Complex, self.try_complex_patterns(address, program_id)?),
+            PatternFamily::Timestamp => single(PdaPattern::Timestamp, self.try_timestamp_patterns(address, program_id)?),
+            PatternFamily::EpochSlot => single(PdaPattern::EpochSlot, self.try_epoch_slot_patterns(address, program_id)?),
+        })
+    }
+
+    /// Try Associated Token Account pattern: [wallet, token_program, mint]
+    fn try_associated_token_account(&mut self, address: &Pubkey, program_id: &Pubkey) -> Result<Option<(PdaInfo, f64)>> {
+        let mut budget = CandidateBudget::new(self.config.candidate_limit);
+        let ata_program_id = Pubkey::from_str("ATokenGPvbdGVxr1b2hvZbsiqW5xWH25efTNsLJA8knL")?;
+        
+        if *program_id != ata_program_id {
+            return Ok(None);
+        }
+
+        let spl_token_program = Pubkey::from_str("TokenkegQfeZyiNwAJbNbGKPFXCWuBvf9Ss623VQ5DA")?;
+
+        for wallet in &self.config.ata_wallets {
+            for mint in &self.config.ata_mints {
+                let seeds = &[
+                    wallet.as_ref(),
+                    spl_token_program.as_ref(),
+                    mint.as_ref(),
+                ];
+                let values = || vec![
+                    SeedValue::Pubkey(*wallet),
+                    SeedValue::Pubkey(spl_token_program),
+                    SeedValue::Pubkey(*mint),
+                ];
+                
+                if !self.tried.first_attempt(seeds) {
+                    continue;
+                }
+                if !budget.take() {
+                    return Ok(None);
+                }
+                let derived = Pubkey::try_find_program_address(seeds, program_id);
+                let matched = matches!(derived, Some((derived_address, _)) if derived_address == *address);
+                self.trace.record(PdaPattern::AssociatedTokenAccount, address, program_id, values, matched);
+
+                if let Some((derived_address, bump)) = derived {
+                    if derived_address == *address {
+                        let pda_info = PdaInfo {
+                            address: *address,
+                            program_id: *program_id,
+                            seeds: values(),
                             bump,
                             first_seen_slot: None,
                             first_seen_transaction: None,
                         };
-                        return Ok(Some((pda_info, PdaPattern::MetaplexMetadata, 0.95)));
+                        return Ok(Some((pda_info, 0.98))); // High confidence for ATA pattern
                     }
                 }
+            }
+        }
+
+        Ok(None)
+    }
+
+    /// Try Metaplex metadata patterns
+    fn try_metaplex_patterns(&mut self, address: &Pubkey, program_id: &Pubkey) -> Result<Option<(PdaInfo, PdaPattern, f64)>> {
+        let mut budget = CandidateBudget::new(self.config.candidate_limit);
+        let metaplex_program_id = Pubkey::from_str("metaqbxxUerdq28cj1RbAWkYQm3ybzjb6a8bt518x1s")?;
+        
+        if *program_id != metaplex_program_id {
+            return Ok(None);
+        }
+
+        for mint in &self.config.metaplex_mints {
+            // Try metadata pattern: ["metadata", program_id, mint]
+            let metadata_seeds = &[
+                b"metadata",
+                program_id.as_ref(),
+                mint.as_ref(),
+            ];
+            let values = || vec![
+                SeedValue::String("metadata".to_string()),
+                SeedValue::Pubkey(*program_id),
+                SeedValue::Pubkey(*mint),
+            ];
+            
+            // Only a repeated mint repeats the metadata seeds, and every set after them
+            if !self.tried.first_attempt(metadata_seeds) {
+                continue;
+            }
+            if !budget.take() {
+                return Ok(None);
+            }
+            let derived = Pubkey::try_find_program_address(metadata_seeds, program_id);
+            let matched = matches!(derived, Some((derived_address, _)) if derived_address == *address);
+            self.trace.record(PdaPattern::MetaplexMetadata, address, program_id, values, matched);
+
+            if let Some((derived_address, bump)) = derived {
+                if derived_address == *address {
+                    let pda_info = PdaInfo {
+                        address: *address,
+                        program_id: *program_id,
+                        seeds: values(),
+                        bump,
+                        first_seen_slot: None,
+                        first_seen_transaction: None,
+                    };
+                    return Ok(Some((pda_info, PdaPattern::MetaplexMetadata, 0.95)));
+                }
+            }
 
-                // Try master edition pattern: ["metadata", program_id, mint, "edition"]
-                let edition_seeds = &[
+            // Try master edition pattern: ["metadata", program_id, mint, "edition"]
+            let edition_seeds = &[
+                b"metadata",
+                program_id.as_ref(),
+                mint.as_ref(),
+                b"edition",
+            ];
+            let values = || vec![
+                SeedValue::String("metadata".to_string()),
+                SeedValue::Pubkey(*program_id),
+                SeedValue::Pubkey(*mint),
+                SeedValue::String("edition".to_string()),
+            ];
+            
+            if !budget.take() {
+                return Ok(None);
+            }
+            let derived = Pubkey::try_find_program_address(edition_seeds, program_id);
+            let matched = matches!(derived, Some((derived_address, _)) if derived_address == *address);
+            self.trace.record(PdaPattern::MetaplexMasterEdition, address, program_id, values, matched);
+
+            if let Some((derived_address, bump)) = derived {
+                if derived_address == *address {
+                    let pda_info = PdaInfo {
+                        address: *address,
+                        program_id: *program_id,
+                        seeds: values(),
+                        bump,
+                        first_seen_slot: None,
+                        first_seen_transaction: None,
+                    };
+                    return Ok(Some((pda_info, PdaPattern::MetaplexMasterEdition, 0.93)));
+                }
+            }
+
+            // Try edition with number: ["metadata", program_id, master_mint, "edition", edition_number]
+            for edition_num in self.config.edition_range.clone() {
+                let numbered_edition_seeds = &[
                     b"metadata",
                     program_id.as_ref(),
                     mint.as_ref(),
                     b"edition",
+                    &edition_num.to_le_bytes(),
+                ];
+                let values = || vec![
+                    SeedValue::String("metadata".to_string()),
+                    SeedValue::Pubkey(*program_id),
+                    SeedValue::Pubkey(*mint),
+                    SeedValue::String("edition".to_string()),
+                    SeedValue::U64(edition_num),
                 ];
                 
-                if let Some((derived_address, bump)) = Pubkey::try_find_program_address(edition_seeds, program_id) {
+                if !budget.take() {
+                    return Ok(None);
+                }
+                let derived = Pubkey::try_find_program_address(numbered_edition_seeds, program_id);
+                let matched = matches!(derived, Some((derived_address, _)) if derived_address == *address);
+                self.trace.record(PdaPattern::MetaplexEdition, address, program_id, values, matched);
+
+                if let Some((derived_address, bump)) = derived {
                     if derived_address == *address {
                         let pda_info = PdaInfo {
                             address: *address,
                             program_id: *program_id,
-                            seeds: vec![
-                                SeedValue::String("metadata".to_string()),
-                                SeedValue::Pubkey(*program_id),
-                                SeedValue::Pubkey(mint),
-                                SeedValue::String("edition".to_string()),
-                            ],
+                            seeds: values(),
                             bump,
                             first_seen_slot: None,
                             first_seen_transaction: None,
                         };
-                        return Ok(Some((pda_info, PdaPattern::MetaplexMasterEdition, 0.93)));
-                    }
-                }
-
-                // Try edition with number: ["metadata", program_id, master_mint, "edition", edition_number]
-                for edition_num in 1..=10u64 {
-                    let numbered_edition_seeds = &[
-                        b"metadata",
-                        program_id.as_ref(),
-                        mint.as_ref(),
-                        b"edition",
-                        &edition_num.to_le_bytes(),
-                    ];
-                    
-                    if let Some((derived_address, bump)) = Pubkey::try_find_program_address(numbered_edition_seeds, program_id) {
-                        if derived_address == *address {
-                            let pda_info = PdaInfo {
-                                address: *address,
-                                program_id: *program_id,
-                                seeds: vec![
-                                    SeedValue::String("metadata".to_string()),
-                                    SeedValue::Pubkey(*program_id),
-                                    SeedValue::Pubkey(mint),
-                                    SeedValue::String("edition".to_string()),
-                                    SeedValue::U64(edition_num),
-                                ],
-                                bump,
-                                first_seen_slot: None,
-                                first_seen_transaction: None,
-                            };
-                            return Ok(Some((pda_info, PdaPattern::MetaplexEdition, 0.90)));
-                        }
+                        return Ok(Some((pda_info, PdaPattern::MetaplexEdition, 0.90)));
                     }
                 }
             }
@@ -359,35 +1420,372 @@ impl PdaAnalyzer {
         Ok(None)
     }
 
+    /// Configured singleton strings, then those learned for `program_id`. Over-long strings
+    /// can never derive an address, so they are dropped, with a warning the first time.
+    fn seed_strings(&mut self, program_id: &Pubkey) -> Vec<String> {
+        let configured_strings = self.config.singleton_strings.iter().map(String::as_str);
+        let learned_strings = self.candidates.strings_for(program_id);
+        let max_len = self.config.max_seed_string_len.min(MAX_SEED_LEN);
+
+        let mut strings = Vec::new();
+        for string in configured_strings.chain(learned_strings) {
+            if string.len() <= max_len {
+                strings.push(string.to_string());
+            } else if self.skipped_strings.insert(string.to_string()) {
+                tracing::warn!(
+                    "Skipping seed string {:?}: {} bytes is over the {}-byte limit",
+                    string,
+                    string.len(),
+                    max_len
+                );
+            }
+        }
+        strings
+    }
+
     /// Try common string singleton patterns
     fn try_string_singleton_patterns(&mut self, address: &Pubkey, program_id: &Pubkey) -> Result<Option<(PdaInfo, f64)>> {
-        let common_strings = [
-            "state", "config", "authority", "vault", "pool", "market",
-            "escrow", "registry", "governance", "proposal", "metadata",
-            "treasury", "rewards", "staking", "lending", "farming",
-            "oracle", "price_feed", "liquidity", "swap", "mint_authority",
-            "global", "settings", "admin", "owner", "controller",
-        ];
-
-        for string in &common_strings {
+        let mut budget = CandidateBudget::new(self.config.candidate_limit);
+        let strings = self.seed_strings(program_id);
+
+        for string in &strings {
+            let string = string.as_str();
             let seeds = &[string.as_bytes()];
-            if let Some((derived_address, bump)) = Pubkey::try_find_program_address(seeds, program_id) {
+            if !self.tried.first_attempt(seeds) {
+                continue;
+            }
+            if !budget.take() {
+                return Ok(None);
+            }
+            // Singletons derived by `warm_up` skip the curve operations
+            let derived = match self.deriver.cached(program_id, seeds) {
+                Some(derived) => derived,
+                None => Pubkey::try_find_program_address(seeds, program_id),
+            };
+            let matched = matches!(derived, Some((derived_address, _)) if derived_address == *address);
+            self.trace.record(PdaPattern::StringSingleton, address, program_id, || vec![SeedValue::String(string.to_string())], matched);
+
+            if let Some((derived_address, bump)) = derived {
                 if derived_address == *address {
-                    let confidence = match *string {
+                    let confidence = match string {
                         "state" | "config" | "authority" => 0.92,
                         "vault" | "pool" | "market" => 0.88,
-                        _ => 0.85,
+                        _ if self.config.singleton_strings.iter().any(|s| s == string) => 0.85,
+                        // Learned strings are unverified guesses
+                        _ => 0.80,
                     };
                     
                     let pda_info = PdaInfo {
                         address: *address,
                         program_id: *program_id,
-                        seeds: vec![SeedValue::String(string.to_string())],
-                        bump,
+                        seeds: vec![SeedValue::String(string.to_string())],
+                        bump,
+                        first_seen_slot: None,
+                        first_seen_transaction: None,
+                    };
+                    if self.is_short_string(&pda_info.seeds) {
+                        self.short_string_matches += 1;
+                        tracing::warn!(
+                            "{} matched the {}-byte seed {:?}, which may be coincidental; confidence capped at {}",
+                            address,
+                            string.len(),
+                            string,
+                            SHORT_STRING_MAX_CONFIDENCE
+                        );
+                        return Ok(Some((pda_info, f64::min(confidence, SHORT_STRING_MAX_CONFIDENCE))));
+                    }
+                    return Ok(Some((pda_info, confidence)));
+                }
+            }
+        }
+
+        Ok(None)
+    }
+
+    /// Try authority patterns
+    fn try_authority_patterns(&mut self, address: &Pubkey, program_id: &Pubkey) -> Result<Option<(PdaInfo, PdaPattern, f64)>> {
+        let mut budget = CandidateBudget::new(self.config.candidate_limit);
+        let authorities: Vec<Pubkey> = self
+            .config
+            .authority_pubkeys
+            .iter()
+            .chain(self.candidates.pubkeys().iter())
+            .copied()
+            .collect();
+
+        for authority in authorities {
+            // Try [authority] pattern
+            let seeds = &[authority.as_ref()];
+            let values = || vec![SeedValue::Pubkey(authority)];
+            // Only a repeated authority repeats this set, and every set after it
+            if !self.tried.first_search(seeds) {
+                continue;
+            }
+            if !budget.take() {
+                return Ok(None);
+            }
+            let found = self.match_seeds(seeds, address, program_id);
+            self.trace.record(PdaPattern::StringAuthority, address, program_id, || arrange_attempt(&found, values()), found.is_some());
+            if let Some(found) = found {
+                let pda_info = PdaInfo {
+                    address: *address,
+                    program_id: *program_id,
+                    seeds: found.arrange(values()),
+                    bump: found.bump,
+                    first_seen_slot: None,
+                    first_seen_transaction: None,
+                };
+                return Ok(Some((pda_info, PdaPattern::StringAuthority, found.confidence(0.87))));
+            }
+
+            // Try ["authority", authority] pattern
+            let seeds = &[b"authority", authority.as_ref()];
+            let values = || vec![
+                SeedValue::String("authority".to_string()),
+                SeedValue::Pubkey(authority),
+            ];
+            if !budget.take() {
+                return Ok(None);
+            }
+            let found = self.match_seeds(seeds, address, program_id);
+            self.trace.record(PdaPattern::StringPubkey, address, program_id, || arrange_attempt(&found, values()), found.is_some());
+            if let Some(found) = found {
+                let pda_info = PdaInfo {
+                    address: *address,
+                    program_id: *program_id,
+                    seeds: found.arrange(values()),
+                    bump: found.bump,
+                    first_seen_slot: None,
+                    first_seen_transaction: None,
+                };
+                return Ok(Some((pda_info, PdaPattern::StringPubkey, found.confidence(0.85))));
+            }
+
+            // Try [authority, nonce] patterns for DEX/AMM
+            for nonce in self.config.nonce_range.clone() {
+                for (nonce_bytes, nonce_seed) in self.config.integer_seeds(nonce, 8) {
+                    let seeds = &[authority.as_ref(), nonce_bytes.as_slice()];
+                    let values = || vec![SeedValue::Pubkey(authority), nonce_seed.clone()];
+                    if !self.tried.first_search(seeds) {
+                        continue;
+                    }
+                    if !budget.take() {
+                        return Ok(None);
+                    }
+                    let found = self.match_seeds(seeds, address, program_id);
+                    self.trace.record(PdaPattern::PubkeyU64, address, program_id, || arrange_attempt(&found, values()), found.is_some());
+                    if let Some(found) = found {
+                        let pda_info = PdaInfo {
+                            address: *address,
+                            program_id: *program_id,
+                            seeds: found.arrange(values()),
+                            bump: found.bump,
+                            first_seen_slot: None,
+                            first_seen_transaction: None,
+                        };
+                        return Ok(Some((pda_info, PdaPattern::PubkeyU64, found.confidence(0.83))));
+                    }
+                }
+            }
+
+            // Try [authority, bump] patterns
+            for bump_seed in self.config.bump_seed_range.clone() {
+                let seeds = &[authority.as_ref(), &[bump_seed]];
+                let values = || vec![SeedValue::Pubkey(authority), SeedValue::U8(bump_seed)];
+                if !self.tried.first_search(seeds) {
+                    continue;
+                }
+                if !budget.take() {
+                    return Ok(None);
+                }
+
+                // The seed may be the bump itself, passed from storage with nothing appended
+                if Pubkey::create_program_address(seeds, program_id) == Ok(*address) {
+                    self.trace.record(PdaPattern::PubkeyU8, address, program_id, values, true);
+                    let pda_info = PdaInfo {
+                        address: *address,
+                        program_id: *program_id,
+                        seeds: values(),
+                        bump: bump_seed,
+                        first_seen_slot: None,
+                        first_seen_transaction: None,
+                    };
+                    return Ok(Some((pda_info, PdaPattern::PubkeyU8, 0.82)));
+                }
+
+                let found = self.match_seeds(seeds, address, program_id);
+                self.trace.record(PdaPattern::PubkeyU8, address, program_id, || arrange_attempt(&found, values()), found.is_some());
+                if let Some(found) = found {
+                    let pda_info = PdaInfo {
+                        address: *address,
+                        program_id: *program_id,
+                        seeds: found.arrange(values()),
+                        bump: found.bump,
+                        first_seen_slot: None,
+                        first_seen_transaction: None,
+                    };
+                    return Ok(Some((pda_info, PdaPattern::PubkeyU8, found.confidence(0.82))));
+                }
+            }
+        }
+
+        Ok(None)
+    }
+
+    /// Try seeds around the program's own ID: `[program_id]`, `[string, program_id]`,
+    /// `[program_id, pubkey]` and `[string, program_id, pubkey]`, over the singleton
+    /// vocabulary and candidate pubkeys. Metaplex's layouts are the best-known case, but
+    /// other programs namespace accounts the same way.
+    fn try_self_referential_patterns(&mut self, address: &Pubkey, program_id: &Pubkey) -> Result<Option<(PdaInfo, f64)>> {
+        let mut budget = CandidateBudget::new(self.config.candidate_limit);
+        let strings = self.seed_strings(program_id);
+        let mut pubkeys: Vec<Pubkey> = Vec::new();
+        for pubkey in self.config.authority_pubkeys.iter().chain(self.candidates.pubkeys()) {
+            if pubkey != program_id && !pubkeys.contains(pubkey) {
+                pubkeys.push(*pubkey);
+            }
+        }
+
+        // An optional string before the program ID and an optional pubkey after it
+        let prefixes: Vec<Option<&str>> = std::iter::once(None).chain(strings.iter().map(|s| Some(s.as_str()))).collect();
+        let suffixes: Vec<Option<Pubkey>> = std::iter::once(None).chain(pubkeys.iter().copied().map(Some)).collect();
+
+        for prefix in &prefixes {
+            for suffix in &suffixes {
+                let mut seeds: Vec<&[u8]> = Vec::with_capacity(3);
+                seeds.extend(prefix.map(str::as_bytes));
+                seeds.push(program_id.as_ref());
+                seeds.extend(suffix.as_ref().map(|pubkey| pubkey.as_ref()));
+
+                let values = || {
+                    let mut values: Vec<SeedValue> = prefix.map(|s| SeedValue::String(s.to_string())).into_iter().collect();
+                    values.push(SeedValue::Pubkey(*program_id));
+                    values.extend(suffix.map(SeedValue::Pubkey));
+                    values
+                };
+
+                if !self.tried.first_search(&seeds) {
+                    continue;
+                }
+                if !budget.take() {
+                    return Ok(None);
+                }
+                let found = self.match_seeds(&seeds, address, program_id);
+                self.trace.record(PdaPattern::ProgramSelfRef, address, program_id, || arrange_attempt(&found, values()), found.is_some());
+                if let Some(found) = found {
+                    // A vocabulary string alongside the ID is the conventional shape
+                    let confidence = match (prefix, suffix) {
+                        (Some(_), None) => 0.86,
+                        (Some(_), Some(_)) => 0.84,
+                        (None, _) => 0.80,
+                    };
+                    let pda_info = PdaInfo {
+                        address: *address,
+                        program_id: *program_id,
+                        seeds: found.arrange(values()),
+                        bump: found.bump,
+                        first_seen_slot: None,
+                        first_seen_transaction: None,
+                    };
+                    return Ok(Some((pda_info, found.confidence(confidence))));
+                }
+            }
+        }
+
+        Ok(None)
+    }
+
+    /// Try `[pubkey, pubkey]` patterns over every ordered pair of distinct candidate pubkeys
+    fn try_pubkey_pubkey_patterns(&mut self, address: &Pubkey, program_id: &Pubkey) -> Result<Option<(PdaInfo, f64)>> {
+        let mut budget = CandidateBudget::new(self.config.candidate_limit);
+        let mut pubkeys: Vec<Pubkey> = Vec::new();
+        for pubkey in self.config.authority_pubkeys.iter().chain(self.candidates.pubkeys()) {
+            if !pubkeys.contains(pubkey) {
+                pubkeys.push(*pubkey);
+            }
+        }
+
+        for first in &pubkeys {
+            for second in pubkeys.iter().filter(|second| *second != first) {
+                let seeds = &[first.as_ref(), second.as_ref()];
+                let values = || vec![SeedValue::Pubkey(*first), SeedValue::Pubkey(*second)];
+                if !self.tried.first_search(seeds) {
+                    continue;
+                }
+                if !budget.take() {
+                    return Ok(None);
+                }
+                let found = self.match_seeds(seeds, address, program_id);
+                self.trace.record(PdaPattern::PubkeyPubkey, address, program_id, || arrange_attempt(&found, values()), found.is_some());
+                if let Some(found) = found {
+                    let pda_info = PdaInfo {
+                        address: *address,
+                        program_id: *program_id,
+                        seeds: found.arrange(values()),
+                        bump: found.bump,
+                        first_seen_slot: None,
+                        first_seen_transaction: None,
+                    };
+                    return Ok(Some((pda_info, found.confidence(0.84))));
+                }
+            }
+        }
+
+        Ok(None)
+    }
+
+    /// Try validator records `["validator", vote]` and stake pool accounts `["stake", pool, vote]`
+    /// and `[vote, pool]` for each candidate vote account, with the configured stake pools and
+    /// candidate pubkeys as pools
+    fn try_stake_patterns(&mut self, address: &Pubkey, program_id: &Pubkey) -> Result<Option<(PdaInfo, PdaPattern, f64)>> {
+        let mut budget = CandidateBudget::new(self.config.candidate_limit);
+        let votes = self.candidates.vote_accounts().to_vec();
+        let mut pools: Vec<Pubkey> = Vec::new();
+        for pool in self.config.stake_pools.iter().chain(self.candidates.pubkeys()) {
+            if !pools.contains(pool) && !votes.contains(pool) {
+                pools.push(*pool);
+            }
+        }
+
+        for vote in &votes {
+            let mut layouts: Vec<(PdaPattern, Vec<SeedValue>, f64)> = vec![(
+                PdaPattern::ValidatorRecord,
+                vec![SeedValue::String("validator".to_string()), SeedValue::Pubkey(*vote)],
+                0.90,
+            )];
+            for pool in &pools {
+                layouts.push((
+                    PdaPattern::StakePoolValidator,
+                    vec![SeedValue::String("stake".to_string()), SeedValue::Pubkey(*pool), SeedValue::Pubkey(*vote)],
+                    0.88,
+                ));
+                layouts.push((PdaPattern::StakePoolValidator, vec![SeedValue::Pubkey(*vote), SeedValue::Pubkey(*pool)], 0.90));
+            }
+
+            for (pattern, values, confidence) in layouts {
+                if !self.config.is_enabled(&pattern) {
+                    continue;
+                }
+                let seed_bytes: Vec<Vec<u8>> = values.iter().map(SeedValue::as_bytes).collect();
+                let seeds: Vec<&[u8]> = seed_bytes.iter().map(Vec::as_slice).collect();
+                if !self.tried.first_search(&seeds) {
+                    continue;
+                }
+                if !budget.take() {
+                    return Ok(None);
+                }
+                let found = self.match_seeds(&seeds, address, program_id);
+                self.trace.record(pattern.clone(), address, program_id, || arrange_attempt(&found, values.clone()), found.is_some());
+                if let Some(found) = found {
+                    let pda_info = PdaInfo {
+                        address: *address,
+                        program_id: *program_id,
+                        seeds: found.arrange(values),
+                        bump: found.bump,
                         first_seen_slot: None,
                         first_seen_transaction: None,
                     };
-                    return Ok(Some((pda_info, confidence)));
+                    return Ok(Some((pda_info, pattern, found.confidence(confidence))));
                 }
             }
         }
@@ -395,140 +1793,123 @@ impl PdaAnalyzer {
         Ok(None)
     }
 
-    /// Try authority patterns
-    fn try_authority_patterns(&mut self, address: &Pubkey, program_id: &Pubkey) -> Result<Option<(PdaInfo, PdaPattern, f64)>> {
-        let test_authorities = [
-            "11111111111111111111111111111112",
-            "TokenkegQfeZyiNwAJbNbGKPFXCWuBvf9Ss623VQ5DA",
-            "9WzDXwBbmkg8ZTbNMqUxvQRAyrZzDsGYdLVL9zYtAWWM",
-            "7gXKKGLQs2HpzrPTtBP7kkQ3LktDShQPE8VV9PYW9RSh",
-        ];
-
-        for auth_str in &test_authorities {
-            if let Ok(authority) = Pubkey::from_str(auth_str) {
-                // Try [authority] pattern
-                let seeds = &[authority.as_ref()];
-                if let Some((derived_address, bump)) = Pubkey::try_find_program_address(seeds, program_id) {
-                    if derived_address == *address {
-                        let pda_info = PdaInfo {
-                            address: *address,
-                            program_id: *program_id,
-                            seeds: vec![SeedValue::Pubkey(authority)],
-                            bump,
-                            first_seen_slot: None,
-                            first_seen_transaction: None,
-                        };
-                        return Ok(Some((pda_info, PdaPattern::StringAuthority, 0.87)));
-                    }
-                }
+    /// Try `[prefix, pubkey, suffix]` patterns: prefixes from the singleton strings and the
+    /// program's vocabulary, suffixes from the configured suffixes and the vocabulary, around
+    /// authority and candidate pubkeys
+    fn try_suffixed_pubkey_patterns(&mut self, address: &Pubkey, program_id: &Pubkey) -> Result<Option<(PdaInfo, f64)>> {
+        let mut budget = CandidateBudget::new(self.config.candidate_limit);
+        let prefixes = self.seed_strings(program_id);
+        let max_len = self.config.max_seed_string_len.min(MAX_SEED_LEN);
+        let mut suffixes: Vec<String> = Vec::new();
+        for suffix in self.config.suffix_strings.iter().map(String::as_str).chain(self.candidates.strings_for(program_id)) {
+            if suffix.len() <= max_len && !suffixes.iter().any(|s| s == suffix) {
+                suffixes.push(suffix.to_string());
+            }
+        }
+        let mut pubkeys: Vec<Pubkey> = Vec::new();
+        for pubkey in self.config.authority_pubkeys.iter().chain(self.candidates.pubkeys()) {
+            if !pubkeys.contains(pubkey) {
+                pubkeys.push(*pubkey);
+            }
+        }
 
-                // Try ["authority", authority] pattern
-                let seeds = &[b"authority", authority.as_ref()];
-                if let Some((derived_address, bump)) = Pubkey::try_find_program_address(seeds, program_id) {
-                    if derived_address == *address {
+        for pubkey in &pubkeys {
+            for prefix in &prefixes {
+                for suffix in suffixes.iter().filter(|suffix| *suffix != prefix) {
+                    let seeds = &[prefix.as_bytes(), pubkey.as_ref(), suffix.as_bytes()];
+                    let values = || vec![
+                        SeedValue::String(prefix.clone()),
+                        SeedValue::Pubkey(*pubkey),
+                        SeedValue::String(suffix.clone()),
+                    ];
+                    if !self.tried.first_search(seeds) {
+                        continue;
+                    }
+                    if !budget.take() {
+                        return Ok(None);
+                    }
+                    let found = self.match_seeds(seeds, address, program_id);
+                    self.trace.record(PdaPattern::StringPubkeyStringSuffix, address, program_id, || arrange_attempt(&found, values()), found.is_some());
+                    if let Some(found) = found {
                         let pda_info = PdaInfo {
                             address: *address,
                             program_id: *program_id,
-                            seeds: vec![
-                                SeedValue::String("authority".to_string()),
-                                SeedValue::Pubkey(authority),
-                            ],
-                            bump,
+                            seeds: found.arrange(values()),
+                            bump: found.bump,
                             first_seen_slot: None,
                             first_seen_transaction: None,
                         };
-                        return Ok(Some((pda_info, PdaPattern::StringPubkey, 0.85)));
+                        return Ok(Some((pda_info, found.confidence(0.81))));
                     }
                 }
+            }
+        }
 
-                // Try [authority, nonce] patterns for DEX/AMM
-                for nonce in 0..=10u64 {
-                    let seeds = &[authority.as_ref(), &nonce.to_le_bytes()];
-                    if let Some((derived_address, bump)) = Pubkey::try_find_program_address(seeds, program_id) {
-                        if derived_address == *address {
-                            let pda_info = PdaInfo {
-                                address: *address,
-                                program_id: *program_id,
-                                seeds: vec![
-                                    SeedValue::Pubkey(authority),
-                                    SeedValue::U64(nonce),
-                                ],
-                                bump,
-                                first_seen_slot: None,
-                                first_seen_transaction: None,
-                            };
-                            return Ok(Some((pda_info, PdaPattern::PubkeyU64, 0.83)));
-                        }
-                    }
+        Ok(None)
+    }
+
+    /// Find the bump and seed ordering that derive `address`, canonical bump and given order first
+    fn match_seeds(&self, seeds: &[&[u8]], address: &Pubkey, program_id: &Pubkey) -> Option<SeedMatch> {
+        let orderings = if self.config.permute_seed_order && seeds.len() <= MAX_PERMUTED_SEEDS {
+            seed_orderings(seeds.len())
+        } else {
+            vec![(0..seeds.len()).collect()]
+        };
+
+        for order in &orderings {
+            let ordered: Vec<&[u8]> = order.iter().map(|&i| seeds[i]).collect();
+            if let Some((derived_address, bump)) = Pubkey::try_find_program_address(&ordered, program_id) {
+                if derived_address == *address {
+                    return Some(SeedMatch { bump, order: order.clone() });
                 }
+            }
+        }
 
-                // Try [authority, bump] patterns
-                for bump_seed in 250..=255u8 {
-                    let seeds = &[authority.as_ref(), &[bump_seed]];
-                    if let Some((derived_address, bump)) = Pubkey::try_find_program_address(seeds, program_id) {
-                        if derived_address == *address {
-                            let pda_info = PdaInfo {
-                                address: *address,
-                                program_id: *program_id,
-                                seeds: vec![
-                                    SeedValue::Pubkey(authority),
-                                    SeedValue::U8(bump_seed),
-                                ],
-                                bump,
-                                first_seen_slot: None,
-                                first_seen_transaction: None,
-                            };
-                            return Ok(Some((pda_info, PdaPattern::PubkeyU8, 0.82)));
-                        }
-                    }
+        if self.config.search_non_canonical_bumps {
+            for order in orderings {
+                let ordered: Vec<&[u8]> = order.iter().map(|&i| seeds[i]).collect();
+                if let Some(bump) = find_non_canonical_bump(&ordered, address, program_id) {
+                    return Some(SeedMatch { bump, order });
                 }
             }
         }
 
-        Ok(None)
+        None
     }
 
     /// Try sequential patterns (numbered accounts)
     fn try_sequential_patterns(&mut self, address: &Pubkey, program_id: &Pubkey) -> Result<Option<(PdaInfo, f64)>> {
-        let prefixes = ["account", "user", "pool", "vault", "market", "index", "item"];
-        
-        for prefix in &prefixes {
-            for i in 0..=50u64 {
-                // Try [prefix, number] as u64
-                let seeds = &[prefix.as_bytes(), &i.to_le_bytes()];
-                if let Some((derived_address, bump)) = Pubkey::try_find_program_address(seeds, program_id) {
-                    if derived_address == *address {
-                        let pda_info = PdaInfo {
-                            address: *address,
-                            program_id: *program_id,
-                            seeds: vec![
-                                SeedValue::String(prefix.to_string()),
-                                SeedValue::U64(i),
-                            ],
-                            bump,
-                            first_seen_slot: None,
-                            first_seen_transaction: None,
-                        };
-                        return Ok(Some((pda_info, 0.80)));
-                    }
-                }
+        let mut budget = CandidateBudget::new(self.config.candidate_limit);
+        for prefix in &self.config.sequential_prefixes {
+            for i in self.config.sequential_range.clone() {
+                // Try [prefix, number] as u64, then as u32 when it fits
+                for (width, confidence) in [(8, 0.80), (4, 0.78)] {
+                    for (number_bytes, number_seed) in self.config.integer_seeds(i, width) {
+                        let seeds = &[prefix.as_bytes(), number_bytes.as_slice()];
+                        let values = || vec![SeedValue::String(prefix.clone()), number_seed.clone()];
+                        if !self.tried.first_attempt(seeds) {
+                            continue;
+                        }
+                        if !budget.take() {
+                            return Ok(None);
+                        }
+                        let derived = Pubkey::try_find_program_address(seeds, program_id);
+                        let matched = matches!(derived, Some((derived_address, _)) if derived_address == *address);
+                        self.trace.record(PdaPattern::Sequential, address, program_id, values, matched);
 
-                // Try [prefix, number] as u32
-                let seeds = &[prefix.as_bytes(), &(i as u32).to_le_bytes()];
-                if let Some((derived_address, bump)) = Pubkey::try_find_program_address(seeds, program_id) {
-                    if derived_address == *address {
-                        let pda_info = PdaInfo {
-                            address: *address,
-                            program_id: *program_id,
-                            seeds: vec![
-                                SeedValue::String(prefix.to_string()),
-                                SeedValue::U32(i as u32),
-                            ],
-                            bump,
-                            first_seen_slot: None,
-                            first_seen_transaction: None,
-                        };
-                        return Ok(Some((pda_info, 0.78)));
+                        if let Some((derived_address, bump)) = derived {
+                            if derived_address == *address {
+                                let pda_info = PdaInfo {
+                                    address: *address,
+                                    program_id: *program_id,
+                                    seeds: values(),
+                                    bump,
+                                    first_seen_slot: None,
+                                    first_seen_transaction: None,
+                                };
+                                return Ok(Some((pda_info, confidence)));
+                            }
+                        }
                     }
                 }
             }
@@ -537,45 +1918,69 @@ impl PdaAnalyzer {
         Ok(None)
     }
 
-    /// Try complex multi-seed patterns
+    /// Try complex multi-seed patterns, recording how many combinations were derived
     fn try_complex_patterns(&mut self, address: &Pubkey, program_id: &Pubkey) -> Result<Option<(PdaInfo, f64)>> {
-        let strings = ["governance", "proposal", "vote", "realm", "council"];
-        let test_pubkeys = [
-            "11111111111111111111111111111112",
-            "DPiH3H3c7t47BMxqTxLsuPQpEC6Kne8GA9VXbxpnZxFE",
-            "7gXKKGLQs2HpzrPTtBP7kkQ3LktDShQPE8VV9PYW9RSh",
-        ];
+        let mut stats = ComplexSearchStats::default();
+        let found = self.search_complex_patterns(address, program_id, &mut stats);
+        if stats.truncated {
+            tracing::debug!("{} for {}", stats, address);
+        }
+        self.trace.complex = Some(stats);
+        found
+    }
+
+    fn search_complex_patterns(
+        &mut self,
+        address: &Pubkey,
+        program_id: &Pubkey,
+        stats: &mut ComplexSearchStats,
+    ) -> Result<Option<(PdaInfo, f64)>> {
+        let mut budget = CandidateBudget::new(self.config.candidate_limit);
+        let max_combinations = self.config.complex_max_combinations.map(|max| max as u64);
+        let strings = &self.config.complex_strings;
         
-        for s1 in &strings {
-            for pubkey_str in &test_pubkeys {
-                if let Ok(pubkey) = Pubkey::from_str(pubkey_str) {
-                    for s2 in &strings {
-                        if s1 != s2 {
-                            for &num in &[0u32, 1u32, 2u32] {
+        for s1 in strings {
+            for pubkey in &self.config.complex_pubkeys {
+                for s2 in strings {
+                    if s1 != s2 {
+                        for num in self.config.complex_numbers.clone() {
+                            for (num_bytes, num_seed) in self.config.integer_seeds(num as u64, 4) {
                                 // Try [string1, pubkey, string2, number]
                                 let seeds = &[
                                     s1.as_bytes(),
                                     pubkey.as_ref(),
                                     s2.as_bytes(),
-                                    &num.to_le_bytes(),
+                                    num_bytes.as_slice(),
+                                ];
+                                let values = || vec![
+                                    SeedValue::String(s1.clone()),
+                                    SeedValue::Pubkey(*pubkey),
+                                    SeedValue::String(s2.clone()),
+                                    num_seed.clone(),
                                 ];
-                                if let Some((derived_address, bump)) = Pubkey::try_find_program_address(seeds, program_id) {
-                                    if derived_address == *address {
-                                        let pda_info = PdaInfo {
-                                            address: *address,
-                                            program_id: *program_id,
-                                            seeds: vec![
-                                                SeedValue::String(s1.to_string()),
-                                                SeedValue::Pubkey(pubkey),
-                                                SeedValue::String(s2.to_string()),
-                                                SeedValue::U32(num),
-                                            ],
-                                            bump,
-                                            first_seen_slot: None,
-                                            first_seen_transaction: None,
-                                        };
-                                        return Ok(Some((pda_info, 0.75)));
-                                    }
+                                if !self.tried.first_search(seeds) {
+                                    continue;
+                                }
+                                if max_combinations.is_some_and(|max| stats.combinations >= max) {
+                                    stats.truncated = true;
+                                    return Ok(None);
+                                }
+                                if !budget.take() {
+                                    return Ok(None);
+                                }
+                                stats.combinations += 1;
+                                let found = self.match_seeds(seeds, address, program_id);
+                                self.trace.record(PdaPattern::Complex, address, program_id, || arrange_attempt(&found, values()), found.is_some());
+                                if let Some(found) = found {
+                                    let pda_info = PdaInfo {
+                                        address: *address,
+                                        program_id: *program_id,
+                                        seeds: found.arrange(values()),
+                                        bump: found.bump,
+                                        first_seen_slot: None,
+                                        first_seen_transaction: None,
+                                    };
+                                    return Ok(Some((pda_info, decay_with_depth(found.confidence(0.75), stats.combinations))));
                                 }
                             }
                         }
@@ -587,38 +1992,164 @@ impl PdaAnalyzer {
         Ok(None)
     }
 
-    /// Derive a PDA with specific seeds
-    pub fn derive_pda(&mut self, program_id: &Pubkey, seeds: &[SeedValue]) -> Result<PdaInfo> {
-        let seed_bytes: Vec<Vec<u8>> = seeds.iter().map(|s| s.as_bytes()).collect();
-        let cache_key = (program_id.clone(), seed_bytes.clone());
+    /// Try timestamp-seeded patterns: [prefix, ts], [prefix, pubkey, ts] and
+    /// [prefix, pubkey_a, pubkey_b, ts] over the configured window, using registered candidate pubkeys
+    fn try_timestamp_patterns(&mut self, address: &Pubkey, program_id: &Pubkey) -> Result<Option<(PdaInfo, f64)>> {
+        let mut budget = CandidateBudget::new(self.config.candidate_limit);
+        let Some(range) = self.config.timestamp_range.clone() else {
+            return Ok(None);
+        };
 
-        if let Some(cached_result) = self.cache.get(&cache_key) {
-            if let Some(pda_info) = cached_result {
-                return Ok(pda_info.clone());
+        // Pubkeys between the prefix and the timestamp: none, one, or an ordered pair (e.g. both traders)
+        let pubkeys = self.candidates.pubkeys();
+        let mut shapes: Vec<Vec<Pubkey>> = vec![Vec::new()];
+        shapes.extend(pubkeys.iter().map(|pubkey| vec![*pubkey]));
+        for a in pubkeys {
+            for b in pubkeys {
+                if a != b {
+                    shapes.push(vec![*a, *b]);
+                }
             }
         }
 
-        let seed_refs: Vec<&[u8]> = seed_bytes.iter().map(|s| s.as_slice()).collect();
-        
-        match Pubkey::try_find_program_address(&seed_refs, program_id) {
-            Some((address, bump)) => {
-                let pda_info = PdaInfo {
-                    address,
-                    program_id: *program_id,
-                    seeds: seeds.to_vec(),
-                    bump,
-                    first_seen_slot: None,
-                    first_seen_transaction: None,
-                };
-                
-                self.cache.insert(cache_key, Some(pda_info.clone()));
-                Ok(pda_info)
+        for timestamp in range.iter() {
+            for (ts_bytes, ts_seed) in self.config.integer_seeds(timestamp, 8) {
+                for prefix in &self.config.timestamp_prefixes {
+                    for shape in &shapes {
+                        let mut seeds: Vec<&[u8]> = vec![prefix.as_bytes()];
+                        seeds.extend(shape.iter().map(|pubkey| pubkey.as_ref()));
+                        seeds.push(&ts_bytes);
+
+                        let values = || {
+                            let mut values = vec![SeedValue::String(prefix.clone())];
+                            values.extend(shape.iter().copied().map(SeedValue::Pubkey));
+                            values.push(ts_seed.clone());
+                            values
+                        };
+
+                        if !self.tried.first_search(&seeds) {
+                            continue;
+                        }
+                        if !budget.take() {
+                            return Ok(None);
+                        }
+                        let found = self.match_seeds(&seeds, address, program_id);
+                        self.trace.record(PdaPattern::Timestamp, address, program_id, || arrange_attempt(&found, values()), found.is_some());
+                        if let Some(found) = found {
+                            let pda_info = PdaInfo {
+                                address: *address,
+                                program_id: *program_id,
+                                seeds: found.arrange(values()),
+                                bump: found.bump,
+                                first_seen_slot: None,
+                                first_seen_transaction: None,
+                            };
+                            return Ok(Some((pda_info, found.confidence(0.80))));
+                        }
+                    }
+                }
             }
-            None => {
-                self.cache.insert(cache_key, None);
-                Err(crate::PdaAnalyzerError::PdaDerivationFailed("Invalid seeds".to_string()))
+        }
+
+        Ok(None)
+    }
+
+    /// Try [prefix, n] and [prefix, pubkey, n] where n is an epoch (as `u64` or `u32`) or a
+    /// slot near the configured chain context, epochs first as they're far fewer
+    fn try_epoch_slot_patterns(&mut self, address: &Pubkey, program_id: &Pubkey) -> Result<Option<(PdaInfo, f64)>> {
+        let mut budget = CandidateBudget::new(self.config.candidate_limit);
+        let Some(context) = self.config.chain_context else {
+            return Ok(None);
+        };
+
+        let mut shapes: Vec<Option<Pubkey>> = vec![None];
+        shapes.extend(self.candidates.pubkeys().iter().copied().map(Some));
+
+        let numbers: Vec<(Vec<u8>, SeedValue)> = context
+            .epochs()
+            .flat_map(|epoch| {
+                let mut seeds = self.config.integer_seeds(epoch, 8);
+                seeds.extend(self.config.integer_seeds(epoch, 4));
+                seeds
+            })
+            .chain(context.slots().flat_map(|slot| self.config.integer_seeds(slot, 8)))
+            .collect();
+
+        for (number_bytes, number_seed) in &numbers {
+            for prefix in &self.config.epoch_slot_prefixes {
+                for pubkey in &shapes {
+                    let mut seeds: Vec<&[u8]> = vec![prefix.as_bytes()];
+                    seeds.extend(pubkey.as_ref().map(|pubkey| pubkey.as_ref()));
+                    seeds.push(number_bytes);
+
+                    let values = || {
+                        let mut values = vec![SeedValue::String(prefix.clone())];
+                        values.extend(pubkey.map(SeedValue::Pubkey));
+                        values.push(number_seed.clone());
+                        values
+                    };
+
+                    if !self.tried.first_search(&seeds) {
+                        continue;
+                    }
+                    if !budget.take() {
+                        return Ok(None);
+                    }
+                    let found = self.match_seeds(&seeds, address, program_id);
+                    self.trace.record(PdaPattern::EpochSlot, address, program_id, || arrange_attempt(&found, values()), found.is_some());
+                    if let Some(found) = found {
+                        let pda_info = PdaInfo {
+                            address: *address,
+                            program_id: *program_id,
+                            seeds: found.arrange(values()),
+                            bump: found.bump,
+                            first_seen_slot: None,
+                            first_seen_transaction: None,
+                        };
+                        return Ok(Some((pda_info, found.confidence(0.78))));
+                    }
+                }
+            }
+        }
+
+        Ok(None)
+    }
+
+    /// Derive a PDA with specific seeds
+    pub fn derive_pda(&mut self, program_id: &Pubkey, seeds: &[SeedValue]) -> Result<PdaInfo> {
+        self.deriver.derive_pda(program_id, seeds)
+    }
+
+    /// Derive every seed set, failing if any two derive the same address. Seeds are hashed
+    /// end to end, so e.g. `["ab", "c"]` and `["a", "bc"]` collide; the error names every
+    /// colliding pair by index and seeds.
+    pub fn derive_batch_checked(&mut self, program_id: &Pubkey, seed_sets: &[Vec<SeedValue>]) -> Result<Vec<PdaInfo>> {
+        let mut derived = Vec::with_capacity(seed_sets.len());
+        let mut first_by_address: HashMap<Pubkey, usize> = HashMap::new();
+        let mut collisions = Vec::new();
+
+        for (i, seeds) in seed_sets.iter().enumerate() {
+            let pda_info = self.derive_pda(program_id, seeds)?;
+            match first_by_address.get(&pda_info.address) {
+                Some(&first) => collisions.push(format!(
+                    "seed sets {} {} and {} {} both derive {}",
+                    first,
+                    format_seed_set(&seed_sets[first]),
+                    i,
+                    format_seed_set(seeds),
+                    pda_info.address
+                )),
+                None => {
+                    first_by_address.insert(pda_info.address, i);
+                }
             }
+            derived.push(pda_info);
+        }
+
+        if !collisions.is_empty() {
+            return Err(PdaAnalyzerError::SeedCollision(collisions.join("; ")));
         }
+        Ok(derived)
     }
 
     /// Get program name if known
@@ -626,26 +2157,184 @@ impl PdaAnalyzer {
         self.known_programs.get(program_id)
     }
 
+    /// Known programs whose name contains `query`, ignoring case, ordered by name.
+    /// An empty query lists every known program.
+    pub fn find_programs_by_name(&self, query: &str) -> Vec<(Pubkey, &str)> {
+        let query = query.trim().to_lowercase();
+        let mut programs: Vec<(Pubkey, &str)> = self
+            .known_programs
+            .iter()
+            .filter(|(_, name)| name.to_lowercase().contains(&query))
+            .map(|(program_id, name)| (*program_id, name.as_str()))
+            .collect();
+        programs.sort_by(|a, b| a.1.cmp(b.1).then_with(|| a.0.cmp(&b.0)));
+        programs
+    }
+
+    /// Everything that can be said about an address `analyze_pda` returned `None` for
+    pub fn describe_unrecognized(&self, address: &Pubkey, program_id: &Pubkey) -> UnrecognizedPda {
+        UnrecognizedPda {
+            address: *address,
+            program_id: *program_id,
+            program_name: self.get_program_name(program_id).cloned(),
+            address_kind: AddressKind::of(address),
+        }
+    }
+
+    /// After `analyze_pda` returned `None`, the candidate values most likely to recover the
+    /// seeds: the inputs a well-known program's layout needs, otherwise whichever candidates
+    /// the enabled families ran short of. Patterns seen more often so far are suggested first.
+    pub fn suggest_missing_candidates(&self, address: &Pubkey, program_id: &Pubkey) -> SuggestionReport {
+        let unrecognized = self.describe_unrecognized(address, program_id);
+        if unrecognized.is_inapplicable() {
+            return SuggestionReport { unrecognized, suggestions: Vec::new() };
+        }
+
+        let mut suggestions = Vec::new();
+        let mut suggest = |pattern: PdaPattern, kind: CandidateKind, reason: String| {
+            if self.config.is_enabled(&pattern) {
+                suggestions.push(CandidateSuggestion { kind, pattern, reason });
+            }
+        };
+
+        let program = program_id.to_string();
+        if program == "ATokenGPvbdGVxr1b2hvZbsiqW5xWH25efTNsLJA8knL" {
+            suggest(
+                PdaPattern::AssociatedTokenAccount,
+                CandidateKind::Wallet,
+                format!("associated token accounts are [wallet, token program, mint]; {} wallets are configured", self.config.ata_wallets.len()),
+            );
+            suggest(
+                PdaPattern::AssociatedTokenAccount,
+                CandidateKind::Mint,
+                format!("{} mints are configured for associated token accounts", self.config.ata_mints.len()),
+            );
+        } else if program == "SPoo1Ku8WFXoNDMHPsrGSTSG1Y47rzgn41SLUNakuHy" {
+            suggest(
+                PdaPattern::StakePoolValidator,
+                CandidateKind::VoteAccount,
+                format!(
+                    "stake pool accounts are derived from a validator's vote account and the pool; {} vote accounts and {} pools are registered",
+                    self.candidates.vote_accounts().len(),
+                    self.config.stake_pools.len()
+                ),
+            );
+        } else if program == "metaqbxxUerdq28cj1RbAWkYQm3ybzjb6a8bt518x1s" {
+            suggest(
+                PdaPattern::MetaplexMetadata,
+                CandidateKind::Mint,
+                format!("Metaplex accounts are derived from the NFT's mint; {} mints are configured", self.config.metaplex_mints.len()),
+            );
+        } else {
+            if self.candidates.program_string_count(program_id) == 0 {
+                suggest(
+                    PdaPattern::StringSingleton,
+                    CandidateKind::SeedString,
+                    "no seed strings are registered for this program, only the built-in vocabulary".to_string(),
+                );
+            }
+            if self.config.authority_pubkeys.is_empty() && self.candidates.pubkeys().is_empty() {
+                suggest(
+                    PdaPattern::StringPubkey,
+                    CandidateKind::Pubkey,
+                    "no candidate pubkeys are registered, so authority and user-keyed seeds can't be tried".to_string(),
+                );
+            }
+            suggest(
+                PdaPattern::Sequential,
+                CandidateKind::Number,
+                format!(
+                    "indices {}..={} were scanned after each sequential prefix",
+                    self.config.sequential_range.start(),
+                    self.config.sequential_range.end()
+                ),
+            );
+            if self.config.timestamp_range.is_none() {
+                suggest(
+                    PdaPattern::Timestamp,
+                    CandidateKind::TimeWindow,
+                    "timestamp-seeded accounts are only searched within a configured time window".to_string(),
+                );
+            }
+            if self.config.chain_context.is_none() {
+                suggest(
+                    PdaPattern::EpochSlot,
+                    CandidateKind::TimeWindow,
+                    "epoch- and slot-seeded accounts are only searched near a known slot, such as when the account was first seen".to_string(),
+                );
+            }
+        }
+
+        let seen = self.pattern_stats.snapshot();
+        suggestions.sort_by_key(|suggestion| std::cmp::Reverse(seen.get(&suggestion.pattern).copied().unwrap_or(0)));
+        SuggestionReport { unrecognized, suggestions }
+    }
+
+    /// Seed candidates learned at runtime
+    pub fn candidates(&self) -> &CandidateRegistry {
+        &self.candidates
+    }
+
+    pub fn candidates_mut(&mut self) -> &mut CandidateRegistry {
+        Arc::make_mut(&mut self.candidates)
+    }
+
+    /// Add harvested seed strings to a program's vocabulary, returning how many were new
+    pub fn register_seed_strings(&mut self, program_id: Pubkey, strings: Vec<String>) -> usize {
+        Arc::make_mut(&mut self.candidates).add_program_strings(program_id, strings)
+    }
+
+    /// Register a program so it is reported by name in analysis results
+    pub fn register_program(&mut self, program_id: Pubkey, name: String) {
+        Arc::make_mut(&mut self.known_programs).insert(program_id, name);
+    }
+
+    /// Built-in and registered programs reported by name
+    pub fn known_program_count(&self) -> usize {
+        self.known_programs.len()
+    }
+
     /// Update pattern statistics
-    fn update_pattern_stats(&mut self, pattern: &PdaPattern) {
-        *self.pattern_stats.entry(pattern.clone()).or_insert(0) += 1;
+    fn update_pattern_stats(&self, pattern: &PdaPattern) {
+        self.pattern_stats.record(pattern);
     }
 
     /// Get pattern statistics
-    pub fn get_pattern_stats(&self) -> &HashMap<PdaPattern, u32> {
+    pub fn get_pattern_stats(&self) -> HashMap<PdaPattern, u32> {
+        self.pattern_stats.snapshot()
+    }
+
+    /// The live counters, for reading stats without access to the analyzer itself
+    pub fn pattern_stats(&self) -> &PatternStats {
         &self.pattern_stats
     }
 
     /// Clear the cache
     pub fn clear_cache(&mut self) {
-        self.cache.clear();
+        self.deriver.clear_cache();
     }
 
     /// Get cache statistics
     pub fn cache_stats(&self) -> (usize, usize) {
-        let hits = self.cache.values().filter(|v| v.is_some()).count();
-        let total = self.cache.len();
-        (hits, total)
+        self.deriver.cache_stats()
+    }
+
+    /// Derive every known program's most common singleton PDAs (`state`, `config` and
+    /// `authority`) into the cache, so the first analysis of one is a lookup. Returns how many
+    /// were derived; `clear_cache` discards them.
+    pub fn warm_up(&mut self) -> usize {
+        let program_ids: Vec<Pubkey> = self.known_programs.keys().copied().collect();
+        let mut derived = 0;
+
+        for program_id in &program_ids {
+            for word in WARM_UP_SINGLETONS {
+                if self.deriver.derive_pda(program_id, &[SeedValue::String(word.to_string())]).is_ok() {
+                    derived += 1;
+                }
+            }
+        }
+
+        derived
     }
 
     /// Batch analyze multiple PDAs
@@ -658,4 +2347,203 @@ impl PdaAnalyzer {
         
         Ok(results)
     }
-}
\ No newline at end of file
+
+    /// `batch_analyze` across `config.batch_jobs` threads, each working with a `worker` of
+    /// this analyzer. Results keep the order of `addresses`; pattern stats are shared as usual.
+    pub fn batch_analyze_parallel(&self, addresses: &[(Pubkey, Pubkey)]) -> Result<Vec<Option<PdaAnalysisResult>>> {
+        self.batch_analyze_parallel_with(addresses, |_, _| {})
+    }
+
+    /// `batch_analyze_parallel`, calling `on_result` with each address and its result as soon
+    /// as it's analyzed, e.g. to drive a progress bar. Calls come from the workers in completion order.
+    pub fn batch_analyze_parallel_with<F>(&self, addresses: &[(Pubkey, Pubkey)], on_result: F) -> Result<Vec<Option<PdaAnalysisResult>>>
+    where
+        F: Fn(&Pubkey, &Option<PdaAnalysisResult>) + Sync,
+    {
+        self.batch_pool()?.install(|| {
+            addresses
+                .par_iter()
+                .map_init(
+                    || self.worker(),
+                    |analyzer, (address, program_id)| {
+                        let result = analyzer.analyze_pda(address, program_id)?;
+                        on_result(address, &result);
+                        Ok(result)
+                    },
+                )
+                .collect()
+        })
+    }
+
+    /// An analyzer for one batch worker: it shares this one's programs, config, candidates
+    /// and pattern stats, with its own derivation cache and per-analysis scratch state
+    fn worker(&self) -> Self {
+        Self {
+            deriver: PdaDeriver::new(),
+            known_programs: Arc::clone(&self.known_programs),
+            pattern_stats: self.pattern_stats.clone(),
+            config: Arc::clone(&self.config),
+            candidates: Arc::clone(&self.candidates),
+            batch_pool: Arc::clone(&self.batch_pool),
+            trace: AttemptTrace::default(),
+            tried: TriedSeeds::default(),
+            skipped_strings: HashSet::new(),
+            short_string_matches: 0,
+        }
+    }
+
+    /// The pool of `config.batch_jobs` worker threads, one per physical core by default.
+    /// It's started on first use and reused by later batches and by clones of this analyzer.
+    fn batch_pool(&self) -> Result<&rayon::ThreadPool> {
+        if let Some(pool) = self.batch_pool.get() {
+            return Ok(pool);
+        }
+        let jobs = self.config.batch_jobs.map_or_else(num_cpus::get_physical, |jobs| jobs.get());
+        let pool = rayon::ThreadPoolBuilder::new()
+            .num_threads(jobs)
+            .thread_name(|i| format!("pda-batch-{}", i))
+            .build()
+            .map_err(|e| PdaAnalyzerError::ConfigurationError(format!("Failed to start {} batch workers: {}", jobs, e)))?;
+        Ok(self.batch_pool.get_or_init(|| pool))
+    }
+
+    /// Recover `[string, market, user]` position accounts for a whole set of `targets` at
+    /// once. Every combination of the program's seed strings, `markets` and `users` is
+    /// derived a single time and looked up in the target set, so many targets cost no more
+    /// than one; the cross product is too large to scan per address. The scan runs on
+    /// `config.batch_jobs` threads and stops as soon as every target is solved.
+    pub fn solve_market_user_positions(
+        &mut self,
+        program_id: &Pubkey,
+        targets: &[Pubkey],
+        markets: &[Pubkey],
+        users: &[Pubkey],
+    ) -> Result<TargetSetOutcome> {
+        let start_time = Instant::now();
+        let strings = self.seed_strings(program_id);
+        // On-curve targets can't be PDAs, so they'd only keep the scan from stopping early
+        let wanted: HashSet<Pubkey> = targets.iter().copied().filter(|target| !target.is_on_curve()).collect();
+        let unsolved = AtomicUsize::new(wanted.len());
+        let combinations = AtomicU64::new(0);
+        let found: DashMap<Pubkey, (Vec<SeedValue>, u8)> = DashMap::new();
+
+        let rows: Vec<(&str, &Pubkey)> = strings
+            .iter()
+            .flat_map(|string| markets.iter().map(move |market| (string.as_str(), market)))
+            .collect();
+        self.batch_pool()?.install(|| {
+            rows.par_iter().for_each(|(string, market)| {
+                for user in users.iter().filter(|user| *user != *market) {
+                    if unsolved.load(Ordering::Relaxed) == 0 {
+                        return;
+                    }
+                    combinations.fetch_add(1, Ordering::Relaxed);
+                    let seeds = [string.as_bytes(), market.as_ref(), user.as_ref()];
+                    let Some((address, bump)) = Pubkey::try_find_program_address(&seeds, program_id) else {
+                        continue;
+                    };
+                    if wanted.contains(&address) && !found.contains_key(&address) {
+                        let values = vec![SeedValue::String(string.to_string()), SeedValue::Pubkey(**market), SeedValue::Pubkey(*user)];
+                        if found.insert(address, (values, bump)).is_none() {
+                            unsolved.fetch_sub(1, Ordering::Relaxed);
+                        }
+                    }
+                }
+            })
+        });
+
+        let mut outcome = TargetSetOutcome {
+            solved: Vec::new(),
+            unsolved: Vec::new(),
+            combinations: combinations.into_inner(),
+        };
+        for target in targets {
+            match found.get(target).map(|entry| entry.value().clone()) {
+                Some((seeds, bump)) => {
+                    let pda_info = PdaInfo {
+                        address: *target,
+                        program_id: *program_id,
+                        seeds,
+                        bump,
+                        first_seen_slot: None,
+                        first_seen_transaction: None,
+                    };
+                    outcome.solved.push(self.finish(pda_info, PdaPattern::StringMarketUser, 0.86, start_time));
+                }
+                None => outcome.unsolved.push(*target),
+            }
+        }
+
+        Ok(outcome)
+    }
+
+    /// Analyze an address against several candidate owner programs, best matches first as
+    /// ranked by `rank_results`
+    pub fn analyze_pda_across_programs(&mut self, address: &Pubkey, program_ids: &[Pubkey]) -> Result<Vec<PdaAnalysisResult>> {
+        let mut results = Vec::new();
+
+        for program_id in program_ids {
+            if let Some(result) = self.analyze_pda(address, program_id)? {
+                results.push(result);
+            }
+        }
+
+        rank_results(&mut results);
+        Ok(results)
+    }
+}
+
+/// `[seed, seed, ...]` using each seed's `Display`
+fn format_seed_set(seeds: &[SeedValue]) -> String {
+    let seeds: Vec<String> = seeds.iter().map(ToString::to_string).collect();
+    format!("[{}]", seeds.join(", "))
+}
+
+/// Seeds as a trace should show them: in matched order on success, as tried otherwise
+fn arrange_attempt(found: &Option<SeedMatch>, seeds: Vec<SeedValue>) -> Vec<SeedValue> {
+    match found {
+        Some(found) => found.arrange(seeds),
+        None => seeds,
+    }
+}
+
+/// Lower `confidence` for a match found after `tried` combinations: a hit deep in a
+/// brute-force scan is weaker evidence than one found on the first few attempts
+fn decay_with_depth(confidence: f64, tried: u64) -> f64 {
+    let decay = DEPTH_DECAY_PER_DECADE * (tried.max(1) as f64).log10();
+    (confidence - decay).max(MIN_DEPTH_CONFIDENCE.min(confidence))
+}
+
+/// All orderings of `len` seed indices, starting with the identity ordering
+fn seed_orderings(len: usize) -> Vec<Vec<usize>> {
+    if len == 0 {
+        return vec![Vec::new()];
+    }
+
+    let mut orderings = Vec::new();
+    for first in 0..len {
+        for rest in seed_orderings(len - 1) {
+            let mut order = vec![first];
+            order.extend(rest.into_iter().map(|i| if i >= first { i + 1 } else { i }));
+            orderings.push(order);
+        }
+    }
+    orderings
+}
+
+/// Try every bump with `create_program_address`, for programs that store explicit bumps
+fn find_non_canonical_bump(seeds: &[&[u8]], address: &Pubkey, program_id: &Pubkey) -> Option<u8> {
+    for bump in (0..=u8::MAX).rev() {
+        let bump_seed = [bump];
+        let mut seeds_with_bump = seeds.to_vec();
+        seeds_with_bump.push(&bump_seed);
+
+        if let Ok(derived_address) = Pubkey::create_program_address(&seeds_with_bump, program_id) {
+            if derived_address == *address {
+                return Some(bump);
+            }
+        }
+    }
+
+    None
+}
@@ -0,0 +1,18 @@
+//! Everything needed to analyze PDAs as a library, from one import:
+//!
+//! ```
+//! use solana_pda_analyzer_core::prelude::*;
+//!
+//! let program_id = Pubkey::new_unique();
+//! let (address, _) = Pubkey::find_program_address(&[b"config"], &program_id);
+//! let mut analyzer = PdaAnalyzer::builder().config(SearchConfig::default()).build();
+//! let result = analyzer.analyze_pda(&address, &program_id)?.expect("a single-string seed is recovered");
+//! assert_eq!(result.pda_info.seeds, vec![SeedValue::String("config".to_string())]);
+//! # Ok::<(), PdaAnalyzerError>(())
+//! ```
+
+pub use crate::{
+    parse_address, AnalysisOutcome, PdaAnalysisResult, PdaAnalyzer, PdaAnalyzerBuilder, PdaAnalyzerError, PdaDeriver,
+    PdaInfo, PdaPattern, Result, SearchConfig, SeedValue,
+};
+pub use solana_sdk::pubkey::Pubkey;
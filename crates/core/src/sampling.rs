@@ -0,0 +1,44 @@
+use rand::Rng;
+
+/// Uniform random sample of at most `capacity` items from a stream of unknown length
+/// (Vitter's Algorithm R), holding only the sample in memory
+#[derive(Debug, Clone)]
+pub struct Reservoir<T> {
+    capacity: usize,
+    seen: usize,
+    items: Vec<T>,
+}
+
+impl<T> Reservoir<T> {
+    pub fn new(capacity: usize) -> Self {
+        Self {
+            capacity,
+            seen: 0,
+            items: Vec::with_capacity(capacity),
+        }
+    }
+
+    /// Consider one more item; every item offered so far stays in the sample with
+    /// equal probability
+    pub fn offer<R: Rng + ?Sized>(&mut self, item: T, rng: &mut R) {
+        self.seen += 1;
+        if self.items.len() < self.capacity {
+            self.items.push(item);
+            return;
+        }
+
+        let slot = rng.gen_range(0..self.seen);
+        if slot < self.capacity {
+            self.items[slot] = item;
+        }
+    }
+
+    /// Items offered so far, sampled or not
+    pub fn seen(&self) -> usize {
+        self.seen
+    }
+
+    pub fn into_items(self) -> Vec<T> {
+        self.items
+    }
+}
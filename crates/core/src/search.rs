@@ -0,0 +1,209 @@
+use crate::{PdaAnalyzerError, Result, SeedValue};
+use serde::{Deserialize, Serialize};
+use solana_sdk::pubkey::{Pubkey, MAX_SEED_LEN};
+use std::path::Path;
+
+/// Combinations tried between progress callbacks when no interval is configured
+const DEFAULT_CHECKPOINT_INTERVAL: u64 = 10_000;
+
+/// Brute-force search over ordered combinations of dictionary seeds.
+///
+/// Combinations are enumerated deterministically (all single seeds, then all
+/// pairs, ...) so a combination index saved in a checkpoint always refers to
+/// the same seeds when the search is restarted with the same vocabulary.
+#[derive(Debug, Clone)]
+pub struct DictionarySearch {
+    vocabulary: Vec<SeedValue>,
+    seed_bytes: Vec<Vec<u8>>,
+    max_seeds: usize,
+    checkpoint_interval: u64,
+}
+
+/// Where a search stopped, persisted so it can be resumed after a crash
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SearchCheckpoint {
+    pub address: String,
+    pub program_id: String,
+    /// Identifies the vocabulary and depth the index was computed against
+    pub fingerprint: u64,
+    pub next_index: u64,
+    pub total: u64,
+}
+
+#[derive(Debug, Clone)]
+pub enum SearchOutcome {
+    Found { seeds: Vec<SeedValue>, bump: u8, index: u64 },
+    Exhausted { tried: u64 },
+}
+
+impl DictionarySearch {
+    /// Build a search over `vocabulary`, combining up to `max_seeds` entries.
+    /// Duplicates and entries too long to be a seed are dropped, keeping the first occurrence.
+    pub fn new(vocabulary: Vec<SeedValue>, max_seeds: usize) -> Self {
+        let mut kept = Vec::new();
+        let mut seed_bytes: Vec<Vec<u8>> = Vec::new();
+
+        for seed in vocabulary {
+            let bytes = seed.as_bytes();
+            if bytes.len() > MAX_SEED_LEN || seed_bytes.contains(&bytes) {
+                continue;
+            }
+            seed_bytes.push(bytes);
+            kept.push(seed);
+        }
+
+        Self {
+            vocabulary: kept,
+            seed_bytes,
+            max_seeds: max_seeds.max(1),
+            checkpoint_interval: DEFAULT_CHECKPOINT_INTERVAL,
+        }
+    }
+
+    /// How many combinations are tried between progress callbacks
+    pub fn with_checkpoint_interval(mut self, interval: u64) -> Self {
+        self.checkpoint_interval = interval.max(1);
+        self
+    }
+
+    pub fn vocabulary(&self) -> &[SeedValue] {
+        &self.vocabulary
+    }
+
+    /// Number of combinations in the search space, saturating at `u64::MAX`
+    pub fn total_combinations(&self) -> u64 {
+        let size = self.vocabulary.len() as u64;
+        let mut total: u64 = 0;
+        let mut level: u64 = 1;
+        for _ in 0..self.max_seeds {
+            level = level.saturating_mul(size);
+            total = total.saturating_add(level);
+        }
+        total
+    }
+
+    /// The seed indices for combination `index`, or `None` past the end of the search
+    fn combination_indices(&self, mut index: u64) -> Option<Vec<usize>> {
+        let size = self.vocabulary.len() as u64;
+        if size == 0 {
+            return None;
+        }
+
+        let mut level: u64 = 1;
+        for length in 1..=self.max_seeds {
+            level = level.saturating_mul(size);
+            if index < level {
+                // Mixed-radix digits, most significant first, so order is lexicographic
+                let mut digits = vec![0; length];
+                for digit in digits.iter_mut().rev() {
+                    *digit = (index % size) as usize;
+                    index /= size;
+                }
+                return Some(digits);
+            }
+            index -= level;
+        }
+
+        None
+    }
+
+    /// The seeds tried at combination `index`
+    pub fn combination(&self, index: u64) -> Option<Vec<SeedValue>> {
+        self.combination_indices(index)
+            .map(|digits| digits.into_iter().map(|i| self.vocabulary[i].clone()).collect())
+    }
+
+    /// Stable hash of the vocabulary and depth (FNV-1a), used to reject mismatched checkpoints
+    pub fn fingerprint(&self) -> u64 {
+        let mut hash: u64 = 0xcbf29ce484222325;
+        let mut feed = |bytes: &[u8]| {
+            for byte in bytes {
+                hash ^= *byte as u64;
+                hash = hash.wrapping_mul(0x100000001b3);
+            }
+        };
+
+        feed(&(self.max_seeds as u64).to_le_bytes());
+        for bytes in &self.seed_bytes {
+            feed(&(bytes.len() as u64).to_le_bytes());
+            feed(bytes);
+        }
+        hash
+    }
+
+    /// Checkpoint describing a search of `address` that will continue at `next_index`
+    pub fn checkpoint(&self, address: &Pubkey, program_id: &Pubkey, next_index: u64) -> SearchCheckpoint {
+        SearchCheckpoint {
+            address: address.to_string(),
+            program_id: program_id.to_string(),
+            fingerprint: self.fingerprint(),
+            next_index,
+            total: self.total_combinations(),
+        }
+    }
+
+    /// Index to resume from, after checking the checkpoint belongs to this search
+    pub fn resume_index(&self, checkpoint: &SearchCheckpoint, address: &Pubkey, program_id: &Pubkey) -> Result<u64> {
+        if checkpoint.address != address.to_string() || checkpoint.program_id != program_id.to_string() {
+            return Err(PdaAnalyzerError::ConfigurationError(format!(
+                "Checkpoint is for {} under {}, not {} under {}",
+                checkpoint.address, checkpoint.program_id, address, program_id
+            )));
+        }
+        if checkpoint.fingerprint != self.fingerprint() {
+            return Err(PdaAnalyzerError::ConfigurationError(
+                "Checkpoint was written for a different vocabulary or seed depth".to_string(),
+            ));
+        }
+        Ok(checkpoint.next_index)
+    }
+
+    /// Try combinations starting at `start`, calling `on_progress` with the next
+    /// untried index every checkpoint interval. An error from the callback aborts the search.
+    pub fn run<F>(&self, address: &Pubkey, program_id: &Pubkey, start: u64, mut on_progress: F) -> Result<SearchOutcome>
+    where
+        F: FnMut(u64) -> Result<()>,
+    {
+        let mut index = start;
+
+        while let Some(digits) = self.combination_indices(index) {
+            let seeds: Vec<&[u8]> = digits.iter().map(|&i| self.seed_bytes[i].as_slice()).collect();
+            if let Some((derived, bump)) = Pubkey::try_find_program_address(&seeds, program_id) {
+                if derived == *address {
+                    let seeds = digits.into_iter().map(|i| self.vocabulary[i].clone()).collect();
+                    return Ok(SearchOutcome::Found { seeds, bump, index });
+                }
+            }
+
+            index += 1;
+            if (index - start).is_multiple_of(self.checkpoint_interval) {
+                on_progress(index)?;
+            }
+        }
+
+        on_progress(index)?;
+        Ok(SearchOutcome::Exhausted { tried: index - start })
+    }
+}
+
+impl SearchCheckpoint {
+    pub fn load(path: &Path) -> Result<Self> {
+        let contents = std::fs::read_to_string(path).map_err(|e| {
+            PdaAnalyzerError::ConfigurationError(format!("Failed to read checkpoint {}: {}", path.display(), e))
+        })?;
+        serde_json::from_str(&contents).map_err(|e| PdaAnalyzerError::SerializationError(e.to_string()))
+    }
+
+    /// Write the checkpoint atomically so a crash mid-write never leaves a truncated file
+    pub fn save(&self, path: &Path) -> Result<()> {
+        let contents = serde_json::to_string_pretty(self)
+            .map_err(|e| PdaAnalyzerError::SerializationError(e.to_string()))?;
+
+        let tmp_path = path.with_extension("tmp");
+        std::fs::write(&tmp_path, contents)
+            .and_then(|_| std::fs::rename(&tmp_path, path))
+            .map_err(|e| {
+                PdaAnalyzerError::ConfigurationError(format!("Failed to write checkpoint {}: {}", path.display(), e))
+            })
+    }
+}
@@ -3,8 +3,12 @@ use solana_sdk::pubkey::Pubkey;
 use solana_sdk::transaction::Transaction;
 use solana_sdk::instruction::Instruction;
 use chrono::{DateTime, Utc};
+use sha2::{Digest, Sha256};
 use std::collections::HashMap;
 
+/// Account data and lamports before and after a transaction
+pub type AccountChanges = (Option<Vec<u8>>, Option<Vec<u8>>, Option<u64>, Option<u64>);
+
 #[derive(Debug, Clone)]
 pub struct TransactionAnalyzer {
     known_programs: HashMap<Pubkey, String>,
@@ -32,6 +36,7 @@ impl TransactionAnalyzer {
         }
     }
 
+    #[allow(clippy::too_many_arguments)]
     pub fn analyze_transaction(
         &self,
         signature: &str,
@@ -102,7 +107,7 @@ impl TransactionAnalyzer {
         })
     }
 
-    fn detect_pda(&self, address: &Pubkey, program_id: &Pubkey) -> Option<PdaInfo> {
+    pub fn detect_pda(&self, address: &Pubkey, program_id: &Pubkey) -> Option<PdaInfo> {
         // Simple heuristic: check if the address is on-curve
         // Real PDAs are off-curve points
         if address.is_on_curve() {
@@ -121,7 +126,7 @@ impl TransactionAnalyzer {
         })
     }
 
-    fn determine_interaction_type(
+    pub fn determine_interaction_type(
         &self,
         _instruction_index: usize,
         _account_index: usize,
@@ -151,12 +156,12 @@ impl TransactionAnalyzer {
         }
     }
 
-    fn get_account_changes(
+    pub fn get_account_changes(
         &self,
         account_pubkey: &Pubkey,
         pre_states: &[AccountState],
         post_states: &[AccountState],
-    ) -> (Option<Vec<u8>>, Option<Vec<u8>>, Option<u64>, Option<u64>) {
+    ) -> AccountChanges {
         let pre_state = pre_states.iter().find(|s| s.pubkey == *account_pubkey);
         let post_state = post_states.iter().find(|s| s.pubkey == *account_pubkey);
         
@@ -168,6 +173,12 @@ impl TransactionAnalyzer {
         (data_before, data_after, lamports_before, lamports_after)
     }
 
+    /// Hex SHA-256 of an account's data, stored as a PDA's `data_hash` so later
+    /// snapshots of the account can be compared without keeping its data
+    pub fn hash_account_data(data: &[u8]) -> String {
+        hex::encode(Sha256::digest(data))
+    }
+
     pub fn add_known_program(&mut self, program_id: Pubkey, name: String) {
         self.known_programs.insert(program_id, name);
     }
@@ -175,6 +186,10 @@ impl TransactionAnalyzer {
     pub fn get_program_name(&self, program_id: &Pubkey) -> Option<&String> {
         self.known_programs.get(program_id)
     }
+
+    pub fn known_programs(&self) -> &HashMap<Pubkey, String> {
+        &self.known_programs
+    }
 }
 
 impl Default for TransactionAnalyzer {
@@ -230,12 +245,11 @@ mod tests {
     use super::*;
     use solana_sdk::signature::Keypair;
     use solana_sdk::signer::Signer;
-    use solana_sdk::system_instruction;
     
     #[test]
     fn test_transaction_analyzer_creation() {
         let analyzer = TransactionAnalyzer::new();
-        assert!(analyzer.known_programs.len() > 0);
+        assert!(!analyzer.known_programs.is_empty());
     }
     
     #[test]
@@ -1,7 +1,18 @@
 use serde::{Deserialize, Serialize};
 use solana_sdk::pubkey::Pubkey;
 use chrono::{DateTime, Utc};
+use std::collections::BTreeMap;
 use uuid::Uuid;
+use crate::{PatternCategory, PdaAnalyzerError, PdaPattern, Result};
+
+/// Longest string seed still counted as a typical short ASCII name
+const SHORT_STRING_MAX_LEN: usize = 16;
+
+/// Length typicality of an ASCII string seed longer than `SHORT_STRING_MAX_LEN`
+const LONG_STRING_TYPICALITY: f64 = 0.7;
+
+/// Length typicality of raw bytes or non-ASCII strings not sized like an integer or key
+const UNUSUAL_LENGTH_TYPICALITY: f64 = 0.4;
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct PdaInfo {
@@ -13,34 +24,163 @@ pub struct PdaInfo {
     pub first_seen_transaction: Option<String>,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+/// Seeds compare and hash by their encoded bytes, as derivation sees them, so `String("a")`,
+/// `Bytes([0x61])` and `U8(0x61)` are the same seed; `seed_type` tells them apart.
+///
+/// In JSON a seed is `{"type": "Pubkey", "value": "<base58>"}`, with bytes and hashes as byte
+/// arrays. The older `{"Pubkey": [..32 bytes]}` form is still read, as stored rows use it.
+#[derive(Debug, Clone, Serialize)]
+#[serde(tag = "type", content = "value")]
 pub enum SeedValue {
     String(String),
     Bytes(Vec<u8>),
-    Pubkey(Pubkey),
+    /// A SHA-256 digest, as programs use to fit names and other long inputs into one seed
+    Hash([u8; 32]),
+    Pubkey(#[serde(serialize_with = "serialize_base58")] Pubkey),
     U64(u64),
     U32(u32),
     U16(u16),
     U8(u8),
 }
 
+impl PdaInfo {
+    /// How believable the recovered seeds are as something a program would choose, in `[0, 1]`.
+    /// Pubkeys, typed integers and identifier-like words score 1.0; raw bytes and other strings
+    /// are scored by byte entropy, so all-zero or repeated bytes rank lowest. The set scores the
+    /// average of its best seed and its mean, so one weak seed beside a pubkey costs little.
+    pub fn plausibility_score(&self) -> f64 {
+        let scores: Vec<f64> = self.seeds.iter().map(SeedValue::plausibility).collect();
+        if scores.is_empty() {
+            return 0.5;
+        }
+
+        let best = scores.iter().cloned().fold(0.0, f64::max);
+        let mean = scores.iter().sum::<f64>() / scores.len() as f64;
+        (best + mean) / 2.0
+    }
+
+    /// Whether the last seed is the bump itself: a `U8` with which the recovered seeds derive the
+    /// address via `create_program_address`, no bump appended. Programs that do this pass a
+    /// stored bump rather than finding one, so `bump` repeats that seed and may not be canonical.
+    pub fn bump_is_last_seed(&self) -> bool {
+        let Some(SeedValue::U8(_)) = self.seeds.last() else {
+            return false;
+        };
+
+        let bytes: Vec<Vec<u8>> = self.seeds.iter().map(SeedValue::as_bytes).collect();
+        let seeds: Vec<&[u8]> = bytes.iter().map(Vec::as_slice).collect();
+        Pubkey::create_program_address(&seeds, &self.program_id) == Ok(self.address)
+    }
+
+    /// How conventional the recovered seeds' byte lengths are, in `[0, 1]`, as the mean over
+    /// seeds. Weights: typed integers, pubkeys, hashes, ASCII strings of up to 16 bytes and raw
+    /// bytes 1, 2, 4, 8 or 32 long score 1.0; longer ASCII strings 0.7; anything else 0.4.
+    /// A match leaning on odd lengths is more likely a coincidence.
+    pub fn seed_length_typicality(&self) -> f64 {
+        if self.seeds.is_empty() {
+            return 1.0;
+        }
+
+        self.seeds.iter().map(SeedValue::length_typicality).sum::<f64>() / self.seeds.len() as f64
+    }
+}
+
 impl SeedValue {
+    /// Plausibility of this seed alone; see `PdaInfo::plausibility_score`
+    fn plausibility(&self) -> f64 {
+        match self {
+            SeedValue::Pubkey(_)
+            | SeedValue::Hash(_)
+            | SeedValue::U64(_)
+            | SeedValue::U32(_)
+            | SeedValue::U16(_)
+            | SeedValue::U8(_) => 1.0,
+            SeedValue::String(s) if is_vocabulary_word(s) => 1.0,
+            SeedValue::String(s) => 0.3 + 0.7 * normalized_entropy(s.as_bytes()),
+            SeedValue::Bytes(b) => 0.2 + 0.8 * normalized_entropy(b),
+        }
+    }
+
+    /// Length typicality of this seed alone; see `PdaInfo::seed_length_typicality`
+    fn length_typicality(&self) -> f64 {
+        match self {
+            SeedValue::Pubkey(_)
+            | SeedValue::Hash(_)
+            | SeedValue::U64(_)
+            | SeedValue::U32(_)
+            | SeedValue::U16(_)
+            | SeedValue::U8(_) => 1.0,
+            SeedValue::String(s) if s.is_ascii() && s.len() <= SHORT_STRING_MAX_LEN => 1.0,
+            SeedValue::String(s) if s.is_ascii() => LONG_STRING_TYPICALITY,
+            SeedValue::String(_) | SeedValue::Bytes(_) => match self.as_bytes().len() {
+                1 | 2 | 4 | 8 | 32 => 1.0,
+                _ => UNUSUAL_LENGTH_TYPICALITY,
+            },
+        }
+    }
+
+    /// Seed for raw bytes read from outside, e.g. a dictionary file: a string when the bytes are
+    /// valid UTF-8 without control characters, otherwise `Bytes` so the seed keeps its exact bytes
+    pub fn from_seed_bytes(bytes: &[u8]) -> Self {
+        match std::str::from_utf8(bytes) {
+            Ok(s) if !s.chars().any(char::is_control) => SeedValue::String(s.to_string()),
+            _ => SeedValue::Bytes(bytes.to_vec()),
+        }
+    }
+
+    /// The canonical form of this seed. Typed seeds are kept as the caller wrote them, since
+    /// their type is information the bytes don't carry. Raw `Bytes` take the typed form their
+    /// bytes fit, so `Bytes([5, 0, 0, 0])` and `U32(5)` are stored alike. First match wins:
+    /// UTF-8 without control characters is a `String`, 32 bytes a `Pubkey`, 8, 4, 2 and 1 bytes
+    /// a `U64`, `U32`, `U16` and `U8`, and anything else stays `Bytes`.
+    pub fn normalized(&self) -> SeedValue {
+        let SeedValue::Bytes(bytes) = self else {
+            return self.clone();
+        };
+        match SeedValue::from_seed_bytes(bytes) {
+            SeedValue::String(s) => SeedValue::String(s),
+            _ => match bytes.len() {
+                32 => SeedValue::Pubkey(Pubkey::try_from(bytes.as_slice()).expect("32 bytes")),
+                8 => SeedValue::U64(u64::from_le_bytes(bytes[..].try_into().expect("8 bytes"))),
+                4 => SeedValue::U32(u32::from_le_bytes(bytes[..].try_into().expect("4 bytes"))),
+                2 => SeedValue::U16(u16::from_le_bytes(bytes[..].try_into().expect("2 bytes"))),
+                1 => SeedValue::U8(bytes[0]),
+                _ => SeedValue::Bytes(bytes.clone()),
+            },
+        }
+    }
+
+    /// `seeds` in canonical form, as stored; see `normalized`
+    pub fn normalize_all(seeds: &[SeedValue]) -> Vec<SeedValue> {
+        seeds.iter().map(SeedValue::normalized).collect()
+    }
+
     pub fn as_bytes(&self) -> Vec<u8> {
+        self.with_bytes(<[u8]>::to_vec)
+    }
+
+    /// Call `f` with the encoded seed, without allocating
+    fn with_bytes<R>(&self, f: impl FnOnce(&[u8]) -> R) -> R {
         match self {
-            SeedValue::String(s) => s.as_bytes().to_vec(),
-            SeedValue::Bytes(b) => b.clone(),
-            SeedValue::Pubkey(pk) => pk.as_ref().to_vec(),
-            SeedValue::U64(n) => n.to_le_bytes().to_vec(),
-            SeedValue::U32(n) => n.to_le_bytes().to_vec(),
-            SeedValue::U16(n) => n.to_le_bytes().to_vec(),
-            SeedValue::U8(n) => vec![*n],
+            SeedValue::String(s) => f(s.as_bytes()),
+            SeedValue::Bytes(b) => f(b),
+            SeedValue::Hash(h) => f(h),
+            SeedValue::Pubkey(pk) => f(pk.as_ref()),
+            SeedValue::U64(n) => f(&n.to_le_bytes()),
+            SeedValue::U32(n) => f(&n.to_le_bytes()),
+            SeedValue::U16(n) => f(&n.to_le_bytes()),
+            SeedValue::U8(n) => f(&[*n]),
         }
     }
     
+    /// Every name `seed_type()` can return; templates must use these exact strings
+    pub const SEED_TYPES: [&'static str; 8] = ["string", "bytes", "hash", "pubkey", "u64", "u32", "u16", "u8"];
+
     pub fn seed_type(&self) -> &'static str {
         match self {
             SeedValue::String(_) => "string",
             SeedValue::Bytes(_) => "bytes",
+            SeedValue::Hash(_) => "hash",
             SeedValue::Pubkey(_) => "pubkey",
             SeedValue::U64(_) => "u64",
             SeedValue::U32(_) => "u32",
@@ -48,6 +188,171 @@ impl SeedValue {
             SeedValue::U8(_) => "u8",
         }
     }
+
+    /// Seed holding the SHA-256 digest of `input`
+    pub fn sha256(input: &[u8]) -> Self {
+        use sha2::{Digest, Sha256};
+        SeedValue::Hash(Sha256::digest(input).into())
+    }
+
+    /// Map a seed type name in any casing (e.g. the serde tag `Pubkey`) to its canonical form
+    pub fn normalize_seed_type(seed_type: &str) -> Option<&'static str> {
+        let seed_type = seed_type.trim();
+        Self::SEED_TYPES
+            .iter()
+            .copied()
+            .find(|known| known.eq_ignore_ascii_case(seed_type))
+    }
+
+    /// Parse a `type:value` seed spec such as `str:vault`, `pubkey:<base58 or hex>`, `u64:7`, `bytes:<hex>` or `hash:<hex>`
+    pub fn parse_spec(spec: &str) -> Result<Self> {
+        let (seed_type, value) = spec
+            .split_once(':')
+            .ok_or_else(|| PdaAnalyzerError::InvalidSeedData(format!("Expected `type:value`, got '{}'", spec)))?;
+        let invalid = |e: &dyn std::fmt::Display| {
+            PdaAnalyzerError::InvalidSeedData(format!("Invalid {} seed '{}': {}", seed_type, value, e))
+        };
+
+        let seed_type = match seed_type.trim().to_ascii_lowercase().as_str() {
+            "str" => "string",
+            other => Self::normalize_seed_type(other)
+                .ok_or_else(|| PdaAnalyzerError::InvalidSeedData(format!("Unknown seed type '{}'", seed_type)))?,
+        };
+
+        Ok(match seed_type {
+            "string" => SeedValue::String(value.to_string()),
+            "bytes" => SeedValue::Bytes(hex::decode(value.trim_start_matches("0x")).map_err(|e| invalid(&e))?),
+            "hash" => {
+                let digest = hex::decode(value.trim_start_matches("0x")).map_err(|e| invalid(&e))?;
+                SeedValue::Hash(digest.try_into().map_err(|d: Vec<u8>| invalid(&format!("expected 32 bytes, got {}", d.len())))?)
+            }
+            "pubkey" => SeedValue::Pubkey(crate::parse_address(value).map_err(|e| invalid(&e))?),
+            "u64" => SeedValue::U64(value.parse().map_err(|e| invalid(&e))?),
+            "u32" => SeedValue::U32(value.parse().map_err(|e| invalid(&e))?),
+            "u16" => SeedValue::U16(value.parse().map_err(|e| invalid(&e))?),
+            _ => SeedValue::U8(value.parse().map_err(|e| invalid(&e))?),
+        })
+    }
+
+    /// Parse whitespace-separated seed specs, e.g. `str:metadata pubkey:<base58> u64:5`
+    pub fn parse_specs(specs: &str) -> Result<Vec<Self>> {
+        specs.split_whitespace().map(Self::parse_spec).collect()
+    }
+
+    /// The `type:value` spec `parse_spec` reads back as this seed. Strings holding whitespace or
+    /// control characters are written as `bytes:` so the spec survives splitting on whitespace;
+    /// they derive the same address.
+    pub fn to_spec(&self) -> String {
+        match self {
+            SeedValue::String(s) if !s.chars().any(|c| c.is_whitespace() || c.is_control()) => format!("str:{}", s),
+            SeedValue::String(s) => format!("bytes:{}", hex::encode(s)),
+            SeedValue::Bytes(b) => format!("bytes:{}", hex::encode(b)),
+            SeedValue::Hash(h) => format!("hash:{}", hex::encode(h)),
+            SeedValue::Pubkey(pubkey) => format!("pubkey:{}", pubkey),
+            SeedValue::U64(n) => format!("u64:{}", n),
+            SeedValue::U32(n) => format!("u32:{}", n),
+            SeedValue::U16(n) => format!("u16:{}", n),
+            SeedValue::U8(n) => format!("u8:{}", n),
+        }
+    }
+}
+
+fn serialize_base58<S: serde::Serializer>(pubkey: &Pubkey, serializer: S) -> std::result::Result<S::Ok, S::Error> {
+    serializer.collect_str(pubkey)
+}
+
+impl<'de> Deserialize<'de> for SeedValue {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> std::result::Result<Self, D::Error> {
+        let json = serde_json::Value::deserialize(deserializer)?;
+        SeedValue::from_json(json).map_err(serde::de::Error::custom)
+    }
+}
+
+impl SeedValue {
+    /// Read a seed in either JSON form, checking the value fits its type
+    fn from_json(json: serde_json::Value) -> std::result::Result<Self, String> {
+        let serde_json::Value::Object(mut fields) = json else {
+            return Err(format!("expected a seed object like {{\"type\": \"String\", \"value\": \"vault\"}}, got {}", json));
+        };
+        let (tag, value) = match (fields.remove("type"), fields.remove("value")) {
+            (Some(serde_json::Value::String(tag)), Some(value)) => (tag, value),
+            (Some(_), _) => return Err("seed `type` must be a string with a `value` beside it".to_string()),
+            // Externally tagged, e.g. `{"String": "vault"}`
+            (None, None) if fields.len() == 1 => fields.into_iter().next().expect("one field"),
+            _ => return Err("seed objects need `type` and `value` fields".to_string()),
+        };
+
+        let seed_type = Self::normalize_seed_type(&tag).ok_or_else(|| {
+            format!("unknown seed type '{}', expected one of {}", tag, Self::SEED_TYPES.join(", "))
+        })?;
+        let invalid = |e: &dyn std::fmt::Display| format!("invalid {} seed: {}", seed_type, e);
+
+        Ok(match seed_type {
+            "string" => SeedValue::String(serde_json::from_value(value).map_err(|e| invalid(&e))?),
+            "bytes" => SeedValue::Bytes(serde_json::from_value(value).map_err(|e| invalid(&e))?),
+            "hash" => {
+                let digest: Vec<u8> = serde_json::from_value(value).map_err(|e| invalid(&e))?;
+                SeedValue::Hash(digest.try_into().map_err(|d: Vec<u8>| invalid(&format!("expected 32 bytes, got {}", d.len())))?)
+            }
+            "pubkey" => SeedValue::Pubkey(match value {
+                serde_json::Value::String(address) => address.parse().map_err(|e| invalid(&e))?,
+                bytes => serde_json::from_value(bytes).map_err(|e| invalid(&e))?,
+            }),
+            "u64" => SeedValue::U64(serde_json::from_value(value).map_err(|e| invalid(&e))?),
+            "u32" => SeedValue::U32(serde_json::from_value(value).map_err(|e| invalid(&e))?),
+            "u16" => SeedValue::U16(serde_json::from_value(value).map_err(|e| invalid(&e))?),
+            _ => SeedValue::U8(serde_json::from_value(value).map_err(|e| invalid(&e))?),
+        })
+    }
+}
+
+impl PartialEq for SeedValue {
+    fn eq(&self, other: &Self) -> bool {
+        self.with_bytes(|bytes| other.with_bytes(|other| bytes == other))
+    }
+}
+
+impl Eq for SeedValue {}
+
+impl std::hash::Hash for SeedValue {
+    fn hash<H: std::hash::Hasher>(&self, state: &mut H) {
+        self.with_bytes(|bytes| bytes.hash(state))
+    }
+}
+
+/// Identity of a seed set as the derivation cache sees it: the same for sets that encode to
+/// the same bytes seed by seed, whatever their types, and the same across runs and builds.
+/// Seed boundaries count, so `["ab", "c"]` and `["a", "bc"]` differ even though they derive
+/// the same address.
+pub fn seeds_fingerprint(seeds: &[SeedValue]) -> u64 {
+    use sha2::{Digest, Sha256};
+
+    let mut hasher = Sha256::new();
+    for seed in seeds {
+        seed.with_bytes(|bytes| {
+            hasher.update((bytes.len() as u64).to_le_bytes());
+            hasher.update(bytes);
+        });
+    }
+    let digest = hasher.finalize();
+    u64::from_le_bytes(digest[..8].try_into().expect("SHA-256 digests are 32 bytes"))
+}
+
+/// The seed's value alone, safe to print: strings are quoted with control and other
+/// non-printable characters escaped, bytes and hashes are `0x`-prefixed hex
+impl std::fmt::Display for SeedValue {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            SeedValue::String(s) => write!(f, "\"{}\"", s.escape_debug()),
+            SeedValue::Bytes(b) => write!(f, "0x{}", hex::encode(b)),
+            SeedValue::Hash(h) => write!(f, "0x{}", hex::encode(h)),
+            SeedValue::Pubkey(pk) => write!(f, "{}", pk),
+            SeedValue::U64(n) => write!(f, "{}", n),
+            SeedValue::U32(n) => write!(f, "{}", n),
+            SeedValue::U16(n) => write!(f, "{}", n),
+            SeedValue::U8(n) => write!(f, "{}", n),
+        }
+    }
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -67,6 +372,13 @@ pub struct SeedTemplate {
     pub is_variable: bool,
 }
 
+impl SeedTemplate {
+    /// Whether `seed` fits this template slot, regardless of how the template spells its type
+    pub fn accepts(&self, seed: &SeedValue) -> bool {
+        SeedValue::normalize_seed_type(&self.seed_type) == Some(seed.seed_type())
+    }
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct TransactionAnalysis {
     pub signature: String,
@@ -78,6 +390,122 @@ pub struct TransactionAnalysis {
     pub discovered_pdas: Vec<PdaInfo>,
 }
 
+impl TransactionAnalysis {
+    /// Start building the analysis of a successful transaction
+    pub fn builder(signature: impl Into<String>, slot: u64) -> TransactionAnalysisBuilder {
+        TransactionAnalysisBuilder {
+            analysis: TransactionAnalysis {
+                signature: signature.into(),
+                slot,
+                block_time: None,
+                success: true,
+                error_message: None,
+                pda_interactions: Vec::new(),
+                discovered_pdas: Vec::new(),
+            },
+        }
+    }
+
+    /// Hash of `address`'s data as this transaction left it, from the last interaction that
+    /// recorded its post-transaction data; see `TransactionAnalyzer::hash_account_data`
+    pub fn data_hash(&self, address: &Pubkey) -> Option<String> {
+        self.pda_interactions
+            .iter()
+            .rev()
+            .filter(|interaction| interaction.pda_address == *address)
+            .find_map(|interaction| interaction.data_after.as_deref())
+            .map(crate::TransactionAnalyzer::hash_account_data)
+    }
+
+    /// Check this analysis against the transaction it claims to describe: the signature must be
+    /// the transaction's and verify, every interaction's account must be passed to the instruction it names
+    /// (writable, unless it is only read), and every discovered PDA must be among the account keys.
+    /// Rejects interactions reported by clients that never happened on chain.
+    pub fn validate(&self, tx: &solana_sdk::transaction::Transaction) -> Result<()> {
+        let invalid = |message: String| Err(PdaAnalyzerError::TransactionParsingError(message));
+        let message = &tx.message;
+
+        match tx.signatures.first() {
+            Some(signature) if signature.to_string() == self.signature => {}
+            _ => return invalid(format!("Signature {} is not this transaction's", self.signature)),
+        }
+        if tx.verify().is_err() {
+            return invalid(format!("Transaction {} is not validly signed", self.signature));
+        }
+
+        for interaction in &self.pda_interactions {
+            let Some(instruction) = message.instructions.get(interaction.instruction_index as usize) else {
+                return invalid(format!(
+                    "Instruction {} does not exist; the transaction has {}",
+                    interaction.instruction_index,
+                    message.instructions.len()
+                ));
+            };
+
+            let account_index = instruction
+                .accounts
+                .iter()
+                .map(|&index| index as usize)
+                .find(|&index| message.account_keys.get(index) == Some(&interaction.pda_address));
+            let Some(account_index) = account_index else {
+                return invalid(format!(
+                    "{} is not an account of instruction {}",
+                    interaction.pda_address, interaction.instruction_index
+                ));
+            };
+
+            if interaction.interaction_type != InteractionType::Read && !message.is_writable(account_index) {
+                return invalid(format!(
+                    "{} is read-only in the transaction but recorded as {:?}",
+                    interaction.pda_address, interaction.interaction_type
+                ));
+            }
+        }
+
+        if let Some(pda) = self.discovered_pdas.iter().find(|pda| !message.account_keys.contains(&pda.address)) {
+            return invalid(format!("Discovered PDA {} is not in the transaction's account keys", pda.address));
+        }
+
+        Ok(())
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct TransactionAnalysisBuilder {
+    analysis: TransactionAnalysis,
+}
+
+impl TransactionAnalysisBuilder {
+    pub fn block_time(mut self, block_time: DateTime<Utc>) -> Self {
+        self.analysis.block_time = Some(block_time);
+        self
+    }
+
+    /// Mark the transaction as failed with the given error
+    pub fn failed(mut self, error_message: impl Into<String>) -> Self {
+        self.analysis.success = false;
+        self.analysis.error_message = Some(error_message.into());
+        self
+    }
+
+    pub fn interaction(mut self, interaction: PdaInteraction) -> Self {
+        self.analysis.pda_interactions.push(interaction);
+        self
+    }
+
+    /// Add a discovered PDA, ignoring addresses already recorded
+    pub fn discovered_pda(mut self, pda: PdaInfo) -> Self {
+        if !self.analysis.discovered_pdas.iter().any(|known| known.address == pda.address) {
+            self.analysis.discovered_pdas.push(pda);
+        }
+        self
+    }
+
+    pub fn build(self) -> TransactionAnalysis {
+        self.analysis
+    }
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct PdaInteraction {
     pub pda_address: Pubkey,
@@ -89,7 +517,22 @@ pub struct PdaInteraction {
     pub lamports_after: Option<u64>,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+impl PdaInteraction {
+    /// An interaction with no recorded account data or lamport changes
+    pub fn new(pda_address: Pubkey, instruction_index: u32, interaction_type: InteractionType) -> Self {
+        Self {
+            pda_address,
+            instruction_index,
+            interaction_type,
+            data_before: None,
+            data_after: None,
+            lamports_before: None,
+            lamports_after: None,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 pub enum InteractionType {
     Read,
     Write,
@@ -111,7 +554,119 @@ pub struct ProgramInfo {
 pub struct SeedDerivationAttempt {
     pub pda_address: Pubkey,
     pub program_id: Pubkey,
+    /// Pattern the seed set was generated for
+    pub pattern: PdaPattern,
     pub attempted_seeds: Vec<SeedValue>,
     pub success: bool,
     pub attempted_at: DateTime<Utc>,
-}
\ No newline at end of file
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct PatternStat {
+    pub pattern: String,
+    pub count: i64,
+    pub percentage: f64,
+}
+
+/// Pattern counts ranked most frequent first, with each pattern's share of the total
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct PatternHistogram {
+    pub total: i64,
+    pub patterns: Vec<RankedPatternStat>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct RankedPatternStat {
+    /// 1-based; patterns with equal counts share a rank
+    pub rank: usize,
+    #[serde(flatten)]
+    pub stat: PatternStat,
+    /// `PdaPattern::description` for built-in pattern names
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+    /// `PdaPattern::category` for built-in pattern names
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub category: Option<PatternCategory>,
+}
+
+impl PatternHistogram {
+    pub fn from_counts(counts: impl IntoIterator<Item = (String, i64)>) -> Self {
+        let mut counts: Vec<(String, i64)> = counts.into_iter().collect();
+        // Ties are ordered by name so the response is stable
+        counts.sort_by(|a, b| b.1.cmp(&a.1).then_with(|| a.0.cmp(&b.0)));
+        let total: i64 = counts.iter().map(|(_, count)| count).sum();
+
+        let mut patterns: Vec<RankedPatternStat> = Vec::with_capacity(counts.len());
+        for (i, (pattern, count)) in counts.into_iter().enumerate() {
+            let rank = match patterns.last() {
+                Some(previous) if previous.stat.count == count => previous.rank,
+                _ => i + 1,
+            };
+            let percentage = if total > 0 { count as f64 / total as f64 * 100.0 } else { 0.0 };
+            let known = PdaPattern::ALL.iter().find(|known| known.as_str() == pattern);
+            patterns.push(RankedPatternStat {
+                rank,
+                stat: PatternStat { pattern, count, percentage },
+                description: known.map(|known| known.description().to_string()),
+                category: known.map(PdaPattern::category),
+            });
+        }
+
+        Self { total, patterns }
+    }
+
+    /// Counts summed per category, largest first; names that aren't built-in patterns, such
+    /// as `UNRECOGNIZED` or custom patterns, are left out
+    pub fn by_category(&self) -> Vec<(PatternCategory, i64)> {
+        let mut totals: BTreeMap<PatternCategory, i64> = BTreeMap::new();
+        for entry in &self.patterns {
+            if let Some(category) = entry.category {
+                *totals.entry(category).or_insert(0) += entry.stat.count;
+            }
+        }
+
+        let mut totals: Vec<(PatternCategory, i64)> = totals.into_iter().collect();
+        totals.sort_by(|a, b| b.1.cmp(&a.1).then_with(|| a.0.cmp(&b.0)));
+        totals
+    }
+
+    /// Each pattern's count scaled from this histogram's total up to `population`, for
+    /// histograms built from a random sample
+    pub fn extrapolate(&self, population: i64) -> Vec<(String, i64)> {
+        self.patterns
+            .iter()
+            .map(|entry| (entry.stat.pattern.clone(), (entry.stat.percentage / 100.0 * population as f64).round() as i64))
+            .collect()
+    }
+}
+
+/// Lowercase identifier of at least three characters, such as `vault` or `escrow_v2`
+fn is_vocabulary_word(s: &str) -> bool {
+    s.len() >= 3
+        && s.starts_with(|c: char| c.is_ascii_lowercase())
+        && s.chars().all(|c| c.is_ascii_lowercase() || c.is_ascii_digit() || c == '_' || c == '-')
+}
+
+/// Shannon entropy of `bytes` relative to the most their length allows, in `[0, 1]`
+fn normalized_entropy(bytes: &[u8]) -> f64 {
+    if bytes.len() < 2 {
+        return 0.0;
+    }
+
+    let mut counts = [0usize; 256];
+    for byte in bytes {
+        counts[*byte as usize] += 1;
+    }
+
+    let len = bytes.len() as f64;
+    let entropy: f64 = counts
+        .iter()
+        .filter(|count| **count > 0)
+        .map(|count| {
+            let p = *count as f64 / len;
+            -p * p.log2()
+        })
+        .sum();
+
+    entropy / len.min(256.0).log2()
+}
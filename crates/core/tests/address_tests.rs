@@ -0,0 +1,39 @@
+use solana_pda_analyzer_core::{parse_address, AddressFormat, PdaAnalyzerError, SeedValue};
+use solana_sdk::pubkey::Pubkey;
+
+#[test]
+fn test_parse_address_accepts_base58_hex_and_byte_arrays() {
+    let key = Pubkey::new_unique();
+    let hex = hex::encode(key.to_bytes());
+    let bytes: Vec<String> = key.to_bytes().iter().map(u8::to_string).collect();
+
+    assert_eq!(parse_address(&key.to_string()).unwrap(), key);
+    assert_eq!(parse_address(&format!("0x{}", hex)).unwrap(), key);
+    assert_eq!(parse_address(&format!(" 0X{} ", hex.to_uppercase())).unwrap(), key);
+    assert_eq!(parse_address(&hex).unwrap(), key);
+    assert_eq!(parse_address(&format!("[{}]", bytes.join(", "))).unwrap(), key);
+
+    assert_eq!(AddressFormat::detect(&key.to_string()), AddressFormat::Base58);
+    assert_eq!(AddressFormat::detect(&hex), AddressFormat::Hex);
+    assert_eq!(AddressFormat::detect("[1, 2]"), AddressFormat::ByteArray);
+
+    let seed = SeedValue::parse_spec(&format!("pubkey:0x{}", hex)).unwrap();
+    assert_eq!(seed, SeedValue::Pubkey(key));
+}
+
+#[test]
+fn test_parse_address_explains_bad_input() {
+    let message = |input: &str| match parse_address(input) {
+        Err(PdaAnalyzerError::InvalidPublicKey(message)) => message,
+        other => panic!("expected an invalid key error for {}, got {:?}", input, other),
+    };
+
+    assert!(message("0xabcd").contains("2 bytes of hex, expected 32"));
+    assert!(message(&format!("0x{}", "zz".repeat(32))).contains("not valid hex"));
+    assert!(message("[1, 2, 3]").contains("3 bytes, expected 32"));
+    assert!(message("[1, 2, 300]").contains("'300'"));
+    assert!(message("[1, 2").contains("closing ']'"));
+    // Hex digits that are too short for hex and don't decode as base58 either
+    assert!(message(&"ab".repeat(20)).contains("hex addresses are 64 digits, got 40"));
+    assert!(message("not-a-key").contains("not a base58 pubkey"));
+}
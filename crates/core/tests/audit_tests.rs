@@ -0,0 +1,85 @@
+use solana_pda_analyzer_core::{audit_bumps, check_bump, BumpCheck, PdaInfo, SeedValue};
+use solana_sdk::pubkey::Pubkey;
+
+fn pda(address: Pubkey, program_id: Pubkey, seeds: Vec<SeedValue>, bump: u8) -> PdaInfo {
+    PdaInfo {
+        address,
+        program_id,
+        seeds,
+        bump,
+        first_seen_slot: None,
+        first_seen_transaction: None,
+    }
+}
+
+/// The highest bump below the canonical one that still derives an off-curve address
+fn non_canonical(seeds: &[&[u8]], program_id: &Pubkey) -> (Pubkey, u8, u8) {
+    let (_, canonical_bump) = Pubkey::find_program_address(seeds, program_id);
+    (0..canonical_bump)
+        .rev()
+        .find_map(|bump| {
+            let bump_seed = [bump];
+            let mut seeds = seeds.to_vec();
+            seeds.push(&bump_seed);
+            Pubkey::create_program_address(&seeds, program_id).ok().map(|address| (address, bump, canonical_bump))
+        })
+        .unwrap()
+}
+
+#[test]
+fn test_check_bump_classifies_derivations() {
+    let program_id = Pubkey::new_unique();
+    let seeds = vec![SeedValue::String("vault".to_string())];
+
+    let (address, canonical_bump) = Pubkey::find_program_address(&[b"vault"], &program_id);
+    assert_eq!(
+        check_bump(&pda(address, program_id, seeds.clone(), canonical_bump)),
+        BumpCheck::Canonical { bump: canonical_bump }
+    );
+
+    let (address, bump, canonical_bump) = non_canonical(&[b"vault"], &program_id);
+    assert_eq!(
+        check_bump(&pda(address, program_id, seeds.clone(), bump)),
+        BumpCheck::NonCanonical { bump, canonical_bump }
+    );
+    // A wrong stored bump doesn't hide which bump derives the address
+    assert_eq!(
+        check_bump(&pda(address, program_id, seeds.clone(), 0)),
+        BumpCheck::NonCanonical { bump, canonical_bump }
+    );
+
+    // The bump passed as the last seed is the bump under audit
+    let mut with_bump = seeds.clone();
+    with_bump.push(SeedValue::U8(bump));
+    assert_eq!(
+        check_bump(&pda(address, program_id, with_bump, bump)),
+        BumpCheck::NonCanonical { bump, canonical_bump }
+    );
+
+    assert_eq!(check_bump(&pda(Pubkey::new_unique(), program_id, seeds, 255)), BumpCheck::Underivable);
+}
+
+#[test]
+fn test_audit_bumps_summarizes_program() {
+    let program_id = Pubkey::new_unique();
+    let (canonical_address, canonical_bump) = Pubkey::find_program_address(&[b"config"], &program_id);
+    let (address, bump, _) = non_canonical(&[b"vault"], &program_id);
+
+    let pdas = vec![
+        pda(canonical_address, program_id, vec![SeedValue::String("config".to_string())], canonical_bump),
+        pda(address, program_id, vec![SeedValue::String("vault".to_string())], bump),
+        pda(Pubkey::new_unique(), program_id, Vec::new(), 255),
+        // Another program's PDA is not part of the audit
+        pda(canonical_address, Pubkey::new_unique(), vec![SeedValue::String("config".to_string())], canonical_bump),
+    ];
+
+    let audit = audit_bumps(&program_id, &pdas);
+    assert_eq!(audit.total, 3);
+    assert_eq!(audit.canonical, 1);
+    assert_eq!(audit.underivable, 1);
+    assert_eq!(audit.non_canonical.len(), 1);
+    assert_eq!(audit.non_canonical[0].address, address);
+    assert_eq!(audit.non_canonical_share(), 0.5);
+
+    assert_eq!(audit_bumps(&Pubkey::new_unique(), &pdas).non_canonical_share(), 0.0);
+}
@@ -0,0 +1,79 @@
+use solana_pda_analyzer_core::{extract_seed_strings, CandidateRegistry, PdaAnalyzer, SeedSource, SeedValue};
+use solana_sdk::pubkey::Pubkey;
+
+#[test]
+fn test_extract_seed_strings_from_account_data() {
+    let mut data = vec![0u8, 7, 255];
+    data.extend_from_slice(b"vault");
+    data.extend_from_slice(&[0, 1, 2]);
+    data.extend_from_slice(b"ab");
+    data.push(0);
+    data.extend_from_slice(b"12345");
+
+    assert_eq!(extract_seed_strings(&data), vec!["vault".to_string()]);
+}
+
+#[test]
+fn test_extract_seed_strings_from_logs() {
+    let strings = extract_seed_strings(b"Program log: Instruction: InitializeVault");
+    assert_eq!(strings, vec!["InitializeVault".to_string(), "initializevault".to_string()]);
+}
+
+#[test]
+fn test_registry_program_strings() {
+    let program_id = Pubkey::new_unique();
+    let other = Pubkey::new_unique();
+    let mut registry = CandidateRegistry::new();
+
+    registry.add_string("global".to_string());
+    let added = registry.add_program_strings(program_id, vec!["escrow".to_string(), "escrow".to_string()]);
+
+    assert_eq!(added, 1);
+    assert_eq!(registry.strings_for(&program_id).collect::<Vec<_>>(), vec!["global", "escrow"]);
+    assert_eq!(registry.strings_for(&other).collect::<Vec<_>>(), vec!["global"]);
+}
+
+#[test]
+fn test_analyzer_uses_learned_seed_strings() {
+    let program_id = Pubkey::new_unique();
+    let (address, _bump) = Pubkey::find_program_address(&[b"escrow_v2"], &program_id);
+
+    let mut analyzer = PdaAnalyzer::new();
+    assert!(analyzer.analyze_pda(&address, &program_id).unwrap().is_none());
+
+    let added = analyzer.register_seed_strings(program_id, extract_seed_strings(b"Program log: seeds escrow_v2"));
+    assert!(added > 0);
+
+    let result = analyzer.analyze_pda(&address, &program_id).unwrap().unwrap();
+    assert!(matches!(&result.pda_info.seeds[0], SeedValue::String(s) if s == "escrow_v2"));
+    assert_eq!(result.seed_sources, vec![SeedSource::ProgramVocabulary]);
+    assert!(result.confidence > 0.80);
+}
+
+#[test]
+fn test_program_vocabulary_outweighs_global_registry() {
+    let program_id = Pubkey::new_unique();
+    let other = Pubkey::new_unique();
+    let mut registry = CandidateRegistry::new();
+    registry.add_string("escrow_v2".to_string());
+    registry.add_program_strings(program_id, vec!["escrow_v2".to_string()]);
+
+    let seed = SeedValue::String("escrow_v2".to_string());
+    assert_eq!(registry.source_of(&program_id, &seed), Some(SeedSource::ProgramVocabulary));
+    assert_eq!(registry.source_of(&other, &seed), Some(SeedSource::GlobalRegistry));
+    assert_eq!(registry.source_of(&other, &SeedValue::String("vault".to_string())), None);
+
+    let mut analyzer = PdaAnalyzer::builder().candidate_registry(registry).build();
+    let (address, _) = Pubkey::find_program_address(&[b"escrow_v2"], &program_id);
+    let program_match = analyzer.analyze_pda(&address, &program_id).unwrap().unwrap();
+    let (address, _) = Pubkey::find_program_address(&[b"escrow_v2"], &other);
+    let global_match = analyzer.analyze_pda(&address, &other).unwrap().unwrap();
+
+    assert_eq!(global_match.seed_sources, vec![SeedSource::GlobalRegistry]);
+    assert_eq!(global_match.confidence, 0.80);
+    assert!(program_match.confidence > global_match.confidence);
+
+    // Built-in dictionary seeds are recorded as such
+    let (address, _) = Pubkey::find_program_address(&[b"config"], &other);
+    assert_eq!(analyzer.analyze_pda(&address, &other).unwrap().unwrap().seed_sources, vec![SeedSource::BuiltIn]);
+}
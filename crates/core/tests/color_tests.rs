@@ -0,0 +1,26 @@
+use solana_pda_analyzer_core::ColorChoice;
+
+fn args(args: &[&str]) -> Vec<String> {
+    args.iter().map(|arg| arg.to_string()).collect()
+}
+
+#[test]
+fn test_color_choice_from_args() {
+    assert_eq!(ColorChoice::from_args(args(&[])).unwrap(), ColorChoice::Auto);
+    assert_eq!(ColorChoice::from_args(args(&["--color", "never"])).unwrap(), ColorChoice::Never);
+    assert_eq!(ColorChoice::from_args(args(&["--verbose", "--color=Always"])).unwrap(), ColorChoice::Always);
+    assert!(ColorChoice::from_args(args(&["--color"])).is_err());
+    assert!(ColorChoice::from_args(args(&["--color=rainbow"])).is_err());
+}
+
+#[test]
+fn test_color_choice_enabled() {
+    // Auto colors only terminals, and NO_COLOR turns it off
+    assert!(ColorChoice::Auto.enabled(false, true));
+    assert!(!ColorChoice::Auto.enabled(false, false));
+    assert!(!ColorChoice::Auto.enabled(true, true));
+
+    // Explicit choices win over both
+    assert!(ColorChoice::Always.enabled(true, false));
+    assert!(!ColorChoice::Never.enabled(false, true));
+}
@@ -0,0 +1,126 @@
+use solana_pda_analyzer_core::{AnchorIdl, IdlSeed, IdlSeedType, MatchKind, PdaAnalyzer, PdaInfo, SeedMismatch, SeedValue, IDL_CONFIRMED_CONFIDENCE};
+use solana_sdk::pubkey::Pubkey;
+
+/// A current-layout IDL declaring `vault = [b"vault", authority, index: u64]`
+fn current_idl(program_id: &Pubkey) -> String {
+    format!(
+        r#"{{
+            "address": "{}",
+            "instructions": [{{
+                "name": "open_vault",
+                "args": [{{"name": "index", "type": "u64"}}],
+                "accounts": [
+                    {{"name": "authority", "signer": true}},
+                    {{"name": "vault", "writable": true, "pda": {{"seeds": [
+                        {{"kind": "const", "value": [118, 97, 117, 108, 116]}},
+                        {{"kind": "account", "path": "authority"}},
+                        {{"kind": "arg", "path": "index"}}
+                    ]}}}}
+                ]
+            }}]
+        }}"#,
+        program_id
+    )
+}
+
+fn vault(program_id: Pubkey, seeds: Vec<SeedValue>) -> PdaInfo {
+    PdaInfo {
+        address: Pubkey::new_unique(),
+        program_id,
+        seeds,
+        bump: 255,
+        first_seen_slot: None,
+        first_seen_transaction: None,
+    }
+}
+
+#[test]
+fn test_parse_current_and_legacy_layouts() {
+    let program_id = Pubkey::new_unique();
+    let idl = AnchorIdl::parse(&current_idl(&program_id)).unwrap();
+    assert_eq!(idl.program_id, Some(program_id));
+    assert_eq!(idl.pda_accounts.len(), 1);
+    assert_eq!(
+        idl.pda_accounts[0].seeds,
+        vec![
+            IdlSeed::Const(b"vault".to_vec()),
+            IdlSeed::Account { path: "authority".to_string(), seed_type: Some(IdlSeedType::Pubkey) },
+            IdlSeed::Arg { path: "index".to_string(), seed_type: Some(IdlSeedType::Integer(8)) },
+        ]
+    );
+
+    // Legacy IDLs type every seed inline and nest account groups
+    let legacy = r#"{
+        "instructions": [{
+            "name": "openVault",
+            "args": [],
+            "accounts": [{"name": "group", "accounts": [
+                {"name": "vault", "isMut": true, "isSigner": false, "pda": {"seeds": [
+                    {"kind": "const", "type": "string", "value": "vault"},
+                    {"kind": "account", "type": "publicKey", "path": "authority"},
+                    {"kind": "arg", "type": "u32", "path": "index"}
+                ]}}
+            ]}]
+        }]
+    }"#;
+    let idl = AnchorIdl::parse(legacy).unwrap();
+    assert_eq!(idl.program_id, None);
+    assert_eq!(idl.pda_accounts[0].name, "vault");
+    assert_eq!(idl.pda_accounts[0].seeds[0], IdlSeed::Const(b"vault".to_vec()));
+    assert_eq!(
+        idl.pda_accounts[0].seeds[2],
+        IdlSeed::Arg { path: "index".to_string(), seed_type: Some(IdlSeedType::Integer(4)) }
+    );
+}
+
+#[test]
+fn test_validate_reports_mismatches() {
+    let program_id = Pubkey::new_unique();
+    let idl = AnchorIdl::parse(&current_idl(&program_id)).unwrap();
+    let authority = Pubkey::new_unique();
+
+    let matching = vault(program_id, vec![SeedValue::String("vault".to_string()), SeedValue::Pubkey(authority), SeedValue::U64(3)]);
+    let validation = idl.validate(&matching, "vault").unwrap();
+    assert!(validation.agrees());
+    assert_eq!(validation.instruction, "open_vault");
+
+    let wrong = vault(program_id, vec![SeedValue::String("pool".to_string()), SeedValue::Pubkey(authority), SeedValue::U32(3)]);
+    let mismatches = idl.validate(&wrong, "vault").unwrap().mismatches;
+    assert!(matches!(&mismatches[0], SeedMismatch::Constant { index: 0, .. }));
+    assert!(matches!(&mismatches[1], SeedMismatch::Type { index: 2, found: "u32", .. }));
+    assert_eq!(mismatches[1].to_string(), "seed 3 (index) should be a 64-bit integer, found a u32");
+
+    let short = vault(Pubkey::new_unique(), vec![SeedValue::String("vault".to_string())]);
+    let mismatches = idl.validate(&short, "vault").unwrap().mismatches;
+    assert!(matches!(mismatches[0], SeedMismatch::Program { .. }));
+    assert!(matches!(mismatches[1], SeedMismatch::SeedCount { declared: 3, recovered: 1 }));
+
+    assert!(idl.validate(&matching, "treasury").is_none());
+}
+
+#[test]
+fn test_confirm_raises_confidence_of_agreeing_recovery() {
+    let program_id = Pubkey::new_unique();
+    let (address, _) = Pubkey::find_program_address(&[b"pool", &7u64.to_le_bytes()], &program_id);
+    let idl = AnchorIdl::parse(
+        r#"{"instructions": [{"name": "create_pool", "args": [{"name": "index", "type": "u64"}], "accounts": [
+            {"name": "pool", "pda": {"seeds": [{"kind": "const", "value": [112, 111, 111, 108]}, {"kind": "arg", "path": "index"}]}}
+        ]}]}"#,
+    )
+    .unwrap();
+
+    let mut result = PdaAnalyzer::new().analyze_pda(&address, &program_id).unwrap().unwrap();
+    assert!(result.confidence < IDL_CONFIRMED_CONFIDENCE);
+
+    let validation = idl.confirm(&mut result).unwrap();
+    assert_eq!(validation.account, "pool");
+    assert_eq!(result.confidence, IDL_CONFIRMED_CONFIDENCE);
+    assert_eq!(result.match_kind, MatchKind::Structural);
+
+    // A recovery the IDL disagrees with keeps its heuristic confidence
+    let other = current_idl(&program_id);
+    let mut result = PdaAnalyzer::new().analyze_pda(&address, &program_id).unwrap().unwrap();
+    let confidence = result.confidence;
+    assert!(AnchorIdl::parse(&other).unwrap().confirm(&mut result).is_none());
+    assert_eq!(result.confidence, confidence);
+}
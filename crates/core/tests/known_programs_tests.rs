@@ -0,0 +1,59 @@
+use solana_pda_analyzer_core::*;
+use solana_sdk::pubkey::Pubkey;
+use std::str::FromStr;
+
+#[test]
+fn test_builtin_known_programs() {
+    let programs = builtin_known_programs();
+    assert!(programs.iter().any(|(id, name)| *id == solana_sdk::system_program::id() && name == "System Program"));
+
+    let analyzer = PdaAnalyzer::new();
+    assert_eq!(analyzer.known_program_count(), programs.len());
+    let ata = Pubkey::from_str("ATokenGPvbdGVxr1b2hvZbsiqW5xWH25efTNsLJA8knL").unwrap();
+    assert_eq!(analyzer.get_program_name(&ata).map(String::as_str), Some("SPL Associated Token Account"));
+}
+
+#[test]
+fn test_user_programs_merge_over_builtins() {
+    let custom = Pubkey::new_unique();
+    let json = format!(
+        r#"[
+            {{"program_id": "{}", "name": "My Program"}},
+            {{"program_id": "TokenkegQfeZyiNwAJbNbGKPFXCWuBvf9Ss623VQ5DA", "name": "Token Program"}}
+        ]"#,
+        custom
+    );
+    let programs = parse_known_programs(&json).unwrap();
+
+    let analyzer = PdaAnalyzer::builder().programs(programs).build();
+    assert_eq!(analyzer.known_program_count(), builtin_known_programs().len() + 1);
+    assert_eq!(analyzer.get_program_name(&custom).map(String::as_str), Some("My Program"));
+    let token = Pubkey::from_str("TokenkegQfeZyiNwAJbNbGKPFXCWuBvf9Ss623VQ5DA").unwrap();
+    assert_eq!(analyzer.get_program_name(&token).map(String::as_str), Some("Token Program"));
+}
+
+#[test]
+fn test_find_programs_by_name() {
+    let analyzer = PdaAnalyzer::new();
+    let marinade = analyzer.find_programs_by_name("marinade");
+    assert_eq!(marinade.len(), 1);
+    assert_eq!(marinade[0].1, "Marinade Finance");
+    assert_eq!(analyzer.get_program_name(&marinade[0].0).map(String::as_str), Some("Marinade Finance"));
+
+    let metaplex: Vec<&str> = analyzer.find_programs_by_name("METAPLEX").into_iter().map(|(_, name)| name).collect();
+    assert_eq!(metaplex, ["Metaplex Auction House", "Metaplex Candy Machine", "Metaplex Token Metadata"]);
+
+    assert!(analyzer.find_programs_by_name("no such protocol").is_empty());
+    assert_eq!(analyzer.find_programs_by_name("").len(), analyzer.known_program_count());
+}
+
+#[test]
+fn test_known_programs_validation() {
+    let error = parse_known_programs(r#"[{"program_id": "not-a-key", "name": "Broken"}]"#).unwrap_err();
+    assert!(error.to_string().contains("entry 0"), "{}", error);
+
+    let blank = format!(r#"[{{"program_id": "{}", "name": " "}}]"#, Pubkey::new_unique());
+    assert!(parse_known_programs(&blank).is_err());
+    assert!(parse_known_programs("{}").is_err());
+    assert!(load_known_programs(std::path::Path::new("/nonexistent/known_programs.json")).is_err());
+}
@@ -1,5 +1,6 @@
-use solana_pda_analyzer_core::{PdaDeriver, PdaAnalyzer, SeedValue, PdaInfo};
+use solana_pda_analyzer_core::{rank_results, AddressKind, AnalysisOutcome, CandidateKind, CandidateRegistry, ChainContext, ComplexSearchStats, Endianness, FailureReason, FailureSummary, MatchKind, PatternCategory, PdaAnalyzerError, PdaDeriver, PdaAnalyzer, PatternStats, PdaPattern, SearchConfig, SeedValue, TimestampRange};
 use solana_sdk::pubkey::Pubkey;
+use std::str::FromStr;
 
 #[test]
 fn test_pda_derivation_with_string_seed() {
@@ -13,7 +14,7 @@ fn test_pda_derivation_with_string_seed() {
     let pda_info = result.unwrap();
     assert_eq!(pda_info.program_id, program_id);
     assert_eq!(pda_info.seeds, seeds);
-    assert!(pda_info.bump < 256);
+    assert_eq!(Pubkey::create_program_address(&[b"metadata", &[pda_info.bump]], &program_id).unwrap(), pda_info.address);
 }
 
 #[test]
@@ -194,15 +195,45 @@ fn test_large_seed_data() {
     let mut deriver = PdaDeriver::new();
     let program_id = Pubkey::new_unique();
     
-    // Test with large byte array
+    // Seeds longer than 32 bytes are rejected by Solana
     let large_data = vec![0u8; 1000];
-    let seeds = vec![SeedValue::Bytes(large_data.clone())];
+    let seeds = vec![SeedValue::Bytes(large_data)];
     
     let result = deriver.derive_pda(&program_id, &seeds);
-    assert!(result.is_ok());
-    
-    let pda_info = result.unwrap();
-    assert_eq!(pda_info.seeds[0].as_bytes(), large_data);
+    assert!(matches!(result, Err(PdaAnalyzerError::InvalidSeedData(message)) if message.contains("32 byte limit")));
+
+    // Exactly 32 bytes is fine
+    let seeds = vec![SeedValue::Bytes(vec![0u8; 32])];
+    assert!(deriver.derive_pda(&program_id, &seeds).is_ok());
+}
+
+#[test]
+fn test_zero_length_seed() {
+    let mut deriver = PdaDeriver::new();
+    let program_id = Pubkey::new_unique();
+    let seeds = vec![SeedValue::Bytes(vec![]), SeedValue::String("vault".to_string())];
+
+    let pda_info = deriver.derive_pda(&program_id, &seeds).unwrap();
+    let (expected, bump) = Pubkey::find_program_address(&[b"", b"vault"], &program_id);
+    assert_eq!(pda_info.address, expected);
+    assert_eq!(pda_info.bump, bump);
+}
+
+#[test]
+fn test_too_many_seeds() {
+    let mut deriver = PdaDeriver::new();
+    let program_id = Pubkey::new_unique();
+
+    let seeds = vec![SeedValue::U8(1); 17];
+    let result = deriver.derive_pda(&program_id, &seeds);
+    assert!(matches!(result, Err(PdaAnalyzerError::InvalidSeedData(_))));
+
+    // 16 seeds leave no room for the bump seed either
+    let seeds = vec![SeedValue::U8(1); 16];
+    assert!(deriver.derive_pda(&program_id, &seeds).is_err());
+
+    let seeds = vec![SeedValue::U8(1); 15];
+    assert!(deriver.derive_pda(&program_id, &seeds).is_ok());
 }
 
 #[test] 
@@ -227,4 +258,997 @@ fn test_mixed_seed_types() {
     let pda_info = result.unwrap();
     assert_eq!(pda_info.seeds.len(), 7);
     assert_eq!(pda_info.program_id, program_id);
-}
\ No newline at end of file
+}
+
+#[test]
+fn test_non_canonical_bump_recovery() {
+    let program_id = Pubkey::new_unique();
+    let authority = Pubkey::from_str("9WzDXwBbmkg8ZTbNMqUxvQRAyrZzDsGYdLVL9zYtAWWM").unwrap();
+    let nonce = 3u64.to_le_bytes();
+
+    let (_, canonical_bump) = Pubkey::find_program_address(&[authority.as_ref(), &nonce], &program_id);
+    let (address, bump) = (0..canonical_bump)
+        .rev()
+        .find_map(|bump| {
+            Pubkey::create_program_address(&[authority.as_ref(), &nonce, &[bump]], &program_id)
+                .ok()
+                .map(|address| (address, bump))
+        })
+        .unwrap();
+
+    let mut analyzer = PdaAnalyzer::new();
+    assert!(analyzer.analyze_pda(&address, &program_id).unwrap().is_none());
+
+    let mut analyzer = PdaAnalyzer::with_config(SearchConfig {
+        search_non_canonical_bumps: true,
+        ..Default::default()
+    });
+    let result = analyzer.analyze_pda(&address, &program_id).unwrap().unwrap();
+    assert_eq!(result.pda_info.bump, bump);
+    assert!(matches!(result.pda_info.seeds[1], SeedValue::U64(3)));
+}
+
+#[test]
+fn test_bump_passed_as_last_seed() {
+    let authority = Pubkey::from_str("9WzDXwBbmkg8ZTbNMqUxvQRAyrZzDsGYdLVL9zYtAWWM").unwrap();
+
+    // A stored, non-canonical bump passed straight to `create_program_address`. Roughly one
+    // program in ten has no off-curve bump between 250 and its canonical one, so take the
+    // first that does.
+    let (program_id, address, stored_bump) = std::iter::repeat_with(Pubkey::new_unique)
+        .find_map(|program_id| {
+            let (_, canonical_bump) = Pubkey::find_program_address(&[authority.as_ref()], &program_id);
+            (250..canonical_bump).rev().find_map(|bump| {
+                Pubkey::create_program_address(&[authority.as_ref(), &[bump]], &program_id)
+                    .ok()
+                    .map(|address| (program_id, address, bump))
+            })
+        })
+        .unwrap();
+
+    let mut analyzer = PdaAnalyzer::new();
+    let result = analyzer.analyze_pda(&address, &program_id).unwrap().unwrap();
+    assert!(result.bump_as_explicit_seed);
+    assert_eq!(result.pda_info.bump, stored_bump);
+    assert!(matches!(result.pda_info.seeds.last(), Some(SeedValue::U8(b)) if *b == stored_bump));
+
+    // A u8 seed followed by a found bump is an ordinary seed
+    let (address, _) = Pubkey::find_program_address(&[authority.as_ref(), &[252]], &program_id);
+    let result = analyzer.analyze_pda(&address, &program_id).unwrap().unwrap();
+    assert!(!result.bump_as_explicit_seed);
+    assert!(matches!(result.pda_info.seeds.last(), Some(SeedValue::U8(252))));
+}
+
+#[test]
+fn test_permuted_seed_order_recovery() {
+    let program_id = Pubkey::new_unique();
+    let authority = Pubkey::from_str("7gXKKGLQs2HpzrPTtBP7kkQ3LktDShQPE8VV9PYW9RSh").unwrap();
+
+    // Program puts the nonce before the authority
+    let (address, bump) = Pubkey::find_program_address(&[&5u64.to_le_bytes(), authority.as_ref()], &program_id);
+
+    let mut analyzer = PdaAnalyzer::new();
+    assert!(analyzer.analyze_pda(&address, &program_id).unwrap().is_none());
+
+    let mut analyzer = PdaAnalyzer::with_config(SearchConfig {
+        permute_seed_order: true,
+        ..Default::default()
+    });
+    let result = analyzer.analyze_pda(&address, &program_id).unwrap().unwrap();
+    assert_eq!(result.pda_info.bump, bump);
+    assert!(matches!(result.pda_info.seeds[0], SeedValue::U64(5)));
+    assert!(matches!(result.pda_info.seeds[1], SeedValue::Pubkey(pk) if pk == authority));
+}
+
+#[test]
+fn test_analyze_pda_across_programs() {
+    let owner = Pubkey::new_unique();
+    let other = Pubkey::new_unique();
+    let (address, _bump) = Pubkey::find_program_address(&[b"config"], &owner);
+
+    let mut analyzer = PdaAnalyzer::new();
+    let results = analyzer.analyze_pda_across_programs(&address, &[other, owner]).unwrap();
+
+    assert_eq!(results.len(), 1);
+    assert_eq!(results[0].pda_info.program_id, owner);
+
+    let results = analyzer.analyze_pda_across_programs(&address, &[other]).unwrap();
+    assert!(results.is_empty());
+}
+
+#[test]
+fn test_rank_results_prefers_stronger_match_kinds() {
+    let program_id = Pubkey::new_unique();
+    let (config, _) = Pubkey::find_program_address(&[b"config"], &program_id);
+    let (pool, _) = Pubkey::find_program_address(&[b"pool", &3u64.to_le_bytes()], &program_id);
+
+    let mut analyzer = PdaAnalyzer::new();
+    let dictionary_hit = analyzer.analyze_pda(&config, &program_id).unwrap().unwrap();
+    let mut brute_forced = analyzer.analyze_pda(&pool, &program_id).unwrap().unwrap();
+    assert_eq!(dictionary_hit.match_kind, MatchKind::DictionaryHit);
+    assert_eq!(brute_forced.match_kind, MatchKind::BruteForce);
+    assert_eq!(PdaPattern::AssociatedTokenAccount.match_kind(), MatchKind::Structural);
+    assert_eq!(PdaPattern::AssociatedTokenAccount.category(), PatternCategory::Token);
+    assert_eq!(PdaPattern::MetaplexEdition.category(), PatternCategory::Nft);
+    assert_eq!(PdaPattern::StakePoolValidator.category(), PatternCategory::Staking);
+    assert_eq!(dictionary_hit.pattern.category(), PatternCategory::Generic);
+
+    // A brute-forced hit ranks below a dictionary hit even with higher confidence
+    brute_forced.confidence = dictionary_hit.confidence + 0.01;
+    let mut results = vec![brute_forced, dictionary_hit];
+    rank_results(&mut results);
+    assert_eq!(results[0].pda_info.address, config);
+    assert_eq!(results[1].pda_info.address, pool);
+}
+
+#[test]
+fn test_suggest_missing_candidates() {
+    let mut analyzer = PdaAnalyzer::new();
+
+    // An ATA needs a wallet and mint, whatever else is registered
+    let ata_program = Pubkey::from_str("ATokenGPvbdGVxr1b2hvZbsiqW5xWH25efTNsLJA8knL").unwrap();
+    let (ata, _) = Pubkey::find_program_address(&[b"unknown"], &ata_program);
+    let kinds: Vec<CandidateKind> = analyzer.suggest_missing_candidates(&ata, &ata_program).suggestions.iter().map(|s| s.kind).collect();
+    assert_eq!(kinds, [CandidateKind::Wallet, CandidateKind::Mint]);
+
+    // Other programs are told what the vocabulary lacks
+    let program_id = Pubkey::new_unique();
+    let (address, _) = Pubkey::find_program_address(&[b"obscure"], &program_id);
+    assert!(analyzer.analyze_pda(&address, &program_id).unwrap().is_none());
+    let report = analyzer.suggest_missing_candidates(&address, &program_id);
+    assert_eq!(report.unrecognized.address, address);
+    assert!(report.suggestions.iter().any(|s| s.kind == CandidateKind::SeedString));
+    assert!(report.suggestions.iter().any(|s| s.kind == CandidateKind::TimeWindow));
+
+    analyzer.register_seed_strings(program_id, vec!["other".to_string()]);
+    let report = analyzer.suggest_missing_candidates(&address, &program_id);
+    assert!(!report.suggestions.iter().any(|s| s.kind == CandidateKind::SeedString));
+
+    // Nothing helps an on-curve address
+    let wallet = Pubkey::from_str("9WzDXwBbmkg8ZTbNMqUxvQRAyrZzDsGYdLVL9zYtAWWM").unwrap();
+    assert!(analyzer.suggest_missing_candidates(&wallet, &program_id).suggestions.is_empty());
+}
+
+#[test]
+fn test_failure_summary_ranks_reasons() {
+    let analyzer = PdaAnalyzer::new();
+    let ata_program = Pubkey::from_str("ATokenGPvbdGVxr1b2hvZbsiqW5xWH25efTNsLJA8knL").unwrap();
+    let unknown_program = Pubkey::new_unique();
+    let wallet = Pubkey::from_str("9WzDXwBbmkg8ZTbNMqUxvQRAyrZzDsGYdLVL9zYtAWWM").unwrap();
+
+    let mut failures = vec![(wallet, unknown_program)];
+    for i in 0..2u8 {
+        failures.push((Pubkey::find_program_address(&[b"ata", &[i]], &ata_program).0, ata_program));
+    }
+    for i in 0..3u8 {
+        failures.push((Pubkey::find_program_address(&[b"obscure", &[i]], &unknown_program).0, unknown_program));
+    }
+    let reports: Vec<_> = failures.iter().map(|(address, program_id)| analyzer.suggest_missing_candidates(address, program_id)).collect();
+
+    let summary = FailureSummary::from_reports(&reports);
+    assert_eq!(summary.total, 6);
+    assert_eq!(
+        summary.reasons,
+        [
+            (FailureReason::UnknownProgram, 3),
+            (FailureReason::MissingCandidate(CandidateKind::Wallet), 2),
+            (FailureReason::OnCurve, 1),
+        ]
+    );
+    assert_eq!(summary.share(3), 0.5);
+    assert_eq!(FailureReason::MissingCandidate(CandidateKind::Wallet).describe(), "likely need a wallet hint");
+
+    let system = analyzer.suggest_missing_candidates(&wallet, &solana_sdk::system_program::id());
+    assert_eq!(system.failure_reason(), FailureReason::SystemProgram);
+    assert_eq!(FailureSummary::from_reports(&[]).share(0), 0.0);
+}
+
+#[test]
+fn test_disabled_pattern_families_are_skipped() {
+    let program_id = Pubkey::new_unique();
+    let (address, _bump) = Pubkey::find_program_address(&[b"pool", &5u64.to_le_bytes()], &program_id);
+
+    let mut analyzer = PdaAnalyzer::with_config(SearchConfig {
+        enabled_patterns: PdaPattern::parse_filter("singleton,complex").unwrap(),
+        ..Default::default()
+    });
+    assert!(analyzer.analyze_pda(&address, &program_id).unwrap().is_none());
+
+    let sequential = PdaPattern::parse_filter("sequential").unwrap();
+    let result = analyzer.with_enabled_patterns(sequential, |analyzer| analyzer.analyze_pda(&address, &program_id));
+    assert_eq!(result.unwrap().unwrap().pattern, PdaPattern::Sequential);
+    assert!(!analyzer.config().is_enabled(&PdaPattern::Sequential));
+}
+
+#[test]
+fn test_parse_pattern_filter() {
+    let patterns = PdaPattern::parse_filter("metaplex, STRING_PUBKEY").unwrap();
+    assert_eq!(patterns.len(), 4);
+    assert!(patterns.contains(&PdaPattern::MetaplexEdition));
+    assert!(patterns.contains(&PdaPattern::StringPubkey));
+
+    assert!(PdaPattern::parse_filter("sequential,bogus").is_err());
+    assert!(PdaPattern::parse_filter(" , ").is_err());
+}
+
+#[test]
+fn test_timestamp_pattern_recovery() {
+    let program_id = Pubkey::new_unique();
+    let trader_a = Pubkey::new_unique();
+    let trader_b = Pubkey::new_unique();
+    let timestamp: u64 = 1_640_995_200;
+    let (address, bump) = Pubkey::find_program_address(
+        &[b"escrow", trader_a.as_ref(), trader_b.as_ref(), &timestamp.to_le_bytes()],
+        &program_id,
+    );
+
+    // Without a window timestamps are never scanned
+    let mut analyzer = PdaAnalyzer::new();
+    analyzer.candidates_mut().add_pubkey(trader_a);
+    analyzer.candidates_mut().add_pubkey(trader_b);
+    assert!(analyzer.analyze_pda(&address, &program_id).unwrap().is_none());
+
+    let mut analyzer = PdaAnalyzer::with_config(SearchConfig {
+        timestamp_range: Some(TimestampRange::new(timestamp - 600, timestamp + 600, 60).unwrap()),
+        ..Default::default()
+    });
+    analyzer.candidates_mut().add_pubkey(trader_a);
+    analyzer.candidates_mut().add_pubkey(trader_b);
+
+    let result = analyzer.analyze_pda(&address, &program_id).unwrap().unwrap();
+    assert_eq!(result.pattern, PdaPattern::Timestamp);
+    assert_eq!(result.pda_info.bump, bump);
+    assert!(matches!(result.pda_info.seeds[3], SeedValue::U64(ts) if ts == timestamp));
+}
+
+#[test]
+fn test_epoch_and_slot_seeds_near_chain_context() {
+    let program_id = Pubkey::new_unique();
+    let staker = Pubkey::new_unique();
+    let (epoch_pda, _) = Pubkey::find_program_address(&[b"rewards", &611u64.to_le_bytes()], &program_id);
+    let (slot_pda, bump) = Pubkey::find_program_address(&[b"snapshot", staker.as_ref(), &250_000_120u64.to_le_bytes()], &program_id);
+
+    // Far outside any blind numeric scan, and not tried without chain context
+    let mut analyzer = PdaAnalyzer::new();
+    analyzer.candidates_mut().add_pubkey(staker);
+    assert!(analyzer.analyze_pda(&epoch_pda, &program_id).unwrap().is_none());
+
+    let context = ChainContext::new(250_000_000, 612);
+    let result = analyzer.analyze_near(&epoch_pda, &program_id, context).unwrap().into_result().unwrap();
+    assert_eq!(result.pattern, PdaPattern::EpochSlot);
+    assert!(matches!(result.pda_info.seeds[1], SeedValue::U64(611)));
+
+    let result = analyzer.analyze_near(&slot_pda, &program_id, context).unwrap().into_result().unwrap();
+    assert_eq!(result.pda_info.bump, bump);
+    assert_eq!(result.pda_info.seeds[1], SeedValue::Pubkey(staker));
+    assert!(matches!(result.pda_info.seeds[2], SeedValue::U64(250_000_120)));
+
+    // The context only applies to the call it was given to
+    assert!(analyzer.analyze_pda(&slot_pda, &program_id).unwrap().is_none());
+    assert_eq!(ChainContext::new(1, 0).epochs().collect::<Vec<_>>(), vec![0, 1, 2]);
+}
+
+#[test]
+fn test_timestamp_range_validation() {
+    assert_eq!(TimestampRange::new(0, 59, 60).unwrap().count(), 1);
+    assert_eq!(TimestampRange::new(100, 200, 10).unwrap().count(), 11);
+    assert!(TimestampRange::new(200, 100, 10).is_err());
+    assert!(TimestampRange::new(0, 100, 0).is_err());
+    assert!(TimestampRange::new(0, u64::MAX, 1).is_err());
+}
+
+#[test]
+fn test_search_config_custom_sequential_prefix() {
+    let program_id = Pubkey::new_unique();
+    let (address, _) = Pubkey::find_program_address(&[b"ticket", &120u64.to_le_bytes()], &program_id);
+
+    let mut analyzer = PdaAnalyzer::new();
+    assert!(analyzer.analyze_pda(&address, &program_id).unwrap().is_none());
+
+    let mut analyzer = PdaAnalyzer::with_config(SearchConfig {
+        sequential_prefixes: vec!["ticket".to_string()],
+        sequential_range: 100..=200,
+        ..Default::default()
+    });
+    let result = analyzer.analyze_pda(&address, &program_id).unwrap().unwrap();
+    assert_eq!(result.pattern, PdaPattern::Sequential);
+    assert!(matches!(result.pda_info.seeds[1], SeedValue::U64(120)));
+}
+
+#[test]
+fn test_sequential_scan_across_u32_boundary() {
+    let program_id = Pubkey::new_unique();
+    let boundary = u32::MAX as u64;
+    let mut analyzer = PdaAnalyzer::with_config(SearchConfig {
+        sequential_prefixes: vec!["ticket".to_string()],
+        sequential_range: boundary - 1..=boundary + 1,
+        ..Default::default()
+    });
+
+    // The last value that fits is still tried as a u32
+    let (address, _) = Pubkey::find_program_address(&[b"ticket", &u32::MAX.to_le_bytes()], &program_id);
+    let result = analyzer.analyze_pda(&address, &program_id).unwrap().unwrap();
+    assert!(matches!(result.pda_info.seeds[1], SeedValue::U32(u32::MAX)));
+
+    // Past the boundary only the u64 encoding is tried
+    let (address, _) = Pubkey::find_program_address(&[b"ticket", &(boundary + 1).to_le_bytes()], &program_id);
+    let result = analyzer.analyze_pda(&address, &program_id).unwrap().unwrap();
+    assert!(matches!(result.pda_info.seeds[1], SeedValue::U64(n) if n == boundary + 1));
+
+    // u32::MAX + 1 truncated to a u32 is 0, which is outside the range
+    let (address, _) = Pubkey::find_program_address(&[b"ticket", &0u32.to_le_bytes()], &program_id);
+    assert!(analyzer.analyze_pda(&address, &program_id).unwrap().is_none());
+}
+
+#[test]
+fn test_search_config_big_endian_seeds() {
+    let program_id = Pubkey::new_unique();
+    let (address, _) = Pubkey::find_program_address(&[b"pool", &7u32.to_be_bytes()], &program_id);
+
+    let mut analyzer = PdaAnalyzer::new();
+    assert!(analyzer.analyze_pda(&address, &program_id).unwrap().is_none());
+
+    let mut analyzer = PdaAnalyzer::with_config(SearchConfig {
+        endianness: Endianness::Both,
+        ..Default::default()
+    });
+    let result = analyzer.analyze_pda(&address, &program_id).unwrap().unwrap();
+    assert_eq!(result.pattern, PdaPattern::Sequential);
+    assert!(matches!(&result.pda_info.seeds[1], SeedValue::Bytes(bytes) if bytes == &[0, 0, 0, 7]));
+}
+
+#[test]
+fn test_complex_confidence_decays_with_search_depth() {
+    let program_id = Pubkey::new_unique();
+    let first = Pubkey::from_str("11111111111111111111111111111112").unwrap();
+    let last = Pubkey::from_str("7gXKKGLQs2HpzrPTtBP7kkQ3LktDShQPE8VV9PYW9RSh").unwrap();
+    let mut analyzer = PdaAnalyzer::with_config(SearchConfig {
+        enabled_patterns: PdaPattern::parse_filter("complex").unwrap(),
+        ..Default::default()
+    });
+
+    // The very first combination tried keeps the full structural confidence
+    let (address, _) = Pubkey::find_program_address(&[b"governance", first.as_ref(), b"proposal", &0u32.to_le_bytes()], &program_id);
+    let early = analyzer.analyze_pda(&address, &program_id).unwrap().unwrap();
+    assert_eq!(early.pattern, PdaPattern::Complex);
+    assert!((early.confidence - 0.75).abs() < 1e-9);
+
+    // The last one is only found after scanning every other combination
+    let (address, _) = Pubkey::find_program_address(&[b"council", last.as_ref(), b"realm", &2u32.to_le_bytes()], &program_id);
+    let late = analyzer.analyze_pda(&address, &program_id).unwrap().unwrap();
+    assert_eq!(late.pattern, PdaPattern::Complex);
+    assert!(late.confidence < early.confidence);
+    assert!(late.confidence >= 0.5);
+}
+
+#[test]
+fn test_describe_unrecognized_address_kind() {
+    use solana_sdk::signature::{Keypair, Signer};
+
+    let analyzer = PdaAnalyzer::new();
+    let token_program = Pubkey::from_str("TokenkegQfeZyiNwAJbNbGKPFXCWuBvf9Ss623VQ5DA").unwrap();
+
+    let wallet = Keypair::new().pubkey();
+    let described = analyzer.describe_unrecognized(&wallet, &token_program);
+    assert_eq!(described.address_kind, AddressKind::OnCurve);
+    assert_eq!(described.program_name.as_deref(), Some("SPL Token"));
+    assert!(described.message().contains("cannot be a PDA for any program"));
+
+    let (pda, _) = Pubkey::find_program_address(&[b"unguessable-seed-4821"], &token_program);
+    let described = analyzer.describe_unrecognized(&pda, &token_program);
+    assert_eq!(described.address_kind, AddressKind::OffCurve);
+    assert!(described.message().contains("SPL Token"));
+}
+
+#[test]
+fn test_traced_analysis_records_attempts() {
+    let program_id = Pubkey::new_unique();
+    let (address, _) = Pubkey::find_program_address(&[b"vault"], &program_id);
+    let mut analyzer = PdaAnalyzer::new();
+
+    let (result, attempts) = analyzer.analyze_pda_traced(&address, &program_id).unwrap();
+    assert_eq!(result.unwrap().pattern, PdaPattern::StringSingleton);
+
+    let last = attempts.last().unwrap();
+    assert!(last.success);
+    assert_eq!(last.pattern, PdaPattern::StringSingleton);
+    assert!(matches!(&last.attempted_seeds[..], [SeedValue::String(s)] if s == "vault"));
+    assert!(attempts[..attempts.len() - 1].iter().all(|attempt| !attempt.success));
+    // "state", "config", "authority" and "vault" precede it in the default dictionary
+    assert!(attempts.iter().filter(|attempt| attempt.pattern == PdaPattern::StringSingleton).count() >= 4);
+
+    // Untraced analysis keeps nothing around
+    analyzer.analyze_pda(&address, &program_id).unwrap();
+    let (_, attempts) = analyzer.analyze_pda_traced(&Pubkey::new_unique(), &program_id).unwrap();
+    assert!(attempts.iter().all(|attempt| !attempt.success));
+}
+
+#[test]
+fn test_system_program_and_on_curve_addresses_skip_heuristics() {
+    use solana_sdk::signature::{Keypair, Signer};
+
+    let mut analyzer = PdaAnalyzer::new();
+    let system_program = solana_sdk::system_program::id();
+
+    // The examples' degenerate case: the System Program as both address and owner
+    let (result, attempts) = analyzer.analyze_pda_traced(&system_program, &system_program).unwrap();
+    assert!(result.is_none());
+    assert!(attempts.is_empty());
+    let described = analyzer.describe_unrecognized(&system_program, &system_program);
+    assert!(described.is_inapplicable());
+    assert!(described.message().contains("System Program"));
+
+    // Even an address that is a real PDA of the System Program's key is not analyzed
+    let (pda, _) = Pubkey::find_program_address(&[b"state"], &system_program);
+    assert!(analyzer.analyze_pda(&pda, &system_program).unwrap().is_none());
+
+    let wallet = Keypair::new().pubkey();
+    let (_, attempts) = analyzer.analyze_pda_traced(&wallet, &Pubkey::new_unique()).unwrap();
+    assert!(attempts.is_empty());
+    assert!(analyzer.describe_unrecognized(&wallet, &Pubkey::new_unique()).is_inapplicable());
+}
+
+#[test]
+fn test_overlong_seed_strings_are_skipped() {
+    let program_id = Pubkey::new_unique();
+    let long_word = "a_vocabulary_word_well_past_the_seed_limit".to_string();
+    let (address, _) = Pubkey::find_program_address(&[b"treasury"], &program_id);
+
+    let mut analyzer = PdaAnalyzer::new();
+    analyzer.register_seed_strings(program_id, vec![long_word.clone()]);
+    let (_, attempts) = analyzer.analyze_pda_traced(&Pubkey::new_unique(), &program_id).unwrap();
+    assert!(!attempts
+        .iter()
+        .any(|attempt| matches!(&attempt.attempted_seeds[..], [SeedValue::String(s)] if *s == long_word)));
+
+    // A tighter configured limit drops short dictionary words too
+    let mut analyzer = PdaAnalyzer::with_config(SearchConfig {
+        max_seed_string_len: 6,
+        ..Default::default()
+    });
+    assert!(analyzer.analyze_pda(&address, &program_id).unwrap().is_none());
+    let mut analyzer = PdaAnalyzer::new();
+    assert_eq!(analyzer.analyze_pda(&address, &program_id).unwrap().unwrap().pattern, PdaPattern::StringSingleton);
+}
+
+#[test]
+fn test_pubkey_pair_recovery() {
+    let program_id = Pubkey::new_unique();
+    let pool = Pubkey::new_unique();
+    let user = Pubkey::new_unique();
+    let (address, bump) = Pubkey::find_program_address(&[pool.as_ref(), user.as_ref()], &program_id);
+
+    let mut analyzer = PdaAnalyzer::new();
+    assert!(analyzer.analyze_pda(&address, &program_id).unwrap().is_none());
+
+    // Registered in the opposite order to the seeds
+    analyzer.candidates_mut().add_pubkey(user);
+    analyzer.candidates_mut().add_pubkey(pool);
+    let result = analyzer.analyze_pda(&address, &program_id).unwrap().unwrap();
+    assert_eq!(result.pattern, PdaPattern::PubkeyPubkey);
+    assert_eq!(result.pda_info.bump, bump);
+    assert!(matches!(&result.pda_info.seeds[..], [SeedValue::Pubkey(a), SeedValue::Pubkey(b)] if *a == pool && *b == user));
+}
+
+#[test]
+fn test_candidate_limit_caps_each_family() {
+    let program_id = Pubkey::new_unique();
+    // "index" is the sixth sequential prefix, well past the first 50 candidates
+    let (address, _) = Pubkey::find_program_address(&[b"index", &40u64.to_le_bytes()], &program_id);
+
+    let mut analyzer = PdaAnalyzer::with_config(SearchConfig {
+        candidate_limit: Some(50),
+        ..Default::default()
+    });
+    let (result, attempts) = analyzer.analyze_pda_traced(&address, &program_id).unwrap();
+    assert!(result.is_none());
+    let sequential = attempts.iter().filter(|attempt| attempt.pattern == PdaPattern::Sequential).count();
+    assert_eq!(sequential, 50);
+
+    let mut analyzer = PdaAnalyzer::new();
+    assert_eq!(analyzer.analyze_pda(&address, &program_id).unwrap().unwrap().pattern, PdaPattern::Sequential);
+}
+
+#[test]
+fn test_analyzer_builder() {
+    let program_id = Pubkey::new_unique();
+    let (address, _) = Pubkey::find_program_address(&[b"reserve_ledger"], &program_id);
+
+    let default_analyzer = PdaAnalyzer::default();
+    assert!(default_analyzer.get_program_name(&program_id).is_none());
+    assert!(default_analyzer.candidates().is_empty());
+
+    let mut analyzer = PdaAnalyzer::builder()
+        .config(SearchConfig {
+            enabled_patterns: PdaPattern::parse_filter("singleton").unwrap(),
+            ..Default::default()
+        })
+        .programs([(program_id, "Lending".to_string())])
+        .vocabulary(["reserve_ledger".to_string()])
+        .build();
+
+    assert_eq!(analyzer.get_program_name(&program_id).map(String::as_str), Some("Lending"));
+    assert!(!analyzer.config().is_enabled(&PdaPattern::Sequential));
+    let result = analyzer.analyze_pda(&address, &program_id).unwrap().unwrap();
+    assert!(matches!(&result.pda_info.seeds[..], [SeedValue::String(s)] if s == "reserve_ledger"));
+
+    // A registry passed in wholesale replaces the vocabulary
+    let analyzer = PdaAnalyzer::builder()
+        .vocabulary(["reserve_ledger".to_string()])
+        .candidate_registry(CandidateRegistry::new())
+        .build();
+    assert!(analyzer.candidates().is_empty());
+}
+
+#[test]
+fn test_pattern_stats_are_shared_and_thread_safe() {
+    let stats = PatternStats::new();
+    let program_id = Pubkey::new_unique();
+    let (address, _) = Pubkey::find_program_address(&[b"vault"], &program_id);
+
+    let mut first = PdaAnalyzer::builder().pattern_stats(stats.clone()).build();
+    let mut second = PdaAnalyzer::builder().pattern_stats(stats.clone()).build();
+    first.analyze_pda(&address, &program_id).unwrap().unwrap();
+    second.analyze_pda(&address, &program_id).unwrap().unwrap();
+    assert_eq!(stats.get(&PdaPattern::StringSingleton), 2);
+    assert_eq!(first.get_pattern_stats()[&PdaPattern::StringSingleton], 2);
+
+    std::thread::scope(|scope| {
+        for _ in 0..4 {
+            scope.spawn(|| {
+                for _ in 0..100 {
+                    stats.record(&PdaPattern::Sequential);
+                }
+            });
+        }
+    });
+    assert_eq!(stats.snapshot()[&PdaPattern::Sequential], 400);
+    assert_eq!(stats.get(&PdaPattern::Complex), 0);
+}
+
+#[test]
+fn test_duplicate_candidates_are_derived_once() {
+    let program_id = Pubkey::new_unique();
+    let authority = Pubkey::new_unique();
+
+    let mut analyzer = PdaAnalyzer::with_config(SearchConfig {
+        authority_pubkeys: vec![authority],
+        ..Default::default()
+    });
+    // Both already in the configured lists
+    analyzer.register_seed_strings(program_id, vec!["vault".to_string()]);
+    analyzer.candidates_mut().add_pubkey(authority);
+
+    // Off the curve, so the heuristics run
+    let (unrelated, _) = Pubkey::find_program_address(&[b"unrelated"], &Pubkey::new_unique());
+    let (result, attempts) = analyzer.analyze_pda_traced(&unrelated, &program_id).unwrap();
+    assert!(result.is_none());
+    let vault_attempts = attempts
+        .iter()
+        .filter(|attempt| matches!(&attempt.attempted_seeds[..], [SeedValue::String(s)] if s == "vault"))
+        .count();
+    assert_eq!(vault_attempts, 1);
+    let authority_attempts = attempts
+        .iter()
+        .filter(|attempt| matches!(&attempt.attempted_seeds[..], [SeedValue::Pubkey(p)] if *p == authority))
+        .count();
+    assert_eq!(authority_attempts, 1);
+
+    // Each analysis starts afresh
+    let (address, _) = Pubkey::find_program_address(&[b"vault"], &program_id);
+    assert_eq!(analyzer.analyze_pda(&address, &program_id).unwrap().unwrap().pattern, PdaPattern::StringSingleton);
+}
+
+#[test]
+fn test_custom_pattern_names_round_trip() {
+    use std::str::FromStr;
+
+    let custom = PdaPattern::Custom("LP_POSITION".to_string());
+    assert_eq!(custom.name(), "LP_POSITION");
+    assert_eq!(custom.as_str(), "CUSTOM");
+    assert_eq!(PdaPattern::from_str(&custom.name()).unwrap(), custom);
+
+    for pattern in PdaPattern::ALL {
+        assert_eq!(pattern.name(), pattern.as_str());
+        assert_eq!(PdaPattern::from_str(&pattern.name()).unwrap(), pattern);
+    }
+    assert!(PdaPattern::from_str("").is_err());
+
+    let json = serde_json::to_string(&custom).unwrap();
+    assert_eq!(serde_json::from_str::<PdaPattern>(&json).unwrap(), custom);
+}
+
+#[test]
+fn test_derive_batch_checked_reports_collisions() {
+    let program_id = Pubkey::new_unique();
+    let mut analyzer = PdaAnalyzer::new();
+
+    let distinct = vec![
+        vec![SeedValue::String("vault".to_string())],
+        vec![SeedValue::String("vault".to_string()), SeedValue::U64(1)],
+    ];
+    let derived = analyzer.derive_batch_checked(&program_id, &distinct).unwrap();
+    assert_eq!(derived.len(), 2);
+    assert_ne!(derived[0].address, derived[1].address);
+
+    // The same bytes split differently across seeds
+    let colliding = vec![
+        vec![SeedValue::String("ab".to_string()), SeedValue::String("c".to_string())],
+        vec![SeedValue::String("config".to_string())],
+        vec![SeedValue::String("a".to_string()), SeedValue::String("bc".to_string())],
+    ];
+    let error = analyzer.derive_batch_checked(&program_id, &colliding).unwrap_err();
+    assert!(matches!(error, PdaAnalyzerError::SeedCollision(_)));
+    let message = error.to_string();
+    assert!(message.contains(r#"seed sets 0 ["ab", "c"] and 2 ["a", "bc"]"#), "{}", message);
+}
+
+#[test]
+fn test_batch_analyze_parallel_matches_sequential() {
+    let program_id = Pubkey::new_unique();
+    let mut addresses: Vec<(Pubkey, Pubkey)> = (0..20u64)
+        .map(|i| (Pubkey::find_program_address(&[b"vault", &i.to_le_bytes()], &program_id).0, program_id))
+        .collect();
+    addresses.push((Pubkey::find_program_address(&[b"no-such-prefix"], &program_id).0, program_id));
+
+    let mut sequential = PdaAnalyzer::new();
+    let expected = sequential.batch_analyze(&addresses).unwrap();
+
+    let parallel = PdaAnalyzer::with_config(SearchConfig {
+        batch_jobs: std::num::NonZeroUsize::new(3),
+        ..Default::default()
+    });
+    let results = parallel.batch_analyze_parallel(&addresses).unwrap();
+
+    assert_eq!(results.len(), expected.len());
+    for (result, expected) in results.iter().zip(&expected) {
+        assert_eq!(result.as_ref().map(|r| r.pda_info.address), expected.as_ref().map(|r| r.pda_info.address));
+        assert_eq!(result.as_ref().map(|r| &r.pattern), expected.as_ref().map(|r| &r.pattern));
+    }
+    assert!(results.last().unwrap().is_none());
+    assert_eq!(parallel.get_pattern_stats().get(&PdaPattern::Sequential), Some(&20));
+}
+
+#[test]
+fn test_batch_pool_reused_across_batches_and_clones() {
+    let program_id = Pubkey::new_unique();
+    let addresses: Vec<(Pubkey, Pubkey)> = (0..8u64)
+        .map(|i| (Pubkey::find_program_address(&[b"vault", &i.to_le_bytes()], &program_id).0, program_id))
+        .collect();
+    let analyzer = PdaAnalyzer::with_config(SearchConfig {
+        batch_jobs: std::num::NonZeroUsize::new(2),
+        ..Default::default()
+    });
+
+    let workers = std::sync::Mutex::new(std::collections::HashSet::new());
+    let record = |_: &Pubkey, _: &Option<_>| {
+        workers.lock().unwrap().insert(std::thread::current().id());
+    };
+    analyzer.batch_analyze_parallel_with(&addresses, record).unwrap();
+    analyzer.batch_analyze_parallel_with(&addresses, record).unwrap();
+    analyzer.clone().batch_analyze_parallel_with(&addresses, record).unwrap();
+
+    // A pool started per batch would bring new threads each time
+    let workers = workers.into_inner().unwrap();
+    assert!(!workers.is_empty() && workers.len() <= 2, "{} worker threads", workers.len());
+}
+
+#[test]
+fn test_program_self_reference_recovery() {
+    let program_id = Pubkey::new_unique();
+    let user = Pubkey::new_unique();
+    let (config, _) = Pubkey::find_program_address(&[b"config", program_id.as_ref()], &program_id);
+    let (position, bump) = Pubkey::find_program_address(&[b"position", program_id.as_ref(), user.as_ref()], &program_id);
+
+    let mut analyzer = PdaAnalyzer::new();
+    let result = analyzer.analyze_pda(&config, &program_id).unwrap().unwrap();
+    assert_eq!(result.pattern, PdaPattern::ProgramSelfRef);
+    assert_eq!(result.match_kind, MatchKind::DictionaryHit);
+    assert!(matches!(&result.pda_info.seeds[..], [SeedValue::String(s), SeedValue::Pubkey(p)] if s == "config" && *p == program_id));
+
+    // The trailing pubkey has to be a candidate
+    analyzer.register_seed_strings(program_id, vec!["position".to_string()]);
+    assert!(analyzer.analyze_pda(&position, &program_id).unwrap().is_none());
+    analyzer.candidates_mut().add_pubkey(user);
+    let result = analyzer.analyze_pda(&position, &program_id).unwrap().unwrap();
+    assert_eq!(result.pattern, PdaPattern::ProgramSelfRef);
+    assert_eq!(result.pda_info.bump, bump);
+    assert_eq!(result.pda_info.seeds.len(), 3);
+
+    let mut analyzer = PdaAnalyzer::with_config(SearchConfig {
+        enabled_patterns: PdaPattern::parse_filter("singleton,authority").unwrap(),
+        ..Default::default()
+    });
+    assert!(analyzer.analyze_pda(&config, &program_id).unwrap().is_none());
+}
+
+#[test]
+fn test_suffixed_pubkey_recovery() {
+    let program_id = Pubkey::new_unique();
+    let user = Pubkey::new_unique();
+    let (position, bump) = Pubkey::find_program_address(&[b"position", user.as_ref(), b"v2"], &program_id);
+    let (ledger, _) = Pubkey::find_program_address(&[b"vault", user.as_ref(), b"ledger"], &program_id);
+
+    // Both the pubkey and the prefix have to be known; `v2` is a built-in suffix
+    let mut analyzer = PdaAnalyzer::new();
+    analyzer.candidates_mut().add_pubkey(user);
+    assert!(analyzer.analyze_pda(&position, &program_id).unwrap().is_none());
+    analyzer.register_seed_strings(program_id, vec!["position".to_string()]);
+    let result = analyzer.analyze_pda(&position, &program_id).unwrap().unwrap();
+    assert_eq!(result.pattern, PdaPattern::StringPubkeyStringSuffix);
+    assert_eq!(result.match_kind, MatchKind::DictionaryHit);
+    assert_eq!(result.pda_info.bump, bump);
+    assert!(matches!(
+        &result.pda_info.seeds[..],
+        [SeedValue::String(prefix), SeedValue::Pubkey(p), SeedValue::String(suffix)] if prefix == "position" && *p == user && suffix == "v2"
+    ));
+
+    // Vocabulary strings serve as suffixes too
+    assert!(analyzer.analyze_pda(&ledger, &program_id).unwrap().is_none());
+    analyzer.register_seed_strings(program_id, vec!["ledger".to_string()]);
+    let result = analyzer.analyze_pda(&ledger, &program_id).unwrap().unwrap();
+    assert_eq!(result.pattern, PdaPattern::StringPubkeyStringSuffix);
+
+    let complex_only = PdaPattern::parse_filter("complex").unwrap();
+    assert!(analyzer.with_enabled_patterns(complex_only, |analyzer| analyzer.analyze_pda(&position, &program_id)).unwrap().is_none());
+}
+
+#[test]
+fn test_analysis_outcomes_are_distinguished() {
+    use solana_sdk::signature::{Keypair, Signer};
+
+    let program_id = Pubkey::new_unique();
+    let mut analyzer = PdaAnalyzer::new();
+
+    let (vault, _) = Pubkey::find_program_address(&[b"vault"], &program_id);
+    assert!(matches!(analyzer.analyze(&vault, &program_id).unwrap(), AnalysisOutcome::Recognized(result) if result.pda_info.address == vault));
+
+    let wallet = Keypair::new().pubkey();
+    assert!(matches!(analyzer.analyze(&wallet, &program_id).unwrap(), AnalysisOutcome::NotAPda));
+    assert!(matches!(analyzer.analyze(&vault, &solana_sdk::system_program::id()).unwrap(), AnalysisOutcome::NotAPda));
+
+    let (unknown, _) = Pubkey::find_program_address(&[b"no such seed here"], &program_id);
+    let mut analyzer = PdaAnalyzer::with_config(SearchConfig {
+        enabled_patterns: PdaPattern::parse_filter("ata,singleton,sequential").unwrap(),
+        ..Default::default()
+    });
+    match analyzer.analyze(&unknown, &program_id).unwrap() {
+        // The ATA family only runs for the associated token program
+        AnalysisOutcome::Unrecognized { attempted } => {
+            assert_eq!(attempted, vec![PdaPattern::StringSingleton, PdaPattern::Sequential]);
+        }
+        outcome => panic!("expected an unrecognized PDA, got {:?}", outcome),
+    }
+    assert!(analyzer.analyze_pda(&unknown, &program_id).unwrap().is_none());
+}
+
+#[test]
+fn test_complex_search_cap_truncates() {
+    let program_id = Pubkey::new_unique();
+    let last = Pubkey::from_str("7gXKKGLQs2HpzrPTtBP7kkQ3LktDShQPE8VV9PYW9RSh").unwrap();
+    let (address, _) = Pubkey::find_program_address(&[b"council", last.as_ref(), b"realm", &2u32.to_le_bytes()], &program_id);
+    let config = SearchConfig {
+        enabled_patterns: PdaPattern::parse_filter("complex").unwrap(),
+        ..Default::default()
+    };
+
+    let mut analyzer = PdaAnalyzer::with_config(config.clone());
+    assert!(analyzer.analyze_pda(&address, &program_id).unwrap().is_some());
+    let full = analyzer.complex_search_stats().unwrap();
+    assert!(!full.truncated);
+    assert!(full.combinations > 10);
+
+    let mut analyzer = PdaAnalyzer::with_config(SearchConfig {
+        complex_max_combinations: Some(10),
+        ..config
+    });
+    assert!(analyzer.analyze_pda(&address, &program_id).unwrap().is_none());
+    let capped = analyzer.complex_search_stats().unwrap();
+    assert_eq!(capped, ComplexSearchStats { combinations: 10, truncated: true });
+    assert_eq!(capped.to_string(), "complex search truncated after 10 combinations");
+}
+
+#[test]
+fn test_warm_up_caches_known_program_singletons() {
+    let mut analyzer = PdaAnalyzer::new();
+    let program_id = Pubkey::from_str("TokenkegQfeZyiNwAJbNbGKPFXCWuBvf9Ss623VQ5DA").unwrap();
+    assert!(analyzer.get_program_name(&program_id).is_some());
+
+    let derived = analyzer.warm_up();
+    assert_eq!(derived, analyzer.known_program_count() * 3);
+    assert_eq!(analyzer.cache_stats().0, derived);
+
+    let (config, _) = Pubkey::find_program_address(&[b"config"], &program_id);
+    let result = analyzer.analyze_pda(&config, &program_id).unwrap().unwrap();
+    assert_eq!(result.pattern, PdaPattern::StringSingleton);
+    assert_eq!(result.pda_info.seeds, vec![SeedValue::String("config".to_string())]);
+
+    // Analysis reads the warmed entries without adding its own
+    assert_eq!(analyzer.cache_stats().1, derived);
+}
+
+#[test]
+fn test_seed_spec_string_round_trips() {
+    let program_id = Pubkey::new_unique();
+    let authority = Pubkey::from_str("7gXKKGLQs2HpzrPTtBP7kkQ3LktDShQPE8VV9PYW9RSh").unwrap();
+    let (pool, _) = Pubkey::find_program_address(&[b"pool", &3u64.to_le_bytes()], &program_id);
+    let explicit_bump = (0..=255u8)
+        .rev()
+        .find_map(|bump| Pubkey::create_program_address(&[authority.as_ref(), &[bump]], &program_id).ok())
+        .unwrap();
+
+    let mut analyzer = PdaAnalyzer::new();
+    let mut deriver = PdaDeriver::new();
+    for address in [pool, explicit_bump] {
+        let result = analyzer.analyze_pda(&address, &program_id).unwrap().unwrap();
+        let spec = result.to_seed_spec_string();
+        let seeds = SeedValue::parse_specs(&spec).unwrap();
+        assert_eq!(deriver.derive_pda(&program_id, &seeds).unwrap().address, address, "{}", spec);
+    }
+
+    let result = analyzer.analyze_pda(&pool, &program_id).unwrap().unwrap();
+    assert_eq!(result.to_seed_spec_string(), "str:pool u64:3");
+
+    // Strings that wouldn't survive splitting on whitespace are written as bytes
+    let seed = SeedValue::String("two words".to_string());
+    assert_eq!(seed.to_spec(), "bytes:74776f20776f726473");
+    assert_eq!(SeedValue::parse_spec(&seed.to_spec()).unwrap().as_bytes(), seed.as_bytes());
+    for seed in [SeedValue::Pubkey(authority), SeedValue::Hash([7; 32]), SeedValue::U16(9), SeedValue::Bytes(vec![0, 255])] {
+        assert_eq!(SeedValue::parse_spec(&seed.to_spec()).unwrap(), seed);
+    }
+}
+
+#[test]
+fn test_short_singleton_strings_are_downranked() {
+    let program_id = Pubkey::new_unique();
+    let (short, _) = Pubkey::find_program_address(&[b"a"], &program_id);
+    let (config, _) = Pubkey::find_program_address(&[b"config"], &program_id);
+
+    let mut analyzer = PdaAnalyzer::with_config(SearchConfig {
+        singleton_strings: vec!["a".to_string(), "config".to_string()],
+        ..Default::default()
+    });
+    let short_match = analyzer.analyze_pda(&short, &program_id).unwrap().unwrap();
+    assert!(short_match.short_string_match);
+    assert!(short_match.confidence <= 0.5);
+    assert_eq!(analyzer.short_string_matches(), 1);
+
+    let config_match = analyzer.analyze_pda(&config, &program_id).unwrap().unwrap();
+    assert!(!config_match.short_string_match);
+    assert_eq!(analyzer.short_string_matches(), 1);
+
+    let mut ranked = vec![short_match, config_match];
+    rank_results(&mut ranked);
+    assert_eq!(ranked[0].pda_info.address, config);
+
+    // The threshold is configurable
+    let mut analyzer = PdaAnalyzer::with_config(SearchConfig {
+        singleton_strings: vec!["a".to_string()],
+        min_confident_string_len: 1,
+        ..Default::default()
+    });
+    assert!(!analyzer.analyze_pda(&short, &program_id).unwrap().unwrap().short_string_match);
+}
+
+#[test]
+fn test_stake_pattern_recovery() {
+    let program_id = Pubkey::new_unique();
+    let vote = Pubkey::new_unique();
+    let pool = Pubkey::new_unique();
+    let (record, _) = Pubkey::find_program_address(&[b"validator", vote.as_ref()], &program_id);
+    let (stake, _) = Pubkey::find_program_address(&[b"stake", pool.as_ref(), vote.as_ref()], &program_id);
+    let (spl_stake, _) = Pubkey::find_program_address(&[vote.as_ref(), pool.as_ref()], &program_id);
+
+    // Nothing to try without vote accounts
+    let mut analyzer = PdaAnalyzer::with_config(SearchConfig {
+        stake_pools: vec![pool],
+        ..Default::default()
+    });
+    assert!(analyzer.analyze_pda(&record, &program_id).unwrap().is_none());
+
+    analyzer.candidates_mut().add_vote_account(vote);
+    let result = analyzer.analyze_pda(&record, &program_id).unwrap().unwrap();
+    assert_eq!(result.pattern, PdaPattern::ValidatorRecord);
+    assert_eq!(result.match_kind, MatchKind::DictionaryHit);
+    assert_eq!(result.pda_info.seeds, vec![SeedValue::String("validator".to_string()), SeedValue::Pubkey(vote)]);
+
+    let result = analyzer.analyze_pda(&stake, &program_id).unwrap().unwrap();
+    assert_eq!(result.pattern, PdaPattern::StakePoolValidator);
+    assert_eq!(result.pda_info.seeds[1], SeedValue::Pubkey(pool));
+
+    let result = analyzer.analyze_pda(&spl_stake, &program_id).unwrap().unwrap();
+    assert_eq!(result.pattern, PdaPattern::StakePoolValidator);
+    assert_eq!(result.pda_info.seeds, vec![SeedValue::Pubkey(vote), SeedValue::Pubkey(pool)]);
+
+    let filter = PdaPattern::parse_filter("stake").unwrap();
+    assert!(filter.contains(&PdaPattern::ValidatorRecord) && filter.contains(&PdaPattern::StakePoolValidator));
+}
+
+#[test]
+fn test_pattern_priority_reorders_families() {
+    let program_id = Pubkey::new_unique();
+    let authority = Pubkey::new_unique();
+    let (nonce, _) = Pubkey::find_program_address(&[authority.as_ref(), &3u64.to_le_bytes()], &program_id);
+    let first_tried = |analyzer: &mut PdaAnalyzer| {
+        let (result, attempts) = analyzer.analyze_pda_traced(&nonce, &program_id).unwrap();
+        (result.unwrap().pattern, attempts[0].pattern.clone())
+    };
+
+    let config = SearchConfig {
+        authority_pubkeys: vec![authority],
+        ..Default::default()
+    };
+    let mut analyzer = PdaAnalyzer::with_config(config.clone());
+    assert_eq!(first_tried(&mut analyzer), (PdaPattern::PubkeyU64, PdaPattern::StringSingleton));
+
+    // A DEX's nonce accounts are searched for before anything else
+    let mut analyzer = PdaAnalyzer::with_config(SearchConfig {
+        pattern_priority: PdaPattern::parse_priority("PUBKEY_U64").unwrap(),
+        ..config
+    });
+    let (pattern, first) = first_tried(&mut analyzer);
+    assert_eq!(pattern, PdaPattern::PubkeyU64);
+    assert!(PdaPattern::parse_filter("authority").unwrap().contains(&first));
+
+    assert_eq!(
+        PdaPattern::parse_priority("sequential, authority, SEQUENTIAL").unwrap()[..2],
+        [PdaPattern::Sequential, PdaPattern::StringAuthority]
+    );
+    assert!(PdaPattern::parse_priority("bogus").is_err());
+}
+
+#[test]
+fn test_market_user_positions_solved_as_a_set() {
+    let program_id = Pubkey::new_unique();
+    let markets: Vec<Pubkey> = (0..3).map(|_| Pubkey::new_unique()).collect();
+    let users: Vec<Pubkey> = (0..4).map(|_| Pubkey::new_unique()).collect();
+    let position = |market: &Pubkey, user: &Pubkey| {
+        Pubkey::find_program_address(&[b"obligation", market.as_ref(), user.as_ref()], &program_id).0
+    };
+    let first = position(&markets[2], &users[1]);
+    let second = position(&markets[0], &users[3]);
+    let (unrelated, _) = Pubkey::find_program_address(&[b"config"], &Pubkey::new_unique());
+
+    let mut analyzer = PdaAnalyzer::new();
+    analyzer.register_seed_strings(program_id, vec!["obligation".to_string()]);
+    let outcome = analyzer
+        .solve_market_user_positions(&program_id, &[second, unrelated, first], &markets, &users)
+        .unwrap();
+
+    let solved: Vec<Pubkey> = outcome.solved.iter().map(|result| result.pda_info.address).collect();
+    assert_eq!(solved, vec![second, first]);
+    assert_eq!(outcome.unsolved, vec![unrelated]);
+    assert!(outcome.combinations > 0);
+
+    let result = &outcome.solved[1];
+    assert_eq!(result.pattern, PdaPattern::StringMarketUser);
+    assert_eq!(
+        result.pda_info.seeds,
+        vec![SeedValue::String("obligation".to_string()), SeedValue::Pubkey(markets[2]), SeedValue::Pubkey(users[1])]
+    );
+    assert!(PdaDeriver::new().verify_pda(&first, &program_id, &result.pda_info.seeds).unwrap());
+
+    // Nothing left to find once every target is solved
+    let outcome = analyzer.solve_market_user_positions(&program_id, &[], &markets, &users).unwrap();
+    assert_eq!(outcome.combinations, 0);
+}
+
+#[test]
+fn test_recovered_addresses_are_off_curve() {
+    let program_id = Pubkey::new_unique();
+    let (config, _) = Pubkey::find_program_address(&[b"config"], &program_id);
+    let (pool, _) = Pubkey::find_program_address(&[b"pool", &3u64.to_le_bytes()], &program_id);
+
+    let mut analyzer = PdaAnalyzer::new();
+    for address in [config, pool] {
+        let result = analyzer.analyze_pda(&address, &program_id).unwrap().unwrap();
+        assert!(result.off_curve);
+
+        // Results serialized before the field existed read back as off curve
+        let mut json = serde_json::to_value(&result).unwrap();
+        assert_eq!(json["off_curve"], true);
+        json.as_object_mut().unwrap().remove("off_curve");
+        let result: solana_pda_analyzer_core::PdaAnalysisResult = serde_json::from_value(json).unwrap();
+        assert!(result.off_curve);
+    }
+}
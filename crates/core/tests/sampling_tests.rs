@@ -0,0 +1,44 @@
+use rand::rngs::StdRng;
+use rand::SeedableRng;
+use solana_pda_analyzer_core::{PatternHistogram, Reservoir};
+
+#[test]
+fn test_reservoir_keeps_everything_under_capacity() {
+    let mut rng = StdRng::seed_from_u64(7);
+    let mut reservoir = Reservoir::new(10);
+    for i in 0..4 {
+        reservoir.offer(i, &mut rng);
+    }
+
+    assert_eq!(reservoir.seen(), 4);
+    assert_eq!(reservoir.into_items(), vec![0, 1, 2, 3]);
+}
+
+#[test]
+fn test_reservoir_samples_uniformly() {
+    let mut rng = StdRng::seed_from_u64(7);
+    let mut hits = [0u32; 100];
+
+    for _ in 0..2_000 {
+        let mut reservoir = Reservoir::new(10);
+        for i in 0..100 {
+            reservoir.offer(i, &mut rng);
+        }
+        assert_eq!(reservoir.seen(), 100);
+        for item in reservoir.into_items() {
+            hits[item] += 1;
+        }
+    }
+
+    // Each item is expected in 10% of samples, i.e. 200 times
+    assert!(hits.iter().all(|&count| (120..=280).contains(&count)), "{:?}", hits);
+}
+
+#[test]
+fn test_histogram_extrapolates_to_population() {
+    let sample = PatternHistogram::from_counts([("SEQUENTIAL".to_string(), 30), ("UNRECOGNIZED".to_string(), 10)]);
+    assert_eq!(
+        sample.extrapolate(10_000),
+        vec![("SEQUENTIAL".to_string(), 7_500), ("UNRECOGNIZED".to_string(), 2_500)]
+    );
+}
@@ -0,0 +1,66 @@
+use solana_pda_analyzer_core::*;
+use solana_sdk::pubkey::Pubkey;
+
+fn words(words: &[&str]) -> Vec<SeedValue> {
+    words.iter().map(|w| SeedValue::String(w.to_string())).collect()
+}
+
+#[test]
+fn test_combinations_are_deterministic() {
+    let search = DictionarySearch::new(words(&["a", "b", "c", "a"]), 2);
+
+    // Duplicates are dropped, leaving 3 singles + 9 pairs
+    assert_eq!(search.vocabulary().len(), 3);
+    assert_eq!(search.total_combinations(), 12);
+
+    let seeds = |index| search.combination(index).map(|s| s.iter().map(SeedValue::as_bytes).collect::<Vec<_>>());
+    assert_eq!(seeds(0), Some(vec![b"a".to_vec()]));
+    assert_eq!(seeds(3), Some(vec![b"a".to_vec(), b"a".to_vec()]));
+    assert_eq!(seeds(11), Some(vec![b"c".to_vec(), b"c".to_vec()]));
+    assert_eq!(seeds(12), None);
+}
+
+#[test]
+fn test_search_resumes_from_checkpoint() {
+    let program_id = Pubkey::new_unique();
+    let (address, expected_bump) = Pubkey::find_program_address(&[b"pool", b"vault"], &program_id);
+    let search = DictionarySearch::new(words(&["config", "pool", "state", "vault"]), 2)
+        .with_checkpoint_interval(2);
+
+    let mut checkpoints = Vec::new();
+    let outcome = search.run(&address, &program_id, 0, |next| {
+        checkpoints.push(search.checkpoint(&address, &program_id, next));
+        Ok(())
+    }).unwrap();
+    let SearchOutcome::Found { bump, index, .. } = outcome else {
+        panic!("expected seeds to be found");
+    };
+    assert_eq!(bump, expected_bump);
+
+    // Resuming from any checkpoint before the match finds the same combination
+    let checkpoint = checkpoints.iter().rev().find(|c| c.next_index <= index).unwrap();
+    let path = std::env::temp_dir().join(format!("pda-search-{}.json", program_id));
+    checkpoint.save(&path).unwrap();
+    let loaded = SearchCheckpoint::load(&path).unwrap();
+    std::fs::remove_file(&path).unwrap();
+    assert_eq!(&loaded, checkpoint);
+
+    let start = search.resume_index(&loaded, &address, &program_id).unwrap();
+    match search.run(&address, &program_id, start, |_| Ok(())).unwrap() {
+        SearchOutcome::Found { index: resumed, .. } => assert_eq!(resumed, index),
+        SearchOutcome::Exhausted { .. } => panic!("resumed search should find the seeds"),
+    }
+}
+
+#[test]
+fn test_checkpoint_rejects_other_vocabulary() {
+    let program_id = Pubkey::new_unique();
+    let address = Pubkey::new_unique();
+    let search = DictionarySearch::new(words(&["pool", "vault"]), 2);
+    let checkpoint = search.checkpoint(&address, &program_id, 3);
+
+    let other = DictionarySearch::new(words(&["vault", "pool"]), 2);
+    assert!(other.resume_index(&checkpoint, &address, &program_id).is_err());
+    assert!(search.resume_index(&checkpoint, &Pubkey::new_unique(), &program_id).is_err());
+    assert_eq!(search.resume_index(&checkpoint, &address, &program_id).unwrap(), 3);
+}
@@ -0,0 +1,84 @@
+use proptest::prelude::*;
+use solana_pda_analyzer_core::SeedValue;
+use solana_sdk::pubkey::Pubkey;
+
+fn any_seed() -> impl Strategy<Value = SeedValue> {
+    prop_oneof![
+        any::<String>().prop_map(SeedValue::String),
+        prop::collection::vec(any::<u8>(), 0..64).prop_map(SeedValue::Bytes),
+        any::<[u8; 32]>().prop_map(SeedValue::Hash),
+        any::<[u8; 32]>().prop_map(|bytes| SeedValue::Pubkey(Pubkey::new_from_array(bytes))),
+        any::<u64>().prop_map(SeedValue::U64),
+        any::<u32>().prop_map(SeedValue::U32),
+        any::<u16>().prop_map(SeedValue::U16),
+        any::<u8>().prop_map(SeedValue::U8),
+    ]
+}
+
+/// Specs with a known or near-miss type prefix, so values reach the per-type parsers
+fn spec_like() -> impl Strategy<Value = String> {
+    let seed_type = prop_oneof![
+        prop::sample::select(vec![
+            "str", "String", "bytes", "hash", "pubkey", "PUBKEY", "u64", "u32", "u16", "u8", " u8 ", "digest", "",
+        ])
+        .prop_map(str::to_string),
+        "[a-zA-Z0-9]{0,8}",
+    ];
+    let value = prop_oneof![
+        "(0x)?[0-9a-fA-F]{0,70}",
+        "[1-9A-HJ-NP-Za-km-z]{0,50}",
+        "-?[0-9]{0,25}",
+        any::<String>(),
+    ];
+    (seed_type, value).prop_map(|(seed_type, value)| format!("{}:{}", seed_type, value))
+}
+
+proptest! {
+    #[test]
+    fn prop_parse_spec_never_panics(spec in any::<String>()) {
+        let _ = SeedValue::parse_spec(&spec);
+        let _ = SeedValue::parse_specs(&spec);
+    }
+
+    #[test]
+    fn prop_spec_like_input_reparses_to_same_bytes(spec in spec_like()) {
+        // Whatever parses must survive encode -> reparse with its bytes intact
+        if let Ok(seed) = SeedValue::parse_spec(&spec) {
+            let reparsed = SeedValue::parse_spec(&seed.to_spec()).unwrap();
+            prop_assert_eq!(reparsed.as_bytes(), seed.as_bytes());
+            prop_assert_eq!(reparsed.to_spec(), seed.to_spec());
+        }
+    }
+
+    #[test]
+    fn prop_to_spec_round_trips(seed in any_seed()) {
+        let spec = seed.to_spec();
+        let reparsed = SeedValue::parse_spec(&spec).unwrap();
+        prop_assert_eq!(reparsed.as_bytes(), seed.as_bytes());
+        // Reparsing is stable: the spec doesn't drift on a second pass
+        prop_assert_eq!(reparsed.to_spec(), spec);
+        match (&seed, &reparsed) {
+            // Strings that can't be written as `str:` come back as the same bytes
+            (SeedValue::String(_), SeedValue::Bytes(_)) => {}
+            _ => prop_assert_eq!(reparsed.seed_type(), seed.seed_type()),
+        }
+    }
+
+    #[test]
+    fn prop_joined_specs_round_trip(seeds in prop::collection::vec(any_seed(), 0..8)) {
+        let specs: Vec<String> = seeds.iter().map(SeedValue::to_spec).collect();
+        let reparsed = SeedValue::parse_specs(&specs.join(" ")).unwrap();
+        prop_assert_eq!(reparsed.len(), seeds.len());
+        for (seed, reparsed) in seeds.iter().zip(&reparsed) {
+            prop_assert_eq!(reparsed.as_bytes(), seed.as_bytes());
+        }
+    }
+
+    #[test]
+    fn prop_fixed_width_seeds_encode_little_endian(n in any::<u64>()) {
+        prop_assert_eq!(SeedValue::U64(n).as_bytes(), n.to_le_bytes().to_vec());
+        prop_assert_eq!(SeedValue::U32(n as u32).as_bytes(), (n as u32).to_le_bytes().to_vec());
+        prop_assert_eq!(SeedValue::U16(n as u16).as_bytes(), (n as u16).to_le_bytes().to_vec());
+        prop_assert_eq!(SeedValue::U8(n as u8).as_bytes(), vec![n as u8]);
+    }
+}
@@ -1,12 +1,11 @@
 use solana_pda_analyzer_core::{
     TransactionAnalyzer, AccountState, InteractionType, 
-    TransactionAnalysis, PdaInteraction
+    TransactionAnalysis, PdaInteraction, PdaInfo, ProgramInfo, SeedValue
 };
 use solana_sdk::{
     pubkey::Pubkey, 
     transaction::Transaction, 
     message::Message,
-    instruction::Instruction,
     signature::Keypair,
     signer::Signer,
     system_instruction,
@@ -27,7 +26,7 @@ fn create_test_account_state(pubkey: Pubkey, lamports: u64, data: Vec<u8>) -> Ac
 #[test]
 fn test_transaction_analyzer_creation() {
     let analyzer = TransactionAnalyzer::new();
-    assert!(analyzer.known_programs.len() > 0);
+    assert!(!analyzer.known_programs().is_empty());
     assert!(analyzer.get_program_name(&solana_sdk::system_program::id()).is_some());
 }
 
@@ -239,7 +238,7 @@ fn test_transaction_analysis_with_error() {
     assert!(result.is_ok());
     let analysis = result.unwrap();
     assert_eq!(analysis.signature, signature);
-    assert_eq!(analysis.success, false);
+    assert!(!analysis.success);
     assert_eq!(analysis.error_message, error_message);
 }
 
@@ -291,7 +290,7 @@ fn test_multiple_instructions_transaction() {
     assert!(result.is_ok());
     let analysis = result.unwrap();
     assert_eq!(analysis.signature, signature);
-    assert_eq!(analysis.success, true);
+    assert!(analysis.success);
     // Transaction has 2 instructions
     assert_eq!(transaction.message.instructions.len(), 2);
 }
@@ -304,7 +303,7 @@ fn test_instruction_analysis() {
     let recipient = Keypair::new();
     
     let instruction = system_instruction::transfer(&payer.pubkey(), &recipient.pubkey(), 1000);
-    let message = Message::new(&[instruction.clone()], Some(&payer.pubkey()));
+    let message = Message::new(std::slice::from_ref(&instruction), Some(&payer.pubkey()));
     
     let analysis = InstructionAnalysis::from_instruction(&instruction, &message);
     
@@ -348,4 +347,180 @@ fn test_empty_transaction_analysis() {
     assert_eq!(analysis.signature, signature);
     assert_eq!(analysis.pda_interactions.len(), 0);
     assert_eq!(analysis.discovered_pdas.len(), 0);
-}
\ No newline at end of file
+}
+
+fn sample_analysis() -> TransactionAnalysis {
+    let pda = Pubkey::new_unique();
+    let program_id = Pubkey::new_unique();
+
+    let mut write = PdaInteraction::new(pda, 1, InteractionType::Write);
+    write.data_before = Some(vec![1, 2, 3]);
+    write.data_after = Some(vec![4, 5, 6]);
+    write.lamports_before = Some(1000);
+    write.lamports_after = Some(900);
+
+    TransactionAnalysis::builder("round_trip_tx", 42)
+        .block_time(DateTime::from_timestamp(1_700_000_000, 0).unwrap())
+        .failed("custom program error: 0x1")
+        .interaction(PdaInteraction::new(pda, 0, InteractionType::Read))
+        .interaction(write)
+        .discovered_pda(PdaInfo {
+            address: pda,
+            program_id,
+            seeds: vec![SeedValue::String("vault".to_string()), SeedValue::U64(7)],
+            bump: 254,
+            first_seen_slot: Some(42),
+            first_seen_transaction: Some("round_trip_tx".to_string()),
+        })
+        .build()
+}
+
+#[test]
+fn test_transaction_analysis_builder() {
+    let analysis = TransactionAnalysis::builder("built_tx", 7).build();
+    assert!(analysis.success);
+    assert!(analysis.block_time.is_none());
+    assert!(analysis.pda_interactions.is_empty());
+
+    let analysis = sample_analysis();
+    assert!(!analysis.success);
+    assert_eq!(analysis.error_message.as_deref(), Some("custom program error: 0x1"));
+    assert_eq!(analysis.pda_interactions.len(), 2);
+    assert_eq!(analysis.pda_interactions[1].interaction_type, InteractionType::Write);
+
+    // The same PDA touched twice is only discovered once
+    let pda = analysis.discovered_pdas[0].clone();
+    let analysis = TransactionAnalysis::builder("built_tx", 7)
+        .discovered_pda(pda.clone())
+        .discovered_pda(pda)
+        .build();
+    assert_eq!(analysis.discovered_pdas.len(), 1);
+}
+
+#[test]
+fn test_transaction_analysis_serde_round_trip() {
+    let analysis = sample_analysis();
+
+    let json = serde_json::to_string(&analysis).unwrap();
+    let decoded: TransactionAnalysis = serde_json::from_str(&json).unwrap();
+
+    assert_eq!(decoded.signature, analysis.signature);
+    assert_eq!(decoded.slot, analysis.slot);
+    assert_eq!(decoded.block_time, analysis.block_time);
+    assert_eq!(decoded.error_message, analysis.error_message);
+    assert_eq!(decoded.pda_interactions.len(), 2);
+    assert_eq!(decoded.pda_interactions[1].data_after, Some(vec![4, 5, 6]));
+    assert_eq!(decoded.pda_interactions[1].lamports_after, Some(900));
+    assert_eq!(decoded.discovered_pdas[0].address, analysis.discovered_pdas[0].address);
+    assert_eq!(decoded.discovered_pdas[0].bump, 254);
+}
+
+#[test]
+fn test_pda_interaction_json_shape() {
+    // Interactions are persisted as JSON; renaming a field breaks stored rows
+    let analysis = sample_analysis();
+    let value = serde_json::to_value(&analysis.pda_interactions).unwrap();
+    let interaction = value[1].as_object().unwrap();
+
+    let mut keys: Vec<&str> = interaction.keys().map(String::as_str).collect();
+    keys.sort_unstable();
+    assert_eq!(
+        keys,
+        vec![
+            "data_after", "data_before", "instruction_index", "interaction_type",
+            "lamports_after", "lamports_before", "pda_address",
+        ]
+    );
+    assert_eq!(interaction["interaction_type"], "Write");
+    assert_eq!(interaction["instruction_index"], 1);
+
+    let decoded: Vec<PdaInteraction> = serde_json::from_value(value).unwrap();
+    assert_eq!(decoded[0].interaction_type, InteractionType::Read);
+}
+
+#[test]
+fn test_program_info_serde_round_trip() {
+    let info = ProgramInfo {
+        id: uuid::Uuid::new_v4(),
+        program_id: Pubkey::new_unique(),
+        name: Some("Escrow".to_string()),
+        description: None,
+        total_pdas: 12,
+        total_transactions: 340,
+    };
+
+    let decoded: ProgramInfo = serde_json::from_str(&serde_json::to_string(&info).unwrap()).unwrap();
+    assert_eq!(decoded.id, info.id);
+    assert_eq!(decoded.program_id, info.program_id);
+    assert_eq!(decoded.name, info.name);
+    assert_eq!(decoded.total_transactions, 340);
+}
+
+#[test]
+fn test_transaction_analysis_validate() {
+    use solana_sdk::{hash::Hash, instruction::{AccountMeta, Instruction}};
+
+    let payer = Keypair::new();
+    let program_id = Pubkey::new_unique();
+    let vault = Pubkey::new_unique();
+    let config = Pubkey::new_unique();
+    let instruction = Instruction::new_with_bytes(
+        program_id,
+        &[],
+        vec![AccountMeta::new(vault, false), AccountMeta::new_readonly(config, false)],
+    );
+    let message = Message::new(&[instruction], Some(&payer.pubkey()));
+    let transaction = Transaction::new(&[&payer], message, Hash::default());
+    let signature = transaction.signatures[0].to_string();
+
+    let analysis = TransactionAnalysis::builder(signature.clone(), 1)
+        .interaction(PdaInteraction::new(vault, 0, InteractionType::Write))
+        .interaction(PdaInteraction::new(config, 0, InteractionType::Read))
+        .build();
+    assert!(analysis.validate(&transaction).is_ok());
+
+    let fabricated = [
+        // Someone else's signature
+        TransactionAnalysis::builder("forged", 1).build(),
+        // An account the transaction never passed
+        TransactionAnalysis::builder(signature.clone(), 1)
+            .interaction(PdaInteraction::new(Pubkey::new_unique(), 0, InteractionType::Read))
+            .build(),
+        // An instruction that doesn't exist
+        TransactionAnalysis::builder(signature.clone(), 1)
+            .interaction(PdaInteraction::new(vault, 3, InteractionType::Write))
+            .build(),
+        // A write to a read-only account
+        TransactionAnalysis::builder(signature, 1)
+            .interaction(PdaInteraction::new(config, 0, InteractionType::Close))
+            .build(),
+    ];
+    for analysis in &fabricated {
+        assert!(analysis.validate(&transaction).is_err());
+    }
+}
+
+#[test]
+fn test_account_data_hash() {
+    // SHA-256 of the empty string
+    assert_eq!(
+        TransactionAnalyzer::hash_account_data(&[]),
+        "e3b0c44298fc1c149afbf4c8996fb92427ae41e4649b934ca495991b7852b855"
+    );
+    assert_ne!(TransactionAnalyzer::hash_account_data(b"a"), TransactionAnalyzer::hash_account_data(b"b"));
+
+    let pda = Pubkey::new_unique();
+    let mut first = PdaInteraction::new(pda, 0, InteractionType::Write);
+    first.data_after = Some(vec![1]);
+    let mut second = PdaInteraction::new(pda, 1, InteractionType::Write);
+    second.data_after = Some(vec![2]);
+    let analysis = TransactionAnalysis::builder("hash_tx", 1)
+        .interaction(first)
+        .interaction(second)
+        .interaction(PdaInteraction::new(pda, 2, InteractionType::Read))
+        .build();
+
+    // The last recorded post-transaction data wins
+    assert_eq!(analysis.data_hash(&pda), Some(TransactionAnalyzer::hash_account_data(&[2])));
+    assert_eq!(analysis.data_hash(&Pubkey::new_unique()), None);
+}
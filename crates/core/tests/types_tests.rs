@@ -0,0 +1,280 @@
+use solana_pda_analyzer_core::*;
+use solana_sdk::pubkey::Pubkey;
+
+fn every_seed_variant() -> Vec<SeedValue> {
+    vec![
+        SeedValue::String("vault".to_string()),
+        SeedValue::Bytes(vec![1, 2, 3]),
+        SeedValue::sha256(b"vault"),
+        SeedValue::Pubkey(Pubkey::new_unique()),
+        SeedValue::U64(1),
+        SeedValue::U32(1),
+        SeedValue::U16(1),
+        SeedValue::U8(1),
+    ]
+}
+
+#[test]
+fn test_seed_types_are_canonical() {
+    let seed_types: Vec<&str> = every_seed_variant().iter().map(SeedValue::seed_type).collect();
+    assert_eq!(seed_types, SeedValue::SEED_TYPES);
+
+    for seed_type in seed_types {
+        assert_eq!(SeedValue::normalize_seed_type(seed_type), Some(seed_type));
+    }
+}
+
+#[test]
+fn test_templates_accept_serde_tag_casing() {
+    for seed in every_seed_variant() {
+        let tag = serde_json::to_value(&seed).unwrap();
+        let tag = tag["type"].as_str().unwrap().to_string();
+
+        let template = SeedTemplate {
+            name: "seed".to_string(),
+            seed_type: tag.clone(),
+            description: None,
+            is_variable: true,
+        };
+        assert!(template.accepts(&seed), "template type {} should accept {:?}", tag, seed);
+    }
+
+    assert_eq!(SeedValue::normalize_seed_type("Hash"), Some("hash"));
+    assert_eq!(SeedValue::normalize_seed_type("Digest"), None);
+}
+
+#[test]
+fn test_parse_seed_specs() {
+    let pubkey = Pubkey::new_unique();
+
+    assert!(matches!(SeedValue::parse_spec("str:vault").unwrap(), SeedValue::String(s) if s == "vault"));
+    assert!(matches!(SeedValue::parse_spec("String:a:b").unwrap(), SeedValue::String(s) if s == "a:b"));
+    assert!(matches!(SeedValue::parse_spec(&format!("pubkey:{}", pubkey)).unwrap(), SeedValue::Pubkey(p) if p == pubkey));
+    assert!(matches!(SeedValue::parse_spec("u64:7").unwrap(), SeedValue::U64(7)));
+    assert!(matches!(SeedValue::parse_spec("u8:255").unwrap(), SeedValue::U8(255)));
+    assert!(matches!(SeedValue::parse_spec("bytes:0x0a0b").unwrap(), SeedValue::Bytes(b) if b == [10, 11]));
+
+    assert!(SeedValue::parse_spec("vault").is_err());
+    assert!(matches!(SeedValue::parse_spec(&format!("hash:{}", "ab".repeat(32))).unwrap(), SeedValue::Hash(h) if h == [0xab; 32]));
+    assert!(SeedValue::parse_spec("hash:abcd").is_err());
+    assert!(SeedValue::parse_spec("digest:abc").is_err());
+    assert!(SeedValue::parse_spec("u8:256").is_err());
+    assert!(SeedValue::parse_spec("pubkey:not-a-key").is_err());
+}
+
+#[test]
+fn test_pattern_histogram_ranks_and_percentages() {
+    let histogram = PatternHistogram::from_counts(vec![
+        ("SEQUENTIAL".to_string(), 2),
+        ("WALLET_TOKEN_MINT".to_string(), 5),
+        ("COMPLEX".to_string(), 2),
+        ("STRING_SINGLETON".to_string(), 1),
+    ]);
+
+    assert_eq!(histogram.total, 10);
+    let ranked: Vec<(usize, &str, f64)> = histogram
+        .patterns
+        .iter()
+        .map(|entry| (entry.rank, entry.stat.pattern.as_str(), entry.stat.percentage))
+        .collect();
+    assert_eq!(
+        ranked,
+        vec![
+            (1, "WALLET_TOKEN_MINT", 50.0),
+            (2, "COMPLEX", 20.0),
+            (2, "SEQUENTIAL", 20.0),
+            (4, "STRING_SINGLETON", 10.0),
+        ]
+    );
+
+    let json = serde_json::to_value(&histogram.patterns[0]).unwrap();
+    assert_eq!(json["rank"], 1);
+    assert_eq!(json["pattern"], "WALLET_TOKEN_MINT");
+    assert_eq!(json["description"], PdaPattern::AssociatedTokenAccount.description());
+
+    assert_eq!(json["category"], "token");
+    assert_eq!(histogram.by_category(), vec![(PatternCategory::Token, 5), (PatternCategory::Generic, 5)]);
+
+    let unrecognized = PatternHistogram::from_counts(vec![("UNRECOGNIZED".to_string(), 1)]);
+    assert!(unrecognized.patterns[0].description.is_none());
+    assert!(unrecognized.patterns[0].category.is_none());
+    assert!(unrecognized.by_category().is_empty());
+
+    assert_eq!(PatternHistogram::from_counts(Vec::new()).total, 0);
+}
+
+#[test]
+fn test_plausibility_score_ranks_seed_entropy() {
+    let pda = |seeds: Vec<SeedValue>| PdaInfo {
+        address: Pubkey::new_unique(),
+        program_id: Pubkey::new_unique(),
+        seeds,
+        bump: 255,
+        first_seen_slot: None,
+        first_seen_transaction: None,
+    };
+
+    let vocabulary = pda(vec![SeedValue::String("vault".to_string())]).plausibility_score();
+    let pubkey = pda(vec![SeedValue::Pubkey(Pubkey::new_unique())]).plausibility_score();
+    let zeros = pda(vec![SeedValue::Bytes(vec![0; 8])]).plausibility_score();
+    let sparse = pda(vec![SeedValue::Bytes(vec![0, 0, 0, 0, 0, 0, 0, 7])]).plausibility_score();
+    let noisy = pda(vec![SeedValue::Bytes((0..8).map(|i| i * 31).collect())]).plausibility_score();
+    let mixed = pda(vec![SeedValue::Pubkey(Pubkey::new_unique()), SeedValue::Bytes(vec![0; 8])]).plausibility_score();
+
+    assert_eq!(vocabulary, 1.0);
+    assert_eq!(pubkey, 1.0);
+    assert!(zeros < sparse && sparse < noisy);
+    assert!(zeros < mixed && mixed < 1.0);
+}
+
+#[test]
+fn test_seed_length_typicality() {
+    let pda = |seeds: Vec<SeedValue>| PdaInfo {
+        address: Pubkey::new_unique(),
+        program_id: Pubkey::new_unique(),
+        seeds,
+        bump: 255,
+        first_seen_slot: None,
+        first_seen_transaction: None,
+    };
+
+    let conventional = pda(vec![
+        SeedValue::String("vault".to_string()),
+        SeedValue::Pubkey(Pubkey::new_unique()),
+        SeedValue::Bytes(42u64.to_le_bytes().to_vec()),
+    ]);
+    let long_name = pda(vec![SeedValue::String("a_rather_long_seed_name".to_string())]);
+    let odd_bytes = pda(vec![SeedValue::Bytes(vec![1, 2, 3, 4, 5])]);
+
+    assert_eq!(conventional.seed_length_typicality(), 1.0);
+    assert_eq!(pda(Vec::new()).seed_length_typicality(), 1.0);
+    assert!(odd_bytes.seed_length_typicality() < long_name.seed_length_typicality());
+    assert!(long_name.seed_length_typicality() < 1.0);
+}
+
+#[test]
+fn test_seed_display_escapes_unprintable_strings() {
+    let seed = SeedValue::String("vault\u{1}\n\"x\"".to_string());
+    assert_eq!(seed.to_string(), r#""vault\u{1}\n\"x\"""#);
+    assert_eq!(SeedValue::String("vault".to_string()).to_string(), "\"vault\"");
+    assert_eq!(SeedValue::Bytes(vec![0xde, 0xad]).to_string(), "0xdead");
+    assert_eq!(SeedValue::U16(7).to_string(), "7");
+    assert_eq!(
+        SeedValue::sha256(b"abc").to_string(),
+        "0xba7816bf8f01cfea414140de5dae2223b00361a396177a9cb410ff61f20015ad"
+    );
+
+    // Control bytes and invalid UTF-8 stay bytes, so nothing is lost or garbled
+    assert_eq!(SeedValue::from_seed_bytes(b"vault"), SeedValue::String("vault".to_string()));
+    assert_eq!(SeedValue::from_seed_bytes(b"va\x00ult"), SeedValue::Bytes(b"va\x00ult".to_vec()));
+    assert_eq!(SeedValue::from_seed_bytes(&[0xc3, 0x28]), SeedValue::Bytes(vec![0xc3, 0x28]));
+    assert_eq!(SeedValue::from_seed_bytes(&[0xc3, 0x28]).to_string(), "0xc328");
+}
+
+#[test]
+fn test_seed_identity_follows_encoding() {
+    use std::collections::HashSet;
+
+    assert_eq!(SeedValue::String("a".to_string()), SeedValue::U8(b'a'));
+    assert_eq!(SeedValue::Bytes(7u32.to_le_bytes().to_vec()), SeedValue::U32(7));
+    assert_ne!(SeedValue::U32(7), SeedValue::U64(7));
+
+    let seeds: HashSet<SeedValue> = [SeedValue::String("ab".to_string()), SeedValue::Bytes(b"ab".to_vec()), SeedValue::U16(0x6261)]
+        .into_iter()
+        .collect();
+    assert_eq!(seeds.len(), 1);
+
+    let typed = vec![SeedValue::String("pool".to_string()), SeedValue::U64(1)];
+    let raw = vec![SeedValue::Bytes(b"pool".to_vec()), SeedValue::Bytes(1u64.to_le_bytes().to_vec())];
+    assert_eq!(seeds_fingerprint(&typed), seeds_fingerprint(&raw));
+    assert_ne!(
+        seeds_fingerprint(&[SeedValue::String("ab".to_string()), SeedValue::String("c".to_string())]),
+        seeds_fingerprint(&[SeedValue::String("a".to_string()), SeedValue::String("bc".to_string())])
+    );
+
+    // A cached derivation reports the seeds as each caller typed them
+    let program_id = Pubkey::new_unique();
+    let mut deriver = PdaDeriver::new();
+    deriver.derive_pda(&program_id, &typed).unwrap();
+    let cached = deriver.derive_pda(&program_id, &raw).unwrap();
+    assert!(matches!(cached.seeds[0], SeedValue::Bytes(_)));
+}
+
+#[test]
+fn test_equivalent_seeds_normalize_to_one_form() {
+    let pubkey = Pubkey::new_unique();
+    // Raw bytes take the typed form they fit
+    let cases = [
+        (SeedValue::U32(5), SeedValue::Bytes(vec![5, 0, 0, 0])),
+        (SeedValue::String("vault".to_string()), SeedValue::Bytes(b"vault".to_vec())),
+        (SeedValue::Pubkey(pubkey), SeedValue::Bytes(pubkey.to_bytes().to_vec())),
+        (SeedValue::U64(1), SeedValue::Bytes(1u64.to_le_bytes().to_vec())),
+        (SeedValue::Bytes(vec![0xff; 3]), SeedValue::Bytes(vec![0xff; 3])),
+    ];
+    for (typed, raw) in cases {
+        assert_eq!(typed.normalized().to_spec(), typed.to_spec());
+        assert_eq!(raw.normalized().to_spec(), typed.to_spec());
+        // Normalizing never changes what a seed derives
+        assert_eq!(typed.normalized().as_bytes(), typed.as_bytes());
+    }
+
+    // Typed seeds keep their type even when their bytes would also read as another one
+    for typed in [
+        SeedValue::U8(65),
+        SeedValue::U16(0x6261),
+        SeedValue::U32(u32::from_le_bytes(*b"abcd")),
+        SeedValue::U64(u64::from_le_bytes(*b"abcdefgh")),
+        SeedValue::Hash(pubkey.to_bytes()),
+    ] {
+        assert_eq!(typed.normalized().to_spec(), typed.to_spec());
+    }
+
+    // Two expressions of one derivation share a single cache entry
+    let program_id = Pubkey::new_unique();
+    let mut deriver = PdaDeriver::new();
+    let typed = deriver.derive_pda(&program_id, &[SeedValue::U32(5)]).unwrap();
+    let raw = deriver.derive_pda(&program_id, &[SeedValue::Bytes(vec![5, 0, 0, 0])]).unwrap();
+    assert_eq!((typed.address, typed.bump), (raw.address, raw.bump));
+    assert_eq!(deriver.cache_stats(), (1, 1));
+    assert_eq!(SeedValue::normalize_all(&raw.seeds)[0].to_spec(), "u32:5");
+}
+
+#[test]
+fn test_seed_json_round_trips_every_variant() {
+    for seed in every_seed_variant() {
+        let json = serde_json::to_value(&seed).unwrap();
+        assert!(json["type"].is_string() && !json["value"].is_null(), "{}", json);
+
+        let decoded: SeedValue = serde_json::from_value(json).unwrap();
+        assert_eq!((decoded.seed_type(), &decoded), (seed.seed_type(), &seed));
+    }
+
+    let pubkey = Pubkey::new_unique();
+    let json = serde_json::to_value(SeedValue::Pubkey(pubkey)).unwrap();
+    assert_eq!(json, serde_json::json!({"type": "Pubkey", "value": pubkey.to_string()}));
+}
+
+#[test]
+fn test_seed_json_reads_stored_form_and_rejects_bad_values() {
+    let pubkey = Pubkey::new_unique();
+    let stored: Vec<SeedValue> = serde_json::from_value(serde_json::json!([
+        {"String": "vault"},
+        {"Pubkey": pubkey.to_bytes()},
+        {"U64": 7}
+    ]))
+    .unwrap();
+    assert_eq!(stored, vec![SeedValue::String("vault".to_string()), SeedValue::Pubkey(pubkey), SeedValue::U64(7)]);
+    assert!(matches!(stored[1], SeedValue::Pubkey(_)));
+
+    let rejected = [
+        serde_json::json!({"type": "Pubkey", "value": "not-base58"}),
+        serde_json::json!({"type": "Hash", "value": [1, 2, 3]}),
+        serde_json::json!({"type": "U8", "value": 256}),
+        serde_json::json!({"type": "Digest", "value": "x"}),
+        serde_json::json!({"type": "String"}),
+        serde_json::json!("vault"),
+    ];
+    for json in rejected {
+        assert!(serde_json::from_value::<SeedValue>(json.clone()).is_err(), "{} should be rejected", json);
+    }
+}
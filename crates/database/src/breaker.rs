@@ -0,0 +1,170 @@
+use serde::{Deserialize, Serialize};
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+#[derive(Debug, Clone)]
+pub struct CircuitBreakerConfig {
+    /// Consecutive connection failures before the breaker opens
+    pub failure_threshold: u32,
+    /// How long to fast-fail before letting a probe request through
+    pub cooldown: Duration,
+}
+
+impl Default for CircuitBreakerConfig {
+    fn default() -> Self {
+        Self {
+            failure_threshold: 5,
+            cooldown: Duration::from_secs(30),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum CircuitState {
+    /// Requests reach the database normally
+    Closed,
+    /// The database is considered down and requests fail immediately
+    Open,
+    /// The cooldown has elapsed and a single probe request is in flight
+    HalfOpen,
+}
+
+#[derive(Debug)]
+struct BreakerState {
+    state: CircuitState,
+    consecutive_failures: u32,
+    opened_at: Option<Instant>,
+}
+
+/// Stops waiting on connection timeouts once the database has failed repeatedly
+#[derive(Debug)]
+pub struct CircuitBreaker {
+    config: CircuitBreakerConfig,
+    inner: Mutex<BreakerState>,
+}
+
+impl CircuitBreaker {
+    pub fn new(config: CircuitBreakerConfig) -> Self {
+        Self {
+            config,
+            inner: Mutex::new(BreakerState {
+                state: CircuitState::Closed,
+                consecutive_failures: 0,
+                opened_at: None,
+            }),
+        }
+    }
+
+    /// Whether a request may go to the database. Once the cooldown has elapsed the
+    /// first caller is let through as a recovery probe; others keep failing fast.
+    pub fn allow_request(&self) -> bool {
+        let mut inner = self.inner.lock().unwrap();
+        match inner.state {
+            CircuitState::Closed => true,
+            // A probe that never reports back (e.g. its request was dropped) is
+            // replaced by another one after a further cooldown
+            CircuitState::Open | CircuitState::HalfOpen => {
+                let cooled_down = inner
+                    .opened_at
+                    .is_none_or(|opened_at| opened_at.elapsed() >= self.config.cooldown);
+                if cooled_down {
+                    inner.state = CircuitState::HalfOpen;
+                    inner.opened_at = Some(Instant::now());
+                }
+                cooled_down
+            }
+        }
+    }
+
+    pub fn record_success(&self) {
+        let mut inner = self.inner.lock().unwrap();
+        inner.state = CircuitState::Closed;
+        inner.consecutive_failures = 0;
+        inner.opened_at = None;
+    }
+
+    pub fn record_failure(&self) {
+        let mut inner = self.inner.lock().unwrap();
+        inner.consecutive_failures += 1;
+
+        // A failed probe reopens immediately; otherwise wait for the threshold
+        if inner.state == CircuitState::HalfOpen || inner.consecutive_failures >= self.config.failure_threshold {
+            inner.state = CircuitState::Open;
+            inner.opened_at = Some(Instant::now());
+        }
+    }
+
+    pub fn state(&self) -> CircuitState {
+        self.inner.lock().unwrap().state
+    }
+
+    /// Time left before the next probe is allowed, if the breaker is open
+    pub fn retry_after(&self) -> Option<Duration> {
+        let inner = self.inner.lock().unwrap();
+        match (inner.state, inner.opened_at) {
+            (CircuitState::Open, Some(opened_at)) => Some(self.config.cooldown.saturating_sub(opened_at.elapsed())),
+            _ => None,
+        }
+    }
+}
+
+impl Default for CircuitBreaker {
+    fn default() -> Self {
+        Self::new(CircuitBreakerConfig::default())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn breaker(cooldown: Duration) -> CircuitBreaker {
+        CircuitBreaker::new(CircuitBreakerConfig {
+            failure_threshold: 3,
+            cooldown,
+        })
+    }
+
+    #[test]
+    fn test_breaker_opens_after_threshold() {
+        let breaker = breaker(Duration::from_secs(60));
+
+        breaker.record_failure();
+        breaker.record_failure();
+        assert_eq!(breaker.state(), CircuitState::Closed);
+        assert!(breaker.allow_request());
+
+        breaker.record_failure();
+        assert_eq!(breaker.state(), CircuitState::Open);
+        assert!(!breaker.allow_request());
+        assert!(breaker.retry_after().is_some());
+    }
+
+    #[test]
+    fn test_breaker_probes_after_cooldown() {
+        let cooldown = Duration::from_millis(20);
+        let breaker = breaker(cooldown);
+        for _ in 0..3 {
+            breaker.record_failure();
+        }
+        assert!(!breaker.allow_request());
+        std::thread::sleep(cooldown);
+
+        // Only one probe is let through at a time
+        assert!(breaker.allow_request());
+        assert_eq!(breaker.state(), CircuitState::HalfOpen);
+        assert!(!breaker.allow_request());
+
+        // A failed probe reopens straight away
+        breaker.record_failure();
+        assert_eq!(breaker.state(), CircuitState::Open);
+        assert!(!breaker.allow_request());
+        std::thread::sleep(cooldown);
+
+        assert!(breaker.allow_request());
+        breaker.record_success();
+        assert_eq!(breaker.state(), CircuitState::Closed);
+        assert!(breaker.allow_request());
+    }
+}
@@ -1,7 +1,11 @@
+pub mod breaker;
 pub mod models;
 pub mod repository;
 pub mod migrations;
+pub mod reanalyze;
 
+pub use breaker::*;
 pub use models::*;
 pub use repository::*;
-pub use migrations::*;
\ No newline at end of file
+pub use migrations::*;
+pub use reanalyze::{reanalyze_stored_pdas, Reanalysis, ReanalyzeReport};
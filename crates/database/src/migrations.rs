@@ -1,7 +1,132 @@
-use sqlx::{PgPool, migrate::MigrateDatabase, Postgres};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::{Executor, PgPool, migrate::MigrateDatabase, Postgres};
 use solana_pda_analyzer_core::{PdaAnalyzerError, Result};
 use tracing::{info, error};
 
+/// Schema migrations in the order they are applied
+const MIGRATIONS: [(&str, &str); 9] = [
+    ("001_initial_schema", include_str!("../../../migrations/001_initial_schema.sql")),
+    // GIN index backing seed containment queries on `pdas.seeds`
+    ("002_pdas_seeds_index", include_str!("../../../migrations/002_pdas_seeds_index.sql")),
+    // Recognized pattern per PDA, so per-program distributions can be grouped in SQL
+    ("003_pdas_pattern", include_str!("../../../migrations/003_pdas_pattern.sql")),
+    // Per-program share of stored PDAs no heuristic recognized
+    ("004_program_pattern_coverage", include_str!("../../../migrations/004_program_pattern_coverage.sql")),
+    // Confidence of stored analyses, so they can be served again without re-analysis
+    ("005_pdas_confidence", include_str!("../../../migrations/005_pdas_confidence.sql")),
+    // Programs whose PDAs each transaction touched, with a GIN index for containment queries
+    ("006_transactions_program_ids", include_str!("../../../migrations/006_transactions_program_ids.sql")),
+    // Slot each PDA was first seen at, and how far each program's account discovery has run
+    ("007_discovery_slots", include_str!("../../../migrations/007_discovery_slots.sql")),
+    // Stored PDA count per program, kept current by each analysis write
+    ("008_programs_pda_count", include_str!("../../../migrations/008_programs_pda_count.sql")),
+    // 006's backfill, for databases the old runner recorded it on without running it
+    ("009_backfill_transaction_program_ids", include_str!("../../../migrations/009_backfill_transaction_program_ids.sql")),
+];
+
+/// Whether one known migration has been applied
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MigrationStatus {
+    pub version: String,
+    /// `None` while the migration is pending
+    pub applied_at: Option<DateTime<Utc>>,
+}
+
+async fn ensure_migrations_table(pool: &PgPool) -> Result<()> {
+    sqlx::query(
+        "CREATE TABLE IF NOT EXISTS schema_migrations (
+            version VARCHAR(255) PRIMARY KEY,
+            applied_at TIMESTAMP WITH TIME ZONE NOT NULL DEFAULT NOW()
+        )",
+    )
+    .execute(pool)
+    .await?;
+    Ok(())
+}
+
+async fn applied_migrations(pool: &PgPool) -> Result<Vec<(String, DateTime<Utc>)>> {
+    Ok(sqlx::query_as("SELECT version, applied_at FROM schema_migrations ORDER BY version")
+        .fetch_all(pool)
+        .await?)
+}
+
+/// Record `001_initial_schema` as applied on databases the pre-versioning runner set up, which
+/// have its tables but no migration records, so it isn't run again over them
+async fn baseline_existing_schema(pool: &PgPool) -> Result<()> {
+    let (recorded, has_schema): (i64, bool) = sqlx::query_as(
+        "SELECT (SELECT COUNT(*) FROM schema_migrations), to_regclass('programs') IS NOT NULL",
+    )
+    .fetch_one(pool)
+    .await?;
+
+    if recorded == 0 && has_schema {
+        let (initial, _) = MIGRATIONS[0];
+        info!("Found a schema without migration records, recording {} as applied", initial);
+        sqlx::query("INSERT INTO schema_migrations (version) VALUES ($1)")
+            .bind(initial)
+            .execute(pool)
+            .await?;
+    }
+    Ok(())
+}
+
+/// Apply every migration not yet recorded in `schema_migrations`, each in one transaction
+/// with its record, so a failed migration leaves nothing half-applied
+pub(crate) async fn apply_pending_migrations(pool: &PgPool) -> Result<()> {
+    info!("Running database migrations...");
+    ensure_migrations_table(pool).await?;
+    baseline_existing_schema(pool).await?;
+
+    let applied = applied_migrations(pool).await?;
+    let mut applied_count = 0;
+
+    for (name, migration_sql) in MIGRATIONS {
+        if applied.iter().any(|(version, _)| version == name) {
+            continue;
+        }
+        info!("Applying migration {}", name);
+
+        // A plain string runs over the simple query protocol, which takes a whole script, so
+        // Postgres itself deals with quoting, `$$` bodies and comments
+        let mut tx = pool.begin().await?;
+        (&mut *tx).execute(migration_sql).await.map_err(|e| {
+            error!("Failed to apply migration {}: {}", name, e);
+            PdaAnalyzerError::Sqlx(e)
+        })?;
+        sqlx::query("INSERT INTO schema_migrations (version) VALUES ($1)")
+            .bind(name)
+            .execute(&mut tx)
+            .await?;
+        tx.commit().await?;
+        applied_count += 1;
+    }
+
+    info!(
+        "Database migrations completed successfully ({} applied, {} already up to date)",
+        applied_count,
+        MIGRATIONS.len() - applied_count
+    );
+    Ok(())
+}
+
+/// Every known migration in order, with when it was applied to `pool`'s database
+pub(crate) async fn migration_status(pool: &PgPool) -> Result<Vec<MigrationStatus>> {
+    ensure_migrations_table(pool).await?;
+    let applied = applied_migrations(pool).await?;
+
+    Ok(MIGRATIONS
+        .iter()
+        .map(|(name, _)| MigrationStatus {
+            version: name.to_string(),
+            applied_at: applied
+                .iter()
+                .find(|(version, _)| version == name)
+                .map(|(_, applied_at)| *applied_at),
+        })
+        .collect())
+}
+
 pub struct DatabaseMigrator {
     database_url: String,
 }
@@ -12,12 +137,9 @@ impl DatabaseMigrator {
     }
 
     pub async fn ensure_database_exists(&self) -> Result<()> {
-        if !Postgres::database_exists(&self.database_url).await
-            .map_err(|e| PdaAnalyzerError::DatabaseError(e.to_string()))?
-        {
+        if !Postgres::database_exists(&self.database_url).await? {
             info!("Creating database...");
-            Postgres::create_database(&self.database_url).await
-                .map_err(|e| PdaAnalyzerError::DatabaseError(e.to_string()))?;
+            Postgres::create_database(&self.database_url).await?;
             info!("Database created successfully");
         } else {
             info!("Database already exists");
@@ -25,34 +147,14 @@ impl DatabaseMigrator {
         Ok(())
     }
 
+    /// Apply every migration not yet recorded in `schema_migrations`, so repeated runs only
+    /// apply new migrations
     pub async fn run_migrations(&self, pool: &PgPool) -> Result<()> {
-        info!("Running database migrations...");
-        
-        // Run the initial schema migration
-        let migration_sql = include_str!("../../../migrations/001_initial_schema.sql");
-        
-        // Split by semicolon and execute each statement
-        let statements: Vec<&str> = migration_sql
-            .split(';')
-            .map(|s| s.trim())
-            .filter(|s| !s.is_empty() && !s.starts_with("--"))
-            .collect();
-
-        for statement in statements {
-            if !statement.is_empty() {
-                sqlx::query(statement)
-                    .execute(pool)
-                    .await
-                    .map_err(|e| {
-                        error!("Failed to execute migration statement: {}", e);
-                        error!("Statement: {}", statement);
-                        PdaAnalyzerError::DatabaseError(e.to_string())
-                    })?;
-            }
-        }
+        apply_pending_migrations(pool).await
+    }
 
-        info!("Database migrations completed successfully");
-        Ok(())
+    pub async fn migration_status(&self, pool: &PgPool) -> Result<Vec<MigrationStatus>> {
+        migration_status(pool).await
     }
 
     pub async fn setup_database(&self) -> Result<PgPool> {
@@ -61,8 +163,7 @@ impl DatabaseMigrator {
 
         // Create connection pool
         let pool = PgPool::connect(&self.database_url)
-            .await
-            .map_err(|e| PdaAnalyzerError::DatabaseError(e.to_string()))?;
+            .await?;
 
         // Run migrations
         self.run_migrations(&pool).await?;
@@ -73,11 +174,8 @@ impl DatabaseMigrator {
     pub async fn reset_database(&self) -> Result<()> {
         info!("Resetting database...");
         
-        if Postgres::database_exists(&self.database_url).await
-            .map_err(|e| PdaAnalyzerError::DatabaseError(e.to_string()))?
-        {
-            Postgres::drop_database(&self.database_url).await
-                .map_err(|e| PdaAnalyzerError::DatabaseError(e.to_string()))?;
+        if Postgres::database_exists(&self.database_url).await? {
+            Postgres::drop_database(&self.database_url).await?;
         }
         
         self.ensure_database_exists().await?;
@@ -151,8 +249,7 @@ impl DatabaseConfig {
             .idle_timeout(std::time::Duration::from_secs(self.idle_timeout))
             .max_lifetime(std::time::Duration::from_secs(self.max_lifetime))
             .connect(&self.database_url())
-            .await
-            .map_err(|e| PdaAnalyzerError::DatabaseError(e.to_string()))?;
+            .await?;
 
         Ok(pool)
     }
@@ -184,8 +281,7 @@ pub async fn initialize_database(config: &DatabaseConfig) -> Result<PgPool> {
 pub async fn health_check(pool: &PgPool) -> Result<()> {
     sqlx::query("SELECT 1")
         .execute(pool)
-        .await
-        .map_err(|e| PdaAnalyzerError::DatabaseError(e.to_string()))?;
+        .await?;
     Ok(())
 }
 
@@ -1,6 +1,8 @@
 use serde::{Deserialize, Serialize};
 use sqlx::FromRow;
 use chrono::{DateTime, Utc};
+use solana_pda_analyzer_core::{PdaPattern, SeedValue};
+use std::str::FromStr;
 use uuid::Uuid;
 
 #[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
@@ -9,6 +11,8 @@ pub struct ProgramRecord {
     pub program_id: String,
     pub name: Option<String>,
     pub description: Option<String>,
+    /// When an analysis of one of the program's PDAs was last stored
+    pub last_analyzed: Option<DateTime<Utc>>,
     pub created_at: DateTime<Utc>,
     pub updated_at: DateTime<Utc>,
 }
@@ -24,6 +28,15 @@ pub struct PdaPatternRecord {
     pub updated_at: DateTime<Utc>,
 }
 
+/// A stored pattern template with the base58 id of its program
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+pub struct ProgramPatternRecord {
+    pub program_address: String,
+    #[sqlx(flatten)]
+    #[serde(flatten)]
+    pub pattern: PdaPatternRecord,
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
 pub struct TransactionRecord {
     pub id: Uuid,
@@ -33,6 +46,8 @@ pub struct TransactionRecord {
     pub fee: Option<i64>,
     pub success: bool,
     pub error_message: Option<String>,
+    /// Programs whose PDAs the transaction revealed or interacted with
+    pub program_ids: Vec<String>,
     pub created_at: DateTime<Utc>,
     pub updated_at: DateTime<Utc>,
 }
@@ -46,10 +61,37 @@ pub struct PdaRecord {
     pub bump: i16,
     pub first_seen_transaction: Option<Uuid>,
     pub data_hash: Option<String>,
+    /// `PdaPattern` name the seeds were recovered with, if any
+    pub pattern: Option<String>,
     pub created_at: DateTime<Utc>,
     pub updated_at: DateTime<Utc>,
 }
 
+impl PdaRecord {
+    /// The stored pattern name as a `PdaPattern`; names the analyzer doesn't build in
+    /// come back as `PdaPattern::Custom`
+    pub fn recovered_pattern(&self) -> Option<PdaPattern> {
+        self.pattern.as_deref().and_then(|name| PdaPattern::from_str(name).ok())
+    }
+
+    /// The seeds at a glance, e.g. `metadata + pubkey + u64`: string seeds by their text, others
+    /// by type. Seeds that aren't stored as `SeedValue`s read as `?`.
+    pub fn seed_summary(&self) -> String {
+        match Vec::<SeedValue>::deserialize(&self.seeds) {
+            Ok(seeds) if seeds.is_empty() => "(no seeds)".to_string(),
+            Ok(seeds) => seeds
+                .iter()
+                .map(|seed| match seed {
+                    SeedValue::String(s) => s.as_str(),
+                    other => other.seed_type(),
+                })
+                .collect::<Vec<_>>()
+                .join(" + "),
+            Err(_) => "?".to_string(),
+        }
+    }
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
 pub struct AccountInteractionRecord {
     pub id: Uuid,
@@ -90,6 +132,14 @@ pub struct CreatePdaPatternRequest {
     pub description: Option<String>,
 }
 
+/// A pattern template `register_custom_program` stores along with its program
+#[derive(Debug, Clone)]
+pub struct CreateCustomPatternRequest {
+    pub pattern_name: String,
+    pub seeds_template: serde_json::Value,
+    pub description: Option<String>,
+}
+
 #[derive(Debug, Clone)]
 pub struct CreateTransactionRequest {
     pub signature: String,
@@ -107,7 +157,11 @@ pub struct CreatePdaRequest {
     pub seeds: serde_json::Value,
     pub bump: i16,
     pub first_seen_transaction: Option<Uuid>,
+    /// Slot of `first_seen_transaction`. Upserts keep the earliest slot and the transaction
+    /// seen at it, whichever order they arrive in.
+    pub first_seen_slot: Option<i64>,
     pub data_hash: Option<String>,
+    pub pattern: Option<String>,
 }
 
 #[derive(Debug, Clone)]
@@ -142,6 +196,8 @@ pub struct ProgramFilter {
 #[derive(Debug, Clone, Default)]
 pub struct TransactionFilter {
     pub signature: Option<String>,
+    /// Only transactions that touched this program's PDAs, by base58 program ID
+    pub program_id: Option<String>,
     pub slot_range: Option<(i64, i64)>,
     pub success: Option<bool>,
     pub limit: Option<i64>,
@@ -175,6 +231,50 @@ pub struct ProgramStats {
     pub success_rate: f64,
 }
 
+/// How many of a program's stored PDAs no heuristic recognized, from the `program_pattern_coverage` view
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+pub struct ProgramCoverage {
+    pub program_id: String,
+    pub name: Option<String>,
+    pub total_pdas: i64,
+    /// PDAs stored with no pattern or `UNKNOWN`
+    pub unrecognized_pdas: i64,
+    pub unrecognized_share: f64,
+}
+
+/// A stored PDA and the analysis it was stored with, for jobs that retry analyses
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+pub struct StoredPdaAnalysis {
+    pub address: String,
+    pub program_id: String,
+    pub pattern: Option<String>,
+    pub confidence: Option<f64>,
+}
+
+/// What `store_transaction_analysis` wrote for one transaction
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct StoredTransaction {
+    pub transaction_id: Uuid,
+    pub pdas: usize,
+    pub interactions: usize,
+}
+
+/// What `dedupe_programs` merged, or would merge on a dry run
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct ProgramDedupeReport {
+    pub dry_run: bool,
+    /// Programs stored under more than one row
+    pub programs_merged: usize,
+    /// Duplicate rows folded into their program's canonical row
+    pub rows_removed: usize,
+    /// Rows whose `program_id` was rewritten to base58
+    pub ids_normalized: usize,
+    /// PDAs moved to the canonical row; not counted on a dry run
+    pub pdas_repointed: u64,
+    /// Stored `program_id`s that aren't addresses at all, left for manual cleanup
+    pub unparseable: Vec<String>,
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
 pub struct PdaStats {
     pub pda_id: Uuid,
@@ -184,6 +284,20 @@ pub struct PdaStats {
     pub interaction_types: serde_json::Value,
 }
 
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+pub struct FeeStats {
+    pub total_transactions: i64,
+    pub total_fees: i64,
+    pub avg_fee: f64,
+    pub p50_fee: f64,
+    pub p95_fee: f64,
+    pub successful_transactions: i64,
+    pub successful_fees: i64,
+    pub avg_successful_fee: f64,
+    /// Fees paid by transactions that failed, i.e. spent for nothing
+    pub failed_fees: i64,
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct DatabaseMetrics {
     pub total_programs: i64,
@@ -211,4 +325,35 @@ pub fn string_to_interaction_type(s: String) -> solana_pda_analyzer_core::Intera
         "close" => solana_pda_analyzer_core::InteractionType::Close,
         _ => solana_pda_analyzer_core::InteractionType::Read,
     }
-}
\ No newline at end of file
+}
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn record(seeds: serde_json::Value) -> PdaRecord {
+        PdaRecord {
+            id: Uuid::new_v4(),
+            address: "11111111111111111111111111111111".to_string(),
+            program_id: Uuid::new_v4(),
+            seeds,
+            bump: 255,
+            first_seen_transaction: None,
+            data_hash: None,
+            pattern: None,
+            created_at: Utc::now(),
+            updated_at: Utc::now(),
+        }
+    }
+
+    #[test]
+    fn test_seed_summary() {
+        let seeds = serde_json::json!([
+            {"String": "metadata"},
+            {"type": "Pubkey", "value": "11111111111111111111111111111111"},
+            {"type": "U64", "value": 7},
+        ]);
+        assert_eq!(record(seeds).seed_summary(), "metadata + pubkey + u64");
+        assert_eq!(record(serde_json::json!([])).seed_summary(), "(no seeds)");
+        assert_eq!(record(serde_json::json!({"not": "seeds"})).seed_summary(), "?");
+    }
+}
@@ -0,0 +1,185 @@
+use crate::models::StoredPdaAnalysis;
+use crate::repository::DatabaseRepository;
+use serde::{Deserialize, Serialize};
+use solana_pda_analyzer_core::{PdaAnalysisResult, PdaAnalyzer, PdaAnalyzerError, PdaPattern, Result};
+use solana_sdk::pubkey::Pubkey;
+use std::str::FromStr;
+use tracing::{info, warn};
+
+/// Stored PDAs fetched and analyzed per round trip
+const PAGE_SIZE: i64 = 500;
+
+/// What a fresh analysis adds over the stored one
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Reanalysis {
+    /// Stored with no pattern or `UNKNOWN`, now recognized
+    Resolved,
+    /// Recognized before, now matched with higher confidence
+    Improved,
+}
+
+impl Reanalysis {
+    /// Whether `result` should replace what was stored for its PDA
+    pub fn classify(stored: &StoredPdaAnalysis, result: &PdaAnalysisResult) -> Option<Self> {
+        if result.pattern == PdaPattern::Unknown {
+            return None;
+        }
+
+        match stored.pattern.as_deref() {
+            None | Some("UNKNOWN") => Some(Self::Resolved),
+            Some(_) if result.confidence > stored.confidence.unwrap_or(0.0) => Some(Self::Improved),
+            Some(_) => None,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ReanalyzeReport {
+    pub scanned: usize,
+    /// Previously unrecognized PDAs that now have a pattern
+    pub resolved: usize,
+    /// Recognized PDAs now matched with higher confidence
+    pub improved: usize,
+    /// Stored rows with unparseable keys, or whose analysis errored
+    pub failed: usize,
+}
+
+/// Retry every stored PDA, or only unrecognized ones, with `analyzer`'s current heuristics and
+/// vocabulary, storing results that resolve or improve on the stored analysis. Pages are
+/// analyzed on the blocking pool, since seed searches would otherwise stall the runtime.
+pub async fn reanalyze_stored_pdas(
+    repository: &DatabaseRepository,
+    mut analyzer: PdaAnalyzer,
+    only_unknown: bool,
+) -> Result<ReanalyzeReport> {
+    let mut report = ReanalyzeReport::default();
+    let mut after: Option<String> = None;
+
+    loop {
+        let page = repository
+            .list_pdas_for_reanalysis(after.as_deref(), only_unknown, PAGE_SIZE)
+            .await?;
+        let Some(last) = page.last() else {
+            break;
+        };
+        after = Some(last.address.clone());
+        report.scanned += page.len();
+
+        let (returned, outcomes) = tokio::task::spawn_blocking(move || {
+            let outcomes: Vec<_> = page.iter().map(|stored| reanalyze_one(&mut analyzer, stored)).collect();
+            (analyzer, outcomes)
+        })
+        .await
+        .map_err(|e| PdaAnalyzerError::DatabaseError(format!("Reanalysis task failed: {}", e)))?;
+        analyzer = returned;
+
+        for outcome in outcomes {
+            match outcome {
+                Ok(Some((Reanalysis::Resolved, result))) => {
+                    repository.store_pda_analysis(&result).await?;
+                    report.resolved += 1;
+                }
+                Ok(Some((Reanalysis::Improved, result))) => {
+                    repository.store_pda_analysis(&result).await?;
+                    report.improved += 1;
+                }
+                Ok(None) => {}
+                Err(()) => report.failed += 1,
+            }
+        }
+
+        info!("Reanalyzed {} stored PDAs so far", report.scanned);
+    }
+
+    info!(
+        "Reanalysis finished: {} scanned, {} resolved, {} improved, {} failed",
+        report.scanned, report.resolved, report.improved, report.failed
+    );
+    Ok(report)
+}
+
+fn reanalyze_one(
+    analyzer: &mut PdaAnalyzer,
+    stored: &StoredPdaAnalysis,
+) -> std::result::Result<Option<(Reanalysis, PdaAnalysisResult)>, ()> {
+    let (Ok(address), Ok(program_id)) = (Pubkey::from_str(&stored.address), Pubkey::from_str(&stored.program_id)) else {
+        warn!("Skipping stored PDA with an invalid address or program ID: {}", stored.address);
+        return Err(());
+    };
+
+    match analyzer.analyze_pda(&address, &program_id) {
+        Ok(Some(result)) => Ok(Reanalysis::classify(stored, &result).map(|reanalysis| (reanalysis, result))),
+        Ok(None) => Ok(None),
+        Err(e) => {
+            warn!("Failed to reanalyze {}: {}", address, e);
+            Err(())
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use solana_pda_analyzer_core::PdaInfo;
+
+    fn stored(pattern: Option<&str>, confidence: Option<f64>) -> StoredPdaAnalysis {
+        StoredPdaAnalysis {
+            address: Pubkey::new_unique().to_string(),
+            program_id: Pubkey::new_unique().to_string(),
+            pattern: pattern.map(str::to_string),
+            confidence,
+        }
+    }
+
+    fn analysis(pattern: PdaPattern, confidence: f64) -> PdaAnalysisResult {
+        PdaAnalysisResult {
+            pda_info: PdaInfo {
+                address: Pubkey::new_unique(),
+                program_id: Pubkey::new_unique(),
+                seeds: Vec::new(),
+                bump: 255,
+                first_seen_slot: None,
+                first_seen_transaction: None,
+            },
+            match_kind: pattern.match_kind(),
+            pattern,
+            confidence,
+            analysis_time_ms: 0,
+            bump_as_explicit_seed: false,
+            account_exists: None,
+            short_string_match: false,
+            off_curve: true,
+            seed_sources: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn test_classify_reanalysis() {
+        let found = analysis(PdaPattern::Sequential, 0.8);
+        assert_eq!(Reanalysis::classify(&stored(None, None), &found), Some(Reanalysis::Resolved));
+        assert_eq!(Reanalysis::classify(&stored(Some("UNKNOWN"), Some(0.0)), &found), Some(Reanalysis::Resolved));
+        assert_eq!(Reanalysis::classify(&stored(Some("COMPLEX"), Some(0.5)), &found), Some(Reanalysis::Improved));
+        assert_eq!(Reanalysis::classify(&stored(Some("SEQUENTIAL"), Some(0.8)), &found), None);
+        assert_eq!(Reanalysis::classify(&stored(None, None), &analysis(PdaPattern::Unknown, 0.0)), None);
+    }
+
+    #[test]
+    fn test_reanalyze_one_recovers_sequential_pda() {
+        let program_id = Pubkey::new_unique();
+        let (address, _) = Pubkey::find_program_address(&[b"pool", &1u64.to_le_bytes()], &program_id);
+        let row = StoredPdaAnalysis {
+            address: address.to_string(),
+            program_id: program_id.to_string(),
+            pattern: Some("UNKNOWN".to_string()),
+            confidence: None,
+        };
+
+        let mut analyzer = PdaAnalyzer::new();
+        let (reanalysis, result) = reanalyze_one(&mut analyzer, &row).unwrap().unwrap();
+        assert_eq!(reanalysis, Reanalysis::Resolved);
+        assert_eq!(result.pda_info.address, address);
+
+        let garbled = StoredPdaAnalysis { address: "not-a-key".to_string(), ..row };
+        assert!(reanalyze_one(&mut analyzer, &garbled).is_err());
+    }
+}
@@ -1,100 +1,242 @@
+use crate::breaker::{CircuitBreaker, CircuitBreakerConfig, CircuitState};
 use crate::models::*;
-use solana_pda_analyzer_core::{PdaAnalyzerError, Result};
-use sqlx::{PgPool, Row};
+use chrono::{DateTime, Utc};
+use solana_pda_analyzer_core::{PdaAnalysisResult, PdaAnalyzerError, PdaInfo, PdaPattern, Result, SeedValue, TransactionAnalysis};
+use solana_sdk::pubkey::Pubkey;
+use sqlx::pool::PoolConnection;
+use sqlx::{Connection, PgConnection, PgPool, Postgres, QueryBuilder, Row};
+use std::sync::Arc;
 use uuid::Uuid;
 use tracing::{info, error, debug};
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
+
+/// Insert a program, or update the name and description of the row already holding its id
+const UPSERT_PROGRAM_SQL: &str = r#"
+    INSERT INTO programs (program_id, name, description)
+    VALUES ($1, $2, $3)
+    ON CONFLICT (program_id) DO UPDATE SET
+        name = EXCLUDED.name,
+        description = EXCLUDED.description,
+        updated_at = NOW()
+    RETURNING id, program_id, name, description, last_analyzed, created_at, updated_at
+"#;
+
+/// Upsert an analyzed PDA; binds address, program row id, seeds, bump, pattern, confidence
+/// and first-seen slot
+const UPSERT_PDA_ANALYSIS_SQL: &str = r#"
+    INSERT INTO pdas (address, program_id, seeds, bump, pattern, confidence, first_seen_slot)
+    VALUES ($1, $2, $3, $4, $5, $6, $7)
+    ON CONFLICT (address) DO UPDATE SET
+        seeds = EXCLUDED.seeds,
+        bump = EXCLUDED.bump,
+        pattern = EXCLUDED.pattern,
+        confidence = EXCLUDED.confidence,
+        first_seen_slot = LEAST(pdas.first_seen_slot, EXCLUDED.first_seen_slot),
+        updated_at = NOW()
+    RETURNING id, address, program_id, seeds, bump, first_seen_transaction, data_hash, pattern, created_at, updated_at
+"#;
+
+/// Recount a program's stored PDAs; binds the program row id
+const UPDATE_PROGRAM_PDA_COUNT_SQL: &str = r#"
+    UPDATE programs
+    SET total_pdas = (SELECT COUNT(*) FROM pdas WHERE pdas.program_id = $1),
+        last_analyzed = NOW(),
+        updated_at = NOW()
+    WHERE id = $1
+"#;
 
 #[derive(Debug, Clone)]
 pub struct DatabaseRepository {
     pool: PgPool,
+    breaker: Arc<CircuitBreaker>,
 }
 
 impl DatabaseRepository {
     pub fn new(pool: PgPool) -> Self {
-        Self { pool }
+        Self::with_circuit_breaker(pool, CircuitBreakerConfig::default())
+    }
+
+    pub fn with_circuit_breaker(pool: PgPool, config: CircuitBreakerConfig) -> Self {
+        Self {
+            pool,
+            breaker: Arc::new(CircuitBreaker::new(config)),
+        }
+    }
+
+    pub fn circuit_state(&self) -> CircuitState {
+        self.breaker.state()
+    }
+
+    /// Check out a connection, failing fast with `DatabaseUnavailable` while the
+    /// circuit breaker is open instead of waiting on the pool's acquire timeout
+    async fn acquire(&self) -> Result<PoolConnection<Postgres>> {
+        if !self.breaker.allow_request() {
+            let retry_after = self.breaker.retry_after().unwrap_or_default();
+            return Err(PdaAnalyzerError::DatabaseUnavailable(format!(
+                "circuit breaker open, retrying in {}s",
+                retry_after.as_secs()
+            )));
+        }
+
+        match self.pool.acquire().await {
+            Ok(connection) => {
+                self.breaker.record_success();
+                Ok(connection)
+            }
+            Err(e) => {
+                self.breaker.record_failure();
+                if self.breaker.state() == CircuitState::Open {
+                    error!("Database circuit breaker opened: {}", e);
+                }
+                Err(e.into())
+            }
+        }
     }
 
     pub async fn from_url(database_url: &str) -> Result<Self> {
-        let pool = PgPool::connect(database_url).await
-            .map_err(|e| PdaAnalyzerError::DatabaseError(e.to_string()))?;
+        let pool = PgPool::connect(database_url).await?;
         Ok(Self::new(pool))
     }
 
+    /// Close the pool, waiting for checked-out connections to be returned. Clones share
+    /// the pool, so this closes it for all of them; later calls are no-ops.
+    pub async fn close(&self) {
+        if self.pool.is_closed() {
+            return;
+        }
+        self.pool.close().await;
+        info!("Database pool closed");
+    }
+
     // Program operations
     pub async fn create_program(&self, request: CreateProgramRequest) -> Result<ProgramRecord> {
-        let record = sqlx::query_as::<_, ProgramRecord>(
-            r#"
-            INSERT INTO programs (program_id, name, description)
-            VALUES ($1, $2, $3)
-            ON CONFLICT (program_id) DO UPDATE SET
-                name = EXCLUDED.name,
-                description = EXCLUDED.description,
-                updated_at = NOW()
-            RETURNING id, program_id, name, description, created_at, updated_at
-            "#,
-        )
-        .bind(request.program_id)
-        .bind(request.name)
-        .bind(request.description)
-        .fetch_one(&self.pool)
-        .await
-        .map_err(|e| PdaAnalyzerError::DatabaseError(e.to_string()))?;
+        let record = sqlx::query_as::<_, ProgramRecord>(UPSERT_PROGRAM_SQL)
+            .bind(request.program_id)
+            .bind(request.name)
+            .bind(request.description)
+            .fetch_one(&mut *self.acquire().await?)
+            .await?;
 
         Ok(record)
     }
 
     pub async fn get_program_by_id(&self, program_id: &str) -> Result<Option<ProgramRecord>> {
         let record = sqlx::query_as::<_, ProgramRecord>(
-            "SELECT id, program_id, name, description, created_at, updated_at FROM programs WHERE program_id = $1"
+            "SELECT id, program_id, name, description, last_analyzed, created_at, updated_at FROM programs WHERE program_id = $1"
         )
         .bind(program_id)
-        .fetch_optional(&self.pool)
-        .await
-        .map_err(|e| PdaAnalyzerError::DatabaseError(e.to_string()))?;
+        .fetch_optional(&mut *self.acquire().await?)
+        .await?;
 
         Ok(record)
     }
 
     pub async fn list_programs(&self, filter: ProgramFilter) -> Result<Vec<ProgramRecord>> {
-        let mut query = "SELECT id, program_id, name, description, created_at, updated_at FROM programs WHERE 1=1".to_string();
-        let mut params = Vec::new();
-        let mut param_count = 1;
-
-        if let Some(program_id) = &filter.program_id {
-            query.push_str(&format!(" AND program_id = ${}", param_count));
-            params.push(program_id.clone());
-            param_count += 1;
+        let mut query = QueryBuilder::<Postgres>::new(
+            "SELECT id, program_id, name, description, last_analyzed, created_at, updated_at FROM programs WHERE 1=1",
+        );
+
+        if let Some(program_id) = filter.program_id {
+            query.push(" AND program_id = ").push_bind(program_id);
         }
 
-        if let Some(name) = &filter.name {
-            query.push_str(&format!(" AND name ILIKE ${}", param_count));
-            params.push(format!("%{}%", name));
-            param_count += 1;
+        if let Some(name) = filter.name {
+            query.push(" AND name ILIKE ").push_bind(format!("%{}%", name));
         }
 
-        query.push_str(" ORDER BY created_at DESC");
+        query.push(" ORDER BY created_at DESC");
+        push_pagination(&mut query, filter.limit, filter.offset);
 
-        if let Some(limit) = filter.limit {
-            query.push_str(&format!(" LIMIT ${}", param_count));
-            params.push(limit.to_string());
-            param_count += 1;
-        }
+        let records = query
+            .build_query_as::<ProgramRecord>()
+            .fetch_all(&mut *self.acquire().await?)
+            .await?;
 
-        if let Some(offset) = filter.offset {
-            query.push_str(&format!(" OFFSET ${}", param_count));
-            params.push(offset.to_string());
-        }
+        Ok(records)
+    }
 
-        let mut sql_query = sqlx::query_as::<_, ProgramRecord>(&query);
-        for param in params {
-            sql_query = sql_query.bind(param);
+    // PDA pattern operations
+    pub async fn create_pda_pattern(&self, request: CreatePdaPatternRequest) -> Result<PdaPatternRecord> {
+        let record = sqlx::query_as::<_, PdaPatternRecord>(
+            r#"
+            INSERT INTO pda_patterns (program_id, pattern_name, seeds_template, description)
+            VALUES ($1, $2, $3, $4)
+            RETURNING id, program_id, pattern_name, seeds_template, description, created_at, updated_at
+            "#,
+        )
+        .bind(request.program_id)
+        .bind(request.pattern_name)
+        .bind(request.seeds_template)
+        .bind(request.description)
+        .fetch_one(&mut *self.acquire().await?)
+        .await?;
+
+        Ok(record)
+    }
+
+    /// Store a new program and its pattern templates in one transaction. Returns `None`,
+    /// storing nothing, when a row for the program already exists, so concurrent registrations
+    /// of one program can't both succeed.
+    pub async fn register_custom_program(
+        &self,
+        program: CreateProgramRequest,
+        patterns: Vec<CreateCustomPatternRequest>,
+    ) -> Result<Option<(ProgramRecord, Vec<PdaPatternRecord>)>> {
+        let mut connection = self.acquire().await?;
+        let mut tx = connection.begin().await?;
+
+        let record = sqlx::query_as::<_, ProgramRecord>(
+            r#"
+            INSERT INTO programs (program_id, name, description)
+            VALUES ($1, $2, $3)
+            ON CONFLICT (program_id) DO NOTHING
+            RETURNING id, program_id, name, description, last_analyzed, created_at, updated_at
+            "#,
+        )
+        .bind(program.program_id)
+        .bind(program.name)
+        .bind(program.description)
+        .fetch_optional(&mut *tx)
+        .await?;
+        let Some(record) = record else {
+            return Ok(None);
+        };
+
+        let mut stored = Vec::with_capacity(patterns.len());
+        for pattern in patterns {
+            let pattern = sqlx::query_as::<_, PdaPatternRecord>(
+                r#"
+                INSERT INTO pda_patterns (program_id, pattern_name, seeds_template, description)
+                VALUES ($1, $2, $3, $4)
+                RETURNING id, program_id, pattern_name, seeds_template, description, created_at, updated_at
+                "#,
+            )
+            .bind(record.id)
+            .bind(pattern.pattern_name)
+            .bind(pattern.seeds_template)
+            .bind(pattern.description)
+            .fetch_one(&mut *tx)
+            .await?;
+            stored.push(pattern);
         }
+        tx.commit().await?;
 
-        let records = sql_query
-            .fetch_all(&self.pool)
-            .await
-            .map_err(|e| PdaAnalyzerError::DatabaseError(e.to_string()))?;
+        Ok(Some((record, stored)))
+    }
+
+    /// Every stored pattern template, oldest first, for reloading pattern registries at startup
+    pub async fn list_pda_patterns(&self) -> Result<Vec<ProgramPatternRecord>> {
+        let records = sqlx::query_as::<_, ProgramPatternRecord>(
+            r#"
+            SELECT p.program_id AS program_address, pp.id, pp.program_id, pp.pattern_name,
+                   pp.seeds_template, pp.description, pp.created_at, pp.updated_at
+            FROM pda_patterns pp
+            JOIN programs p ON p.id = pp.program_id
+            ORDER BY pp.created_at
+            "#,
+        )
+        .fetch_all(&mut *self.acquire().await?)
+        .await?;
 
         Ok(records)
     }
@@ -112,7 +254,7 @@ impl DatabaseRepository {
                 success = EXCLUDED.success,
                 error_message = EXCLUDED.error_message,
                 updated_at = NOW()
-            RETURNING id, signature, slot, block_time, fee, success, error_message, created_at, updated_at
+            RETURNING id, signature, slot, block_time, fee, success, error_message, program_ids, created_at, updated_at
             "#,
         )
         .bind(request.signature)
@@ -121,154 +263,261 @@ impl DatabaseRepository {
         .bind(request.fee)
         .bind(request.success)
         .bind(request.error_message)
-        .fetch_one(&self.pool)
-        .await
-        .map_err(|e| PdaAnalyzerError::DatabaseError(e.to_string()))?;
+        .fetch_one(&mut *self.acquire().await?)
+        .await?;
 
         Ok(record)
     }
 
     pub async fn get_transaction_by_signature(&self, signature: &str) -> Result<Option<TransactionRecord>> {
         let record = sqlx::query_as::<_, TransactionRecord>(
-            "SELECT id, signature, slot, block_time, fee, success, error_message, created_at, updated_at FROM transactions WHERE signature = $1"
+            "SELECT id, signature, slot, block_time, fee, success, error_message, program_ids, created_at, updated_at FROM transactions WHERE signature = $1"
         )
         .bind(signature)
-        .fetch_optional(&self.pool)
-        .await
-        .map_err(|e| PdaAnalyzerError::DatabaseError(e.to_string()))?;
+        .fetch_optional(&mut *self.acquire().await?)
+        .await?;
 
         Ok(record)
     }
 
     pub async fn list_transactions(&self, filter: TransactionFilter) -> Result<Vec<TransactionRecord>> {
-        let mut query = "SELECT id, signature, slot, block_time, fee, success, error_message, created_at, updated_at FROM transactions WHERE 1=1".to_string();
-        let mut params = Vec::new();
-        let mut param_count = 1;
-
-        if let Some(signature) = &filter.signature {
-            query.push_str(&format!(" AND signature = ${}", param_count));
-            params.push(signature.clone());
-            param_count += 1;
-        }
+        let mut query = QueryBuilder::<Postgres>::new(
+            "SELECT id, signature, slot, block_time, fee, success, error_message, program_ids, created_at, updated_at FROM transactions WHERE 1=1",
+        );
 
-        if let Some((min_slot, max_slot)) = filter.slot_range {
-            query.push_str(&format!(" AND slot >= ${} AND slot <= ${}", param_count, param_count + 1));
-            params.push(min_slot.to_string());
-            params.push(max_slot.to_string());
-            param_count += 2;
+        if let Some(signature) = filter.signature {
+            query.push(" AND signature = ").push_bind(signature);
         }
 
-        if let Some(success) = filter.success {
-            query.push_str(&format!(" AND success = ${}", param_count));
-            params.push(success.to_string());
-            param_count += 1;
+        if let Some(program_id) = filter.program_id {
+            query.push(" AND program_ids @> ARRAY[").push_bind(program_id).push("]");
         }
 
-        query.push_str(" ORDER BY slot DESC");
-
-        if let Some(limit) = filter.limit {
-            query.push_str(&format!(" LIMIT ${}", param_count));
-            params.push(limit.to_string());
-            param_count += 1;
+        if let Some((min_slot, max_slot)) = filter.slot_range {
+            query.push(" AND slot >= ").push_bind(min_slot);
+            query.push(" AND slot <= ").push_bind(max_slot);
         }
 
-        if let Some(offset) = filter.offset {
-            query.push_str(&format!(" OFFSET ${}", param_count));
-            params.push(offset.to_string());
+        if let Some(success) = filter.success {
+            query.push(" AND success = ").push_bind(success);
         }
 
-        let mut sql_query = sqlx::query_as::<_, TransactionRecord>(&query);
-        for param in params {
-            sql_query = sql_query.bind(param);
-        }
+        query.push(" ORDER BY slot DESC");
+        push_pagination(&mut query, filter.limit, filter.offset);
 
-        let records = sql_query
-            .fetch_all(&self.pool)
-            .await
-            .map_err(|e| PdaAnalyzerError::DatabaseError(e.to_string()))?;
+        let records = query
+            .build_query_as::<TransactionRecord>()
+            .fetch_all(&mut *self.acquire().await?)
+            .await?;
 
         Ok(records)
     }
 
     // PDA operations
+    /// Upsert a PDA. Seeds that parse as a seed list are stored in canonical form, like
+    /// analyses; other JSON is stored as given.
     pub async fn create_pda(&self, request: CreatePdaRequest) -> Result<PdaRecord> {
+        let seeds = match serde_json::from_value::<Vec<SeedValue>>(request.seeds.clone()) {
+            Ok(seeds) => canonical_seeds_json(&seeds)?,
+            Err(_) => request.seeds,
+        };
         let record = sqlx::query_as::<_, PdaRecord>(
             r#"
-            INSERT INTO pdas (address, program_id, seeds, bump, first_seen_transaction, data_hash)
-            VALUES ($1, $2, $3, $4, $5, $6)
+            INSERT INTO pdas (address, program_id, seeds, bump, first_seen_transaction, first_seen_slot, data_hash, pattern)
+            VALUES ($1, $2, $3, $4, $5, $6, $7, $8)
             ON CONFLICT (address) DO UPDATE SET
                 seeds = EXCLUDED.seeds,
                 bump = EXCLUDED.bump,
-                first_seen_transaction = COALESCE(pdas.first_seen_transaction, EXCLUDED.first_seen_transaction),
+                first_seen_transaction = CASE
+                    WHEN EXCLUDED.first_seen_slot < pdas.first_seen_slot THEN EXCLUDED.first_seen_transaction
+                    ELSE COALESCE(pdas.first_seen_transaction, EXCLUDED.first_seen_transaction)
+                END,
+                first_seen_slot = LEAST(pdas.first_seen_slot, EXCLUDED.first_seen_slot),
                 data_hash = EXCLUDED.data_hash,
+                pattern = COALESCE(EXCLUDED.pattern, pdas.pattern),
                 updated_at = NOW()
-            RETURNING id, address, program_id, seeds, bump, first_seen_transaction, data_hash, created_at, updated_at
+            RETURNING id, address, program_id, seeds, bump, first_seen_transaction, data_hash, pattern, created_at, updated_at
             "#,
         )
         .bind(request.address)
         .bind(request.program_id)
-        .bind(request.seeds)
+        .bind(seeds)
         .bind(request.bump)
         .bind(request.first_seen_transaction)
+        .bind(request.first_seen_slot)
         .bind(request.data_hash)
-        .fetch_one(&self.pool)
-        .await
-        .map_err(|e| PdaAnalyzerError::DatabaseError(e.to_string()))?;
+        .bind(request.pattern)
+        .fetch_one(&mut *self.acquire().await?)
+        .await?;
 
         Ok(record)
     }
 
     pub async fn get_pda_by_address(&self, address: &str) -> Result<Option<PdaRecord>> {
         let record = sqlx::query_as::<_, PdaRecord>(
-            "SELECT id, address, program_id, seeds, bump, first_seen_transaction, data_hash, created_at, updated_at FROM pdas WHERE address = $1"
+            "SELECT id, address, program_id, seeds, bump, first_seen_transaction, data_hash, pattern, created_at, updated_at FROM pdas WHERE address = $1"
         )
         .bind(address)
-        .fetch_optional(&self.pool)
-        .await
-        .map_err(|e| PdaAnalyzerError::DatabaseError(e.to_string()))?;
+        .fetch_optional(&mut *self.acquire().await?)
+        .await?;
 
         Ok(record)
     }
 
-    pub async fn list_pdas(&self, filter: PdaFilter) -> Result<Vec<PdaRecord>> {
-        let mut query = "SELECT id, address, program_id, seeds, bump, first_seen_transaction, data_hash, created_at, updated_at FROM pdas WHERE 1=1".to_string();
-        let mut params = Vec::new();
-        let mut param_count = 1;
-
-        if let Some(address) = &filter.address {
-            query.push_str(&format!(" AND address = ${}", param_count));
-            params.push(address.clone());
-            param_count += 1;
+    /// Delete one PDA by address; its account interactions cascade with it.
+    /// Returns whether a record was removed.
+    pub async fn delete_pda(&self, address: &str) -> Result<bool> {
+        let result = sqlx::query("DELETE FROM pdas WHERE address = $1")
+            .bind(address)
+            .execute(&mut *self.acquire().await?)
+            .await?;
+
+        Ok(result.rows_affected() > 0)
+    }
+
+    /// Delete PDAs not created or re-seen since `cutoff`. With `dry_run` nothing is
+    /// deleted and the count is what would have been. Returns the number of records.
+    pub async fn prune_pdas_older_than(&self, cutoff: DateTime<Utc>, dry_run: bool) -> Result<u64> {
+        if dry_run {
+            let count: i64 = sqlx::query_scalar("SELECT COUNT(*) FROM pdas WHERE updated_at < $1")
+                .bind(cutoff)
+                .fetch_one(&mut *self.acquire().await?)
+                .await?;
+            return Ok(count as u64);
         }
 
-        if let Some(program_id) = filter.program_id {
-            query.push_str(&format!(" AND program_id = ${}", param_count));
-            params.push(program_id.to_string());
-            param_count += 1;
+        let result = sqlx::query("DELETE FROM pdas WHERE updated_at < $1")
+            .bind(cutoff)
+            .execute(&mut *self.acquire().await?)
+            .await?;
+
+        info!("Pruned {} PDAs last seen before {}", result.rows_affected(), cutoff);
+        Ok(result.rows_affected())
+    }
+
+    /// Merge program rows that spell the same program id differently, e.g. hex or padded by
+    /// another writer, into one row holding the base58 form. PDAs, patterns and derivation
+    /// attempts move to the kept row before the others are deleted, all in one transaction.
+    /// With `dry_run` nothing is written.
+    pub async fn dedupe_programs(&self, dry_run: bool) -> Result<ProgramDedupeReport> {
+        let mut connection = self.acquire().await?;
+        let rows: Vec<(Uuid, String)> = sqlx::query_as("SELECT id, program_id FROM programs ORDER BY created_at, id")
+            .fetch_all(&mut *connection)
+            .await?;
+
+        let (merges, unparseable) = plan_program_merges(&rows);
+        let mut report = ProgramDedupeReport {
+            dry_run,
+            programs_merged: merges.iter().filter(|merge| !merge.merged.is_empty()).count(),
+            rows_removed: merges.iter().map(|merge| merge.merged.len()).sum(),
+            ids_normalized: merges.iter().filter(|merge| merge.rename).count(),
+            pdas_repointed: 0,
+            unparseable,
+        };
+        if dry_run || merges.is_empty() {
+            return Ok(report);
         }
 
-        query.push_str(" ORDER BY created_at DESC");
+        let mut tx = connection.begin().await?;
+        for merge in &merges {
+            if !merge.merged.is_empty() {
+                for table in ["pdas", "pda_patterns", "seed_derivation_attempts"] {
+                    let moved = sqlx::query(&format!("UPDATE {} SET program_id = $1 WHERE program_id = ANY($2)", table))
+                        .bind(merge.keep)
+                        .bind(&merge.merged)
+                        .execute(&mut *tx)
+                        .await?;
+                    if table == "pdas" {
+                        report.pdas_repointed += moved.rows_affected();
+                    }
+                }
+
+                sqlx::query(
+                    r#"
+                    UPDATE programs SET
+                        name = COALESCE(programs.name, merged.name),
+                        description = COALESCE(programs.description, merged.description),
+                        discovered_through_slot = GREATEST(programs.discovered_through_slot, merged.discovered_through_slot),
+                        updated_at = NOW()
+                    FROM (
+                        SELECT MAX(name) AS name, MAX(description) AS description, MAX(discovered_through_slot) AS discovered_through_slot
+                        FROM programs WHERE id = ANY($2)
+                    ) merged
+                    WHERE programs.id = $1
+                    "#,
+                )
+                .bind(merge.keep)
+                .bind(&merge.merged)
+                .execute(&mut *tx)
+                .await?;
+
+                sqlx::query("DELETE FROM programs WHERE id = ANY($1)")
+                    .bind(&merge.merged)
+                    .execute(&mut *tx)
+                    .await?;
+            }
 
-        if let Some(limit) = filter.limit {
-            query.push_str(&format!(" LIMIT ${}", param_count));
-            params.push(limit.to_string());
-            param_count += 1;
+            // After the delete, so the base58 id is free even if a merged row held it
+            if merge.rename {
+                sqlx::query("UPDATE programs SET program_id = $2, updated_at = NOW() WHERE id = $1")
+                    .bind(merge.keep)
+                    .bind(&merge.program_id)
+                    .execute(&mut *tx)
+                    .await?;
+            }
         }
+        tx.commit().await?;
+
+        info!(
+            "Merged {} duplicate program rows across {} programs, normalized {} ids, repointed {} PDAs",
+            report.rows_removed, report.programs_merged, report.ids_normalized, report.pdas_repointed
+        );
+        Ok(report)
+    }
+
+    pub async fn list_pdas(&self, filter: PdaFilter) -> Result<Vec<PdaRecord>> {
+        let mut query = QueryBuilder::<Postgres>::new(
+            "SELECT id, address, program_id, seeds, bump, first_seen_transaction, data_hash, pattern, created_at, updated_at FROM pdas WHERE 1=1",
+        );
 
-        if let Some(offset) = filter.offset {
-            query.push_str(&format!(" OFFSET ${}", param_count));
-            params.push(offset.to_string());
+        if let Some(address) = filter.address {
+            query.push(" AND address = ").push_bind(address);
         }
 
-        let mut sql_query = sqlx::query_as::<_, PdaRecord>(&query);
-        for param in params {
-            sql_query = sql_query.bind(param);
+        if let Some(program_id) = filter.program_id {
+            query.push(" AND program_id = ").push_bind(program_id);
         }
 
-        let records = sql_query
-            .fetch_all(&self.pool)
-            .await
-            .map_err(|e| PdaAnalyzerError::DatabaseError(e.to_string()))?;
+        query.push(" ORDER BY created_at DESC");
+        push_pagination(&mut query, filter.limit, filter.offset);
+
+        let records = query
+            .build_query_as::<PdaRecord>()
+            .fetch_all(&mut *self.acquire().await?)
+            .await?;
+
+        Ok(records)
+    }
+
+    /// PDAs whose first seed is `seed`, newest first. The containment check
+    /// uses the GIN index on `seeds`; the position check then narrows it to the first seed.
+    /// Rows stored before seeds were written as `{"type", "value"}` are matched in their old form.
+    pub async fn find_pdas_by_seed_prefix(&self, seed: &SeedValue, limit: i64) -> Result<Vec<PdaRecord>> {
+        let records = sqlx::query_as::<_, PdaRecord>(
+            r#"
+            SELECT id, address, program_id, seeds, bump, first_seen_transaction, data_hash, pattern, created_at, updated_at
+            FROM pdas
+            WHERE (seeds @> $1 AND seeds -> 0 = $1 -> 0)
+               OR (seeds @> $3 AND seeds -> 0 = $3 -> 0)
+            ORDER BY created_at DESC
+            LIMIT $2
+            "#,
+        )
+        .bind(seed_prefix_filter(seed)?)
+        .bind(limit)
+        .bind(legacy_seed_prefix_filter(seed))
+        .fetch_all(&mut *self.acquire().await?)
+        .await?;
 
         Ok(records)
     }
@@ -290,58 +539,64 @@ impl DatabaseRepository {
         .bind(request.data_after)
         .bind(request.lamports_before)
         .bind(request.lamports_after)
-        .fetch_one(&self.pool)
-        .await
-        .map_err(|e| PdaAnalyzerError::DatabaseError(e.to_string()))?;
+        .fetch_one(&mut *self.acquire().await?)
+        .await?;
 
         Ok(record)
     }
 
-    pub async fn list_account_interactions(&self, filter: AccountInteractionFilter) -> Result<Vec<AccountInteractionRecord>> {
-        let mut query = "SELECT id, transaction_id, pda_id, instruction_index, interaction_type, data_before, data_after, lamports_before, lamports_after, created_at FROM account_interactions WHERE 1=1".to_string();
-        let mut params = Vec::new();
-        let mut param_count = 1;
+    /// Store an analyzed transaction with the PDAs it revealed and its PDA interactions in one
+    /// database transaction, so a failed write leaves nothing half-stored. Storing a signature
+    /// again replaces its interactions. PDAs already stored keep their seeds and first-seen
+    /// transaction; interactions with PDAs that are neither discovered here nor stored are skipped.
+    pub async fn store_transaction_analysis(&self, analysis: &TransactionAnalysis, fee: Option<i64>) -> Result<StoredTransaction> {
+        let mut connection = self.acquire().await?;
+        let mut tx = connection.begin().await?;
+
+        let stored = write_transaction_analysis(&mut tx, analysis, fee).await?;
+        tx.commit().await?;
+        Ok(stored)
+    }
 
-        if let Some(transaction_id) = filter.transaction_id {
-            query.push_str(&format!(" AND transaction_id = ${}", param_count));
-            params.push(transaction_id.to_string());
-            param_count += 1;
-        }
+    /// Store several analyzed transactions, each with its fee, the way `store_transaction_analysis`
+    /// stores one, but in a single database transaction: either every analysis is stored or none is
+    pub async fn store_transaction_analyses(&self, analyses: &[(TransactionAnalysis, Option<i64>)]) -> Result<Vec<StoredTransaction>> {
+        let mut connection = self.acquire().await?;
+        let mut tx = connection.begin().await?;
 
-        if let Some(pda_id) = filter.pda_id {
-            query.push_str(&format!(" AND pda_id = ${}", param_count));
-            params.push(pda_id.to_string());
-            param_count += 1;
+        let mut stored = Vec::with_capacity(analyses.len());
+        for (analysis, fee) in analyses {
+            stored.push(write_transaction_analysis(&mut tx, analysis, *fee).await?);
         }
 
-        if let Some(interaction_type) = &filter.interaction_type {
-            query.push_str(&format!(" AND interaction_type = ${}", param_count));
-            params.push(interaction_type.clone());
-            param_count += 1;
-        }
+        tx.commit().await?;
+        Ok(stored)
+    }
 
-        query.push_str(" ORDER BY created_at DESC");
+    pub async fn list_account_interactions(&self, filter: AccountInteractionFilter) -> Result<Vec<AccountInteractionRecord>> {
+        let mut query = QueryBuilder::<Postgres>::new(
+            "SELECT id, transaction_id, pda_id, instruction_index, interaction_type, data_before, data_after, lamports_before, lamports_after, created_at FROM account_interactions WHERE 1=1",
+        );
 
-        if let Some(limit) = filter.limit {
-            query.push_str(&format!(" LIMIT ${}", param_count));
-            params.push(limit.to_string());
-            param_count += 1;
+        if let Some(transaction_id) = filter.transaction_id {
+            query.push(" AND transaction_id = ").push_bind(transaction_id);
         }
 
-        if let Some(offset) = filter.offset {
-            query.push_str(&format!(" OFFSET ${}", param_count));
-            params.push(offset.to_string());
+        if let Some(pda_id) = filter.pda_id {
+            query.push(" AND pda_id = ").push_bind(pda_id);
         }
 
-        let mut sql_query = sqlx::query_as::<_, AccountInteractionRecord>(&query);
-        for param in params {
-            sql_query = sql_query.bind(param);
+        if let Some(interaction_type) = filter.interaction_type {
+            query.push(" AND interaction_type = ").push_bind(interaction_type);
         }
 
-        let records = sql_query
-            .fetch_all(&self.pool)
-            .await
-            .map_err(|e| PdaAnalyzerError::DatabaseError(e.to_string()))?;
+        query.push(" ORDER BY created_at DESC");
+        push_pagination(&mut query, filter.limit, filter.offset);
+
+        let records = query
+            .build_query_as::<AccountInteractionRecord>()
+            .fetch_all(&mut *self.acquire().await?)
+            .await?;
 
         Ok(records)
     }
@@ -355,7 +610,7 @@ impl DatabaseRepository {
                 COUNT(DISTINCT t.id) as total_transactions,
                 COUNT(DISTINCT pd.id) as total_pdas,
                 COUNT(DISTINCT ai.id) as total_interactions,
-                (COUNT(CASE WHEN t.success THEN 1 END) * 100.0 / NULLIF(COUNT(t.id), 0)) as success_rate
+                COALESCE(COUNT(CASE WHEN t.success THEN 1 END) * 100.0 / NULLIF(COUNT(t.id), 0), 0)::FLOAT8 as success_rate
             FROM programs p
             LEFT JOIN pdas pd ON p.id = pd.program_id
             LEFT JOIN account_interactions ai ON pd.id = ai.pda_id
@@ -365,13 +620,132 @@ impl DatabaseRepository {
             "#,
         )
         .bind(program_id)
-        .fetch_one(&self.pool)
-        .await
-        .map_err(|e| PdaAnalyzerError::DatabaseError(e.to_string()))?;
+        .fetch_one(&mut *self.acquire().await?)
+        .await?;
+
+        Ok(stats)
+    }
+
+    /// Number of PDAs stored for a program (by base58 ID) and how many were recovered with each pattern.
+    /// PDAs without a recognized pattern count towards the total only.
+    pub async fn count_pdas_and_patterns(&self, program_id: &str) -> Result<(i64, HashMap<String, i64>)> {
+        let rows = sqlx::query(
+            r#"
+            SELECT pd.pattern, COUNT(*) as count
+            FROM pdas pd
+            JOIN programs p ON p.id = pd.program_id
+            WHERE p.program_id = $1
+            GROUP BY pd.pattern
+            "#,
+        )
+        .bind(program_id)
+        .fetch_all(&mut *self.acquire().await?)
+        .await?;
+
+        let mut total = 0;
+        let mut patterns = HashMap::new();
+        for row in rows {
+            let count: i64 = row.get("count");
+            total += count;
+            if let Some(pattern) = row.get::<Option<String>, _>("pattern") {
+                patterns.insert(pattern, count);
+            }
+        }
+
+        Ok((total, patterns))
+    }
+
+    /// How many stored PDAs, across every program, were recovered with each pattern
+    pub async fn count_pdas_by_pattern(&self) -> Result<HashMap<String, i64>> {
+        let rows = sqlx::query(
+            r#"
+            SELECT pattern, COUNT(*) as count
+            FROM pdas
+            WHERE pattern IS NOT NULL
+            GROUP BY pattern
+            "#,
+        )
+        .fetch_all(&mut *self.acquire().await?)
+        .await?;
+
+        Ok(rows
+            .into_iter()
+            .map(|row| (row.get("pattern"), row.get("count")))
+            .collect())
+    }
+
+    /// Programs ranked by the share of their stored PDAs that went unrecognized, then by count.
+    /// Programs with fewer than `min_pdas` stored PDAs are left out, so one-off misses don't dominate.
+    pub async fn list_program_coverage(&self, min_pdas: i64, limit: i64) -> Result<Vec<ProgramCoverage>> {
+        let coverage = sqlx::query_as::<_, ProgramCoverage>(
+            r#"
+            SELECT program_id, name, total_pdas, unrecognized_pdas, unrecognized_share
+            FROM program_pattern_coverage
+            WHERE total_pdas >= $1 AND unrecognized_pdas > 0
+            ORDER BY unrecognized_share DESC, unrecognized_pdas DESC, program_id
+            LIMIT $2
+            "#,
+        )
+        .bind(min_pdas)
+        .bind(limit)
+        .fetch_all(&mut *self.acquire().await?)
+        .await?;
+
+        Ok(coverage)
+    }
+
+    /// Fee rollup over transactions, optionally limited to those touching a program's PDAs
+    pub async fn get_fee_stats(&self, program_id: Option<Uuid>) -> Result<FeeStats> {
+        let stats = sqlx::query_as::<_, FeeStats>(
+            r#"
+            SELECT
+                COUNT(*) as total_transactions,
+                COALESCE(SUM(t.fee), 0)::BIGINT as total_fees,
+                COALESCE(AVG(t.fee), 0)::FLOAT8 as avg_fee,
+                COALESCE(PERCENTILE_CONT(0.5) WITHIN GROUP (ORDER BY t.fee), 0)::FLOAT8 as p50_fee,
+                COALESCE(PERCENTILE_CONT(0.95) WITHIN GROUP (ORDER BY t.fee), 0)::FLOAT8 as p95_fee,
+                COUNT(*) FILTER (WHERE t.success) as successful_transactions,
+                COALESCE(SUM(t.fee) FILTER (WHERE t.success), 0)::BIGINT as successful_fees,
+                COALESCE(AVG(t.fee) FILTER (WHERE t.success), 0)::FLOAT8 as avg_successful_fee,
+                COALESCE(SUM(t.fee) FILTER (WHERE NOT t.success), 0)::BIGINT as failed_fees
+            FROM transactions t
+            WHERE t.fee IS NOT NULL
+              AND ($1::UUID IS NULL OR t.id IN (
+                  SELECT ai.transaction_id
+                  FROM account_interactions ai
+                  JOIN pdas pd ON ai.pda_id = pd.id
+                  WHERE pd.program_id = $1
+              ))
+            "#,
+        )
+        .bind(program_id)
+        .fetch_one(&mut *self.acquire().await?)
+        .await?;
 
         Ok(stats)
     }
 
+    /// Account interactions counted per `interaction_type`, optionally limited to one program's PDAs
+    pub async fn interaction_type_distribution(&self, program_id: Option<Uuid>) -> Result<HashMap<String, i64>> {
+        let rows = sqlx::query(
+            r#"
+            SELECT ai.interaction_type, COUNT(*) as count
+            FROM account_interactions ai
+            JOIN pdas pd ON ai.pda_id = pd.id
+            WHERE $1::UUID IS NULL OR pd.program_id = $1
+            GROUP BY ai.interaction_type
+            "#,
+        )
+        .bind(program_id)
+        .fetch_all(&mut *self.acquire().await?)
+        .await?;
+
+        Ok(rows
+            .into_iter()
+            .map(|row| (row.get("interaction_type"), row.get("count")))
+            .collect())
+    }
+
     pub async fn get_stats(&self) -> Result<DatabaseMetrics> {
         self.get_database_metrics().await
     }
@@ -384,15 +758,13 @@ impl DatabaseRepository {
                 (SELECT COUNT(*) FROM transactions) as total_transactions,
                 (SELECT COUNT(*) FROM pdas) as total_pdas,
                 (SELECT COUNT(*) FROM account_interactions) as total_interactions,
-                (SELECT pg_size_pretty(pg_database_size(current_database()))) as database_size
+                pg_database_size(current_database()) as database_size_bytes
             "#
         )
-        .fetch_one(&self.pool)
-        .await
-        .map_err(|e| PdaAnalyzerError::DatabaseError(e.to_string()))?;
+        .fetch_one(&mut *self.acquire().await?)
+        .await?;
 
-        // Parse database size (simplified)
-        let database_size_mb = 0.0; // In a real implementation, parse the pg_size_pretty output
+        let database_size_mb = bytes_to_mb(row.get::<Option<i64>, _>("database_size_bytes").unwrap_or(0));
 
         Ok(DatabaseMetrics {
             total_programs: row.get::<Option<i64>, _>("total_programs").unwrap_or(0),
@@ -413,13 +785,243 @@ impl DatabaseRepository {
         self.list_pdas(filter).await
     }
 
-    pub async fn store_pda_analysis(&self, _analysis: &solana_pda_analyzer_core::PdaAnalysisResult) -> Result<()> {
-        // TODO: Implement storing PDA analysis results
+    /// Store an analysis result as a PDA record under its pattern name, registering the
+    /// program first if it hasn't been seen
+    pub async fn store_pda_analysis(&self, analysis: &PdaAnalysisResult) -> Result<PdaRecord> {
+        let pda_info = &analysis.pda_info;
+        let program_id = pda_info.program_id.to_string();
+        let program = match self.get_program_by_id(&program_id).await? {
+            Some(program) => program,
+            None => {
+                self.create_program(CreateProgramRequest {
+                    program_id,
+                    name: None,
+                    description: None,
+                })
+                .await?
+            }
+        };
+
+        let seeds = canonical_seeds_json(&pda_info.seeds)?;
+
+        let record = sqlx::query_as::<_, PdaRecord>(UPSERT_PDA_ANALYSIS_SQL)
+            .bind(pda_info.address.to_string())
+            .bind(program.id)
+            .bind(seeds)
+            .bind(pda_info.bump as i16)
+            .bind(analysis.pattern.name().into_owned())
+            .bind(analysis.confidence)
+            .bind(pda_info.first_seen_slot.map(|slot| slot as i64))
+            .fetch_one(&mut *self.acquire().await?)
+            .await?;
+
+        Ok(record)
+    }
+
+    /// Like `store_pda_analysis`, but registering the program, storing the PDA and updating
+    /// the program's PDA count happen in one database transaction, so a failure between them
+    /// can't leave the count out of step with the stored PDAs
+    pub async fn store_analysis_atomic(&self, analysis: &PdaAnalysisResult) -> Result<PdaRecord> {
+        let pda_info = &analysis.pda_info;
+        let seeds = canonical_seeds_json(&pda_info.seeds)?;
+
+        let mut connection = self.acquire().await?;
+        let mut tx = connection.begin().await?;
+
+        // The no-op update returns the id of an existing program without touching its name
+        let program_row_id: Uuid = sqlx::query_scalar(
+            r#"
+            INSERT INTO programs (program_id)
+            VALUES ($1)
+            ON CONFLICT (program_id) DO UPDATE SET program_id = EXCLUDED.program_id
+            RETURNING id
+            "#,
+        )
+        .bind(pda_info.program_id.to_string())
+        .fetch_one(&mut *tx)
+        .await?;
+
+        let record = sqlx::query_as::<_, PdaRecord>(UPSERT_PDA_ANALYSIS_SQL)
+            .bind(pda_info.address.to_string())
+            .bind(program_row_id)
+            .bind(seeds)
+            .bind(pda_info.bump as i16)
+            .bind(analysis.pattern.name().into_owned())
+            .bind(analysis.confidence)
+            .bind(pda_info.first_seen_slot.map(|slot| slot as i64))
+            .fetch_one(&mut *tx)
+            .await?;
+
+        sqlx::query(UPDATE_PROGRAM_PDA_COUNT_SQL)
+            .bind(program_row_id)
+            .execute(&mut *tx)
+            .await?;
+        tx.commit().await?;
+
+        Ok(record)
+    }
+
+    /// A previously stored analysis of `address` under `program_id`, so a solved address
+    /// isn't guessed again after a restart. PDAs stored without a pattern or confidence,
+    /// e.g. from transactions, are not analyses and return `None`.
+    pub async fn get_cached_analysis(&self, address: &Pubkey, program_id: &Pubkey) -> Result<Option<PdaAnalysisResult>> {
+        let row = sqlx::query(
+            r#"
+            SELECT pd.seeds, pd.bump, pd.pattern, pd.confidence
+            FROM pdas pd
+            JOIN programs p ON p.id = pd.program_id
+            WHERE pd.address = $1 AND p.program_id = $2
+              AND pd.pattern IS NOT NULL AND pd.confidence IS NOT NULL
+            "#,
+        )
+        .bind(address.to_string())
+        .bind(program_id.to_string())
+        .fetch_optional(&mut *self.acquire().await?)
+        .await?;
+
+        let Some(row) = row else {
+            return Ok(None);
+        };
+
+        let seeds: Vec<SeedValue> = serde_json::from_value(row.get("seeds"))
+            .map_err(|e| PdaAnalyzerError::SerializationError(e.to_string()))?;
+        let pattern: PdaPattern = row.get::<String, _>("pattern").parse()?;
+
+        let pda_info = PdaInfo {
+            address: *address,
+            program_id: *program_id,
+            seeds,
+            bump: row.get::<i16, _>("bump") as u8,
+            first_seen_slot: None,
+            first_seen_transaction: None,
+        };
+        Ok(Some(PdaAnalysisResult {
+            bump_as_explicit_seed: pda_info.bump_is_last_seed(),
+            off_curve: !pda_info.address.is_on_curve(),
+            pda_info,
+            match_kind: pattern.match_kind(),
+            pattern,
+            confidence: row.get("confidence"),
+            // Nothing was analyzed to answer this
+            analysis_time_ms: 0,
+            account_exists: None,
+            short_string_match: false,
+            // Provenance isn't stored
+            seed_sources: Vec::new(),
+        }))
+    }
+
+    /// Every stored PDA of `program_id` with its seeds and bump, for whole-program checks such
+    /// as bump audits. PDAs stored without recovered seeds come back with no seeds.
+    pub async fn list_program_pda_infos(&self, program_id: &Pubkey) -> Result<Vec<PdaInfo>> {
+        let rows = sqlx::query(
+            r#"
+            SELECT pd.address, pd.seeds, pd.bump
+            FROM pdas pd
+            JOIN programs p ON p.id = pd.program_id
+            WHERE p.program_id = $1
+            ORDER BY pd.address
+            "#,
+        )
+        .bind(program_id.to_string())
+        .fetch_all(&mut *self.acquire().await?)
+        .await?;
+
+        rows.iter()
+            .map(|row| {
+                Ok(PdaInfo {
+                    address: row.get::<String, _>("address").parse()?,
+                    program_id: *program_id,
+                    seeds: serde_json::from_value(row.get("seeds"))
+                        .map_err(|e| PdaAnalyzerError::SerializationError(e.to_string()))?,
+                    bump: row.get::<i16, _>("bump") as u8,
+                    first_seen_slot: None,
+                    first_seen_transaction: None,
+                })
+            })
+            .collect()
+    }
+
+    /// Addresses of `program_id`'s stored PDAs first seen at or before `slot`, which an
+    /// incremental discovery run can skip
+    pub async fn pdas_recorded_through_slot(&self, program_id: &Pubkey, slot: u64) -> Result<HashSet<Pubkey>> {
+        let addresses: Vec<String> = sqlx::query_scalar(
+            r#"
+            SELECT pd.address
+            FROM pdas pd
+            JOIN programs p ON p.id = pd.program_id
+            WHERE p.program_id = $1 AND pd.first_seen_slot <= $2
+            "#,
+        )
+        .bind(program_id.to_string())
+        .bind(slot as i64)
+        .fetch_all(&mut *self.acquire().await?)
+        .await?;
+
+        addresses.iter().map(|address| Ok(address.parse()?)).collect()
+    }
+
+    /// Slot through which `program_id`'s accounts have been discovered, if a run has finished
+    pub async fn discovery_high_water_mark(&self, program_id: &Pubkey) -> Result<Option<u64>> {
+        let slot: Option<Option<i64>> = sqlx::query_scalar("SELECT discovered_through_slot FROM programs WHERE program_id = $1")
+            .bind(program_id.to_string())
+            .fetch_optional(&mut *self.acquire().await?)
+            .await?;
+
+        Ok(slot.flatten().map(|slot| slot as u64))
+    }
+
+    /// Record that every account of `program_id` existing at `slot` has been discovered. The
+    /// mark only moves forward, so a slower, older run finishing late can't rewind it.
+    pub async fn record_discovery_high_water_mark(&self, program_id: &Pubkey, slot: u64) -> Result<()> {
+        sqlx::query(
+            r#"
+            INSERT INTO programs (program_id, discovered_through_slot) VALUES ($1, $2)
+            ON CONFLICT (program_id) DO UPDATE SET
+                discovered_through_slot = GREATEST(programs.discovered_through_slot, EXCLUDED.discovered_through_slot),
+                updated_at = NOW()
+            "#,
+        )
+        .bind(program_id.to_string())
+        .bind(slot as i64)
+        .execute(&mut *self.acquire().await?)
+        .await?;
+
         Ok(())
     }
 
-    pub async fn update_program_pda_count(&self, _program_id: &str) -> Result<()> {
-        // TODO: Implement updating program PDA count
+    /// One page of stored PDAs in address order, for jobs that walk the whole table without
+    /// holding it open. Pass the previous page's last address as `after`. With `only_unknown`,
+    /// only PDAs stored with no pattern or `UNKNOWN` are returned.
+    pub async fn list_pdas_for_reanalysis(&self, after: Option<&str>, only_unknown: bool, limit: i64) -> Result<Vec<StoredPdaAnalysis>> {
+        let records = sqlx::query_as::<_, StoredPdaAnalysis>(
+            r#"
+            SELECT pd.address, p.program_id, pd.pattern, pd.confidence
+            FROM pdas pd
+            JOIN programs p ON p.id = pd.program_id
+            WHERE ($1::TEXT IS NULL OR pd.address > $1)
+              AND (NOT $2 OR pd.pattern IS NULL OR pd.pattern = 'UNKNOWN')
+            ORDER BY pd.address
+            LIMIT $3
+            "#,
+        )
+        .bind(after)
+        .bind(only_unknown)
+        .bind(limit)
+        .fetch_all(&mut *self.acquire().await?)
+        .await?;
+
+        Ok(records)
+    }
+
+    /// Recount the stored PDAs of `program_id`; unknown programs are left alone
+    pub async fn update_program_pda_count(&self, program_id: &str) -> Result<()> {
+        if let Some(program) = self.get_program_by_id(program_id).await? {
+            sqlx::query(UPDATE_PROGRAM_PDA_COUNT_SQL)
+                .bind(program.id)
+                .execute(&mut *self.acquire().await?)
+                .await?;
+        }
         Ok(())
     }
 
@@ -431,31 +1033,45 @@ impl DatabaseRepository {
         self.list_programs(filter).await
     }
 
+    /// PDAs stored for a program given by its base58 ID; none if the program isn't stored
     pub async fn get_pdas_by_program(&self, program_id: &str, limit: i64) -> Result<Vec<PdaRecord>> {
-        // TODO: Convert program_id string to UUID
+        let Some(program) = self.get_program_by_id(program_id).await? else {
+            return Ok(Vec::new());
+        };
         let filter = PdaFilter {
             address: None,
-            program_id: None, // Should be converted from string to UUID
+            program_id: Some(program.id),
             limit: Some(limit),
             offset: None,
         };
         self.list_pdas(filter).await
     }
 
-    pub async fn get_pdas_by_pattern(&self, _pattern: &str, limit: i64) -> Result<Vec<PdaRecord>> {
-        // TODO: Implement pattern-based PDA search
-        let filter = PdaFilter {
-            address: None,
-            program_id: None,
-            limit: Some(limit),
-            offset: None,
-        };
-        self.list_pdas(filter).await
+    /// PDAs stored under the pattern name `pattern`, newest first
+    pub async fn get_pdas_by_pattern(&self, pattern: &str, limit: i64) -> Result<Vec<PdaRecord>> {
+        let records = sqlx::query_as::<_, PdaRecord>(
+            r#"
+            SELECT id, address, program_id, seeds, bump, first_seen_transaction, data_hash, pattern, created_at, updated_at
+            FROM pdas
+            WHERE pattern = $1
+            ORDER BY created_at DESC
+            LIMIT $2
+            "#,
+        )
+        .bind(pattern)
+        .bind(limit)
+        .fetch_all(&mut *self.acquire().await?)
+        .await?;
+
+        Ok(records)
     }
 
     pub async fn migrate(&self) -> Result<()> {
-        // TODO: Implement database migrations
-        Ok(())
+        crate::migrations::apply_pending_migrations(&self.pool).await
+    }
+
+    pub async fn migration_status(&self) -> Result<Vec<crate::migrations::MigrationStatus>> {
+        crate::migrations::migration_status(&self.pool).await
     }
 
     // Batch operations
@@ -475,6 +1091,27 @@ impl DatabaseRepository {
         Ok(results)
     }
 
+    /// Upsert every program in `requests` in one transaction, returning the stored rows in
+    /// request order. Nothing is written if any upsert fails.
+    pub async fn batch_create_programs(&self, requests: Vec<CreateProgramRequest>) -> Result<Vec<ProgramRecord>> {
+        let mut connection = self.acquire().await?;
+        let mut tx = connection.begin().await?;
+        let mut records = Vec::with_capacity(requests.len());
+        for request in requests {
+            let record = sqlx::query_as::<_, ProgramRecord>(UPSERT_PROGRAM_SQL)
+                .bind(request.program_id)
+                .bind(request.name)
+                .bind(request.description)
+                .fetch_one(&mut *tx)
+                .await?;
+            records.push(record);
+        }
+        tx.commit().await?;
+
+        info!("Upserted {} programs", records.len());
+        Ok(records)
+    }
+
     pub async fn batch_create_interactions(&self, requests: Vec<CreateAccountInteractionRequest>) -> Result<Vec<AccountInteractionRecord>> {
         let mut results = Vec::new();
         
@@ -492,10 +1129,244 @@ impl DatabaseRepository {
     }
 }
 
+/// The writes behind `store_transaction_analysis`, on a connection the caller has already
+/// opened a database transaction on
+async fn write_transaction_analysis(connection: &mut PgConnection, analysis: &TransactionAnalysis, fee: Option<i64>) -> Result<StoredTransaction> {
+    let transaction_id: Uuid = sqlx::query_scalar(
+        r#"
+        INSERT INTO transactions (signature, slot, block_time, fee, success, error_message)
+        VALUES ($1, $2, $3, $4, $5, $6)
+        ON CONFLICT (signature) DO UPDATE SET
+            slot = EXCLUDED.slot,
+            block_time = EXCLUDED.block_time,
+            fee = EXCLUDED.fee,
+            success = EXCLUDED.success,
+            error_message = EXCLUDED.error_message,
+            updated_at = NOW()
+        RETURNING id
+        "#,
+    )
+    .bind(&analysis.signature)
+    .bind(analysis.slot as i64)
+    .bind(analysis.block_time)
+    .bind(fee)
+    .bind(analysis.success)
+    .bind(&analysis.error_message)
+    .fetch_one(&mut *connection)
+    .await?;
+
+    // Upsert in address order, so concurrent ingests sharing PDAs lock their rows in the same
+    // order and can't deadlock
+    let mut discovered: Vec<&PdaInfo> = analysis.discovered_pdas.iter().collect();
+    discovered.sort_by_key(|pda| pda.address);
+
+    let mut pda_ids: HashMap<Pubkey, Uuid> = HashMap::new();
+    for pda in discovered {
+        let program_uuid: Uuid = sqlx::query_scalar(
+            r#"
+            INSERT INTO programs (program_id) VALUES ($1)
+            ON CONFLICT (program_id) DO UPDATE SET program_id = EXCLUDED.program_id
+            RETURNING id
+            "#,
+        )
+        .bind(pda.program_id.to_string())
+        .fetch_one(&mut *connection)
+        .await?;
+
+        let pda_id: Uuid = sqlx::query_scalar(
+            r#"
+            INSERT INTO pdas (address, program_id, seeds, bump, first_seen_transaction, first_seen_slot)
+            VALUES ($1, $2, $3, $4, $5, $6)
+            ON CONFLICT (address) DO UPDATE SET
+                first_seen_transaction = CASE
+                    WHEN EXCLUDED.first_seen_slot < pdas.first_seen_slot THEN EXCLUDED.first_seen_transaction
+                    ELSE COALESCE(pdas.first_seen_transaction, EXCLUDED.first_seen_transaction)
+                END,
+                first_seen_slot = LEAST(pdas.first_seen_slot, EXCLUDED.first_seen_slot),
+                updated_at = NOW()
+            RETURNING id
+            "#,
+        )
+        .bind(pda.address.to_string())
+        .bind(program_uuid)
+        .bind(canonical_seeds_json(&pda.seeds)?)
+        .bind(pda.bump as i16)
+        .bind(transaction_id)
+        .bind(analysis.slot as i64)
+        .fetch_one(&mut *connection)
+        .await?;
+        pda_ids.insert(pda.address, pda_id);
+    }
+
+    // Interactions can touch PDAs discovered by earlier transactions
+    let unresolved: Vec<String> = analysis
+        .pda_interactions
+        .iter()
+        .filter(|interaction| !pda_ids.contains_key(&interaction.pda_address))
+        .map(|interaction| interaction.pda_address.to_string())
+        .collect();
+    if !unresolved.is_empty() {
+        let rows = sqlx::query("SELECT id, address FROM pdas WHERE address = ANY($1)")
+            .bind(&unresolved)
+            .fetch_all(&mut *connection)
+            .await?;
+        for row in rows {
+            if let Ok(address) = row.get::<String, _>("address").parse::<Pubkey>() {
+                pda_ids.insert(address, row.get("id"));
+            }
+        }
+    }
+
+    sqlx::query("DELETE FROM account_interactions WHERE transaction_id = $1")
+        .bind(transaction_id)
+        .execute(&mut *connection)
+        .await?;
+
+    let mut interactions = 0;
+    for interaction in &analysis.pda_interactions {
+        let Some(pda_id) = pda_ids.get(&interaction.pda_address) else {
+            debug!("Skipping interaction with unknown PDA {}", interaction.pda_address);
+            continue;
+        };
+
+        sqlx::query(
+            r#"
+            INSERT INTO account_interactions (transaction_id, pda_id, instruction_index, interaction_type, data_before, data_after, lamports_before, lamports_after)
+            VALUES ($1, $2, $3, $4, $5, $6, $7, $8)
+            "#,
+        )
+        .bind(transaction_id)
+        .bind(pda_id)
+        .bind(interaction.instruction_index as i32)
+        .bind(interaction_type_to_string(interaction.interaction_type.clone()))
+        .bind(&interaction.data_before)
+        .bind(&interaction.data_after)
+        .bind(interaction.lamports_before.map(|lamports| lamports as i64))
+        .bind(interaction.lamports_after.map(|lamports| lamports as i64))
+        .execute(&mut *connection)
+        .await?;
+        interactions += 1;
+    }
+
+    // Programs of the PDAs revealed here or interacted with, for per-program listings
+    let discovered_programs: Vec<String> =
+        analysis.discovered_pdas.iter().map(|pda| pda.program_id.to_string()).collect();
+    sqlx::query(
+        r#"
+        UPDATE transactions SET program_ids = ARRAY(
+            SELECT p.program_id
+            FROM account_interactions ai
+            JOIN pdas pd ON pd.id = ai.pda_id
+            JOIN programs p ON p.id = pd.program_id
+            WHERE ai.transaction_id = $1
+            UNION
+            SELECT unnest($2::TEXT[])
+        )
+        WHERE id = $1
+        "#,
+    )
+    .bind(transaction_id)
+    .bind(&discovered_programs)
+    .execute(&mut *connection)
+    .await?;
+
+    Ok(StoredTransaction {
+        transaction_id,
+        pdas: analysis.discovered_pdas.len(),
+        interactions,
+    })
+}
+
+fn bytes_to_mb(bytes: i64) -> f64 {
+    bytes as f64 / (1024.0 * 1024.0)
+}
+
+/// Append `LIMIT`/`OFFSET` clauses for whichever are set, bound as integers
+fn push_pagination(query: &mut QueryBuilder<'_, Postgres>, limit: Option<i64>, offset: Option<i64>) {
+    if let Some(limit) = limit {
+        query.push(" LIMIT ").push_bind(limit);
+    }
+
+    if let Some(offset) = offset {
+        query.push(" OFFSET ").push_bind(offset);
+    }
+}
+
+/// Program rows `dedupe_programs` folds into one
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct ProgramMerge {
+    /// Base58 program id the kept row ends up with
+    program_id: String,
+    keep: Uuid,
+    merged: Vec<Uuid>,
+    /// Whether the kept row's stored id isn't already `program_id`
+    rename: bool,
+}
+
+/// Group `(id, program_id)` rows, oldest first, by the address their id parses to. Each group
+/// keeps the row already holding the base58 form, else the oldest; groups with one row in
+/// base58 need nothing. Also returns the ids that parse to no address.
+fn plan_program_merges(rows: &[(Uuid, String)]) -> (Vec<ProgramMerge>, Vec<String>) {
+    let mut groups: Vec<(Pubkey, Vec<&(Uuid, String)>)> = Vec::new();
+    let mut unparseable = Vec::new();
+    for row in rows {
+        match solana_pda_analyzer_core::parse_address(&row.1) {
+            Ok(address) => match groups.iter_mut().find(|(key, _)| *key == address) {
+                Some((_, group)) => group.push(row),
+                None => groups.push((address, vec![row])),
+            },
+            Err(_) => unparseable.push(row.1.clone()),
+        }
+    }
+
+    let merges = groups
+        .into_iter()
+        .filter_map(|(address, group)| {
+            let program_id = address.to_string();
+            let keep = group.iter().find(|(_, stored)| *stored == program_id).unwrap_or(&group[0]);
+            let merge = ProgramMerge {
+                rename: keep.1 != program_id,
+                keep: keep.0,
+                merged: group.iter().map(|(id, _)| *id).filter(|id| *id != keep.0).collect(),
+                program_id,
+            };
+            (merge.rename || !merge.merged.is_empty()).then_some(merge)
+        })
+        .collect();
+    (merges, unparseable)
+}
+
+/// JSONB array containing just `seed`, serialized the way seeds are stored
+fn seed_prefix_filter(seed: &SeedValue) -> Result<serde_json::Value> {
+    Ok(serde_json::Value::Array(vec![serde_json::to_value(seed.normalized())?]))
+}
+
+/// `seeds` as stored: in canonical form (`SeedValue::normalized`), so equivalent expressions
+/// of one derivation can't leave contradictory rows
+fn canonical_seeds_json(seeds: &[SeedValue]) -> Result<serde_json::Value> {
+    serde_json::to_value(SeedValue::normalize_all(seeds)).map_err(|e| PdaAnalyzerError::SerializationError(e.to_string()))
+}
+
+/// `seed_prefix_filter` in the externally tagged form older rows hold, e.g. `[{"String": "vault"}]`
+/// with pubkeys as byte arrays
+fn legacy_seed_prefix_filter(seed: &SeedValue) -> serde_json::Value {
+    let (tag, value) = match seed {
+        SeedValue::String(s) => ("String", serde_json::json!(s)),
+        SeedValue::Bytes(b) => ("Bytes", serde_json::json!(b)),
+        SeedValue::Hash(h) => ("Hash", serde_json::json!(h)),
+        SeedValue::Pubkey(pk) => ("Pubkey", serde_json::json!(pk.to_bytes())),
+        SeedValue::U64(n) => ("U64", serde_json::json!(n)),
+        SeedValue::U32(n) => ("U32", serde_json::json!(n)),
+        SeedValue::U16(n) => ("U16", serde_json::json!(n)),
+        SeedValue::U8(n) => ("U8", serde_json::json!(n)),
+    };
+    serde_json::json!([{ tag: value }])
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
-    use sqlx::PgPool;
+    
     
     // Note: These tests would require a test database setup
     // For now, they're just structural tests
@@ -507,4 +1378,85 @@ mod tests {
         // let repo = DatabaseRepository::new(pool);
         // assert!(repo.pool is not null);
     }
-}
\ No newline at end of file
+
+    #[test]
+    fn test_bytes_to_mb() {
+        assert_eq!(bytes_to_mb(0), 0.0);
+        assert_eq!(bytes_to_mb(8 * 1024 * 1024), 8.0);
+        assert_eq!(bytes_to_mb(512 * 1024), 0.5);
+    }
+
+    #[test]
+    fn test_push_pagination_numbers_placeholders() {
+        let mut query = QueryBuilder::<Postgres>::new("SELECT id FROM pdas WHERE 1=1");
+        query.push(" AND address = ").push_bind("addr".to_string());
+        push_pagination(&mut query, Some(10), Some(20));
+        assert_eq!(query.sql(), "SELECT id FROM pdas WHERE 1=1 AND address = $1 LIMIT $2 OFFSET $3");
+
+        let mut query = QueryBuilder::<Postgres>::new("SELECT id FROM pdas");
+        push_pagination(&mut query, None, Some(5));
+        assert_eq!(query.sql(), "SELECT id FROM pdas OFFSET $1");
+    }
+
+    #[test]
+    fn test_seed_prefix_filter_matches_stored_format() {
+        let filter = seed_prefix_filter(&SeedValue::String("vault".to_string())).unwrap();
+        assert_eq!(filter, serde_json::json!([{"type": "String", "value": "vault"}]));
+
+        let filter = seed_prefix_filter(&SeedValue::U64(7)).unwrap();
+        assert_eq!(filter, serde_json::json!([{"type": "U64", "value": 7}]));
+
+        // Searches match the canonical form seeds are stored in
+        let filter = seed_prefix_filter(&SeedValue::Bytes(b"vault".to_vec())).unwrap();
+        assert_eq!(filter, serde_json::json!([{"type": "String", "value": "vault"}]));
+    }
+
+    #[test]
+    fn test_canonical_seeds_json_merges_equivalent_seeds() {
+        let typed = canonical_seeds_json(&[SeedValue::U32(5), SeedValue::String("pool".to_string())]).unwrap();
+        let raw = canonical_seeds_json(&[SeedValue::Bytes(vec![5, 0, 0, 0]), SeedValue::Bytes(b"pool".to_vec())]).unwrap();
+        assert_eq!(typed, raw);
+        assert_eq!(typed, serde_json::json!([{"type": "U32", "value": 5}, {"type": "String", "value": "pool"}]));
+    }
+
+    #[test]
+    fn test_plan_program_merges_keeps_base58_row() {
+        let program = Pubkey::new_unique();
+        let (hex_row, base58_row, padded_row, other_row) = (Uuid::new_v4(), Uuid::new_v4(), Uuid::new_v4(), Uuid::new_v4());
+        let other = Pubkey::new_unique();
+        let rows = vec![
+            (hex_row, program.to_bytes().iter().map(|b| format!("{:02x}", b)).collect()),
+            (base58_row, program.to_string()),
+            (padded_row, format!(" {} ", program)),
+            (other_row, other.to_string()),
+            (Uuid::new_v4(), "not-a-program".to_string()),
+        ];
+
+        let (merges, unparseable) = plan_program_merges(&rows);
+        assert_eq!(
+            merges,
+            vec![ProgramMerge {
+                program_id: program.to_string(),
+                keep: base58_row,
+                merged: vec![hex_row, padded_row],
+                rename: false,
+            }]
+        );
+        assert_eq!(unparseable, vec!["not-a-program".to_string()]);
+
+        // Without a base58 row the oldest is kept and renamed
+        let (merges, _) = plan_program_merges(&rows[..1]);
+        assert_eq!(merges[0].keep, hex_row);
+        assert!(merges[0].rename && merges[0].merged.is_empty());
+    }
+
+    #[test]
+    fn test_legacy_seed_prefix_filter_matches_old_rows() {
+        let filter = legacy_seed_prefix_filter(&SeedValue::String("vault".to_string()));
+        assert_eq!(filter, serde_json::json!([{"String": "vault"}]));
+
+        let pubkey = Pubkey::new_unique();
+        let filter = legacy_seed_prefix_filter(&SeedValue::Pubkey(pubkey));
+        assert_eq!(filter, serde_json::json!([{"Pubkey": pubkey.to_bytes()}]));
+    }
+}
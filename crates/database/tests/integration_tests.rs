@@ -24,7 +24,7 @@ async fn setup_test_database() -> PgPool {
     let migrator = DatabaseMigrator::new(config.database_url());
     
     // Create test database
-    if let Err(_) = migrator.ensure_database_exists().await {
+    if migrator.ensure_database_exists().await.is_err() {
         // If we can't create database, skip these tests
         panic!("Cannot create test database - ensure PostgreSQL is running");
     }
@@ -76,7 +76,7 @@ async fn test_program_operations() {
     
     // Test list programs
     let programs = repo.list_programs(ProgramFilter::default()).await.expect("Failed to list programs");
-    assert!(programs.len() > 0);
+    assert!(!programs.is_empty());
     
     // Test update program (upsert)
     let update_request = CreateProgramRequest {
@@ -88,7 +88,22 @@ async fn test_program_operations() {
     let updated_program = repo.create_program(update_request).await.expect("Failed to update program");
     assert_eq!(updated_program.name, Some("Updated Test Program".to_string()));
     
-    cleanup_test_database(&pool, &pool.connect_options().get_database().unwrap()).await;
+    cleanup_test_database(&pool, pool.connect_options().get_database().unwrap()).await;
+}
+
+#[tokio::test]
+async fn test_close_is_shared_and_idempotent() {
+    let pool = setup_test_database().await;
+    let database_name = pool.connect_options().get_database().unwrap().to_string();
+    let repo = DatabaseRepository::new(pool.clone());
+    let clone = repo.clone();
+
+    repo.close().await;
+    clone.close().await;
+    assert!(pool.is_closed());
+    assert!(clone.list_programs(ProgramFilter::default()).await.is_err());
+
+    cleanup_test_database(&pool, &database_name).await;
 }
 
 #[tokio::test]
@@ -109,7 +124,7 @@ async fn test_transaction_operations() {
     let transaction = repo.create_transaction(request).await.expect("Failed to create transaction");
     assert_eq!(transaction.signature, "test_signature_123");
     assert_eq!(transaction.slot, 12345);
-    assert_eq!(transaction.success, true);
+    assert!(transaction.success);
     
     // Test get transaction
     let retrieved = repo.get_transaction_by_signature(&transaction.signature).await.expect("Failed to get transaction");
@@ -119,7 +134,7 @@ async fn test_transaction_operations() {
     
     // Test list transactions
     let transactions = repo.list_transactions(TransactionFilter::default()).await.expect("Failed to list transactions");
-    assert!(transactions.len() > 0);
+    assert!(!transactions.is_empty());
     
     // Test list with filters
     let filter = TransactionFilter {
@@ -130,7 +145,7 @@ async fn test_transaction_operations() {
     let filtered = repo.list_transactions(filter).await.expect("Failed to list filtered transactions");
     assert!(filtered.iter().all(|tx| tx.success));
     
-    cleanup_test_database(&pool, &pool.connect_options().get_database().unwrap()).await;
+    cleanup_test_database(&pool, pool.connect_options().get_database().unwrap()).await;
 }
 
 #[tokio::test]
@@ -158,7 +173,9 @@ async fn test_pda_operations() {
         seeds: seeds_json,
         bump: 254,
         first_seen_transaction: None,
+        first_seen_slot: None,
         data_hash: Some("abcd1234".to_string()),
+        pattern: None,
     };
     
     let pda = repo.create_pda(request).await.expect("Failed to create PDA");
@@ -173,7 +190,7 @@ async fn test_pda_operations() {
     
     // Test list PDAs
     let pdas = repo.list_pdas(PdaFilter::default()).await.expect("Failed to list PDAs");
-    assert!(pdas.len() > 0);
+    assert!(!pdas.is_empty());
     
     // Test list with program filter
     let filter = PdaFilter {
@@ -182,8 +199,186 @@ async fn test_pda_operations() {
     };
     let filtered = repo.list_pdas(filter).await.expect("Failed to list filtered PDAs");
     assert!(filtered.iter().all(|p| p.program_id == program.id));
+
+    // Test prune dry run counts without deleting
+    let cutoff = Utc::now() + chrono::Duration::minutes(1);
+    assert_eq!(repo.prune_pdas_older_than(cutoff, true).await.expect("Failed to count stale PDAs"), 1);
+    assert!(repo.get_pda_by_address(&pda.address).await.unwrap().is_some());
+    assert_eq!(repo.prune_pdas_older_than(Utc::now() - chrono::Duration::days(1), false).await.unwrap(), 0);
+
+    // Test delete PDA
+    assert!(repo.delete_pda(&pda.address).await.expect("Failed to delete PDA"));
+    assert!(!repo.delete_pda(&pda.address).await.expect("Failed to delete PDA"));
+    assert!(repo.get_pda_by_address(&pda.address).await.unwrap().is_none());
     
-    cleanup_test_database(&pool, &pool.connect_options().get_database().unwrap()).await;
+    cleanup_test_database(&pool, pool.connect_options().get_database().unwrap()).await;
+}
+
+#[tokio::test]
+async fn test_custom_pattern_round_trip() {
+    use solana_pda_analyzer_core::{MatchKind, PdaAnalysisResult, PdaInfo, PdaPattern, SeedValue};
+    use solana_sdk::pubkey::Pubkey;
+
+    let pool = setup_test_database().await;
+    let repo = DatabaseRepository::new(pool.clone());
+
+    let program_id = Pubkey::new_unique();
+    let (address, bump) = Pubkey::find_program_address(&[b"lp_position"], &program_id);
+    let analysis = PdaAnalysisResult {
+        pda_info: PdaInfo {
+            address,
+            program_id,
+            seeds: vec![SeedValue::String("lp_position".to_string())],
+            bump,
+            first_seen_slot: None,
+            first_seen_transaction: None,
+        },
+        pattern: PdaPattern::Custom("LP_POSITION".to_string()),
+        confidence: 0.9,
+        match_kind: MatchKind::Structural,
+        analysis_time_ms: 1,
+        bump_as_explicit_seed: false,
+        account_exists: None,
+        short_string_match: false,
+        off_curve: true,
+        seed_sources: Vec::new(),
+    };
+
+    let stored = repo.store_pda_analysis(&analysis).await.expect("Failed to store analysis");
+    assert_eq!(stored.pattern.as_deref(), Some("LP_POSITION"));
+
+    let retrieved = repo.get_pda_by_address(&address.to_string()).await.unwrap().unwrap();
+    assert_eq!(retrieved.recovered_pattern(), Some(PdaPattern::Custom("LP_POSITION".to_string())));
+    assert!(repo.get_program_by_id(&program_id.to_string()).await.unwrap().is_some());
+
+    // Served back as an analysis, but only under the program it was solved for
+    let cached = repo.get_cached_analysis(&address, &program_id).await.unwrap().unwrap();
+    assert_eq!(cached.pattern, analysis.pattern);
+    assert_eq!(cached.pda_info.bump, bump);
+    assert_eq!(cached.confidence, 0.9);
+    assert_eq!(cached.match_kind, MatchKind::Structural);
+    assert!(repo.get_cached_analysis(&address, &Pubkey::new_unique()).await.unwrap().is_none());
+
+    // The atomic write stores the PDA and keeps the program's count in step
+    let (vault, bump) = Pubkey::find_program_address(&[b"vault"], &program_id);
+    let mut vault_analysis = analysis.clone();
+    vault_analysis.pda_info.address = vault;
+    vault_analysis.pda_info.seeds = vec![SeedValue::String("vault".to_string())];
+    vault_analysis.pda_info.bump = bump;
+    repo.store_analysis_atomic(&vault_analysis).await.expect("Failed to store analysis atomically");
+    assert!(repo.get_pda_by_address(&vault.to_string()).await.unwrap().is_some());
+
+    let total_pdas: i64 = sqlx::query_scalar("SELECT total_pdas FROM programs WHERE program_id = $1")
+        .bind(program_id.to_string())
+        .fetch_one(&pool)
+        .await
+        .unwrap();
+    assert_eq!(total_pdas, 2);
+
+    cleanup_test_database(&pool, pool.connect_options().get_database().unwrap()).await;
+}
+
+#[tokio::test]
+async fn test_register_custom_program() {
+    use solana_pda_analyzer_database::CreateCustomPatternRequest;
+
+    let pool = setup_test_database().await;
+    let repo = DatabaseRepository::new(pool.clone());
+
+    let program = CreateProgramRequest {
+        program_id: "Vau1t6sLNxnzB7ZDsef8TLbPLfyZMYXH8WTNqUdm9g8".to_string(),
+        name: Some("Vault".to_string()),
+        description: None,
+    };
+    let patterns = vec![CreateCustomPatternRequest {
+        pattern_name: "Vault".to_string(),
+        seeds_template: serde_json::json!([{"name": "owner", "seed_type": "pubkey", "description": null, "is_variable": true}]),
+        description: Some("Per-user vault".to_string()),
+    }];
+
+    // Both race for the same program; exactly one stores it
+    let (first, second) = tokio::join!(
+        repo.register_custom_program(program.clone(), patterns.clone()),
+        repo.register_custom_program(program.clone(), patterns.clone()),
+    );
+    let stored: Vec<_> = [first.unwrap(), second.unwrap()].into_iter().flatten().collect();
+    assert_eq!(stored.len(), 1);
+    let (record, pattern_records) = &stored[0];
+    assert_eq!(record.name.as_deref(), Some("Vault"));
+    assert_eq!(pattern_records.len(), 1);
+    assert_eq!(pattern_records[0].program_id, record.id);
+
+    let listed = repo.list_pda_patterns().await.unwrap();
+    assert_eq!(listed.len(), 1);
+    assert_eq!(listed[0].program_address, program.program_id);
+    assert_eq!(listed[0].pattern.pattern_name, "Vault");
+
+    cleanup_test_database(&pool, pool.connect_options().get_database().unwrap()).await;
+}
+
+#[tokio::test]
+async fn test_concurrent_pda_upserts_keep_earliest_provenance() {
+    use solana_sdk::pubkey::Pubkey;
+
+    let pool = setup_test_database().await;
+    let repo = DatabaseRepository::new(pool.clone());
+    let program = repo.create_program(CreateProgramRequest {
+        program_id: Pubkey::new_unique().to_string(),
+        name: Some("Concurrent Upsert Program".to_string()),
+        description: None,
+    }).await.expect("Failed to create program");
+
+    // The earliest slot is neither the first nor the last upsert to be issued
+    let mut sightings = Vec::new();
+    for slot in [500i64, 900, 120, 300, 120_000, 250] {
+        let transaction = repo.create_transaction(CreateTransactionRequest {
+            signature: format!("concurrent_upsert_tx_{}", slot),
+            slot,
+            block_time: Some(Utc::now()),
+            fee: Some(5000),
+            success: true,
+            error_message: None,
+        }).await.expect("Failed to create transaction");
+        sightings.push((slot, transaction.id));
+    }
+    let earliest = sightings.iter().min_by_key(|(slot, _)| *slot).unwrap().1;
+
+    let address = Pubkey::new_unique().to_string();
+    let request = |first_seen_transaction, first_seen_slot| CreatePdaRequest {
+        address: address.clone(),
+        program_id: program.id,
+        seeds: serde_json::json!([{"String": "position"}]),
+        bump: 255,
+        first_seen_transaction,
+        first_seen_slot,
+        data_hash: None,
+        pattern: None,
+    };
+    let upserts: Vec<_> = sightings
+        .iter()
+        .map(|&(slot, transaction_id)| {
+            let repo = repo.clone();
+            let request = request(Some(transaction_id), Some(slot));
+            tokio::spawn(async move { repo.create_pda(request).await })
+        })
+        .collect();
+    for upsert in upserts {
+        upsert.await.expect("Upsert task panicked").expect("Failed to upsert PDA");
+    }
+
+    // An upsert that doesn't know when the PDA was seen leaves the provenance alone
+    repo.create_pda(request(None, None)).await.expect("Failed to upsert PDA");
+
+    let pda = repo.get_pda_by_address(&address).await.unwrap().expect("PDA stored");
+    assert_eq!(pda.first_seen_transaction, Some(earliest));
+    let first_seen_slot: Option<i64> = sqlx::query_scalar("SELECT first_seen_slot FROM pdas WHERE address = $1")
+        .bind(&address)
+        .fetch_one(&pool)
+        .await
+        .unwrap();
+    assert_eq!(first_seen_slot, Some(120));
+
+    cleanup_test_database(&pool, pool.connect_options().get_database().unwrap()).await;
 }
 
 #[tokio::test]
@@ -215,7 +410,9 @@ async fn test_account_interaction_operations() {
         seeds: serde_json::json!([{"String": "test"}]),
         bump: 253,
         first_seen_transaction: Some(transaction.id),
+        first_seen_slot: None,
         data_hash: None,
+        pattern: None,
     };
     let pda = repo.create_pda(pda_request).await.expect("Failed to create PDA");
     
@@ -235,9 +432,15 @@ async fn test_account_interaction_operations() {
     assert_eq!(interaction.interaction_type, "write");
     assert_eq!(interaction.instruction_index, 0);
     
+    // Test interaction rollup by type
+    let distribution = repo.interaction_type_distribution(Some(program.id)).await.expect("Failed to fetch interaction distribution");
+    assert_eq!(distribution.get("write"), Some(&1));
+    let other = repo.interaction_type_distribution(Some(Uuid::new_v4())).await.expect("Failed to fetch interaction distribution");
+    assert!(other.is_empty());
+    
     // Test list interactions
     let interactions = repo.list_account_interactions(AccountInteractionFilter::default()).await.expect("Failed to list interactions");
-    assert!(interactions.len() > 0);
+    assert!(!interactions.is_empty());
     
     // Test list with transaction filter
     let filter = AccountInteractionFilter {
@@ -255,7 +458,165 @@ async fn test_account_interaction_operations() {
     let filtered = repo.list_account_interactions(filter).await.expect("Failed to list PDA interactions");
     assert!(filtered.iter().all(|i| i.pda_id == pda.id));
     
-    cleanup_test_database(&pool, &pool.connect_options().get_database().unwrap()).await;
+    cleanup_test_database(&pool, pool.connect_options().get_database().unwrap()).await;
+}
+
+#[tokio::test]
+async fn test_store_transaction_analysis() {
+    use solana_pda_analyzer_core::{InteractionType, PdaInfo, PdaInteraction, SeedValue, TransactionAnalysis};
+    use solana_sdk::pubkey::Pubkey;
+
+    let pool = setup_test_database().await;
+    let repo = DatabaseRepository::new(pool.clone());
+
+    let program_id = Pubkey::new_unique();
+    let seeds = vec![SeedValue::String("vault".to_string())];
+    let (address, bump) = Pubkey::find_program_address(&[b"vault"], &program_id);
+    let analysis = TransactionAnalysis::builder("ingest_test_tx", 777)
+        .block_time(Utc::now())
+        .discovered_pda(PdaInfo {
+            address,
+            program_id,
+            seeds,
+            bump,
+            first_seen_slot: Some(777),
+            first_seen_transaction: Some("ingest_test_tx".to_string()),
+        })
+        .interaction(PdaInteraction::new(address, 0, InteractionType::Write))
+        .interaction(PdaInteraction::new(Pubkey::new_unique(), 1, InteractionType::Read))
+        .build();
+
+    let stored = repo.store_transaction_analysis(&analysis, Some(5000)).await.expect("Failed to store analysis");
+    assert_eq!(stored.pdas, 1);
+    // The second interaction's PDA is unknown, so it is skipped
+    assert_eq!(stored.interactions, 1);
+
+    let transaction = repo.get_transaction_by_signature("ingest_test_tx").await.unwrap().unwrap();
+    assert_eq!(transaction.id, stored.transaction_id);
+    assert_eq!(transaction.fee, Some(5000));
+    assert!(repo.get_pda_by_address(&address.to_string()).await.unwrap().is_some());
+    assert_eq!(transaction.program_ids, vec![program_id.to_string()]);
+
+    // Incremental discovery skips the PDA from slot 777 on, and its mark only moves forward
+    assert!(repo.pdas_recorded_through_slot(&program_id, 776).await.unwrap().is_empty());
+    assert!(repo.pdas_recorded_through_slot(&program_id, 777).await.unwrap().contains(&address));
+    assert_eq!(repo.discovery_high_water_mark(&program_id).await.unwrap(), None);
+    repo.record_discovery_high_water_mark(&program_id, 900).await.unwrap();
+    repo.record_discovery_high_water_mark(&program_id, 800).await.unwrap();
+    assert_eq!(repo.discovery_high_water_mark(&program_id).await.unwrap(), Some(900));
+
+    let by_program = TransactionFilter {
+        program_id: Some(program_id.to_string()),
+        ..Default::default()
+    };
+    assert_eq!(repo.list_transactions(by_program).await.unwrap().len(), 1);
+    let other_program = TransactionFilter {
+        program_id: Some(Pubkey::new_unique().to_string()),
+        ..Default::default()
+    };
+    assert!(repo.list_transactions(other_program).await.unwrap().is_empty());
+
+    // Storing the same signature again replaces rather than duplicates its interactions
+    let again = repo.store_transaction_analysis(&analysis, Some(5000)).await.unwrap();
+    assert_eq!(again.transaction_id, stored.transaction_id);
+    let filter = AccountInteractionFilter {
+        transaction_id: Some(stored.transaction_id),
+        ..Default::default()
+    };
+    assert_eq!(repo.list_account_interactions(filter).await.unwrap().len(), 1);
+
+    cleanup_test_database(&pool, pool.connect_options().get_database().unwrap()).await;
+}
+
+#[tokio::test]
+async fn test_store_transaction_analyses_in_one_transaction() {
+    use solana_pda_analyzer_core::{InteractionType, PdaInfo, PdaInteraction, SeedValue, TransactionAnalysis};
+    use solana_sdk::pubkey::Pubkey;
+
+    let pool = setup_test_database().await;
+    let repo = DatabaseRepository::new(pool.clone());
+
+    let program_id = Pubkey::new_unique();
+    let (address, bump) = Pubkey::find_program_address(&[b"vault"], &program_id);
+    let create = TransactionAnalysis::builder("batch_create_tx", 100)
+        .discovered_pda(PdaInfo {
+            address,
+            program_id,
+            seeds: vec![SeedValue::String("vault".to_string())],
+            bump,
+            first_seen_slot: Some(100),
+            first_seen_transaction: Some("batch_create_tx".to_string()),
+        })
+        .interaction(PdaInteraction::new(address, 0, InteractionType::Create))
+        .build();
+    // Touches the PDA the first transaction of the same batch revealed
+    let update = TransactionAnalysis::builder("batch_update_tx", 101)
+        .interaction(PdaInteraction::new(address, 0, InteractionType::Write))
+        .build();
+
+    let stored = repo
+        .store_transaction_analyses(&[(create, Some(5000)), (update, None)])
+        .await
+        .expect("Failed to store batch");
+    assert_eq!(stored.iter().map(|s| (s.pdas, s.interactions)).collect::<Vec<_>>(), vec![(1, 1), (0, 1)]);
+    assert!(repo.get_transaction_by_signature("batch_update_tx").await.unwrap().is_some());
+
+    // A write that fails rolls back the whole batch
+    let fine = TransactionAnalysis::builder("batch_rolled_back_tx", 102).build();
+    let too_long = TransactionAnalysis::builder("x".repeat(200), 103).build();
+    assert!(repo.store_transaction_analyses(&[(fine, None), (too_long, None)]).await.is_err());
+    assert!(repo.get_transaction_by_signature("batch_rolled_back_tx").await.unwrap().is_none());
+
+    cleanup_test_database(&pool, pool.connect_options().get_database().unwrap()).await;
+}
+
+#[tokio::test]
+async fn test_dedupe_programs() {
+    use solana_sdk::pubkey::Pubkey;
+
+    let pool = setup_test_database().await;
+    let repo = DatabaseRepository::new(pool.clone());
+
+    // Another writer stored the same program padded. Leading zero bytes keep the base58 short
+    // enough that the padded spelling still fits the 44-character column.
+    let mut bytes = Pubkey::new_unique().to_bytes();
+    bytes[..8].fill(0);
+    let program_id = Pubkey::new_from_array(bytes);
+    let canonical = repo.create_program(CreateProgramRequest {
+        program_id: program_id.to_string(),
+        name: None,
+        description: None,
+    }).await.expect("Failed to create program");
+    let duplicate = repo.create_program(CreateProgramRequest {
+        program_id: format!(" {}", program_id),
+        name: Some("Duplicate".to_string()),
+        description: None,
+    }).await.expect("Failed to create program");
+    repo.create_pda(CreatePdaRequest {
+        address: Pubkey::new_unique().to_string(),
+        program_id: duplicate.id,
+        seeds: serde_json::json!([{"type": "String", "value": "vault"}]),
+        bump: 255,
+        first_seen_transaction: None,
+        first_seen_slot: None,
+        data_hash: None,
+        pattern: None,
+    }).await.expect("Failed to create PDA");
+
+    let report = repo.dedupe_programs(true).await.expect("Failed to plan dedupe");
+    assert_eq!((report.programs_merged, report.rows_removed), (1, 1));
+    assert!(repo.get_program_by_id(&duplicate.program_id).await.unwrap().is_some());
+
+    let report = repo.dedupe_programs(false).await.expect("Failed to dedupe programs");
+    assert_eq!(report.pdas_repointed, 1);
+    assert!(repo.get_program_by_id(&duplicate.program_id).await.unwrap().is_none());
+    let merged = repo.get_program_by_id(&program_id.to_string()).await.unwrap().unwrap();
+    assert_eq!(merged.id, canonical.id);
+    assert_eq!(merged.name.as_deref(), Some("Duplicate"));
+    let pdas = repo.list_pdas(PdaFilter { program_id: Some(canonical.id), ..Default::default() }).await.unwrap();
+    assert_eq!(pdas.len(), 1);
+
+    cleanup_test_database(&pool, pool.connect_options().get_database().unwrap()).await;
 }
 
 #[tokio::test]
@@ -287,7 +648,9 @@ async fn test_database_metrics() {
         seeds: serde_json::json!([{"String": "metrics"}]),
         bump: 252,
         first_seen_transaction: None,
+        first_seen_slot: None,
         data_hash: None,
+        pattern: None,
     };
     let _pda = repo.create_pda(pda_request).await.expect("Failed to create PDA");
     
@@ -297,7 +660,7 @@ async fn test_database_metrics() {
     assert!(metrics.total_transactions >= 1);
     assert!(metrics.total_pdas >= 1);
     
-    cleanup_test_database(&pool, &pool.connect_options().get_database().unwrap()).await;
+    cleanup_test_database(&pool, pool.connect_options().get_database().unwrap()).await;
 }
 
 #[tokio::test]
@@ -334,7 +697,9 @@ async fn test_program_stats() {
             seeds: serde_json::json!([{"String": format!("stats_{}", i)}]),
             bump: 250 + i as i16,
             first_seen_transaction: None,
+            first_seen_slot: None,
             data_hash: None,
+            pattern: (i > 0).then(|| "STRING_SINGLETON".to_string()),
         };
         let _pda = repo.create_pda(pda_request).await.expect("Failed to create PDA");
     }
@@ -345,7 +710,19 @@ async fn test_program_stats() {
     // Note: Stats might be 0 because we haven't created the full relationship chain
     // In a real scenario, interactions would link transactions to PDAs
     
-    cleanup_test_database(&pool, &pool.connect_options().get_database().unwrap()).await;
+    let (total, patterns) = repo.count_pdas_and_patterns(&program.program_id).await.expect("Failed to count PDAs");
+    assert_eq!(total, 3);
+    assert_eq!(patterns.len(), 1);
+    assert_eq!(patterns["STRING_SINGLETON"], 2);
+
+    let coverage = repo.list_program_coverage(1, 10).await.expect("Failed to list coverage");
+    let entry = coverage.iter().find(|entry| entry.program_id == program.program_id).unwrap();
+    assert_eq!(entry.total_pdas, 3);
+    assert_eq!(entry.unrecognized_pdas, 1);
+    assert!((entry.unrecognized_share - 1.0 / 3.0).abs() < 1e-9);
+    assert!(repo.list_program_coverage(4, 10).await.unwrap().iter().all(|entry| entry.program_id != program.program_id));
+    
+    cleanup_test_database(&pool, pool.connect_options().get_database().unwrap()).await;
 }
 
 #[tokio::test]
@@ -369,7 +746,9 @@ async fn test_batch_operations() {
             seeds: serde_json::json!([{"String": "batch1"}]),
             bump: 249,
             first_seen_transaction: None,
+            first_seen_slot: None,
             data_hash: None,
+            pattern: None,
         },
         CreatePdaRequest {
             address: "batch_pda_2_00000000000000000000000".to_string(),
@@ -377,14 +756,47 @@ async fn test_batch_operations() {
             seeds: serde_json::json!([{"String": "batch2"}]),
             bump: 248,
             first_seen_transaction: None,
+            first_seen_slot: None,
             data_hash: None,
+            pattern: None,
         },
     ];
     
     let created_pdas = repo.batch_create_pdas(pda_requests).await.expect("Failed to batch create PDAs");
     assert_eq!(created_pdas.len(), 2);
     
-    cleanup_test_database(&pool, &pool.connect_options().get_database().unwrap()).await;
+    cleanup_test_database(&pool, pool.connect_options().get_database().unwrap()).await;
+}
+
+#[tokio::test]
+async fn test_batch_create_programs_upserts_in_order() {
+    let pool = setup_test_database().await;
+    let repo = DatabaseRepository::new(pool.clone());
+
+    let request = |program_id: &str, name: &str| CreateProgramRequest {
+        program_id: program_id.to_string(),
+        name: Some(name.to_string()),
+        description: None,
+    };
+    let programs = repo
+        .batch_create_programs(vec![
+            request("Stake11111111111111111111111111111111111111", "Stake"),
+            request("Vote111111111111111111111111111111111111111", "Vote"),
+        ])
+        .await
+        .expect("Failed to batch create programs");
+    assert_eq!(programs.len(), 2);
+    assert_eq!(programs[1].name.as_deref(), Some("Vote"));
+
+    // Existing rows are updated in place
+    let updated = repo
+        .batch_create_programs(vec![request("Stake11111111111111111111111111111111111111", "Stake Program")])
+        .await
+        .expect("Failed to upsert programs");
+    assert_eq!(updated[0].id, programs[0].id);
+    assert_eq!(updated[0].name.as_deref(), Some("Stake Program"));
+
+    cleanup_test_database(&pool, pool.connect_options().get_database().unwrap()).await;
 }
 
 #[tokio::test]
@@ -418,6 +830,46 @@ async fn test_migration_system() {
         .expect("Failed to count tables");
     
     assert!(table_count.0 >= 6); // We should have at least 6 tables from our schema
+
+    // A second run applies nothing and keeps the original records
+    let before = migrator.migration_status(&pool).await.expect("Failed to read migration status");
+    assert!(before.iter().all(|migration| migration.applied_at.is_some()));
+    migrator.run_migrations(&pool).await.expect("Failed to rerun migrations");
+    let after = migrator.migration_status(&pool).await.expect("Failed to read migration status");
+    assert_eq!(
+        before.iter().map(|m| m.applied_at).collect::<Vec<_>>(),
+        after.iter().map(|m| m.applied_at).collect::<Vec<_>>()
+    );
     
     cleanup_test_database(&pool, &config.database).await;
-}
\ No newline at end of file
+}
+
+#[tokio::test]
+async fn test_migrations_upgrade_unversioned_schema() {
+    let config = DatabaseConfig {
+        database: format!("test_pda_analyzer_{}", Uuid::new_v4().to_string().replace('-', "")),
+        ..Default::default()
+    };
+    let migrator = DatabaseMigrator::new(config.database_url());
+    migrator.ensure_database_exists().await.expect("Failed to ensure database exists");
+    let pool = config.create_pool().await.expect("Failed to create pool");
+
+    // As the runner before `schema_migrations` left a database: the initial schema, unrecorded
+    sqlx::Executor::execute(&pool, include_str!("../../../migrations/001_initial_schema.sql"))
+        .await
+        .expect("Failed to apply the initial schema");
+
+    migrator.run_migrations(&pool).await.expect("Failed to upgrade the existing schema");
+
+    let status = migrator.migration_status(&pool).await.expect("Failed to read migration status");
+    assert!(status.iter().all(|migration| migration.applied_at.is_some()));
+    let (has_pattern,): (bool,) = sqlx::query_as(
+        "SELECT EXISTS (SELECT 1 FROM information_schema.columns WHERE table_name = 'pdas' AND column_name = 'pattern')",
+    )
+    .fetch_one(&pool)
+    .await
+    .expect("Failed to inspect pdas");
+    assert!(has_pattern);
+
+    cleanup_test_database(&pool, &config.database).await;
+}
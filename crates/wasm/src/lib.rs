@@ -0,0 +1,145 @@
+use serde::Serialize;
+use solana_pda_analyzer_core::{parse_address, PdaAnalysisResult, PdaAnalyzer, PdaDeriver, PdaInfo, SeedValue};
+use std::cell::RefCell;
+use wasm_bindgen::prelude::*;
+
+thread_local! {
+    // Wasm runs on one thread; one analyzer keeps its caches across calls
+    static ANALYZER: RefCell<PdaAnalyzer> = RefCell::new(PdaAnalyzer::new());
+}
+
+/// A derived PDA, as `derivePda` returns it
+#[derive(Debug, Serialize)]
+pub struct Derivation {
+    pub address: String,
+    pub program_id: String,
+    /// Canonical bump
+    pub bump: u8,
+}
+
+impl From<&PdaInfo> for Derivation {
+    fn from(pda_info: &PdaInfo) -> Self {
+        Self {
+            address: pda_info.address.to_string(),
+            program_id: pda_info.program_id.to_string(),
+            bump: pda_info.bump,
+        }
+    }
+}
+
+/// A recognized PDA, as `analyzePda` returns it
+#[derive(Debug, Serialize)]
+pub struct Analysis {
+    pub address: String,
+    pub program_id: String,
+    /// In the REST API's JSON form, e.g. `{"type": "Pubkey", "value": "<base58>"}`
+    pub seeds: Vec<SeedValue>,
+    pub bump: u8,
+    pub pattern: String,
+    pub confidence: f64,
+    pub match_kind: &'static str,
+}
+
+impl From<PdaAnalysisResult> for Analysis {
+    fn from(result: PdaAnalysisResult) -> Self {
+        Self {
+            address: result.pda_info.address.to_string(),
+            program_id: result.pda_info.program_id.to_string(),
+            seeds: result.pda_info.seeds,
+            bump: result.pda_info.bump,
+            pattern: result.pattern.name().into_owned(),
+            confidence: result.confidence,
+            match_kind: result.match_kind.as_str(),
+        }
+    }
+}
+
+fn parse_seeds(seeds_json: &str) -> Result<Vec<SeedValue>, String> {
+    serde_json::from_str(seeds_json).map_err(|e| format!("Invalid seeds: {}", e))
+}
+
+fn derive(program_id: &str, seeds_json: &str) -> Result<Derivation, String> {
+    let program_id = parse_address(program_id).map_err(|e| e.to_string())?;
+    let seeds = parse_seeds(seeds_json)?;
+    let pda_info = ANALYZER.with(|analyzer| analyzer.borrow_mut().derive_pda(&program_id, &seeds)).map_err(|e| e.to_string())?;
+    Ok(Derivation::from(&pda_info))
+}
+
+fn verify(address: &str, program_id: &str, seeds_json: &str) -> Result<bool, String> {
+    let address = parse_address(address).map_err(|e| e.to_string())?;
+    let program_id = parse_address(program_id).map_err(|e| e.to_string())?;
+    let seeds = parse_seeds(seeds_json)?;
+    PdaDeriver::new().verify_pda(&address, &program_id, &seeds).map_err(|e| e.to_string())
+}
+
+fn analyze(address: &str, program_id: &str) -> Result<Option<Analysis>, String> {
+    let address = parse_address(address).map_err(|e| e.to_string())?;
+    let program_id = parse_address(program_id).map_err(|e| e.to_string())?;
+    let result = ANALYZER.with(|analyzer| analyzer.borrow_mut().analyze_pda(&address, &program_id)).map_err(|e| e.to_string())?;
+    Ok(result.map(Analysis::from))
+}
+
+fn to_json<T: Serialize>(value: &T) -> Result<String, JsError> {
+    serde_json::to_string(value).map_err(|e| JsError::new(&e.to_string()))
+}
+
+/// Derive the canonical PDA for `seedsJson`, a JSON array of seeds such as
+/// `[{"type": "String", "value": "vault"}, {"type": "U64", "value": 7}]`.
+/// Returns `{address, program_id, bump}` as JSON, with base58 addresses.
+#[wasm_bindgen(js_name = derivePda)]
+pub fn derive_pda(program_id: &str, seeds_json: &str) -> Result<String, JsError> {
+    to_json(&derive(program_id, seeds_json).map_err(|e| JsError::new(&e))?)
+}
+
+/// Whether `seedsJson` derives `address` under `programId` with the canonical bump
+#[wasm_bindgen(js_name = verifyPda)]
+pub fn verify_pda(address: &str, program_id: &str, seeds_json: &str) -> Result<bool, JsError> {
+    verify(address, program_id, seeds_json).map_err(|e| JsError::new(&e))
+}
+
+/// Recover the seeds of `address` with the analyzer's built-in patterns. Returns
+/// `{address, program_id, seeds, bump, pattern, confidence, match_kind}` as JSON, or
+/// `undefined` when no pattern derives the address.
+#[wasm_bindgen(js_name = analyzePda)]
+pub fn analyze_pda(address: &str, program_id: &str) -> Result<Option<String>, JsError> {
+    analyze(address, program_id)
+        .map_err(|e| JsError::new(&e))?
+        .map(|analysis| to_json(&analysis))
+        .transpose()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use solana_sdk::pubkey::Pubkey;
+
+    #[test]
+    fn test_derive_and_verify_round_trip() {
+        let program_id = Pubkey::new_unique();
+        let (address, bump) = Pubkey::find_program_address(&[b"vault", &7u64.to_le_bytes()], &program_id);
+        let seeds = r#"[{"type": "String", "value": "vault"}, {"type": "U64", "value": 7}]"#;
+
+        let derivation = derive(&program_id.to_string(), seeds).unwrap();
+        assert_eq!(derivation.address, address.to_string());
+        assert_eq!(derivation.bump, bump);
+
+        assert!(verify(&address.to_string(), &program_id.to_string(), seeds).unwrap());
+        assert!(!verify(&Pubkey::new_unique().to_string(), &program_id.to_string(), seeds).unwrap());
+        assert!(derive("not-a-key", seeds).is_err());
+        assert!(derive(&program_id.to_string(), "[{\"type\": \"Digest\"}]").is_err());
+    }
+
+    #[test]
+    fn test_analyze_returns_base58_result() {
+        let program_id = Pubkey::new_unique();
+        let (address, _) = Pubkey::find_program_address(&[b"config"], &program_id);
+
+        let analysis = analyze(&address.to_string(), &program_id.to_string()).unwrap().expect("recognized");
+        assert_eq!(analysis.address, address.to_string());
+        assert_eq!(analysis.pattern, "STRING_SINGLETON");
+        let json: serde_json::Value = serde_json::from_str(&serde_json::to_string(&analysis).unwrap()).unwrap();
+        assert_eq!(json["seeds"][0]["value"], "config");
+
+        assert!(analyze(&Pubkey::new_unique().to_string(), &program_id.to_string()).unwrap().is_none());
+    }
+}
@@ -1,7 +1,13 @@
 /// Beautiful Analysis Results Display for Solana PDA Analyzer
 /// This module provides formatted output for PDA analysis results
 
+use solana_pda_analyzer_analyzer::PatternRegistry;
+use solana_pda_analyzer_core::{PdaPattern, PdaPatternTemplate, SeedTemplate, SeedValue};
+use solana_sdk::pubkey::Pubkey;
 use std::collections::HashMap;
+use std::str::FromStr;
+use std::time::Instant;
+use uuid::Uuid;
 
 pub struct AnalysisDisplay {
     pub results: Vec<PdaAnalysisResult>,
@@ -82,11 +88,11 @@ impl AnalysisDisplay {
         println!("   🌱 Seed Breakdown:");
         for (j, seed) in result.seeds.iter().enumerate() {
             let type_icon = match seed.seed_type.as_str() {
-                "String" => "📝",
-                "Pubkey" => "🔑",
-                "U64" | "U32" | "U16" | "U8" => "🔢",
-                "Hash" => "🔒",
-                "Bytes" => "📦",
+                "string" => "📝",
+                "pubkey" => "🔑",
+                "u64" | "u32" | "u16" | "u8" => "🔢",
+                "hash" => "🔒",
+                "bytes" => "📦",
                 _ => "❓"
             };
             println!("      {}. {} {} ({} bytes): {}", 
@@ -133,20 +139,9 @@ impl AnalysisDisplay {
     }
 
     fn print_pattern_descriptions(&self) {
-        let descriptions = [
-            ("WALLET_TOKEN_MINT", "Associated Token Accounts - Standard token ownership pattern"),
-            ("STRING_PROGRAM_MINT", "Metaplex Metadata - NFT and token metadata storage"),
-            ("STRING_AUTHORITY", "Program Authority - Controlled access and permissions"),
-            ("PUBKEY_U64", "Market/Pool Systems - Trading and liquidity protocols"),
-            ("STRING_SINGLETON", "Global State - Single instance program state"),
-            ("PUBKEY_U8", "Bump Seed Pattern - Canonical bump for deterministic PDAs"),
-            ("STRING_PUBKEY_STRING_U32", "Complex Governance - Multi-parameter DAO structures"),
-            ("HASH_HASH", "Name Service - Domain registration and resolution"),
-        ];
-
-        for (pattern, desc) in descriptions.iter() {
-            if self.patterns.contains_key(*pattern) {
-                println!("      • {}: {}", pattern, desc);
+        for pattern in PdaPattern::ALL.iter() {
+            if self.patterns.contains_key(pattern.as_str()) {
+                println!("      • {}: {}", pattern.as_str(), pattern.description());
             }
         }
     }
@@ -216,146 +211,169 @@ impl AnalysisDisplay {
     }
 }
 
-// Example usage and demo data
-pub fn create_demo_analysis() -> AnalysisDisplay {
-    let results = vec![
-        PdaAnalysisResult {
-            name: "USDC Associated Token Account".to_string(),
-            pda_address: "Gh9ZwEmdLJ8DscKNTkTqPbNwLNNBjuSzaG9Vp2KGtKJr".to_string(),
-            program_id: "ATokenGPvbdGVxr1b2hvZbsiqW5xWH25efTNsLJA8knL".to_string(),
-            program_name: "SPL Associated Token".to_string(),
-            description: "Stores USDC tokens for wallet 9WzDXwBbmkg8...".to_string(),
-            seeds: vec![
-                SeedInfo {
-                    seed_type: "Pubkey".to_string(),
-                    value: "9WzDXwBbmkg8ZTbNMqUxvQRAyrZzDsGYdLVL9zYtAWWM".to_string(),
-                    byte_length: 32,
-                    description: "Wallet owner address".to_string(),
-                },
-                SeedInfo {
-                    seed_type: "Pubkey".to_string(),
-                    value: "TokenkegQfeZyiNwAJbNbGKPFXCWuBvf9Ss623VQ5DA".to_string(),
-                    byte_length: 32,
-                    description: "SPL Token Program ID".to_string(),
-                },
-                SeedInfo {
-                    seed_type: "Pubkey".to_string(),
-                    value: "EPjFWdd5AufqSSqeM2qN1xzybapC8G4wEGGkZwyTDt1v".to_string(),
-                    byte_length: 32,
-                    description: "USDC mint address".to_string(),
-                },
-            ],
-            pattern: "WALLET_TOKEN_MINT".to_string(),
-            confidence: 0.98,
-            analysis_time_ms: 12,
+/// Where demo PDAs come from: a seed template and the values its slots draw on
+struct DemoSource {
+    pattern: &'static str,
+    program_name: &'static str,
+    template: PdaPatternTemplate,
+    values: HashMap<String, Vec<SeedValue>>,
+    count: usize,
+}
+
+fn pubkeys(keys: &[&str]) -> Vec<SeedValue> {
+    keys.iter().map(|key| SeedValue::Pubkey(Pubkey::from_str(key).unwrap())).collect()
+}
+
+fn demo_sources(registry: &PatternRegistry) -> Vec<DemoSource> {
+    let token_program = Pubkey::from_str("TokenkegQfeZyiNwAJbNbGKPFXCWuBvf9Ss623VQ5DA").unwrap();
+    let metadata_program = Pubkey::from_str("metaqbxxUerdq28cj1RbAWkYQm3ybzjb6a8bt518x1s").unwrap();
+    let serum_program = Pubkey::from_str("9xQeWvG816bUx9EPjHmaT23yvVM2ZWbrrpZb9PusVFin").unwrap();
+    let marinade_program = Pubkey::from_str("MarBmsSgKXdrN1egZf5sqe1TMai9K1rChYNDJgjq7aD").unwrap();
+    let slot = |name: &str, seed_type: &str, description: &str, is_variable| SeedTemplate {
+        name: name.to_string(),
+        seed_type: seed_type.to_string(),
+        description: Some(description.to_string()),
+        is_variable,
+    };
+    let custom = |program_id, pattern_name: &str, seeds_template, description: &str| PdaPatternTemplate {
+        id: Uuid::new_v4(),
+        program_id,
+        pattern_name: pattern_name.to_string(),
+        seeds_template,
+        description: Some(description.to_string()),
+    };
+
+    vec![
+        DemoSource {
+            pattern: "WALLET_MINT",
+            program_name: "SPL Token",
+            template: registry.templates(&token_program)[0].clone(),
+            values: HashMap::from([
+                ("owner".to_string(), pubkeys(&[
+                    "9WzDXwBbmkg8ZTbNMqUxvQRAyrZzDsGYdLVL9zYtAWWM",
+                    "DPiH3H3c7t47BMxqTxLsuPQpEC6Kne8GA9VXbxpnZxFE",
+                ])),
+                ("mint".to_string(), pubkeys(&[
+                    "EPjFWdd5AufqSSqeM2qN1xzybapC8G4wEGGkZwyTDt1v",
+                    "So11111111111111111111111111111111111111112",
+                    "Es9vMFrzaCERmJfrF4H2FYD4KCoNkY11McCe8BenwNYB",
+                ])),
+            ]),
+            count: 6,
         },
-        PdaAnalysisResult {
-            name: "Bored Ape NFT Metadata".to_string(),
-            pda_address: "8HYrKZBRZk9CgGfVv5u3r5G4W3dP2Qe2Y7rZRzMhQKkx".to_string(),
-            program_id: "metaqbxxUerdq28cj1RbAWkYQm3ybzjb6a8bt518x1s".to_string(),
-            program_name: "Metaplex Token Metadata".to_string(),
-            description: "NFT metadata storage for Bored Ape collection".to_string(),
-            seeds: vec![
-                SeedInfo {
-                    seed_type: "String".to_string(),
-                    value: "metadata".to_string(),
-                    byte_length: 8,
-                    description: "Metaplex metadata identifier".to_string(),
-                },
-                SeedInfo {
-                    seed_type: "Pubkey".to_string(),
-                    value: "metaqbxxUerdq28cj1RbAWkYQm3ybzjb6a8bt518x1s".to_string(),
-                    byte_length: 32,
-                    description: "Metaplex program ID".to_string(),
-                },
-                SeedInfo {
-                    seed_type: "Pubkey".to_string(),
-                    value: "7gXKKGLQs2HpzrPTtBP7kkQ3LktDShQPE8VV9PYW9RSh".to_string(),
-                    byte_length: 32,
-                    description: "NFT mint address".to_string(),
-                },
-            ],
-            pattern: "STRING_PROGRAM_MINT".to_string(),
-            confidence: 0.95,
-            analysis_time_ms: 15,
+        DemoSource {
+            pattern: "STRING_PROGRAM_MINT",
+            program_name: "Metaplex Token Metadata",
+            template: registry.templates(&metadata_program)[0].clone(),
+            values: HashMap::from([
+                ("prefix".to_string(), vec![SeedValue::String("metadata".to_string())]),
+                ("program_id".to_string(), vec![SeedValue::Pubkey(metadata_program)]),
+                ("mint".to_string(), pubkeys(&[
+                    "7gXKKGLQs2HpzrPTtBP7kkQ3LktDShQPE8VV9PYW9RSh",
+                    "DPiH3H3c7t47BMxqTxLsuPQpEC6Kne8GA9VXbxpnZxFE",
+                    "EPjFWdd5AufqSSqeM2qN1xzybapC8G4wEGGkZwyTDt1v",
+                ])),
+            ]),
+            count: 3,
         },
-        PdaAnalysisResult {
-            name: "Serum SOL/USDC Market Authority".to_string(),
-            pda_address: "5Q544fKrFoe6tsEbD7S8EmxGTJYAKtTVhAW5Q5pge4j1".to_string(),
-            program_id: "9xQeWvG816bUx9EPjHmaT23yvVM2ZWbrrpZb9PusVFin".to_string(),
-            program_name: "Serum DEX".to_string(),
-            description: "Market authority for SOL/USDC trading pair".to_string(),
-            seeds: vec![
-                SeedInfo {
-                    seed_type: "Pubkey".to_string(),
-                    value: "9wFFyRfZBsuAha4YcuxcXLKwMxJR43S7fPfQLusDBzvT".to_string(),
-                    byte_length: 32,
-                    description: "Market address".to_string(),
-                },
-                SeedInfo {
-                    seed_type: "U64".to_string(),
-                    value: "0".to_string(),
-                    byte_length: 8,
-                    description: "Vault signer nonce".to_string(),
-                },
-            ],
-            pattern: "PUBKEY_U64".to_string(),
-            confidence: 0.92,
-            analysis_time_ms: 18,
+        DemoSource {
+            pattern: "PUBKEY_U64",
+            program_name: "Serum DEX",
+            template: custom(
+                serum_program,
+                "Market Vault Signer",
+                vec![
+                    slot("market", "pubkey", "Market address", false),
+                    slot("nonce", "u64", "Vault signer nonce", true),
+                ],
+                "Signs for a market's token vaults",
+            ),
+            values: HashMap::from([
+                ("market".to_string(), pubkeys(&["9wFFyRfZBsuAha4YcuxcXLKwMxJR43S7fPfQLusDBzvT"])),
+                ("nonce".to_string(), (0..4).map(SeedValue::U64).collect()),
+            ]),
+            count: 2,
         },
-        PdaAnalysisResult {
-            name: "Marinade Liquid Staking State".to_string(),
-            pda_address: "8szGkuLTAux9XMgZ2vtY39jVSowEcpBfFfD8hXSEqdGC".to_string(),
-            program_id: "MarBmsSgKXdrN1egZf5sqe1TMai9K1rChYNDJgjq7aD".to_string(),
-            program_name: "Marinade Finance".to_string(),
-            description: "Global state for liquid staking protocol".to_string(),
-            seeds: vec![
-                SeedInfo {
-                    seed_type: "String".to_string(),
-                    value: "state".to_string(),
-                    byte_length: 5,
-                    description: "State identifier".to_string(),
-                },
-            ],
-            pattern: "STRING_SINGLETON".to_string(),
-            confidence: 0.99,
-            analysis_time_ms: 8,
+        DemoSource {
+            pattern: "STRING_SINGLETON",
+            program_name: "Marinade Finance",
+            template: custom(
+                marinade_program,
+                "Liquid Staking State",
+                vec![slot("name", "string", "State identifier", false)],
+                "Global state for the liquid staking protocol",
+            ),
+            values: HashMap::from([("name".to_string(), vec![SeedValue::String("state".to_string())])]),
+            count: 1,
         },
-    ];
-
-    let mut patterns = HashMap::new();
-    patterns.insert("WALLET_TOKEN_MINT".to_string(), PatternStats {
-        count: 9,
-        percentage: 45.0,
-        examples: vec!["USDC ATA".to_string(), "SOL ATA".to_string(), "USDT ATA".to_string()],
-    });
-    patterns.insert("STRING_PROGRAM_MINT".to_string(), PatternStats {
-        count: 4,
-        percentage: 20.0,
-        examples: vec!["NFT Metadata".to_string(), "Collection Info".to_string()],
-    });
-    patterns.insert("STRING_AUTHORITY".to_string(), PatternStats {
-        count: 3,
-        percentage: 15.0,
-        examples: vec!["Mint Authority".to_string(), "Pool Authority".to_string()],
-    });
-    patterns.insert("PUBKEY_U64".to_string(), PatternStats {
-        count: 2,
-        percentage: 10.0,
-        examples: vec!["Serum Market".to_string(), "Pool Nonce".to_string()],
-    });
-    patterns.insert("STRING_SINGLETON".to_string(), PatternStats {
-        count: 2,
-        percentage: 10.0,
-        examples: vec!["Marinade State".to_string(), "Global Config".to_string()],
-    });
+    ]
+}
+
+// Demo data, forward-derived from seed templates so every address is a real PDA
+pub fn create_demo_analysis() -> AnalysisDisplay {
+    let registry = PatternRegistry::new();
+    let mut results = Vec::new();
+    let mut counts: Vec<(&str, Vec<String>)> = Vec::new();
+
+    for source in demo_sources(&registry) {
+        let template = &source.template;
+        let started = Instant::now();
+        let examples = registry.generate_examples(&template.program_id, &template.seeds_template, &source.values, source.count);
+        let analysis_time_ms = started.elapsed().as_millis() as u64 / examples.len().max(1) as u64;
+
+        let mut names = Vec::new();
+        for (i, example) in examples.iter().enumerate() {
+            let name = format!("{} #{}", template.pattern_name, i + 1);
+            names.push(name.clone());
+            results.push(PdaAnalysisResult {
+                name,
+                pda_address: example.address.to_string(),
+                program_id: example.program_id.to_string(),
+                program_name: source.program_name.to_string(),
+                description: template.description.clone().unwrap_or_default(),
+                seeds: example
+                    .seeds
+                    .iter()
+                    .zip(&template.seeds_template)
+                    .map(|(seed, slot)| SeedInfo {
+                        seed_type: seed.seed_type().to_string(),
+                        value: seed.to_string(),
+                        byte_length: seed.as_bytes().len(),
+                        description: slot.description.clone().unwrap_or_else(|| slot.name.clone()),
+                    })
+                    .collect(),
+                pattern: source.pattern.to_string(),
+                // Forward-derived, so the seeds are known exactly
+                confidence: 1.0,
+                analysis_time_ms,
+            });
+        }
+        counts.push((source.pattern, names));
+    }
+
+    let total = results.len().max(1) as f64;
+    let patterns: HashMap<String, PatternStats> = counts
+        .iter()
+        .map(|(pattern, names)| {
+            (pattern.to_string(), PatternStats {
+                count: names.len() as u32,
+                percentage: names.len() as f64 / total * 100.0,
+                examples: names.iter().take(3).cloned().collect(),
+            })
+        })
+        .collect();
+    let most_common_pattern = counts
+        .iter()
+        .max_by_key(|(_, names)| names.len())
+        .map(|(pattern, _)| pattern.to_string())
+        .unwrap_or_default();
 
     let summary = AnalysisSummary {
-        total_pdas: 20,
-        patterns_found: 8,
-        success_rate: 95.0,
-        total_time_ms: 234,
-        most_common_pattern: "WALLET_TOKEN_MINT".to_string(),
+        total_pdas: results.len() as u32,
+        patterns_found: patterns.len() as u32,
+        success_rate: 100.0,
+        total_time_ms: results.iter().map(|result| result.analysis_time_ms).sum(),
+        most_common_pattern,
     };
 
     AnalysisDisplay {
@@ -378,9 +396,10 @@ mod tests {
     #[test]
     fn test_demo_creation() {
         let analysis = create_demo_analysis();
-        assert_eq!(analysis.results.len(), 4);
-        assert_eq!(analysis.patterns.len(), 5);
-        assert_eq!(analysis.summary.total_pdas, 20);
+        assert_eq!(analysis.results.len(), 12);
+        assert_eq!(analysis.patterns.len(), 4);
+        assert_eq!(analysis.summary.total_pdas, 12);
+        assert_eq!(analysis.summary.most_common_pattern, "WALLET_MINT");
     }
 
     #[test]
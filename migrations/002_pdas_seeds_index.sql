@@ -0,0 +1 @@
+CREATE INDEX IF NOT EXISTS idx_pdas_seeds ON pdas USING GIN (seeds jsonb_path_ops);
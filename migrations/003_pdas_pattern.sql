@@ -0,0 +1,3 @@
+ALTER TABLE pdas ADD COLUMN IF NOT EXISTS pattern VARCHAR(64);
+
+CREATE INDEX IF NOT EXISTS idx_pdas_program_pattern ON pdas(program_id, pattern);
@@ -0,0 +1,10 @@
+CREATE OR REPLACE VIEW program_pattern_coverage AS
+SELECT
+    p.program_id,
+    p.name,
+    COUNT(pd.id) AS total_pdas,
+    COUNT(pd.id) FILTER (WHERE pd.pattern IS NULL OR pd.pattern = 'UNKNOWN') AS unrecognized_pdas,
+    (COUNT(pd.id) FILTER (WHERE pd.pattern IS NULL OR pd.pattern = 'UNKNOWN'))::FLOAT8 / COUNT(pd.id) AS unrecognized_share
+FROM programs p
+JOIN pdas pd ON pd.program_id = p.id
+GROUP BY p.id, p.program_id, p.name;
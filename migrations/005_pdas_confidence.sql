@@ -0,0 +1 @@
+ALTER TABLE pdas ADD COLUMN IF NOT EXISTS confidence DOUBLE PRECISION;
@@ -0,0 +1,23 @@
+ALTER TABLE transactions ADD COLUMN IF NOT EXISTS program_ids TEXT[] NOT NULL DEFAULT '{}';
+
+-- Backfill from the PDAs each stored transaction revealed or interacted with
+UPDATE transactions t
+SET program_ids = touched.program_ids
+FROM (
+    SELECT transaction_id, array_agg(DISTINCT program_id) AS program_ids
+    FROM (
+        SELECT ai.transaction_id, p.program_id
+        FROM account_interactions ai
+        JOIN pdas pd ON pd.id = ai.pda_id
+        JOIN programs p ON p.id = pd.program_id
+        UNION
+        SELECT pd.first_seen_transaction, p.program_id
+        FROM pdas pd
+        JOIN programs p ON p.id = pd.program_id
+        WHERE pd.first_seen_transaction IS NOT NULL
+    ) AS links
+    GROUP BY transaction_id
+) AS touched
+WHERE touched.transaction_id = t.id;
+
+CREATE INDEX IF NOT EXISTS idx_transactions_program_ids ON transactions USING GIN (program_ids);
@@ -0,0 +1,9 @@
+ALTER TABLE pdas ADD COLUMN IF NOT EXISTS first_seen_slot BIGINT;
+ALTER TABLE programs ADD COLUMN IF NOT EXISTS discovered_through_slot BIGINT;
+
+UPDATE pdas pd -- backfill from the transaction each PDA was first seen in
+SET first_seen_slot = t.slot
+FROM transactions t
+WHERE t.id = pd.first_seen_transaction AND pd.first_seen_slot IS NULL;
+
+CREATE INDEX IF NOT EXISTS idx_pdas_program_first_seen_slot ON pdas (program_id, first_seen_slot);
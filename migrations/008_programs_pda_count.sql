@@ -0,0 +1,5 @@
+ALTER TABLE programs ADD COLUMN IF NOT EXISTS total_pdas BIGINT NOT NULL DEFAULT 0;
+ALTER TABLE programs ADD COLUMN IF NOT EXISTS last_analyzed TIMESTAMP WITH TIME ZONE;
+
+UPDATE programs p
+SET total_pdas = (SELECT COUNT(*) FROM pdas pd WHERE pd.program_id = p.id);
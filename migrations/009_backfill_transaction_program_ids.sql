@@ -0,0 +1,20 @@
+-- 006's backfill again: runners before the comment-line fix skipped it while recording 006 as
+-- applied. Transactions that already list their programs are left alone.
+UPDATE transactions t
+SET program_ids = touched.program_ids
+FROM (
+    SELECT transaction_id, array_agg(DISTINCT program_id) AS program_ids
+    FROM (
+        SELECT ai.transaction_id, p.program_id
+        FROM account_interactions ai
+        JOIN pdas pd ON pd.id = ai.pda_id
+        JOIN programs p ON p.id = pd.program_id
+        UNION
+        SELECT pd.first_seen_transaction, p.program_id
+        FROM pdas pd
+        JOIN programs p ON p.id = pd.program_id
+        WHERE pd.first_seen_transaction IS NOT NULL
+    ) AS links
+    GROUP BY transaction_id
+) AS touched
+WHERE touched.transaction_id = t.id AND t.program_ids = '{}';
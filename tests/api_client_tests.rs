@@ -1,330 +1,18 @@
 //! API Client Tests for Solana PDA Analyzer
 //! 
-//! This module contains tests for the API client functionality,
-//! including comprehensive unit tests for request/response handling,
-//! error scenarios, and client configuration.
+//! This module exercises `PdaAnalyzerClient` from the api crate against a
+//! running server, covering request/response handling, error scenarios,
+//! and client configuration.
 
-use std::collections::HashMap;
-use std::sync::Arc;
 use std::time::Duration;
-use tokio::sync::RwLock;
-use reqwest::{Client, Response};
-use serde::{Deserialize, Serialize};
-use serde_json::Value;
-use anyhow::{Result, Context};
+use anyhow::Result;
 use colored::*;
-
-/// API response wrapper
-#[derive(Debug, Serialize, Deserialize)]
-struct ApiResponse<T> {
-    success: bool,
-    data: Option<T>,
-    error: Option<String>,
-    message: Option<String>,
-}
-
-/// PDA analysis request
-#[derive(Debug, Serialize, Deserialize, Clone)]
-pub struct AnalyzePdaRequest {
-    pub address: String,
-    pub program_id: String,
-}
-
-/// Batch PDA analysis request
-#[derive(Debug, Serialize, Deserialize, Clone)]
-pub struct BatchAnalyzePdaRequest {
-    pub pdas: Vec<AnalyzePdaRequest>,
-}
-
-/// PDA analysis result
-#[derive(Debug, Serialize, Deserialize, Clone)]
-pub struct PdaAnalysisResult {
-    pub address: String,
-    pub program_id: String,
-    pub pattern: Option<String>,
-    pub confidence: Option<f64>,
-    pub seeds: Vec<SeedValue>,
-    pub bump: u8,
-    pub analysis_time_ms: u64,
-}
-
-/// Seed value types
-#[derive(Debug, Serialize, Deserialize, Clone)]
-#[serde(tag = "type", content = "value")]
-pub enum SeedValue {
-    String(String),
-    Bytes(Vec<u8>),
-    Pubkey(String),
-    U64(u64),
-    U32(u32),
-    U16(u16),
-    U8(u8),
-}
-
-/// Program information
-#[derive(Debug, Serialize, Deserialize, Clone)]
-pub struct ProgramInfo {
-    pub program_id: String,
-    pub name: Option<String>,
-    pub description: Option<String>,
-    pub total_pdas: i64,
-    pub total_transactions: i64,
-}
-
-/// Database statistics
-#[derive(Debug, Serialize, Deserialize, Clone)]
-pub struct DatabaseStats {
-    pub total_programs: i64,
-    pub total_pdas: i64,
-    pub total_transactions: i64,
-    pub total_interactions: i64,
-}
-
-/// Configuration for the API client
-#[derive(Debug, Clone)]
-pub struct ApiClientConfig {
-    pub base_url: String,
-    pub timeout: Duration,
-    pub max_retries: u32,
-    pub retry_delay: Duration,
-}
-
-impl Default for ApiClientConfig {
-    fn default() -> Self {
-        Self {
-            base_url: "http://localhost:8080".to_string(),
-            timeout: Duration::from_secs(30),
-            max_retries: 3,
-            retry_delay: Duration::from_millis(500),
-        }
-    }
-}
-
-/// API client for interacting with the Solana PDA Analyzer
-pub struct ApiClient {
-    client: Client,
-    config: ApiClientConfig,
-    stats: Arc<RwLock<ClientStats>>,
-}
-
-#[derive(Debug, Default)]
-struct ClientStats {
-    total_requests: u64,
-    successful_requests: u64,
-    failed_requests: u64,
-    total_response_time: Duration,
-    cache_hits: u64,
-    cache_misses: u64,
-}
-
-impl ApiClient {
-    pub fn new(config: ApiClientConfig) -> Result<Self> {
-        let client = Client::builder()
-            .timeout(config.timeout)
-            .build()
-            .context("Failed to create HTTP client")?;
-
-        Ok(Self {
-            client,
-            config,
-            stats: Arc::new(RwLock::new(ClientStats::default())),
-        })
-    }
-
-    pub fn new_with_default_config() -> Result<Self> {
-        Self::new(ApiClientConfig::default())
-    }
-
-    async fn record_request(&self, success: bool, response_time: Duration) {
-        let mut stats = self.stats.write().await;
-        stats.total_requests += 1;
-        stats.total_response_time += response_time;
-        
-        if success {
-            stats.successful_requests += 1;
-        } else {
-            stats.failed_requests += 1;
-        }
-    }
-
-    async fn make_request<T: for<'de> Deserialize<'de>>(
-        &self,
-        method: &str,
-        endpoint: &str,
-        body: Option<Value>,
-    ) -> Result<T> {
-        let start = std::time::Instant::now();
-        let url = format!("{}{}", self.config.base_url, endpoint);
-        
-        let mut last_error = None;
-        
-        for attempt in 0..=self.config.max_retries {
-            let mut request = match method {
-                "GET" => self.client.get(&url),
-                "POST" => self.client.post(&url),
-                "PUT" => self.client.put(&url),
-                "DELETE" => self.client.delete(&url),
-                _ => return Err(anyhow::anyhow!("Unsupported HTTP method: {}", method)),
-            };
-
-            if let Some(ref body) = body {
-                request = request.json(body);
-            }
-
-            match request.send().await {
-                Ok(response) => {
-                    let elapsed = start.elapsed();
-                    
-                    if response.status().is_success() {
-                        match response.json::<ApiResponse<T>>().await {
-                            Ok(api_response) => {
-                                self.record_request(true, elapsed).await;
-                                
-                                if api_response.success {
-                                    return api_response.data.ok_or_else(|| {
-                                        anyhow::anyhow!("API response missing data field")
-                                    });
-                                } else {
-                                    let error_msg = api_response.error
-                                        .or(api_response.message)
-                                        .unwrap_or_else(|| "Unknown API error".to_string());
-                                    return Err(anyhow::anyhow!("API error: {}", error_msg));
-                                }
-                            }
-                            Err(e) => {
-                                last_error = Some(anyhow::anyhow!("Failed to parse response: {}", e));
-                            }
-                        }
-                    } else {
-                        let status = response.status();
-                        let error_text = response.text().await.unwrap_or_default();
-                        last_error = Some(anyhow::anyhow!("HTTP {}: {}", status, error_text));
-                    }
-                }
-                Err(e) => {
-                    last_error = Some(anyhow::anyhow!("Request failed: {}", e));
-                }
-            }
-            
-            if attempt < self.config.max_retries {
-                tokio::time::sleep(self.config.retry_delay).await;
-            }
-        }
-        
-        let elapsed = start.elapsed();
-        self.record_request(false, elapsed).await;
-        
-        Err(last_error.unwrap_or_else(|| anyhow::anyhow!("All retry attempts failed")))
-    }
-
-    /// Check if the API server is healthy
-    pub async fn health_check(&self) -> Result<bool> {
-        let health: serde_json::Value = self.make_request("GET", "/health", None).await?;
-        Ok(health.get("status").and_then(|s| s.as_str()) == Some("healthy"))
-    }
-
-    /// Analyze a single PDA
-    pub async fn analyze_pda(&self, address: &str, program_id: &str) -> Result<PdaAnalysisResult> {
-        let request = AnalyzePdaRequest {
-            address: address.to_string(),
-            program_id: program_id.to_string(),
-        };
-
-        let body = serde_json::to_value(&request)?;
-        self.make_request("POST", "/api/v1/analyze/pda", Some(body)).await
-    }
-
-    /// Analyze multiple PDAs in batch
-    pub async fn analyze_pdas_batch(&self, pdas: Vec<AnalyzePdaRequest>) -> Result<Vec<Option<PdaAnalysisResult>>> {
-        let request = BatchAnalyzePdaRequest { pdas };
-        let body = serde_json::to_value(&request)?;
-        self.make_request("POST", "/api/v1/analyze/pda/batch", Some(body)).await
-    }
-
-    /// List all programs
-    pub async fn list_programs(&self, limit: Option<i64>, offset: Option<i64>) -> Result<Vec<ProgramInfo>> {
-        let mut endpoint = "/api/v1/programs".to_string();
-        let mut params = Vec::new();
-        
-        if let Some(limit) = limit {
-            params.push(format!("limit={}", limit));
-        }
-        if let Some(offset) = offset {
-            params.push(format!("offset={}", offset));
-        }
-        
-        if !params.is_empty() {
-            endpoint.push('?');
-            endpoint.push_str(&params.join("&"));
-        }
-        
-        self.make_request("GET", &endpoint, None).await
-    }
-
-    /// Get program details
-    pub async fn get_program(&self, program_id: &str) -> Result<ProgramInfo> {
-        let endpoint = format!("/api/v1/programs/{}", program_id);
-        self.make_request("GET", &endpoint, None).await
-    }
-
-    /// List PDAs with optional filtering
-    pub async fn list_pdas(&self, limit: Option<i64>, program_id: Option<&str>) -> Result<Vec<Value>> {
-        let mut endpoint = "/api/v1/pdas".to_string();
-        let mut params = Vec::new();
-        
-        if let Some(limit) = limit {
-            params.push(format!("limit={}", limit));
-        }
-        if let Some(program_id) = program_id {
-            params.push(format!("program_id={}", program_id));
-        }
-        
-        if !params.is_empty() {
-            endpoint.push('?');
-            endpoint.push_str(&params.join("&"));
-        }
-        
-        self.make_request("GET", &endpoint, None).await
-    }
-
-    /// Get database statistics
-    pub async fn get_database_stats(&self) -> Result<DatabaseStats> {
-        self.make_request("GET", "/api/v1/analytics/database", None).await
-    }
-
-    /// Get performance metrics
-    pub async fn get_performance_metrics(&self) -> Result<HashMap<String, Value>> {
-        self.make_request("GET", "/api/v1/analytics/performance", None).await
-    }
-
-    /// Get client statistics
-    pub async fn get_stats(&self) -> ClientStats {
-        self.stats.read().await.clone()
-    }
-
-    /// Reset client statistics
-    pub async fn reset_stats(&self) {
-        let mut stats = self.stats.write().await;
-        *stats = ClientStats::default();
-    }
-}
-
-impl Clone for ClientStats {
-    fn clone(&self) -> Self {
-        Self {
-            total_requests: self.total_requests,
-            successful_requests: self.successful_requests,
-            failed_requests: self.failed_requests,
-            total_response_time: self.total_response_time,
-            cache_hits: self.cache_hits,
-            cache_misses: self.cache_misses,
-        }
-    }
-}
+use solana_pda_analyzer_api::client::{AnalyzePdaRequest, ClientConfig, PdaAnalyzerClient, StatusCode};
+use solana_pda_analyzer_api::ColorChoice;
 
 /// Test harness for API client functionality
 pub struct ApiClientTester {
-    client: ApiClient,
+    client: PdaAnalyzerClient,
     test_results: Vec<TestResult>,
 }
 
@@ -339,21 +27,11 @@ struct TestResult {
 
 impl ApiClientTester {
     pub fn new() -> Result<Self> {
-        let config = ApiClientConfig::default();
-        let client = ApiClient::new(config)?;
-        
-        Ok(Self {
-            client,
-            test_results: Vec::new(),
-        })
+        Self::new_with_url("http://localhost:8080".to_string())
     }
 
     pub fn new_with_url(base_url: String) -> Result<Self> {
-        let config = ApiClientConfig {
-            base_url,
-            ..Default::default()
-        };
-        let client = ApiClient::new(config)?;
+        let client = PdaAnalyzerClient::new(&base_url)?;
         
         Ok(Self {
             client,
@@ -375,7 +53,7 @@ impl ApiClientTester {
 
     async fn run_test<F, Fut>(&mut self, name: &str, test_fn: F) 
     where
-        F: FnOnce(&ApiClient) -> Fut,
+        F: FnOnce(&PdaAnalyzerClient) -> Fut,
         Fut: std::future::Future<Output = Result<String>>,
     {
         self.log_info(&format!("Running test: {}", name));
@@ -414,14 +92,14 @@ impl ApiClientTester {
     pub async fn test_client_configuration(&mut self) -> Result<()> {
         self.run_test("Client Configuration", |_client| async {
             // Test that client can be created with custom configuration
-            let custom_config = ApiClientConfig {
-                base_url: "http://localhost:8080".to_string(),
+            let custom_config = ClientConfig {
                 timeout: Duration::from_secs(10),
                 max_retries: 5,
                 retry_delay: Duration::from_millis(100),
+                ..ClientConfig::default()
             };
             
-            let _custom_client = ApiClient::new(custom_config)?;
+            let _custom_client = PdaAnalyzerClient::with_config("http://localhost:8080", custom_config)?;
             Ok("Client created with custom configuration".to_string())
         }).await;
         
@@ -430,8 +108,8 @@ impl ApiClientTester {
 
     pub async fn test_health_check(&mut self) -> Result<()> {
         self.run_test("Health Check", |client| async {
-            let is_healthy = client.health_check().await?;
-            if is_healthy {
+            let health = client.health_check().await?;
+            if health.status == "healthy" {
                 Ok("Server is healthy".to_string())
             } else {
                 Err(anyhow::anyhow!("Server is not healthy"))
@@ -449,7 +127,7 @@ impl ApiClientTester {
             ).await?;
             
             Ok(format!("PDA analyzed: {}, pattern: {:?}", 
-                result.address, result.pattern))
+                result.pda_info.address, result.pattern))
         }).await;
         
         Ok(())
@@ -519,7 +197,7 @@ impl ApiClientTester {
             
             match result {
                 Err(e) => {
-                    if e.to_string().contains("Invalid") || e.to_string().contains("400") {
+                    if e.status() == Some(StatusCode::BAD_REQUEST) {
                         Ok("Correctly handled invalid PDA address".to_string())
                     } else {
                         Err(anyhow::anyhow!("Unexpected error: {}", e))
@@ -535,13 +213,13 @@ impl ApiClientTester {
     pub async fn test_client_stats(&mut self) -> Result<()> {
         self.run_test("Client Stats", |client| async {
             // Reset stats
-            client.reset_stats().await;
+            client.reset_stats();
             
             // Make a few requests
             let _ = client.health_check().await;
             let _ = client.list_programs(Some(5), None).await;
             
-            let stats = client.get_stats().await;
+            let stats = client.stats();
             
             if stats.total_requests > 0 {
                 Ok(format!("Client stats: {} total requests, {} successful", 
@@ -569,9 +247,10 @@ impl ApiClientTester {
             
             let mut success_count = 0;
             for handle in handles {
-                match handle.await {
-                    Ok(Ok(true)) => success_count += 1,
-                    _ => {}
+                if let Ok(Ok(health)) = handle.await {
+                    if health.status == "healthy" {
+                        success_count += 1;
+                    }
                 }
             }
             
@@ -642,6 +321,9 @@ impl ApiClientTester {
 
 #[tokio::main]
 async fn main() -> Result<()> {
+    let color = ColorChoice::from_args(std::env::args().skip(1))?;
+    colored::control::set_override(color.for_stdout());
+
     let base_url = std::env::var("API_URL").unwrap_or_else(|_| "http://localhost:8080".to_string());
     
     println!("{}", "Solana PDA Analyzer - API Client Tests".blue().bold());
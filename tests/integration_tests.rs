@@ -7,37 +7,12 @@ use std::collections::HashMap;
 use std::time::Duration;
 use tokio::time::sleep;
 use serde_json::Value;
-use reqwest::{Client, Response};
-use serde::{Deserialize, Serialize};
 use anyhow::{Result, Context};
 use colored::*;
-
-#[derive(Debug, Serialize, Deserialize)]
-struct ApiResponse<T> {
-    success: bool,
-    data: Option<T>,
-    error: Option<String>,
-    message: Option<String>,
-}
-
-#[derive(Debug, Serialize, Deserialize)]
-struct AnalyzePdaRequest {
-    address: String,
-    program_id: String,
-}
-
-#[derive(Debug, Serialize, Deserialize)]
-struct BatchAnalyzePdaRequest {
-    pdas: Vec<AnalyzePdaRequest>,
-}
-
-#[derive(Debug, Serialize, Deserialize)]
-struct HealthResponse {
-    status: String,
-    timestamp: String,
-    database_connected: bool,
-    version: String,
-}
+use solana_pda_analyzer_api::client::{
+    AnalyzePdaRequest, BatchAnalyzePdaRequest, ClientConfig, Method, PdaAnalyzerClient, RawResponse,
+};
+use solana_pda_analyzer_api::{ApiError, ApiResponse, ColorChoice, HealthCheckResponse};
 
 #[derive(Debug)]
 struct TestResult {
@@ -71,21 +46,21 @@ impl TestResult {
 }
 
 struct IntegrationTester {
-    client: Client,
-    base_url: String,
+    client: PdaAnalyzerClient,
     results: Vec<TestResult>,
 }
 
 impl IntegrationTester {
     fn new(base_url: String) -> Self {
-        let client = Client::builder()
-            .timeout(Duration::from_secs(30))
-            .build()
+        let config = ClientConfig {
+            timeout: Duration::from_secs(30),
+            ..Default::default()
+        };
+        let client = PdaAnalyzerClient::with_config(&base_url, config)
             .expect("Failed to create HTTP client");
 
         Self {
             client,
-            base_url,
             results: Vec::new(),
         }
     }
@@ -106,22 +81,11 @@ impl IntegrationTester {
         println!("{} {}", "[WARNING]".yellow(), message);
     }
 
-    async fn make_request(&self, method: &str, endpoint: &str, body: Option<Value>) -> Result<Response> {
-        let url = format!("{}{}", self.base_url, endpoint);
-        
-        let mut request = match method {
-            "GET" => self.client.get(&url),
-            "POST" => self.client.post(&url),
-            "PUT" => self.client.put(&url),
-            "DELETE" => self.client.delete(&url),
-            _ => return Err(anyhow::anyhow!("Unsupported HTTP method: {}", method)),
-        };
+    async fn make_request(&self, method: &str, endpoint: &str, body: Option<Value>) -> Result<RawResponse> {
+        let method = Method::from_bytes(method.as_bytes())
+            .with_context(|| format!("Unsupported HTTP method: {}", method))?;
 
-        if let Some(body) = body {
-            request = request.json(&body);
-        }
-
-        request.send().await.context("Failed to send request")
+        self.client.send(method, endpoint, body.as_ref()).await.context("Failed to send request")
     }
 
     async fn test_health_check(&mut self) -> Result<()> {
@@ -132,8 +96,8 @@ impl IntegrationTester {
             Ok(response) => {
                 let duration = start.elapsed();
                 
-                if response.status().is_success() {
-                    match response.json::<ApiResponse<HealthResponse>>().await {
+                if response.status.is_success() {
+                    match response.json::<ApiResponse<HealthCheckResponse>>() {
                         Ok(health) => {
                             if health.success {
                                 let details = format!("Status: {}, DB Connected: {}", 
@@ -157,7 +121,7 @@ impl IntegrationTester {
                     }
                 } else {
                     let result = TestResult::new(test_name, false, duration)
-                        .with_error(format!("HTTP {}", response.status()));
+                        .with_error(format!("HTTP {}", response.status));
                     self.results.push(result);
                 }
             }
@@ -186,10 +150,10 @@ impl IntegrationTester {
         match self.make_request("POST", "/api/v1/analyze/pda", Some(body)).await {
             Ok(response) => {
                 let duration = start.elapsed();
-                let status = response.status();
+                let status = response.status;
 
                 if status.is_success() {
-                    match response.json::<ApiResponse<Value>>().await {
+                    match response.json::<ApiResponse<Value>>() {
                         Ok(api_response) => {
                             if api_response.success {
                                 let details = format!("Analysis completed successfully");
@@ -210,7 +174,7 @@ impl IntegrationTester {
                         }
                     }
                 } else {
-                    let error_text = response.text().await.unwrap_or_default();
+                    let error_text = response.text();
                     let result = TestResult::new(test_name, false, duration)
                         .with_error(format!("HTTP {}: {}", status, error_text));
                     self.results.push(result);
@@ -249,10 +213,10 @@ impl IntegrationTester {
         match self.make_request("POST", "/api/v1/analyze/pda/batch", Some(body)).await {
             Ok(response) => {
                 let duration = start.elapsed();
-                let status = response.status();
+                let status = response.status;
 
                 if status.is_success() {
-                    match response.json::<ApiResponse<Vec<Value>>>().await {
+                    match response.json::<ApiResponse<Vec<Value>>>() {
                         Ok(api_response) => {
                             if api_response.success {
                                 let count = api_response.data.as_ref().map(|d| d.len()).unwrap_or(0);
@@ -274,7 +238,7 @@ impl IntegrationTester {
                         }
                     }
                 } else {
-                    let error_text = response.text().await.unwrap_or_default();
+                    let error_text = response.text();
                     let result = TestResult::new(test_name, false, duration)
                         .with_error(format!("HTTP {}: {}", status, error_text));
                     self.results.push(result);
@@ -298,10 +262,10 @@ impl IntegrationTester {
         match self.make_request("GET", "/api/v1/programs", None).await {
             Ok(response) => {
                 let duration = start.elapsed();
-                let status = response.status();
+                let status = response.status;
 
                 if status.is_success() {
-                    match response.json::<ApiResponse<Vec<Value>>>().await {
+                    match response.json::<ApiResponse<Vec<Value>>>() {
                         Ok(api_response) => {
                             if api_response.success {
                                 let count = api_response.data.as_ref().map(|d| d.len()).unwrap_or(0);
@@ -323,7 +287,7 @@ impl IntegrationTester {
                         }
                     }
                 } else {
-                    let error_text = response.text().await.unwrap_or_default();
+                    let error_text = response.text();
                     let result = TestResult::new(test_name, false, duration)
                         .with_error(format!("HTTP {}: {}", status, error_text));
                     self.results.push(result);
@@ -347,10 +311,10 @@ impl IntegrationTester {
         match self.make_request("GET", "/api/v1/pdas?limit=10", None).await {
             Ok(response) => {
                 let duration = start.elapsed();
-                let status = response.status();
+                let status = response.status;
 
                 if status.is_success() {
-                    match response.json::<ApiResponse<Vec<Value>>>().await {
+                    match response.json::<ApiResponse<Vec<Value>>>() {
                         Ok(api_response) => {
                             if api_response.success {
                                 let count = api_response.data.as_ref().map(|d| d.len()).unwrap_or(0);
@@ -372,7 +336,7 @@ impl IntegrationTester {
                         }
                     }
                 } else {
-                    let error_text = response.text().await.unwrap_or_default();
+                    let error_text = response.text();
                     let result = TestResult::new(test_name, false, duration)
                         .with_error(format!("HTTP {}: {}", status, error_text));
                     self.results.push(result);
@@ -397,10 +361,10 @@ impl IntegrationTester {
         match self.make_request("GET", "/api/v1/analytics/database", None).await {
             Ok(response) => {
                 let duration = start.elapsed();
-                let status = response.status();
+                let status = response.status;
 
                 if status.is_success() {
-                    match response.json::<ApiResponse<Value>>().await {
+                    match response.json::<ApiResponse<Value>>() {
                         Ok(api_response) => {
                             if api_response.success {
                                 let details = "Database metrics retrieved successfully".to_string();
@@ -421,7 +385,7 @@ impl IntegrationTester {
                         }
                     }
                 } else {
-                    let error_text = response.text().await.unwrap_or_default();
+                    let error_text = response.text();
                     let result = TestResult::new(test_name, false, duration)
                         .with_error(format!("HTTP {}: {}", status, error_text));
                     self.results.push(result);
@@ -453,7 +417,7 @@ impl IntegrationTester {
         match self.make_request("POST", "/api/v1/analyze/pda", Some(body)).await {
             Ok(response) => {
                 let duration = start.elapsed();
-                let status = response.status();
+                let status = response.status;
 
                 // Should return 400 for invalid address
                 if status == 400 {
@@ -485,12 +449,18 @@ impl IntegrationTester {
         match self.make_request("GET", "/api/v1/nonexistent", None).await {
             Ok(response) => {
                 let duration = start.elapsed();
-                let status = response.status();
+                let status = response.status;
 
                 if status == 404 {
-                    let details = "Correctly returned 404 for nonexistent endpoint".to_string();
-                    let result = TestResult::new(test_name, true, duration)
-                        .with_details(details);
+                    // Unrouted paths get the same JSON error body as handler failures
+                    let result = match response.json::<ApiError>() {
+                        Ok(error) if error.status_code == 404 => TestResult::new(test_name, true, duration)
+                            .with_details(format!("Correctly returned 404: {}", error.message)),
+                        Ok(error) => TestResult::new(test_name, false, duration)
+                            .with_error(format!("Error body reports status {}", error.status_code)),
+                        Err(e) => TestResult::new(test_name, false, duration)
+                            .with_error(format!("404 body is not a JSON error: {}", e)),
+                    };
                     self.results.push(result);
                 } else {
                     let result = TestResult::new(test_name, false, duration)
@@ -518,10 +488,9 @@ impl IntegrationTester {
 
         for _ in 0..10 {
             let client = self.client.clone();
-            let url = format!("{}/health", self.base_url);
             
             let handle = tokio::spawn(async move {
-                client.get(&url).send().await
+                client.send(Method::GET, "/health", None::<&Value>).await
             });
             
             handles.push(handle);
@@ -534,7 +503,7 @@ impl IntegrationTester {
             total_count += 1;
             match handle.await {
                 Ok(Ok(response)) => {
-                    if response.status().is_success() {
+                    if response.status.is_success() {
                         success_count += 1;
                     }
                 }
@@ -638,6 +607,9 @@ impl IntegrationTester {
 
 #[tokio::main]
 async fn main() -> Result<()> {
+    let color = ColorChoice::from_args(std::env::args().skip(1))?;
+    colored::control::set_override(color.for_stdout());
+
     let base_url = std::env::var("API_URL").unwrap_or_else(|_| "http://localhost:8080".to_string());
     
     println!("{}", "Solana PDA Analyzer - Integration Tests".blue().bold());
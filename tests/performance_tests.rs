@@ -9,30 +9,11 @@ use std::sync::atomic::{AtomicU64, Ordering};
 use std::time::{Duration, Instant};
 use tokio::sync::Semaphore;
 use tokio::time::sleep;
-use reqwest::Client;
-use serde::{Deserialize, Serialize};
 use serde_json::Value;
 use anyhow::{Result, Context};
 use colored::*;
-
-#[derive(Debug, Serialize, Deserialize)]
-struct ApiResponse<T> {
-    success: bool,
-    data: Option<T>,
-    error: Option<String>,
-    message: Option<String>,
-}
-
-#[derive(Debug, Serialize, Deserialize)]
-struct AnalyzePdaRequest {
-    address: String,
-    program_id: String,
-}
-
-#[derive(Debug, Serialize, Deserialize)]
-struct BatchAnalyzePdaRequest {
-    pdas: Vec<AnalyzePdaRequest>,
-}
+use solana_pda_analyzer_api::client::{ClientConfig, Method, PdaAnalyzerClient};
+use solana_pda_analyzer_api::ColorChoice;
 
 #[derive(Debug, Clone)]
 struct PerformanceResult {
@@ -59,22 +40,23 @@ struct RequestResult {
 }
 
 pub struct PerformanceTester {
-    client: Client,
-    base_url: String,
+    client: PdaAnalyzerClient,
     results: Vec<PerformanceResult>,
 }
 
 impl PerformanceTester {
     pub fn new(base_url: String) -> Result<Self> {
-        let client = Client::builder()
-            .timeout(Duration::from_secs(30))
-            .pool_max_idle_per_host(100)
-            .build()
+        // Single attempts only, so retries don't hide failures or skew timings
+        let config = ClientConfig {
+            timeout: Duration::from_secs(30),
+            max_retries: 0,
+            ..Default::default()
+        };
+        let client = PdaAnalyzerClient::with_config(&base_url, config)
             .context("Failed to create HTTP client")?;
 
         Ok(Self {
             client,
-            base_url,
             results: Vec::new(),
         })
     }
@@ -96,57 +78,7 @@ impl PerformanceTester {
     }
 
     async fn make_request(&self, method: &str, endpoint: &str, body: Option<Value>) -> RequestResult {
-        let start = Instant::now();
-        let url = format!("{}{}", self.base_url, endpoint);
-        
-        let mut request = match method {
-            "GET" => self.client.get(&url),
-            "POST" => self.client.post(&url),
-            "PUT" => self.client.put(&url),
-            "DELETE" => self.client.delete(&url),
-            _ => {
-                return RequestResult {
-                    success: false,
-                    response_time: start.elapsed(),
-                    error: Some(format!("Unsupported HTTP method: {}", method)),
-                };
-            }
-        };
-
-        if let Some(body) = body {
-            request = request.json(&body);
-        }
-
-        match request.send().await {
-            Ok(response) => {
-                let response_time = start.elapsed();
-                let success = response.status().is_success();
-                
-                if success {
-                    // Try to consume the response body to simulate real usage
-                    let _ = response.bytes().await;
-                    RequestResult {
-                        success: true,
-                        response_time,
-                        error: None,
-                    }
-                } else {
-                    let error = format!("HTTP {}", response.status());
-                    RequestResult {
-                        success: false,
-                        response_time,
-                        error: Some(error),
-                    }
-                }
-            }
-            Err(e) => {
-                RequestResult {
-                    success: false,
-                    response_time: start.elapsed(),
-                    error: Some(e.to_string()),
-                }
-            }
-        }
+        Self::make_single_request(&self.client, method, endpoint, body).await
     }
 
     async fn run_concurrent_requests(
@@ -204,7 +136,6 @@ impl PerformanceTester {
         for _ in 0..total_requests {
             let semaphore = semaphore.clone();
             let client = self.client.clone();
-            let base_url = self.base_url.clone();
             let method = method.to_string();
             let endpoint = endpoint.to_string();
             let body = body.clone();
@@ -214,7 +145,7 @@ impl PerformanceTester {
             let task = tokio::spawn(async move {
                 let _permit = semaphore.acquire().await.unwrap();
                 
-                let result = Self::make_single_request(&client, &base_url, &method, &endpoint, body).await;
+                let result = Self::make_single_request(&client, &method, &endpoint, body).await;
                 
                 counter.fetch_add(1, Ordering::Relaxed);
                 throughput_counter.fetch_add(1, Ordering::Relaxed);
@@ -255,21 +186,15 @@ impl PerformanceTester {
     }
 
     async fn make_single_request(
-        client: &Client,
-        base_url: &str,
+        client: &PdaAnalyzerClient,
         method: &str,
         endpoint: &str,
         body: Option<Value>,
     ) -> RequestResult {
         let start = Instant::now();
-        let url = format!("{}{}", base_url, endpoint);
-        
-        let mut request = match method {
-            "GET" => client.get(&url),
-            "POST" => client.post(&url),
-            "PUT" => client.put(&url),
-            "DELETE" => client.delete(&url),
-            _ => {
+        let method = match Method::from_bytes(method.as_bytes()) {
+            Ok(method) => method,
+            Err(_) => {
                 return RequestResult {
                     success: false,
                     response_time: start.elapsed(),
@@ -278,38 +203,23 @@ impl PerformanceTester {
             }
         };
 
-        if let Some(body) = body {
-            request = request.json(&body);
-        }
-
-        match request.send().await {
-            Ok(response) => {
-                let response_time = start.elapsed();
-                let success = response.status().is_success();
-                
-                if success {
-                    let _ = response.bytes().await;
-                    RequestResult {
-                        success: true,
-                        response_time,
-                        error: None,
-                    }
-                } else {
-                    let error = format!("HTTP {}", response.status());
-                    RequestResult {
-                        success: false,
-                        response_time,
-                        error: Some(error),
-                    }
-                }
-            }
-            Err(e) => {
-                RequestResult {
-                    success: false,
-                    response_time: start.elapsed(),
-                    error: Some(e.to_string()),
-                }
-            }
+        // The client reads the whole body, as real usage would
+        match client.send(method, endpoint, body.as_ref()).await {
+            Ok(response) if response.status.is_success() => RequestResult {
+                success: true,
+                response_time: start.elapsed(),
+                error: None,
+            },
+            Ok(response) => RequestResult {
+                success: false,
+                response_time: start.elapsed(),
+                error: Some(format!("HTTP {}", response.status)),
+            },
+            Err(e) => RequestResult {
+                success: false,
+                response_time: start.elapsed(),
+                error: Some(e.to_string()),
+            },
         }
     }
 
@@ -522,7 +432,6 @@ impl PerformanceTester {
             if tasks.len() < 100 {
                 let semaphore = semaphore.clone();
                 let client = self.client.clone();
-                let base_url = self.base_url.clone();
                 let counter = counter.clone();
                 let successful_counter = successful_counter.clone();
                 let failed_counter = failed_counter.clone();
@@ -533,7 +442,7 @@ impl PerformanceTester {
                 let task = tokio::spawn(async move {
                     let _permit = semaphore.acquire().await.unwrap();
                     
-                    let result = Self::make_single_request(&client, &base_url, "GET", "/health", None).await;
+                    let result = Self::make_single_request(&client, "GET", "/health", None).await;
                     
                     counter.fetch_add(1, Ordering::Relaxed);
                     throughput_counter.fetch_add(1, Ordering::Relaxed);
@@ -803,6 +712,9 @@ impl PerformanceTester {
 
 #[tokio::main]
 async fn main() -> Result<()> {
+    let color = ColorChoice::from_args(std::env::args().skip(1))?;
+    colored::control::set_override(color.for_stdout());
+
     let base_url = std::env::var("API_URL").unwrap_or_else(|_| "http://localhost:8080".to_string());
     let quick_test = std::env::var("QUICK_TEST").is_ok();
     let sustained_duration = std::env::var("SUSTAINED_DURATION")
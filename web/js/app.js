@@ -198,6 +198,7 @@ class PdaAnalyzerApp {
         } else if (typeof seed === 'number') {
             return 'Number';
         } else if (seed && typeof seed === 'object') {
+            if (seed.type) return seed.type;
             if (seed.String) return 'String';
             if (seed.Bytes) return 'Bytes';
             if (seed.Pubkey) return 'Pubkey';
@@ -215,6 +216,9 @@ class PdaAnalyzerApp {
         } else if (typeof seed === 'number') {
             return seed.toString();
         } else if (seed && typeof seed === 'object') {
+            if (seed.type) {
+                return Array.isArray(seed.value) ? `[${seed.value.join(', ')}]` : String(seed.value);
+            }
             if (seed.String) return seed.String;
             if (seed.Bytes) return `[${seed.Bytes.join(', ')}]`;
             if (seed.Pubkey) return seed.Pubkey;